@@ -5,6 +5,7 @@ pub mod closest_ellipse_point;
 pub mod halley;
 /// <https://en.wikipedia.org/wiki/ITP_method>
 pub mod itp;
+pub mod lambert;
 /// <https://en.wikipedia.org/wiki/Laguerre%27s_method>
 pub mod laguerre;
 pub mod newton_raphson;
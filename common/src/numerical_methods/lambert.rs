@@ -0,0 +1,167 @@
+use std::f64::consts::PI;
+
+use nalgebra_glm::DVec2;
+
+use crate::anticlockwise_angular_distance;
+
+const MAX_Z_INTERVAL: f64 = 1.0e-9;
+const MAX_ITERATIONS: usize = 200;
+const MIN_SINE_OF_TRANSFER_ANGLE: f64 = 1.0e-6;
+
+/// Stumpff function C(z)
+fn stumpff_c(z: f64) -> f64 {
+    if z > 1.0e-6 {
+        (1.0 - z.sqrt().cos()) / z
+    } else if z < -1.0e-6 {
+        ((-z).sqrt().cosh() - 1.0) / -z
+    } else {
+        1.0 / 2.0 - z / 24.0 + z.powi(2) / 720.0
+    }
+}
+
+/// Stumpff function S(z)
+fn stumpff_s(z: f64) -> f64 {
+    if z > 1.0e-6 {
+        let sqrt_z = z.sqrt();
+        (sqrt_z - sqrt_z.sin()) / sqrt_z.powi(3)
+    } else if z < -1.0e-6 {
+        let sqrt_z = (-z).sqrt();
+        (sqrt_z.sinh() - sqrt_z) / sqrt_z.powi(3)
+    } else {
+        1.0 / 6.0 - z / 120.0 + z.powi(2) / 5040.0
+    }
+}
+
+/// Solves Lambert's problem for a zero-revolution transfer using universal
+/// variables (Curtis, Orbital Mechanics for Engineering Students, algorithm 5.2).
+/// Finds the velocities at `start_position` and `end_position` of the conic
+/// that travels between them in `time_of_flight`, going anticlockwise if
+/// `anticlockwise` is set. Returns (start velocity, end velocity), or None
+/// if the transfer angle is degenerate (0 or pi) or no solution converged
+/// <https://en.wikipedia.org/wiki/Lambert%27s_problem>
+pub fn lambert(start_position: DVec2, end_position: DVec2, time_of_flight: f64, mu: f64, anticlockwise: bool) -> Option<(DVec2, DVec2)> {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Lambert");
+    if time_of_flight <= 0.0 {
+        return None;
+    }
+
+    let r_1 = start_position.magnitude();
+    let r_2 = end_position.magnitude();
+    let start_angle = f64::atan2(start_position.y, start_position.x);
+    let end_angle = f64::atan2(end_position.y, end_position.x);
+    let transfer_angle = if anticlockwise {
+        anticlockwise_angular_distance(start_angle, end_angle)
+    } else {
+        anticlockwise_angular_distance(end_angle, start_angle)
+    };
+
+    if transfer_angle.sin().abs() < MIN_SINE_OF_TRANSFER_ANGLE {
+        return None;
+    }
+
+    let a = transfer_angle.sin() * f64::sqrt(r_1 * r_2 / (1.0 - transfer_angle.cos()));
+    let y = |z: f64| r_1 + r_2 + a * (z * stumpff_s(z) - 1.0) / stumpff_c(z).sqrt();
+    // Increases monotonically with z, so we can bracket the root. Where y is
+    // negative there is no valid transfer, but such z are always too low
+    let time_error = |z: f64| {
+        let y = y(z);
+        if y < 0.0 {
+            return -time_of_flight;
+        }
+        ((y / stumpff_c(z)).powf(1.5) * stumpff_s(z) + a * y.sqrt()) / mu.sqrt() - time_of_flight
+    };
+
+    // z = 4pi^2 is the limit for a zero-revolution ellipse
+    let mut high = 4.0 * PI.powi(2) - 1.0e-6;
+    if time_error(high) < 0.0 {
+        return None;
+    }
+    let mut low = -4.0 * PI.powi(2);
+    let mut i = 0;
+    while time_error(low) > 0.0 {
+        low *= 2.0;
+        i += 1;
+        if i > 16 {
+            return None;
+        }
+    }
+
+    // Bisection rather than Newton-Raphson because the derivative is
+    // badly behaved close to the parabolic and degenerate cases
+    let mut i = 0;
+    while high - low > MAX_Z_INTERVAL {
+        let mid = f64::midpoint(low, high);
+        if time_error(mid) < 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+        i += 1;
+        if i > MAX_ITERATIONS {
+            return None;
+        }
+    }
+
+    let y = y(f64::midpoint(low, high));
+    if y < 0.0 {
+        return None;
+    }
+
+    // Lagrange coefficients
+    let f = 1.0 - y / r_1;
+    let g = a * f64::sqrt(y / mu);
+    let g_dot = 1.0 - y / r_2;
+    let start_velocity = (end_position - f * start_position) / g;
+    let end_velocity = (g_dot * end_position - start_position) / g;
+    Some((start_velocity, end_velocity))
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+
+    use nalgebra_glm::vec2;
+
+    use crate::numerical_methods::lambert::lambert;
+
+    const MU: f64 = 3.986e14;
+
+    #[test]
+    fn test_circular_quarter_orbit() {
+        let radius = 7.0e6;
+        let speed = f64::sqrt(MU / radius);
+        let period = 2.0 * PI * radius / speed;
+
+        let (start_velocity, end_velocity) = lambert(vec2(radius, 0.0), vec2(0.0, radius), period / 4.0, MU, true).unwrap();
+        assert!((start_velocity - vec2(0.0, speed)).magnitude() < 1.0e-3 * speed);
+        assert!((end_velocity - vec2(-speed, 0.0)).magnitude() < 1.0e-3 * speed);
+
+        // The same points the other way round take three quarters of an orbit
+        let (start_velocity, _) = lambert(vec2(radius, 0.0), vec2(0.0, radius), 3.0 * period / 4.0, MU, false).unwrap();
+        assert!((start_velocity - vec2(0.0, -speed)).magnitude() < 1.0e-3 * speed);
+    }
+
+    #[test]
+    fn test_conserves_energy_and_angular_momentum() {
+        let start_position = vec2(7.0e6, 1.0e6);
+        let end_position = vec2(-3.0e6, 1.2e7);
+        for time_of_flight in [600.0, 3600.0, 10000.0] {
+            let (start_velocity, end_velocity) = lambert(start_position, end_position, time_of_flight, MU, true).unwrap();
+            let start_energy = start_velocity.magnitude_squared() / 2.0 - MU / start_position.magnitude();
+            let end_energy = end_velocity.magnitude_squared() / 2.0 - MU / end_position.magnitude();
+            assert!((start_energy - end_energy).abs() < 1.0e-6 * start_energy.abs());
+
+            let start_momentum = start_position.x * start_velocity.y - start_position.y * start_velocity.x;
+            let end_momentum = end_position.x * end_velocity.y - end_position.y * end_velocity.x;
+            assert!((start_momentum - end_momentum).abs() < 1.0e-6 * start_momentum.abs());
+            assert!(start_momentum > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert!(lambert(vec2(7.0e6, 0.0), vec2(-8.0e6, 0.0), 3600.0, MU, true).is_none());
+        assert!(lambert(vec2(7.0e6, 0.0), vec2(0.0, 7.0e6), 0.0, MU, true).is_none());
+    }
+}
@@ -3,7 +3,7 @@ use std::fs;
 use eframe::egui::{Context, ViewportCommand};
 use log::error;
 use transfer_window_model::model::Model;
-use transfer_window_view::{game::{self, storyteller::{stories::StoryBuilder, story::Story}, ViewConfig}, Scene};
+use transfer_window_view::{game::{self, session::Session, storyteller::{stories::StoryBuilder, story::Story}, ViewConfig}, saves, Scene};
 
 use crate::Controller;
 
@@ -25,21 +25,23 @@ pub fn new_game<T: StoryBuilder + ?Sized>(controller: &mut Controller, context:
 pub fn load_game(controller: &mut Controller, context: &Context, name: &str) {
     #[cfg(feature = "profiling")]
     let _span = tracy_client::span!("Load game");
-    let serialized = fs::read_to_string("data/saves/".to_string() + name + ".json");
+    let serialized = fs::read_to_string(saves::path(name));
     let Ok(serialized) = serialized else {
         error!("Failed to handle load game; error while loading file: {}", serialized.err().unwrap());
         return;
     };
 
-    let model = Model::deserialize(serialized.as_str());
-    let Ok(model) = model else {
-    error!("Failed to handle load game; error while deserializing: {}", model.err().unwrap());
-    return;
+    let deserialized = Model::deserialize_with_session::<Session>(serialized.as_str());
+    let Ok((model, session)) = deserialized else {
+        error!("Failed to handle load game; error while deserializing: {}", deserialized.err().unwrap());
+        return;
     };
 
-    let view_config = ViewConfig::default();
-
-    controller.scene = Scene::Game(game::View::new(controller.gl.clone(), model, Story::empty(), context.clone(), controller.resources.clone(), view_config, None));
+    let view = match session {
+        Some(session) => game::View::from_session(controller.gl.clone(), model, session, context.clone(), controller.resources.clone()),
+        None => game::View::new(controller.gl.clone(), model, Story::empty(), context.clone(), controller.resources.clone(), ViewConfig::default(), None),
+    };
+    controller.scene = Scene::Game(view);
 }
 
 pub fn finish_level(controller: &mut Controller, level: String) {
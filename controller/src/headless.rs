@@ -0,0 +1,271 @@
+use std::{env, fs, path::Path, process::ExitCode};
+
+use log::{error, info};
+use tracing_subscriber::{fmt::Layer, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use transfer_window_model::{components::{vessel_component::{class_definition::{load_vessel_class_definitions, VESSEL_CLASS_DIRECTORY}}, ComponentType}, model::{state_query::StateQuery, time::TimeStep, Model}};
+use transfer_window_view::game::{replay::{playback::Playback, Replay}, storyteller::stories::story_builder};
+
+const USAGE: &str = "Usage: transfer-window-headless (--story <level> | --save <path> | --replay <path>) [--dt <seconds>] [--duration <seconds>] [--until <condition>] [--dump <path>]
+
+Replays are played with their recorded frame times instead of --dt, until they end
+if neither --duration nor --until is given. The process fails if a replay diverges from its recording
+
+Conditions:
+  destroyed:<vessel name>   stop once the named vessel no longer exists
+  eliminated:<faction>      stop once no vessels of the named faction (for example player, ally or enemy) remain";
+
+enum Source {
+    Story(String),
+    Save(String),
+    Replay(String),
+}
+
+enum Condition {
+    Destroyed(String),
+    Eliminated(String),
+}
+
+impl Condition {
+    fn parse(condition: &str) -> Result<Self, String> {
+        let Some((kind, argument)) = condition.split_once(':') else {
+            return Err(format!("Condition '{condition}' is missing an argument"));
+        };
+        match kind {
+            "destroyed" => Ok(Self::Destroyed(argument.to_string())),
+            "eliminated" => Ok(Self::Eliminated(argument.to_string())),
+            _ => Err(format!("Unknown condition '{kind}'")),
+        }
+    }
+
+    /// Factions are registered in the model, so they can only be checked once it is loaded
+    fn validate(&self, model: &Model) -> Result<(), String> {
+        match self {
+            Self::Eliminated(name) if model.faction_named(name).is_none() => Err(format!("Unknown faction '{name}'")),
+            _ => Ok(()),
+        }
+    }
+
+    fn is_met(&self, model: &Model) -> bool {
+        let mut vessels = model.entities(vec![ComponentType::VesselComponent, ComponentType::NameComponent]).into_iter();
+        match self {
+            Self::Destroyed(name) => !vessels.any(|entity| model.name_component(entity).name() == *name),
+            Self::Eliminated(name) => {
+                let faction = model.faction_named(name);
+                !vessels.any(|entity| Some(model.vessel_component(entity).faction()) == faction)
+            }
+        }
+    }
+}
+
+struct Args {
+    source: Source,
+    dt: f64,
+    duration: Option<f64>,
+    until: Option<Condition>,
+    dump: Option<String>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut source = None;
+        let mut dt = 1.0;
+        let mut duration = None;
+        let mut until = None;
+        let mut dump = None;
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("Missing value for {arg}"));
+            match arg.as_str() {
+                "--story" => source = Some(Source::Story(value()?)),
+                "--save" => source = Some(Source::Save(value()?)),
+                "--replay" => source = Some(Source::Replay(value()?)),
+                "--dt" => dt = parse_seconds(&value()?)?,
+                "--duration" => duration = Some(parse_seconds(&value()?)?),
+                "--until" => until = Some(Condition::parse(&value()?)?),
+                "--dump" => dump = Some(value()?),
+                _ => return Err(format!("Unknown argument '{arg}'")),
+            }
+        }
+
+        let source = source.ok_or("One of --story, --save or --replay must be given")?;
+        if duration.is_none() && until.is_none() && !matches!(source, Source::Replay(_)) {
+            return Err("At least one of --duration or --until must be given".to_string());
+        }
+        Ok(Self { source, dt, duration, until, dump })
+    }
+}
+
+fn parse_seconds(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds > 0.0 => Ok(seconds),
+        _ => Err(format!("'{value}' is not a positive number of seconds")),
+    }
+}
+
+/// Replays also return the playback which drives the model
+fn load_model(source: &Source) -> Result<(Model, Option<Playback>), String> {
+    match source {
+        Source::Story(level) => {
+            let story_builder = story_builder(level).ok_or_else(|| format!("Unknown story '{level}'"))?;
+            // The story itself needs a view to evaluate its conditions, so only the model is kept
+            let (model, _, _, _) = story_builder.build();
+            Ok((model, None))
+        }
+        Source::Save(path) => {
+            let serialized = fs::read_to_string(path).map_err(|error| format!("Failed to read {path}: {error}"))?;
+            let model = Model::deserialize(&serialized).map_err(|error| error.to_string())?;
+            Ok((model, None))
+        }
+        Source::Replay(path) => {
+            let serialized = fs::read_to_string(path).map_err(|error| format!("Failed to read {path}: {error}"))?;
+            let replay = Replay::deserialize(&serialized).map_err(|error| error.to_string())?;
+            let (playback, model) = Playback::new(replay).map_err(|error| error.to_string())?;
+            Ok((model, Some(playback)))
+        }
+    }
+}
+
+/// Steps the model at a fixed dt until the duration elapses or the condition is met.
+/// Returns whether the condition was met.
+fn run(model: &mut Model, args: &Args) -> bool {
+    // Stories often start paused or waiting on dialogue, which nothing will dismiss here
+    model.force_unpause();
+    model.cancel_warp();
+    model.set_time_step(TimeStep::Level { level: 1, paused: false });
+
+    let end_time = args.duration.map(|duration| model.time() + duration);
+    loop {
+        if args.until.as_ref().is_some_and(|until| until.is_met(model)) {
+            return true;
+        }
+        if end_time.is_some_and(|end_time| model.time() >= end_time) {
+            return false;
+        }
+        let dt = end_time.map_or(args.dt, |end_time| f64::min(args.dt, end_time - model.time()));
+        model.update(dt);
+    }
+}
+
+/// Plays the replay frame by frame until it ends, the duration elapses or the condition is met.
+/// Returns whether the condition was met.
+fn run_replay(model: &mut Model, playback: &mut Playback, args: &Args) -> bool {
+    let end_time = args.duration.map(|duration| model.time() + duration);
+    while !playback.is_finished() {
+        if args.until.as_ref().is_some_and(|until| until.is_met(model)) {
+            return true;
+        }
+        if end_time.is_some_and(|end_time| model.time() >= end_time) {
+            return false;
+        }
+        playback.step(model);
+    }
+    args.until.as_ref().is_some_and(|until| until.is_met(model))
+}
+
+fn print_summary(model: &Model) {
+    println!("Time: {:.3} s", model.time());
+    println!("State hash: {:016x}", model.state_hash());
+
+    let mut vessels: Vec<_> = model.entities(vec![ComponentType::VesselComponent, ComponentType::NameComponent])
+        .into_iter()
+        .map(|entity| (model.name_component(entity).name(), entity))
+        .collect();
+    vessels.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (name, entity) in vessels {
+        let vessel_component = model.vessel_component(entity);
+        let position = model.absolute_position(entity);
+        let velocity = model.absolute_velocity(entity);
+        let parent = model.parent(entity).map_or_else(|| "none".to_string(), |parent| model.name_component(parent).name());
+        println!(
+            "{name}: class={:?} faction={} parent={parent} position=({:.3}, {:.3}) velocity=({:.3}, {:.3}) fuel={:.3} kg",
+            vessel_component.class(),
+            model.faction_name(vessel_component.faction()),
+            position.x, position.y,
+            velocity.x, velocity.y,
+            vessel_component.fuel_kg(),
+        );
+    }
+}
+
+fn setup_logging() {
+    // Logs go to stderr so stdout only contains the summary
+    let layer = Layer::new().compact()
+        .with_writer(std::io::stderr);
+    let filter = EnvFilter::builder()
+        .with_default_directive("warn".parse().expect("Failed to parse default directive"))
+        .from_env_lossy();
+    tracing_subscriber::registry()
+        .with(layer)
+        .with(filter)
+        .init();
+}
+
+fn main() -> ExitCode {
+    setup_logging();
+
+    for error in load_vessel_class_definitions(Path::new(VESSEL_CLASS_DIRECTORY)) {
+        error!("Failed to load vessel class definition: {error}");
+    }
+
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let (mut model, mut playback) = match load_model(&args.source) {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            error!("Failed to load model: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(Err(error)) = args.until.as_ref().map(|until| until.validate(&model)) {
+        eprintln!("{error}\n\n{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    let condition_met = if let Some(playback) = &mut playback {
+        info!("Playing replay of {} frames", playback.frame_count());
+        run_replay(&mut model, playback, &args)
+    } else {
+        info!("Running headless simulation with dt={}", args.dt);
+        run(&mut model, &args)
+    };
+
+    print_summary(&model);
+    if let Some(playback) = &playback {
+        println!("Replay: {}/{} frames", playback.frame(), playback.frame_count());
+    }
+
+    if let Some(path) = &args.dump {
+        let serialized = match model.serialize() {
+            Ok(serialized) => serialized,
+            Err(error) => {
+                error!("Failed to serialize model: {error}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if let Err(error) = fs::write(path, serialized) {
+            error!("Failed to write {path}: {error}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if let Some(frame) = playback.as_ref().and_then(Playback::desync) {
+        eprintln!("Replay diverged from the recording at frame {frame}");
+        return ExitCode::FAILURE;
+    }
+
+    // A condition that was never met is treated as a failure so CI can assert on outcomes
+    if args.until.is_some() && !condition_met {
+        eprintln!("Condition was not met after {:.3} s", model.time());
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
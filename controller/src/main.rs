@@ -1,12 +1,13 @@
-use std::{error::Error, fs::{create_dir_all, File}, sync::Arc, time::Instant};
+use std::{env, error::Error, fs::{self, create_dir_all, File}, path::Path, sync::Arc, time::Instant};
 
 use completed_levels::CompletedLevels;
 use eframe::{egui::{Context, Key, ViewportBuilder, ViewportCommand}, glow::{self, HasContext, RENDERER, SHADING_LANGUAGE_VERSION, VERSION}, run_native, App, CreationContext, Frame, NativeOptions};
 use event_handler::{exit_level, finish_level, load_game, new_game, quit};
-use log::{debug, info};
+use log::{debug, error, info};
 use sysinfo::System;
 use tracing_subscriber::{fmt::Layer, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
-use transfer_window_view::{controller_events::ControllerEvent, menu, resources::Resources, Scene};
+use transfer_window_model::components::vessel_component::class_definition::{load_vessel_class_definitions, VESSEL_CLASS_DIRECTORY};
+use transfer_window_view::{controller_events::ControllerEvent, game::{self, network::client::LockstepClient, replay::Replay}, menu, resources::Resources, Scene};
 
 mod event_handler;
 mod completed_levels;
@@ -28,9 +29,15 @@ fn log_gl_info(gl: &Arc<glow::Context>) {
     info!("GLSL version: {}", unsafe { gl.get_parameter_string(SHADING_LANGUAGE_VERSION) });
 }
 
+fn load_replay(path: &str) -> Result<Replay, DynError> {
+    let serialized = fs::read_to_string(path)?;
+    Ok(Replay::deserialize(&serialized)?)
+}
+
 impl Controller {
+    /// Plays the replay at `replay` or connects to the multiplayer server at `connect` if either is given, otherwise starts at the menu
     #[allow(clippy::unnecessary_wraps)]
-    pub fn init(creation_context: &CreationContext) -> Result<Box<dyn App>, DynError> {
+    pub fn init(creation_context: &CreationContext, connect: Option<&str>, replay: Option<&str>) -> Result<Box<dyn App>, DynError> {
         #[cfg(feature = "profiling")]
         let _span = tracy_client::span!("Controller initialisation");
 
@@ -43,7 +50,32 @@ impl Controller {
         egui_extras::install_image_loaders(&creation_context.egui_ctx);
         let gl = creation_context.gl.as_ref().unwrap().clone();
         let resources = Arc::new(Resources::new(&creation_context.egui_ctx, &gl));
-        let view = Scene::Menu(menu::View::new(resources.clone(), &creation_context.egui_ctx, gl.clone()));
+        let menu = || Scene::Menu(menu::View::new(resources.clone(), &creation_context.egui_ctx, gl.clone()));
+        let view = if let Some(path) = replay {
+            match load_replay(path).and_then(|replay| Ok(game::View::new_playback(gl.clone(), replay, creation_context.egui_ctx.clone(), resources.clone())?)) {
+                Ok(view) => {
+                    info!("Playing replay {path}");
+                    Scene::Game(view)
+                }
+                Err(error) => {
+                    error!("Failed to load replay {path}: {error}");
+                    menu()
+                }
+            }
+        } else if let Some(address) = connect {
+            match LockstepClient::connect(address) {
+                Ok((lockstep, model)) => {
+                    info!("Connected to {address} as client {}", lockstep.client());
+                    Scene::Game(game::View::new_multiplayer(gl.clone(), model, lockstep, creation_context.egui_ctx.clone(), resources.clone()))
+                }
+                Err(error) => {
+                    error!("Failed to connect to {address}: {error}");
+                    menu()
+                }
+            }
+        } else {
+            menu()
+        };
         let last_frame = Instant::now();
         let load_menu = false;
         let completed_levels = CompletedLevels::load();
@@ -134,10 +166,24 @@ fn setup_logging() {
 fn main() {
     setup_logging();
 
+    for error in load_vessel_class_definitions(Path::new(VESSEL_CLASS_DIRECTORY)) {
+        error!("Failed to load vessel class definition: {error}");
+    }
+
     let options = NativeOptions {
         viewport: ViewportBuilder::default(),
         ..Default::default()
     };
 
-    let _ = run_native("Transfer Window", options, Box::new(Controller::init));
+    // Starts a multiplayer game, see transfer-window-server
+    let args: Vec<String> = env::args().collect();
+    let value = |name: &str| args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .cloned();
+    let connect = value("--connect");
+    // Watches a replay, such as data/replays/latest.json
+    let replay = value("--replay");
+
+    let _ = run_native("Transfer Window", options, Box::new(move |creation_context| Controller::init(creation_context, connect.as_deref(), replay.as_deref())));
 }
@@ -0,0 +1,138 @@
+use std::{env, fs, path::Path, process::ExitCode};
+
+use log::{error, info};
+use tracing_subscriber::{fmt::Layer, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use transfer_window_model::{components::vessel_component::{class_definition::{load_vessel_class_definitions, VESSEL_CLASS_DIRECTORY}, faction::Faction}, model::Model};
+use transfer_window_view::game::{network::server::LockstepServer, storyteller::stories::story_builder};
+
+const USAGE: &str = "Usage: transfer-window-server (--story <level> | --save <path>) [--address <address>] [--factions <faction>,<faction>...] [--tick <seconds>]
+
+Starts a multiplayer game once one client has connected for each faction.
+Clients connect with: transfer-window-controller --connect <address>
+
+Defaults: --address 127.0.0.1:7878 --factions player,enemy --tick 0.05";
+
+enum Source {
+    Story(String),
+    Save(String),
+}
+
+struct Args {
+    source: Source,
+    address: String,
+    factions: Vec<String>,
+    tick_dt: f64,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut source = None;
+        let mut address = "127.0.0.1:7878".to_string();
+        let mut factions = vec!["player".to_string(), "enemy".to_string()];
+        let mut tick_dt = 0.05;
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("Missing value for {arg}"));
+            match arg.as_str() {
+                "--story" => source = Some(Source::Story(value()?)),
+                "--save" => source = Some(Source::Save(value()?)),
+                "--address" => address = value()?,
+                "--factions" => factions = value()?.split(',').map(str::to_string).collect(),
+                "--tick" => tick_dt = parse_seconds(&value()?)?,
+                _ => return Err(format!("Unknown argument '{arg}'")),
+            }
+        }
+
+        let source = source.ok_or("One of --story or --save must be given")?;
+        Ok(Self { source, address, factions, tick_dt })
+    }
+}
+
+fn parse_seconds(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds > 0.0 => Ok(seconds),
+        _ => Err(format!("'{value}' is not a positive number of seconds")),
+    }
+}
+
+fn load_model(source: &Source) -> Result<Model, String> {
+    match source {
+        Source::Story(level) => {
+            let story_builder = story_builder(level).ok_or_else(|| format!("Unknown story '{level}'"))?;
+            // Stories are single player, so only their starting model is used
+            let (model, _, _, _) = story_builder.build();
+            Ok(model)
+        }
+        Source::Save(path) => {
+            let serialized = fs::read_to_string(path).map_err(|error| format!("Failed to read {path}: {error}"))?;
+            Model::deserialize(&serialized).map_err(|error| error.to_string())
+        }
+    }
+}
+
+fn setup_logging() {
+    let layer = Layer::new().compact()
+        .with_writer(std::io::stderr);
+    let filter = EnvFilter::builder()
+        .with_default_directive("info".parse().expect("Failed to parse default directive"))
+        .from_env_lossy();
+    tracing_subscriber::registry()
+        .with(layer)
+        .with(filter)
+        .init();
+}
+
+fn main() -> ExitCode {
+    setup_logging();
+
+    for error in load_vessel_class_definitions(Path::new(VESSEL_CLASS_DIRECTORY)) {
+        error!("Failed to load vessel class definition: {error}");
+    }
+
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let model = match load_model(&args.source) {
+        Ok(model) => model,
+        Err(error) => {
+            error!("Failed to load model: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let factions: Result<Vec<Faction>, String> = args.factions.iter()
+        .map(|name| model.faction_named(name).ok_or_else(|| format!("Unknown faction '{name}'")))
+        .collect();
+    let factions = match factions {
+        Ok(factions) => factions,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut server = match LockstepServer::bind(&args.address, &model, args.tick_dt) {
+        Ok(server) => server,
+        Err(error) => {
+            error!("Failed to start server on {}: {error}", args.address);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    info!("Waiting for {} clients on {}", factions.len(), args.address);
+    if let Err(error) = server.accept(&factions) {
+        error!("Failed to accept clients: {error}");
+        return ExitCode::FAILURE;
+    }
+
+    info!("All clients connected, starting game");
+    if let Err(error) = server.run() {
+        info!("Game ended: {error}");
+    }
+    ExitCode::SUCCESS
+}
@@ -4,16 +4,19 @@ use serde::{Deserialize, Serialize};
 
 use super::path_component::{orbit::Orbit, segment::Segment};
 
-use self::atmosphere::Atmosphere;
+use self::{atmosphere::Atmosphere, deposit::ResourceDeposit};
 
 pub mod atmosphere;
 pub mod builder;
+pub mod deposit;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 pub enum OrbitableType {
     Star,
     Planet,
     Moon,
+    /// Too small to have much gravity, but can be mined
+    Asteroid,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, QuickImpl)]
@@ -62,7 +65,10 @@ pub struct OrbitableComponent {
     rotation_angle: f64,
     type_: OrbitableType,
     physics: OrbitableComponentPhysics,
-    atmosphere: Option<Atmosphere>
+    atmosphere: Option<Atmosphere>,
+    /// Orbitables from saves before mining existed have no deposits
+    #[serde(default)]
+    deposits: Vec<ResourceDeposit>,
 }
 
 impl OrbitableComponent {
@@ -75,7 +81,13 @@ impl OrbitableComponent {
         physics: OrbitableComponentPhysics,
         atmosphere: Option<Atmosphere>,
     ) -> Self {
-        Self { mass, radius, rotation_period, rotation_angle, type_, physics, atmosphere }
+        let deposits = vec![];
+        Self { mass, radius, rotation_period, rotation_angle, type_, physics, atmosphere, deposits }
+    }
+
+    pub fn with_deposits(mut self, deposits: Vec<ResourceDeposit>) -> Self {
+        self.deposits = deposits;
+        self
     }
 
     pub fn mass(&self) -> f64 {
@@ -149,4 +161,16 @@ impl OrbitableComponent {
     pub fn atmosphere(&self) -> Option<&Atmosphere> {
         self.atmosphere.as_ref()
     }
+
+    pub fn is_asteroid(&self) -> bool {
+        matches!(self.type_, OrbitableType::Asteroid)
+    }
+
+    pub fn deposits(&self) -> &[ResourceDeposit] {
+        &self.deposits
+    }
+
+    pub(crate) fn deposits_mut(&mut self) -> &mut [ResourceDeposit] {
+        &mut self.deposits
+    }
 }
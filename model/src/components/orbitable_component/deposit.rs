@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::components::vessel_component::resource::ResourceType;
+
+/// How close to the surface a vessel has to be to mine
+pub const MINING_DISTANCE: f64 = 1.0e3;
+pub const MINING_SPEED: f64 = 10.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResourceDeposit {
+    resource: ResourceType,
+    remaining: f64,
+}
+
+impl ResourceDeposit {
+    /// # Panics
+    /// Panics if `resource` cannot be carried as cargo
+    pub fn new(resource: ResourceType, amount: f64) -> Self {
+        assert!(resource.is_cargo(), "Attempt to create deposit of {resource:?}");
+        Self { resource, remaining: amount }
+    }
+
+    pub fn resource(&self) -> ResourceType {
+        self.resource
+    }
+
+    pub fn remaining(&self) -> f64 {
+        self.remaining
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.remaining < 1.0e-3
+    }
+
+    /// Returns how much was actually extracted, which is less than
+    /// `amount` if the deposit runs out
+    pub(crate) fn extract(&mut self, amount: f64) -> f64 {
+        let amount = f64::min(amount, self.remaining);
+        self.remaining -= amount;
+        amount
+    }
+}
@@ -9,6 +9,7 @@ use self::{burn::Burn, guidance::Guidance, orbit::Orbit, segment::Segment};
 #[cfg(test)]
 mod brute_force_tester;
 pub mod burn;
+pub mod drag;
 pub mod guidance;
 pub mod orbit;
 pub mod rocket_equation_function;
@@ -86,6 +87,8 @@ impl PathComponent {
         for segment in self.future_segments.iter().rev() {
             match segment {
                 Segment::Orbit(orbit) => orbits.push(orbit),
+                // Drag segments come from prediction rather than being planned, so they count as part of the predicted orbits
+                Segment::Drag(_) => (),
                 Segment::Burn(_) | Segment::Guidance(_) | Segment::Turn(_) => break,
             }
         }
@@ -223,6 +226,7 @@ impl PathComponent {
             Segment::Burn(burn) => burn.current_point().mass(),
             Segment::Guidance(guidance) => guidance.current_point().mass(),
             Segment::Turn(turn) => turn.current_point().mass(),
+            Segment::Drag(drag) => drag.mass(),
         }
     }
 
@@ -232,6 +236,7 @@ impl PathComponent {
             Segment::Burn(burn) => burn.point_at_time(time).mass(),
             Segment::Guidance(guidance) => guidance.point_at_time(time).mass(),
             Segment::Turn(turn) => turn.point_at_time(time).mass(),
+            Segment::Drag(drag) => drag.mass(),
         }
     }
 
@@ -280,6 +285,16 @@ impl PathComponent {
                     }
                 }
 
+                Segment::Drag(drag) => {
+                    if drag.start_point().time() >= time {
+                        self.future_segments.pop_back();
+                    } else if drag.is_time_within_drag(time) {
+                        drag.end_at(time);
+                    } else {
+                        return;
+                    }
+                },
+
                 Segment::Orbit(orbit) => {
                     if orbit.start_point().time() >= time {
                         self.future_segments.pop_back();
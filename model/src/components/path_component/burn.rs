@@ -11,6 +11,9 @@ pub mod builder;
 pub mod burn_point;
 
 const BURN_TIME_STEP: f64 = 0.1;
+/// Burns are clamped to slightly less than the maximum delta-v, because
+/// burning exactly the maximum can leave the fuel a rounding error below zero
+const MAX_DV_MARGIN: f64 = 0.000_000_1;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Burn {
@@ -42,7 +45,7 @@ impl Burn {
         let max_dv = RocketEquationFunction::new(mass_without_fuel, fuel_kg, engine.fuel_kg_per_second(), engine.specific_impulse())
             .remaining_dv();
         if dv.magnitude() > max_dv {
-            dv = dv.normalize() * max_dv;
+            dv = dv.normalize() * (max_dv - MAX_DV_MARGIN);
         }
 
         let start_point = BurnPoint::new(parent_mass, mass_without_fuel, fuel_kg, time, position, velocity);
@@ -191,7 +194,7 @@ impl Burn {
     pub fn adjust(&mut self, adjustment: DVec2) {
         self.dv += adjustment;
         if self.dv.magnitude() > self.max_dv {
-            self.dv = self.dv.normalize() * (self.max_dv - 0.000_000_1);
+            self.dv = self.dv.normalize() * (self.max_dv - MAX_DV_MARGIN);
         }
 
         let start_point = self.start_point().clone();
@@ -0,0 +1,275 @@
+use nalgebra_glm::DVec2;
+use serde::{Deserialize, Serialize};
+use transfer_window_common::numerical_methods::itp::itp;
+
+use crate::{components::orbitable_component::atmosphere::Atmosphere, storage::entity_allocator::Entity};
+
+use self::drag_point::DragPoint;
+
+use super::orbit::scary_math::GRAVITATIONAL_CONSTANT;
+
+/// Atmospheres are rendered far thicker than any real one, so the
+/// density is scaled down to keep a pass through the upper layers
+/// survivable while still bleeding off a useful amount of speed
+const SURFACE_DENSITY: f64 = 1.0e-5;
+/// Drag coefficient multiplied by cross-sectional area (m^2)
+const DRAG_AREA: f64 = 100.0;
+const MAX_DRAG_TIME: f64 = 3.0 * 60.0 * 60.0;
+const DRAG_TIME_STEP: f64 = 2.0;
+
+pub mod builder;
+pub mod drag_point;
+
+/// Numerically integrated segment covering the time a vessel spends inside an atmosphere
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Drag {
+    parent: Entity,
+    parent_mass: f64,
+    parent_radius: f64,
+    atmosphere_height: f64,
+    density: f64,
+    falloff: f64,
+    mass: f64,
+    rotation: f64,
+    current_point: DragPoint,
+    points: Vec<DragPoint>,
+    hits_surface: bool,
+}
+
+impl Drag {
+    #[allow(clippy::too_many_arguments)]
+    pub(self) fn new(
+        parent: Entity,
+        parent_mass: f64,
+        parent_radius: f64,
+        atmosphere: &Atmosphere,
+        mass: f64,
+        rotation: f64,
+        start_time: f64,
+        start_position: DVec2,
+        start_velocity: DVec2,
+    ) -> Self {
+        let mut drag = Self {
+            parent,
+            parent_mass,
+            parent_radius,
+            atmosphere_height: atmosphere.height() * parent_radius,
+            density: atmosphere.density(),
+            falloff: atmosphere.falloff(),
+            mass,
+            rotation,
+            current_point: DragPoint::new(start_time, start_position, start_velocity, DVec2::zeros()),
+            points: vec![],
+            hits_surface: false,
+        };
+        let start_point = drag.point(start_time, start_position, start_velocity);
+        drag.current_point = start_point.clone();
+        drag.compute_points(start_point);
+        drag
+    }
+
+    fn density_at_altitude(&self, altitude: f64) -> f64 {
+        // Same profile the atmosphere shader uses
+        let x = altitude / self.atmosphere_height;
+        if !(0.0..1.0).contains(&x) {
+            return 0.0;
+        }
+        SURFACE_DENSITY * self.density * (1.0 - x) * f64::exp(-self.falloff * x)
+    }
+
+    fn drag_acceleration(&self, position: DVec2, velocity: DVec2) -> DVec2 {
+        let density = self.density_at_altitude(position.magnitude() - self.parent_radius);
+        -velocity * velocity.magnitude() * 0.5 * density * DRAG_AREA / self.mass
+    }
+
+    fn point(&self, time: f64, position: DVec2, velocity: DVec2) -> DragPoint {
+        DragPoint::new(time, position, velocity, self.drag_acceleration(position, velocity))
+    }
+
+    fn gravity_acceleration(&self, position: DVec2) -> DVec2 {
+        -position.normalize() * (GRAVITATIONAL_CONSTANT * self.parent_mass) / position.magnitude_squared()
+    }
+
+    /// Velocity Verlet, with the drag at the end of the step evaluated
+    /// using an Euler estimate of the velocity
+    fn next_point(&self, point: &DragPoint, delta_time: f64) -> DragPoint {
+        let acceleration = self.gravity_acceleration(point.position()) + point.drag_acceleration();
+        let position = point.position() + point.velocity() * delta_time + acceleration * delta_time.powi(2) / 2.0;
+        let estimated_velocity = point.velocity() + acceleration * delta_time;
+        let next_acceleration = self.gravity_acceleration(position) + self.drag_acceleration(position, estimated_velocity);
+        let velocity = point.velocity() + (acceleration + next_acceleration) * delta_time / 2.0;
+        self.point(point.time() + delta_time, position, velocity)
+    }
+
+    /// Integrates until the vessel leaves the atmosphere, hits the surface, or
+    /// we exceed the max drag time
+    fn compute_points(&mut self, start_point: DragPoint) {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Compute drag points");
+        let start_time = start_point.time();
+        self.points = vec![start_point];
+
+        loop {
+            let last = self.points.last().unwrap();
+            let altitude = last.position().magnitude() - self.parent_radius;
+
+            let ascending = last.position().dot(&last.velocity()) > 0.0;
+            if self.points.len() > 1 && altitude >= self.atmosphere_height && ascending {
+                return;
+            }
+
+            if last.time() - start_time > MAX_DRAG_TIME {
+                return;
+            }
+
+            let next = self.next_point(last, DRAG_TIME_STEP);
+            if next.position().magnitude() <= self.parent_radius {
+                // Find exactly when we reach the surface so the segment ends on it
+                let depth = |delta_time: f64| self.parent_radius - self.next_point(last, delta_time).position().magnitude();
+                let next = match itp(&depth, 0.0, DRAG_TIME_STEP) {
+                    Ok(delta_time) => self.next_point(last, delta_time),
+                    Err(_) => next,
+                };
+                self.points.push(next);
+                self.hits_surface = true;
+                return;
+            }
+            self.points.push(next);
+        }
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    pub fn start_point(&self) -> &DragPoint {
+        self.points.first().unwrap()
+    }
+
+    pub fn current_point(&self) -> &DragPoint {
+        &self.current_point
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    pub fn end_point(&self) -> &DragPoint {
+        self.points.last().unwrap()
+    }
+
+    /// `time` is absolute
+    pub fn point_at_time(&self, time: f64) -> DragPoint {
+        if time >= self.end_point().time() {
+            return self.end_point().clone();
+        }
+        let time_since_start = time - self.start_point().time();
+        let index = (time_since_start / DRAG_TIME_STEP) as usize;
+        match self.points.get(index) {
+            Some(closest_previous_point) => self.next_point(closest_previous_point, time - closest_previous_point.time()),
+            None => self.end_point().clone(),
+        }
+    }
+
+    /// Cuts the segment short, for example when a burn is placed partway through
+    pub fn end_at(&mut self, time: f64) {
+        let end_point = self.point_at_time(time);
+        self.points.retain(|point| point.time() < time);
+        self.points.push(end_point);
+        self.hits_surface = false;
+    }
+
+    pub fn parent(&self) -> Entity {
+        self.parent
+    }
+
+    pub fn parent_mass(&self) -> f64 {
+        self.parent_mass
+    }
+
+    pub fn mass(&self) -> f64 {
+        self.mass
+    }
+
+    pub fn rotation(&self) -> f64 {
+        self.rotation
+    }
+
+    pub fn duration(&self) -> f64 {
+        self.end_point().time() - self.start_point().time()
+    }
+
+    pub fn remaining_time(&self) -> f64 {
+        self.end_point().time() - self.current_point().time()
+    }
+
+    /// Whether the segment ends because the vessel reached the surface
+    pub fn hits_surface(&self) -> bool {
+        self.hits_surface
+    }
+
+    pub fn is_time_within_drag(&self, time: f64) -> bool {
+        time > self.start_point().time() && time < self.end_point().time()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current_point.time() >= self.end_point().time()
+    }
+
+    pub fn reset(&mut self) {
+        self.current_point = self.start_point().clone();
+    }
+
+    pub fn next(&mut self, time: f64) {
+        self.current_point = self.point_at_time(time);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ecolor::Color32;
+    use nalgebra_glm::vec2;
+
+    use crate::{components::{orbitable_component::atmosphere::Atmosphere, path_component::orbit::scary_math::GRAVITATIONAL_CONSTANT}, storage::entity_allocator::Entity, test_util::{EARTH_MASS, EARTH_RADIUS}};
+
+    use super::builder::DragBuilder;
+
+    fn circular_drag(altitude: f64, density: f64) -> super::Drag {
+        let radius = EARTH_RADIUS + altitude;
+        let speed = f64::sqrt(GRAVITATIONAL_CONSTANT * EARTH_MASS / radius);
+        DragBuilder {
+            parent: Entity::mock(),
+            parent_mass: EARTH_MASS,
+            parent_radius: EARTH_RADIUS,
+            atmosphere: Atmosphere::new(Color32::WHITE, density, 0.3, 4.0, vec![]),
+            mass: 1.0e4,
+            rotation: 0.0,
+            time: 0.0,
+            position: vec2(radius, 0.0),
+            velocity: vec2(0.0, speed),
+        }.build()
+    }
+
+    #[test]
+    fn test_without_density_keeps_altitude() {
+        let drag = circular_drag(1.0e6, 0.0);
+        for time in [100.0, 1000.0, 5000.0] {
+            let altitude = drag.point_at_time(time).position().magnitude() - EARTH_RADIUS;
+            assert!((altitude - 1.0e6).abs() < 1.0e3, "{altitude}");
+        }
+    }
+
+    #[test]
+    fn test_drag_decays_orbit() {
+        let drag = circular_drag(1.0e6, 0.95);
+        let start_speed = drag.start_point().velocity().magnitude();
+        let start_energy = start_speed.powi(2) / 2.0 - GRAVITATIONAL_CONSTANT * EARTH_MASS / drag.start_point().position().magnitude();
+        let end_speed = drag.end_point().velocity().magnitude();
+        let end_energy = end_speed.powi(2) / 2.0 - GRAVITATIONAL_CONSTANT * EARTH_MASS / drag.end_point().position().magnitude();
+        assert!(end_energy < start_energy);
+        assert!(drag.end_point().position().magnitude() < drag.start_point().position().magnitude());
+    }
+
+    #[test]
+    fn test_end_at() {
+        let mut drag = circular_drag(1.0e6, 0.95);
+        let expected = drag.point_at_time(101.0).position();
+        drag.end_at(101.0);
+        assert!((drag.end_point().time() - 101.0).abs() < 1.0e-9);
+        assert!((drag.end_point().position() - expected).magnitude() < 1.0e-6);
+    }
+}
@@ -0,0 +1,24 @@
+use nalgebra_glm::DVec2;
+
+use crate::{components::orbitable_component::atmosphere::Atmosphere, storage::entity_allocator::Entity};
+
+use super::Drag;
+
+#[derive(Debug, Clone)]
+pub struct DragBuilder {
+    pub parent: Entity,
+    pub parent_mass: f64,
+    pub parent_radius: f64,
+    pub atmosphere: Atmosphere,
+    pub mass: f64,
+    pub rotation: f64,
+    pub time: f64,
+    pub position: DVec2,
+    pub velocity: DVec2,
+}
+
+impl DragBuilder {
+    pub fn build(self) -> Drag {
+        Drag::new(self.parent, self.parent_mass, self.parent_radius, &self.atmosphere, self.mass, self.rotation, self.time, self.position, self.velocity)
+    }
+}
@@ -0,0 +1,32 @@
+use nalgebra_glm::DVec2;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DragPoint {
+    time: f64,
+    position: DVec2,
+    velocity: DVec2,
+    drag_acceleration: DVec2,
+}
+
+impl DragPoint {
+    pub fn new(time: f64, position: DVec2, velocity: DVec2, drag_acceleration: DVec2) -> Self {
+        Self { time, position, velocity, drag_acceleration }
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn position(&self) -> DVec2 {
+        self.position
+    }
+
+    pub fn velocity(&self) -> DVec2 {
+        self.velocity
+    }
+
+    pub fn drag_acceleration(&self) -> DVec2 {
+        self.drag_acceleration
+    }
+}
@@ -48,6 +48,11 @@ fn compute_guidance_points(
     let _span = tracy_client::span!("Compute guidance points");
     let mut points = vec![start_point.clone()];
 
+    // Targets which have never been seen cannot be located at all
+    if model.snapshot_at_last_known(start_point.time(), faction).parent(target).is_none() {
+        return (false, points);
+    }
+
     loop {
         let last = points.last().unwrap();
         let time = last.time();
@@ -66,7 +71,7 @@ fn compute_guidance_points(
         let distance_at_delta_time = |delta_time: f64| {
             let point = last.next(delta_time, engine);
             let parent_absolute_position = model.snapshot_at_observe(point.time(), faction).absolute_position(parent);
-            let target_absolute_position = model.snapshot_at_observe(point.time(), faction).absolute_position(target);
+            let target_absolute_position = model.snapshot_at_last_known(point.time(), faction).absolute_position(target);
             (parent_absolute_position + point.position() - target_absolute_position).magnitude()
         };
         let distance_prime_at_delta_time = |delta_time: f64| {
@@ -90,8 +95,8 @@ fn compute_guidance_points(
         // Calculate acceleration
         let absolute_position = model.snapshot_at_observe(time, faction).absolute_position(parent) + last.position();
         let absolute_velocity = model.snapshot_at_observe(time, faction).absolute_velocity(parent) + last.velocity();
-        let target_absolute_position = model.snapshot_at_observe(time, faction).absolute_position(target);
-        let target_absolute_velocity = model.snapshot_at_observe(time, faction).absolute_velocity(target);
+        let target_absolute_position = model.snapshot_at_last_known(time, faction).absolute_position(target);
+        let target_absolute_velocity = model.snapshot_at_last_known(time, faction).absolute_velocity(target);
         let requested_acceleration = proportional_guidance_acceleration(absolute_position, target_absolute_position, absolute_velocity, target_absolute_velocity);
 
         // Make sure guidance acceleration does not exceed max acceleration
@@ -1,4 +1,5 @@
 use nalgebra_glm::{vec2, DVec2};
+use serde::{Deserialize, Serialize};
 
 use crate::{model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
 
@@ -22,13 +23,19 @@ impl OrbitBuilder {
 }
 
 // For an entity's first orbit
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum InitialOrbitBuilder {
     Circular  { parent: Entity, distance: f64, angle: f64, direction: OrbitDirection },
     Freeform  { parent: Entity, distance: f64, angle: f64, direction: OrbitDirection, speed: f64 }
 }
 
 impl InitialOrbitBuilder {
+    pub fn parent(&self) -> Entity {
+        match self {
+            InitialOrbitBuilder::Circular { parent, .. } | InitialOrbitBuilder::Freeform { parent, .. } => *parent,
+        }
+    }
+
     pub fn build(self, model: &Model, mass: f64) -> Orbit {
         match self {
             InitialOrbitBuilder::Circular { parent, distance, angle, direction } => {
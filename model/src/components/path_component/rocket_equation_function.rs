@@ -26,7 +26,7 @@ impl RocketEquationFunction {
     /// # Panics
     /// Panics if the vessel component has no engine installed
     pub fn fuel_from_vessel_component(vessel_component: &VesselComponent) -> Self {
-        let dry_mass_kg = vessel_component.dry_mass();
+        let dry_mass_kg = vessel_component.empty_mass();
         let initial_fuel_mass_kg = vessel_component.fuel_kg();
         let fuel_consumption_kg_per_second = vessel_component.fuel_kg_per_second();
         let specific_impulse = vessel_component.specific_impulse().unwrap();
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::storage::entity_allocator::Entity;
 
-use super::{burn::Burn, guidance::Guidance, orbit::Orbit, turn::Turn};
+use super::{burn::Burn, drag::Drag, guidance::Guidance, orbit::Orbit, turn::Turn};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum Segment {
@@ -11,6 +11,7 @@ pub enum Segment {
     Burn(Burn),
     Guidance(Guidance),
     Turn(Turn),
+    Drag(Drag),
 }
 
 impl Segment {
@@ -20,6 +21,7 @@ impl Segment {
             Segment::Burn(burn) => burn.start_point().time(),
             Segment::Guidance(guidance) => guidance.start_point().time(),
             Segment::Turn(turn) => turn.start_point().time(),
+            Segment::Drag(drag) => drag.start_point().time(),
         }
     }
 
@@ -29,6 +31,7 @@ impl Segment {
             Segment::Burn(burn) => burn.start_point().mass(),
             Segment::Guidance(guidance) => guidance.start_point().mass(),
             Segment::Turn(turn) => turn.start_point().mass(),
+            Segment::Drag(drag) => drag.mass(),
         }
     }
 
@@ -38,6 +41,7 @@ impl Segment {
             Segment::Burn(burn) => burn.rotation(),
             Segment::Guidance(guidance) => guidance.start_point().rotation(),
             Segment::Turn(turn) => turn.start_point().rotation(),
+            Segment::Drag(drag) => drag.rotation(),
         }
     }
 
@@ -47,6 +51,7 @@ impl Segment {
             Segment::Burn(burn) => burn.start_point().position(),
             Segment::Guidance(guidance) => guidance.start_point().position(),
             Segment::Turn(turn) => turn.start_point().position(),
+            Segment::Drag(drag) => drag.start_point().position(),
         }
     }
 
@@ -56,6 +61,7 @@ impl Segment {
             Segment::Burn(burn) => burn.start_point().velocity(),
             Segment::Guidance(guidance) => guidance.start_point().velocity(),
             Segment::Turn(turn) => turn.start_point().velocity(),
+            Segment::Drag(drag) => drag.start_point().velocity(),
         }
     }
 
@@ -65,6 +71,7 @@ impl Segment {
             Segment::Burn(burn) => burn.current_point().mass(),
             Segment::Guidance(guidance) => guidance.current_point().mass(),
             Segment::Turn(turn) => turn.current_point().mass(),
+            Segment::Drag(drag) => drag.mass(),
         }
     }
 
@@ -74,6 +81,7 @@ impl Segment {
             Segment::Burn(burn) => burn.rotation(),
             Segment::Guidance(guidance) => guidance.current_point().rotation(),
             Segment::Turn(turn) => turn.current_point().rotation(),
+            Segment::Drag(drag) => drag.rotation(),
         }
     }
 
@@ -83,6 +91,7 @@ impl Segment {
             Segment::Burn(burn) => burn.current_point().position(),
             Segment::Guidance(guidance) => guidance.current_point().position(),
             Segment::Turn(turn) => turn.current_point().position(),
+            Segment::Drag(drag) => drag.current_point().position(),
         }
     }
 
@@ -92,6 +101,7 @@ impl Segment {
             Segment::Burn(burn) => burn.current_point().velocity(),
             Segment::Guidance(guidance) => guidance.current_point().velocity(),
             Segment::Turn(turn) => turn.current_point().velocity(),
+            Segment::Drag(drag) => drag.current_point().velocity(),
         }
     }
 
@@ -101,6 +111,7 @@ impl Segment {
             Segment::Burn(burn) => burn.end_point().time(),
             Segment::Guidance(guidance) => guidance.end_point().time(),
             Segment::Turn(turn) => turn.end_point().time(),
+            Segment::Drag(drag) => drag.end_point().time(),
         }
     }
 
@@ -110,6 +121,7 @@ impl Segment {
             Segment::Burn(burn) => burn.end_point().mass(),
             Segment::Guidance(guidance) => guidance.end_point().mass(),
             Segment::Turn(turn) => turn.end_point().mass(),
+            Segment::Drag(drag) => drag.mass(),
         }
     }
 
@@ -119,6 +131,7 @@ impl Segment {
             Segment::Burn(burn) => burn.rotation(),
             Segment::Guidance(guidance) => guidance.end_point().rotation(),
             Segment::Turn(turn) => turn.end_point().rotation(),
+            Segment::Drag(drag) => drag.rotation(),
         }
     }
 
@@ -128,6 +141,7 @@ impl Segment {
             Segment::Burn(burn) => burn.end_point().position(),
             Segment::Guidance(guidance) => guidance.end_point().position(),
             Segment::Turn(turn) => turn.end_point().position(),
+            Segment::Drag(drag) => drag.end_point().position(),
         }
     }
 
@@ -137,6 +151,7 @@ impl Segment {
             Segment::Burn(burn) => burn.end_point().velocity(),
             Segment::Guidance(guidance) => guidance.end_point().velocity(),
             Segment::Turn(turn) => turn.end_point().velocity(),
+            Segment::Drag(drag) => drag.end_point().velocity(),
         }
     }
 
@@ -146,6 +161,7 @@ impl Segment {
             Segment::Burn(burn) => burn.point_at_time(time).mass(),
             Segment::Guidance(guidance) => guidance.point_at_time(time).mass(),
             Segment::Turn(turn) => turn.point_at_time(time).mass(),
+            Segment::Drag(drag) => drag.mass(),
         }
     }
 
@@ -155,6 +171,7 @@ impl Segment {
             Segment::Burn(burn) => burn.rotation(),
             Segment::Guidance(guidance) => guidance.point_at_time(time).rotation(),
             Segment::Turn(turn) => turn.point_at_time(time).rotation(),
+            Segment::Drag(drag) => drag.rotation(),
         }
     }
 
@@ -164,6 +181,7 @@ impl Segment {
             Segment::Burn(burn) => burn.point_at_time(time).position(),
             Segment::Guidance(guidance) => guidance.point_at_time(time).position(),
             Segment::Turn(turn) => turn.point_at_time(time).position(),
+            Segment::Drag(drag) => drag.point_at_time(time).position(),
         }
     }
 
@@ -173,6 +191,7 @@ impl Segment {
             Segment::Burn(burn) => burn.point_at_time(time).velocity(),
             Segment::Guidance(guidance) => guidance.point_at_time(time).velocity(),
             Segment::Turn(turn) => turn.point_at_time(time).velocity(),
+            Segment::Drag(drag) => drag.point_at_time(time).velocity(),
         }
     }
 
@@ -182,6 +201,7 @@ impl Segment {
             Segment::Burn(burn) => burn.parent(),
             Segment::Guidance(guidance) => guidance.parent(),
             Segment::Turn(turn) => turn.parent(),
+            Segment::Drag(drag) => drag.parent(),
         }
     }
 
@@ -191,6 +211,7 @@ impl Segment {
             Segment::Burn(burn) => burn.is_finished(),
             Segment::Guidance(guidance) => guidance.is_finished(),
             Segment::Turn(turn) => turn.is_finished(),
+            Segment::Drag(drag) => drag.is_finished(),
         }
     }
 
@@ -210,6 +231,10 @@ impl Segment {
         matches!(self, Segment::Turn(_))
     }
 
+    pub fn is_drag(&self) -> bool {
+        matches!(self, Segment::Drag(_))
+    }
+
     pub fn duration(&self) -> f64 {
         self.end_time() - self.start_time()
     }
@@ -278,12 +303,21 @@ impl Segment {
         }
     }
 
+    pub fn as_drag(&self) -> Option<&Drag> {
+        if let Segment::Drag(drag) = self {
+            Some(drag)
+        } else {
+            None
+        }
+    }
+
     pub fn next(&mut self, time: f64) {
         match self {
             Segment::Orbit(orbit) => orbit.next(time),
             Segment::Burn(burn) => burn.next(time),
             Segment::Guidance(guidance) => guidance.next(time),
             Segment::Turn(turn) => turn.next(time),
+            Segment::Drag(drag) => drag.next(time),
         }
     }
 }
@@ -1,14 +1,22 @@
 use std::collections::BTreeMap;
 
+use cargo_hold::CargoHold;
 use class::VesselClass;
+use class_definition::VesselClassDefinition;
 use docking::{Docking, DockingPort, DockingPortLocation};
 use engine::Engine;
 use faction::Faction;
 use fuel_tank::FuelTank;
+use gun::Gun;
+use health::{Health, Subsystem};
 use log::error;
-use nalgebra_glm::{vec2, DVec2};
-use rcs::{Rcs, RcsControlScheme, RcsThruster};
+use nalgebra_glm::DVec2;
+use point_defense::PointDefense;
+use rcs::Rcs;
+use resource::{ResourceStorage, ResourceType};
+use slots::{ModuleType, Slot};
 use serde::{Deserialize, Serialize};
+use shipyard::BuildQueue;
 use timeline::Timeline;
 use torpedo_launcher::TorpedoLauncher;
 use torpedo_storage::TorpedoStorage;
@@ -16,12 +24,20 @@ use crate::storage::entity_allocator::Entity;
 
 use super::path_component::orbit::scary_math::STANDARD_GRAVITY;
 
+pub mod cargo_hold;
 pub mod class;
+pub mod class_definition;
 pub mod docking;
 pub mod engine;
 pub mod faction;
 pub mod fuel_tank;
+pub mod gun;
+pub mod health;
+pub mod point_defense;
 pub mod rcs;
+pub mod resource;
+pub mod shipyard;
+pub mod slots;
 pub mod timeline;
 pub mod torpedo_launcher;
 pub mod torpedo_storage;
@@ -34,6 +50,13 @@ pub struct VesselComponent {
     faction: Faction,
     dry_mass: f64,
     is_ghost: bool,
+    /// Saves from before AI existed have no AI controlled vessels
+    #[serde(default)]
+    is_ai_controlled: bool,
+    /// Vessels from saves before damage existed have none, and are
+    /// destroyed by any hit
+    #[serde(default)]
+    health: Option<Health>,
     can_dock: bool,
     dimensions: DVec2,
     timeline: Timeline,
@@ -44,155 +67,123 @@ pub struct VesselComponent {
     torpedo_storage: Option<TorpedoStorage>,
     torpedo_launcher: Option<TorpedoLauncher>,
     docking: Option<Docking>,
+    /// Vessels from saves before cargo existed have no hold
+    #[serde(default)]
+    cargo_hold: Option<CargoHold>,
+    /// The asteroid being mined, if any
+    #[serde(default)]
+    mining: Option<Entity>,
+    /// Only provided by modules, so absent from saves before slots existed
+    #[serde(default)]
+    point_defense: Option<PointDefense>,
+    /// Only provided by modules, so absent from saves before slots existed
+    #[serde(default)]
+    gun: Option<Gun>,
+    /// Vessels from saves before slots existed have none
+    #[serde(default)]
+    slots: Vec<Slot>,
 }
 
 impl VesselComponent {
+    /// Creates a vessel with the stats of `class`, taken from its loaded
+    /// definition if there is one
     pub fn new(class: VesselClass, faction: Faction) -> Self {
-        match class {
-            VesselClass::Torpedo => Self { 
-                class, faction,
-                dry_mass: 2.0e3,
-                is_ghost: true,
-                can_dock: false,
-                dimensions: vec2(0.0, 0.0),
-                timeline: Timeline::default(),
-                target: None,
-                fuel_tank: Some(FuelTank::new(10_000.0)),
-                engine: Some(Engine::new(7.0, 15_000.0)),
-                rcs: None,
-                torpedo_storage: None,
-                torpedo_launcher: None,
-                docking: None,
-            },
-
-            VesselClass::Station => Self { 
-                class, faction,
-                dry_mass: 380.0e3,
-                is_ghost: false,
-                can_dock: false,
-                dimensions: vec2(2680.0, 2000.0),
-                timeline: Timeline::default(),
-                target: None,
-                fuel_tank: Some(FuelTank::new(140_000.0)),
-                engine: None,
-                rcs: None,
-                torpedo_storage: Some(TorpedoStorage::new(5)),
-                torpedo_launcher: None,
-                docking: Some(Docking::new(vec![DockingPortLocation::West, DockingPortLocation::East])),
-            },
-
-            VesselClass::Scout1 => Self {
-                class, faction,
-                dry_mass: 10.0e3,
-                is_ghost: false,
-                can_dock: true,
-                dimensions: vec2(540.0, 200.0),
-                timeline: Timeline::default(),
-                target: None,
-                fuel_tank: Some(FuelTank::new(30_000.0)),
-                engine: Some(Engine::new(30.0, 70_000.0)),
-                rcs: Some(Rcs::new(5.0, 
-                    vec![
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, 78.0), vec2(0.0, -1.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, 78.0), vec2(1.0, 0.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, 78.0), vec2(-1.0, 0.0)),
-
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, -78.0), vec2(0.0, 1.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, -78.0), vec2(1.0, 0.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, -78.0), vec2(-1.0, 0.0)),
-
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, 88.0), vec2(0.0, -1.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, 88.0), vec2(1.0, 0.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, 88.0), vec2(-1.0, 0.0)),
-
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, -88.0), vec2(0.0, 1.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, -88.0), vec2(1.0, 0.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, -88.0), vec2(-1.0, 0.0)),
-                    ],
-                    RcsControlScheme::new(None, Some(541.0), 
-                        vec![
-                            0.0, 0.0, 1.0,
-                            1.0, 1.0, 0.0,
-                            1.0, 0.0, 1.0,
-                            0.0, 1.0, 0.0
-                        ],
-                        vec![
-                            1.0, 1.0, 0.0,
-                            0.0, 0.0, 1.0,
-                            0.0, 1.0, 0.0,
-                            1.0, 0.0, 1.0
-                        ])
-                    )
-                ),
-                torpedo_storage: None,
-                torpedo_launcher: None,
-                docking: None,
-            },
-
-            VesselClass::Frigate1 => Self {
-                class, faction,
-                dry_mass: 30.0e3,
-                is_ghost: false,
-                can_dock: true,
-                dimensions: vec2(0.0, 0.0),
-                timeline: Timeline::default(),
-                target: None,
-                fuel_tank: Some(FuelTank::new(60_000.0)),
-                engine: Some(Engine::new(30.0, 70_000.0)),
-                rcs: None,
-                torpedo_storage: Some(TorpedoStorage::new(2)),
-                torpedo_launcher: Some(TorpedoLauncher::new(2.0 * 60.0 * 60.0)),
-                docking: None,
-            },
-
-            VesselClass::TestShip => Self {
-                class, faction,
-                dry_mass: 50.0e3,
-                is_ghost: false,
-                can_dock: true,
-                dimensions: vec2(0.0, 0.0),
-                timeline: Timeline::default(),
-                target: None,
-                fuel_tank: Some(FuelTank::new(60_000.0)),
-                engine: Some(Engine::new(10000.0, 10_000_000.0)),
-                rcs: Some(Rcs::new(5.0, 
-                    vec![
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, 78.0), vec2(0.0, -1.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, 78.0), vec2(1.0, 0.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, 78.0), vec2(-1.0, 0.0)),
-
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, -78.0), vec2(0.0, 1.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, -78.0), vec2(1.0, 0.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(10.0, -78.0), vec2(-1.0, 0.0)),
-
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, 88.0), vec2(0.0, -1.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, 88.0), vec2(1.0, 0.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, 88.0), vec2(-1.0, 0.0)),
-
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, -88.0), vec2(0.0, 1.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, -88.0), vec2(1.0, 0.0)),
-                        RcsThruster::new(1.0, 0.1, vec2(-199.0, -88.0), vec2(-1.0, 0.0)),
-                    ],
-                    RcsControlScheme::new(None, Some(541.0), 
-                        vec![
-                            0.0, 0.0, 1.0,
-                            1.0, 1.0, 0.0,
-                            1.0, 0.0, 1.0,
-                            0.0, 1.0, 0.0
-                        ],
-                        vec![
-                            1.0, 1.0, 0.0,
-                            0.0, 0.0, 1.0,
-                            0.0, 1.0, 0.0,
-                            1.0, 0.0, 1.0
-                        ])
-                    )
-                ),
-                torpedo_storage: Some(TorpedoStorage::new(8)),
-                torpedo_launcher: Some(TorpedoLauncher::new(60.0 * 60.0)),
-                docking: None,
-            }
+        let definition = class.definition();
+        let mut vessel_component = Self {
+            class, faction,
+            dry_mass: definition.dry_mass(),
+            is_ghost: class.is_torpedo() || class.is_projectile(),
+            is_ai_controlled: false,
+            health: Some(Health::new(definition.hit_points())),
+            can_dock: definition.can_dock(),
+            dimensions: definition.dimensions(),
+            timeline: Timeline::default(),
+            target: None,
+            fuel_tank: None,
+            engine: None,
+            rcs: definition.rcs(),
+            torpedo_storage: None,
+            torpedo_launcher: None,
+            docking: definition.docking(),
+            cargo_hold: definition.cargo_hold(),
+            mining: None,
+            point_defense: None,
+            gun: None,
+            slots: definition.slots().to_vec(),
+        };
+        vessel_component.apply_loadout(&definition);
+        if let Some(fuel_tank) = &mut vessel_component.fuel_tank {
+            fuel_tank.set_fuel_kg(fuel_tank.capacity_kg());
+        }
+        if let Some(torpedo_storage) = &mut vessel_component.torpedo_storage {
+            torpedo_storage.set_torpedoes(torpedo_storage.capacity());
+        }
+        if let Some(point_defense) = &mut vessel_component.point_defense {
+            point_defense.set_ammo(point_defense.ammo_capacity());
+        }
+        if let Some(gun) = &mut vessel_component.gun {
+            gun.set_ammo(gun.ammo_capacity());
         }
+        vessel_component
+    }
+
+    /// Recomputes the stats which depend on fitted modules. Keeps as much
+    /// fuel and as many torpedoes as still fit, and any torpedo launcher
+    /// has to reload
+    fn apply_loadout(&mut self, definition: &VesselClassDefinition) {
+        let modules: Vec<ModuleType> = self.slots.iter().filter_map(Slot::module).collect();
+        self.dry_mass = definition.dry_mass() + modules.iter().map(ModuleType::mass).sum::<f64>();
+        self.engine = modules.iter().find_map(ModuleType::engine).or_else(|| definition.engine());
+
+        let fuel_kg = self.fuel_kg();
+        let fuel_capacity_litres = definition.fuel_tank().map_or(0.0, |fuel_tank| fuel_tank.capacity_litres())
+            + modules.iter().map(ModuleType::fuel_capacity_litres).sum::<f64>();
+        self.fuel_tank = (definition.fuel_tank().is_some() || fuel_capacity_litres > 0.0).then(|| {
+            let mut fuel_tank = FuelTank::new(fuel_capacity_litres);
+            fuel_tank.set_fuel_kg(f64::min(fuel_kg, fuel_tank.capacity_kg()));
+            fuel_tank
+        });
+
+        let torpedoes = self.torpedoes();
+        let torpedo_capacity = definition.torpedo_storage().map_or(0, |torpedo_storage| torpedo_storage.capacity())
+            + modules.iter().map(ModuleType::torpedo_capacity).sum::<usize>();
+        self.torpedo_storage = (definition.torpedo_storage().is_some() || torpedo_capacity > 0).then(|| {
+            let mut torpedo_storage = TorpedoStorage::new(torpedo_capacity);
+            torpedo_storage.set_torpedoes(usize::min(torpedoes, torpedo_capacity));
+            torpedo_storage
+        });
+
+        let ammo = self.point_defense_ammo();
+        self.point_defense = modules.iter()
+            .filter_map(ModuleType::point_defense)
+            .reduce(|a, b| a.combine(&b))
+            .map(|mut point_defense| {
+                point_defense.set_ammo(usize::min(ammo, point_defense.ammo_capacity()));
+                point_defense
+            });
+
+        let gun_ammo = self.gun_ammo();
+        self.gun = modules.iter()
+            .filter_map(ModuleType::gun)
+            .reduce(|a, b| a.combine(&b))
+            .map(|mut gun| {
+                gun.set_ammo(usize::min(gun_ammo, gun.ammo_capacity()));
+                gun
+            });
+
+        // The fastest fitted launcher is used
+        let cooldown = modules.iter()
+            .filter_map(ModuleType::torpedo_launcher_cooldown)
+            .reduce(f64::min)
+            .or_else(|| definition.torpedo_launcher().map(|torpedo_launcher| torpedo_launcher.cooldown()));
+        let had_torpedo_launcher = self.torpedo_launcher.is_some();
+        self.torpedo_launcher = cooldown.map(|cooldown| {
+            let mut torpedo_launcher = TorpedoLauncher::new(cooldown);
+            if had_torpedo_launcher {
+                torpedo_launcher.reset_time_to_reload();
+            }
+            torpedo_launcher
+        });
     }
 
     // ------------------------
@@ -207,6 +198,25 @@ impl VesselComponent {
         self.is_ghost = false;
     }
 
+    /// Hands the vessel over to the AI of its faction
+    pub fn with_ai_control(mut self) -> Self {
+        self.is_ai_controlled = true;
+        self
+    }
+
+    /// # Panics
+    /// Panics if the slot does not exist or is of the wrong type for the module
+    pub fn with_module(mut self, slot: usize, module: ModuleType) -> Self {
+        self.fit_module(slot, Some(module));
+        if let Some(point_defense) = &mut self.point_defense {
+            point_defense.set_ammo(point_defense.ammo_capacity());
+        }
+        if let Some(gun) = &mut self.gun {
+            gun.set_ammo(gun.ammo_capacity());
+        }
+        self
+    }
+
     pub fn class(&self) -> VesselClass {
         self.class
     }
@@ -219,6 +229,10 @@ impl VesselComponent {
         self.is_ghost
     }
 
+    pub fn is_ai_controlled(&self) -> bool {
+        self.is_ai_controlled
+    }
+
     pub fn timeline(&self) -> &Timeline {
         &self.timeline
     }
@@ -228,9 +242,46 @@ impl VesselComponent {
     }
 
     pub(crate) fn should_recompute_trajectory(&self) -> bool {
-        !(self.class().is_torpedo() && self.timeline().last_event().is_some_and(|event| event.is_intercept()))
+        let is_weapon = self.class().is_torpedo() || self.class().is_projectile();
+        !(is_weapon && self.timeline().last_event().is_some_and(|event| event.is_intercept() || event.is_point_defense()))
     }
     
+    // ------------------------
+    // Health
+    // ------------------------
+    pub fn health(&self) -> Option<&Health> {
+        self.health.as_ref()
+    }
+
+    pub fn has_subsystem(&self, subsystem: Subsystem) -> bool {
+        match subsystem {
+            Subsystem::Engine => self.has_engine(),
+            Subsystem::Rcs => self.has_rcs(),
+            Subsystem::TorpedoLauncher => self.has_torpedo_launcher(),
+        }
+    }
+
+    pub fn is_subsystem_disabled(&self, subsystem: Subsystem) -> bool {
+        self.health.as_ref().is_some_and(|health| health.is_disabled(subsystem))
+    }
+
+    /// Returns true if the damage destroyed the vessel
+    pub(crate) fn damage(&mut self, amount: f64) -> bool {
+        match &mut self.health {
+            Some(health) => {
+                health.damage(amount);
+                health.is_destroyed()
+            }
+            None => true,
+        }
+    }
+
+    pub(crate) fn disable_subsystem(&mut self, subsystem: Subsystem) {
+        if let Some(health) = &mut self.health {
+            health.disable(subsystem);
+        }
+    }
+
     // ------------------------
     // Target
     // ------------------------
@@ -303,11 +354,16 @@ impl VesselComponent {
     }
 
     pub fn wet_mass(&self) -> f64 {
-        self.dry_mass() + self.fuel_capacity_kg()
+        self.dry_mass() + self.cargo_mass_kg() + self.fuel_capacity_kg()
     }
 
     pub fn mass(&self) -> f64 {
-        self.dry_mass() + self.fuel_kg()
+        self.dry_mass() + self.cargo_mass_kg() + self.fuel_kg()
+    }
+
+    /// Mass once all fuel is burnt, which includes any cargo
+    pub fn empty_mass(&self) -> f64 {
+        self.dry_mass() + self.cargo_mass_kg()
     }
 
     // ------------------------
@@ -335,8 +391,8 @@ impl VesselComponent {
     pub fn max_dv(&self) -> f64 {
         match &self.engine {
             Some(engine) => {
-                let initial_mass = self.dry_mass() + self.fuel_capacity_kg();
-                let final_mass = self.dry_mass();
+                let initial_mass = self.wet_mass();
+                let final_mass = self.empty_mass();
                 let isp = engine.specific_impulse();
                 isp * STANDARD_GRAVITY * f64::ln(initial_mass / final_mass)
             },
@@ -348,7 +404,7 @@ impl VesselComponent {
         match &self.engine {
             Some(engine) => {
                 let initial_mass = self.mass();
-                let final_mass = self.dry_mass();
+                let final_mass = self.empty_mass();
                 let isp = engine.specific_impulse();
                 isp * STANDARD_GRAVITY * f64::ln(initial_mass / final_mass)
             },
@@ -414,6 +470,74 @@ impl VesselComponent {
         }
     }
 
+    // ------------------------
+    // Cargo hold
+    // ------------------------
+    pub fn has_cargo_hold(&self) -> bool {
+        self.cargo_hold.is_some()
+    }
+
+    pub fn cargo_hold(&self) -> Option<&CargoHold> {
+        self.cargo_hold.as_ref()
+    }
+
+    pub fn cargo_mass_kg(&self) -> f64 {
+        self.cargo_hold.as_ref().map_or(0.0, CargoHold::mass_kg)
+    }
+
+    // ------------------------
+    // Resources
+    // ------------------------
+    /// Whether the vessel has anywhere to keep `resource`
+    pub fn can_store(&self, resource: ResourceType) -> bool {
+        match resource.definition().storage() {
+            ResourceStorage::FuelTank => self.has_fuel_tank(),
+            ResourceStorage::TorpedoStorage => self.has_torpedo_storage(),
+            ResourceStorage::CargoHold => self.has_cargo_hold(),
+        }
+    }
+
+    pub fn resource_amount(&self, resource: ResourceType) -> f64 {
+        match resource.definition().storage() {
+            ResourceStorage::FuelTank => self.fuel_kg(),
+            ResourceStorage::TorpedoStorage => self.torpedoes() as f64,
+            ResourceStorage::CargoHold => self.cargo_hold.as_ref().map_or(0.0, |cargo_hold| cargo_hold.amount(resource)),
+        }
+    }
+
+    /// How much more of `resource` the vessel can take. Cargo resources
+    /// share the hold, so this depends on what else is in it
+    pub fn resource_space(&self, resource: ResourceType) -> f64 {
+        match resource.definition().storage() {
+            ResourceStorage::FuelTank => f64::max(self.fuel_capacity_kg() - self.fuel_kg(), 0.0),
+            ResourceStorage::TorpedoStorage => (self.torpedo_capacity() - self.torpedoes()) as f64,
+            ResourceStorage::CargoHold => self.cargo_hold.as_ref().map_or(0.0, |cargo_hold| cargo_hold.space_for(resource)),
+        }
+    }
+
+    pub fn is_resource_empty(&self, resource: ResourceType) -> bool {
+        self.resource_amount(resource) < 1.0e-3
+    }
+
+    pub fn is_resource_full(&self, resource: ResourceType) -> bool {
+        self.resource_space(resource) < 1.0e-3
+    }
+
+    /// Discrete resources are rounded to whole units
+    /// # Panics
+    /// Panics if the vessel has nowhere to keep `resource` or it does not fit
+    pub fn set_resource_amount(&mut self, resource: ResourceType, amount: f64) {
+        match resource.definition().storage() {
+            ResourceStorage::FuelTank => self.set_fuel_kg(amount),
+            ResourceStorage::TorpedoStorage => self.torpedo_storage.as_mut()
+                .expect("Attempt to set torpedoes on vessel without torpedo storage")
+                .set_torpedoes(amount.round() as usize),
+            ResourceStorage::CargoHold => self.cargo_hold.as_mut()
+                .expect("Attempt to set cargo on vessel without cargo hold")
+                .set_amount(resource, amount),
+        }
+    }
+
     // ------------------------
     // Torpedo launcher
     // ------------------------
@@ -429,6 +553,108 @@ impl VesselComponent {
         self.torpedo_launcher.as_ref().unwrap().time_to_reload()
     }
 
+    // ------------------------
+    // Point defense
+    // ------------------------
+    pub fn has_point_defense(&self) -> bool {
+        self.point_defense.is_some()
+    }
+
+    pub fn point_defense(&self) -> Option<&PointDefense> {
+        self.point_defense.as_ref()
+    }
+
+    pub fn point_defense_ammo(&self) -> usize {
+        match &self.point_defense {
+            Some(point_defense) => point_defense.ammo(),
+            None => 0,
+        }
+    }
+
+    pub fn consume_point_defense_ammo(&mut self, shots: usize) {
+        if let Some(point_defense) = &mut self.point_defense {
+            point_defense.consume_ammo(shots);
+        } else {
+            error!("Attempt to consume point defense ammo on vessel without point defense");
+        }
+    }
+
+    // ------------------------
+    // Gun
+    // ------------------------
+    pub fn has_gun(&self) -> bool {
+        self.gun.is_some()
+    }
+
+    pub fn gun(&self) -> Option<&Gun> {
+        self.gun.as_ref()
+    }
+
+    pub fn gun_ammo(&self) -> usize {
+        match &self.gun {
+            Some(gun) => gun.ammo(),
+            None => 0,
+        }
+    }
+
+    pub fn final_gun_ammo(&self) -> usize {
+        self.gun_ammo().saturating_sub(self.timeline().depleted_gun_ammo())
+    }
+
+    pub fn decrement_gun_ammo(&mut self) {
+        if let Some(gun) = &mut self.gun {
+            gun.decrement_ammo();
+        } else {
+            error!("Attempt to decrement ammo on vessel without a gun");
+        }
+    }
+
+    // ------------------------
+    // Slots
+    // ------------------------
+    pub fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+
+    /// # Panics
+    /// Panics if the slot does not exist or is of the wrong type for the module
+    pub(crate) fn fit_module(&mut self, slot: usize, module: Option<ModuleType>) {
+        self.slots[slot].set_module(module);
+        self.apply_loadout(&self.class.definition());
+    }
+
+    /// The range of the best fitted sensor, or zero if there is none
+    pub fn sensor_range(&self) -> f64 {
+        self.slots.iter()
+            .filter_map(Slot::module)
+            .map(|module| module.sensor_range())
+            .fold(0.0, f64::max)
+    }
+
+    // ------------------------
+    // Mining
+    // ------------------------
+    /// Kilograms per second extracted by every fitted drill together
+    pub fn mining_rate(&self) -> f64 {
+        self.slots.iter()
+            .filter_map(Slot::module)
+            .map(|module| module.mining_rate())
+            .sum()
+    }
+
+    /// Mined resources have to go somewhere, so a hold is needed as well as a drill
+    pub fn can_ever_mine(&self) -> bool {
+        self.mining_rate() > 0.0 && self.has_cargo_hold()
+    }
+
+    pub fn mining(&self) -> Option<Entity> {
+        self.mining
+    }
+
+    pub(crate) fn set_mining(&mut self, asteroid: Option<Entity>) {
+        self.mining = asteroid;
+    }
+
     // ------------------------
     // Docking
     // ------------------------
@@ -462,6 +688,14 @@ impl VesselComponent {
         self.can_dock
     }
 
+    pub fn build_queue(&self) -> Option<&BuildQueue> {
+        Some(self.docking.as_ref()?.build_queue())
+    }
+
+    pub(crate) fn build_queue_mut(&mut self) -> Option<&mut BuildQueue> {
+        Some(self.docking.as_mut()?.build_queue_mut())
+    }
+
     pub fn dock(&mut self, location: DockingPortLocation, entity: Entity) {
         self.docking.as_mut().expect("Attempt to dock to vessel without docking ports").dock(location, entity);
     }
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::resource::ResourceType;
+
+/// Holds any mix of cargo resources, limited by both their total mass and
+/// their total volume
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CargoHold {
+    mass_capacity_kg: f64,
+    volume_capacity_litres: f64,
+    contents: BTreeMap<ResourceType, f64>,
+}
+
+impl CargoHold {
+    pub fn new(mass_capacity_kg: f64, volume_capacity_litres: f64) -> Self {
+        let contents = BTreeMap::new();
+        Self { mass_capacity_kg, volume_capacity_litres, contents }
+    }
+
+    pub fn mass_capacity_kg(&self) -> f64 {
+        self.mass_capacity_kg
+    }
+
+    pub fn volume_capacity_litres(&self) -> f64 {
+        self.volume_capacity_litres
+    }
+
+    pub fn contents(&self) -> &BTreeMap<ResourceType, f64> {
+        &self.contents
+    }
+
+    pub fn amount(&self, resource: ResourceType) -> f64 {
+        self.contents.get(&resource).copied().unwrap_or(0.0)
+    }
+
+    pub fn mass_kg(&self) -> f64 {
+        self.contents.iter()
+            .map(|(resource, amount)| amount * resource.definition().unit_mass_kg())
+            .sum()
+    }
+
+    pub fn volume_litres(&self) -> f64 {
+        self.contents.iter()
+            .map(|(resource, amount)| amount * resource.definition().unit_volume_litres())
+            .sum()
+    }
+
+    /// How much more of `resource` fits before either the mass or the
+    /// volume limit is reached
+    pub fn space_for(&self, resource: ResourceType) -> f64 {
+        let definition = resource.definition();
+        let by_mass = (self.mass_capacity_kg - self.mass_kg()) / definition.unit_mass_kg();
+        let by_volume = (self.volume_capacity_litres - self.volume_litres()) / definition.unit_volume_litres();
+        f64::max(f64::min(by_mass, by_volume), 0.0)
+    }
+
+    /// # Panics
+    /// Panics if `resource` is not cargo or the new amount does not fit
+    pub fn set_amount(&mut self, resource: ResourceType, amount: f64) {
+        assert!(resource.is_cargo(), "Attempt to put {resource:?} in a cargo hold");
+        assert!(amount - self.amount(resource) <= self.space_for(resource) + 1.0e-6, "Attempt to overfill cargo hold");
+        if amount < 1.0e-6 {
+            self.contents.remove(&resource);
+        } else {
+            self.contents.insert(resource, amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::components::vessel_component::resource::ResourceType;
+
+    use super::CargoHold;
+
+    #[test]
+    fn test_space_is_limited_by_mass_and_volume() {
+        let mut cargo_hold = CargoHold::new(1000.0, 500.0);
+        // Oil runs out of volume before mass, and metal the other way round
+        assert!((cargo_hold.space_for(ResourceType::Oil) - 500.0 / 1.1).abs() < 1.0e-6);
+        assert!((cargo_hold.space_for(ResourceType::Metal) - 1000.0).abs() < 1.0e-6);
+
+        cargo_hold.set_amount(ResourceType::Metal, 600.0);
+        assert!((cargo_hold.mass_kg() - 600.0).abs() < 1.0e-6);
+        assert!((cargo_hold.space_for(ResourceType::Metal) - 400.0).abs() < 1.0e-6);
+        assert!((cargo_hold.space_for(ResourceType::Oil) - (500.0 - 600.0 * 0.13) / 1.1).abs() < 1.0e-6);
+
+        cargo_hold.set_amount(ResourceType::Metal, 0.0);
+        assert!(cargo_hold.contents().is_empty());
+    }
+}
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum VesselClass {
     Torpedo,
+    /// Inert round fired by a kinetic gun
+    Projectile,
     Station,
     Scout1,
     Frigate1,
@@ -11,9 +13,12 @@ pub enum VesselClass {
 }
 
 impl VesselClass {
+    pub const ALL: [VesselClass; 6] = [VesselClass::Torpedo, VesselClass::Projectile, VesselClass::Station, VesselClass::Scout1, VesselClass::Frigate1, VesselClass::TestShip];
+
     pub fn name(&self) -> &'static str {
         match self {
             VesselClass::Torpedo => "Torpedo",
+            VesselClass::Projectile => "Projectile",
             VesselClass::Station => "Hub",
             VesselClass::Scout1 => "Scout I",
             VesselClass::Frigate1 => "Frigate I",
@@ -24,4 +29,8 @@ impl VesselClass {
     pub fn is_torpedo(&self) -> bool {
         matches!(self, VesselClass::Torpedo)
     }
+
+    pub fn is_projectile(&self) -> bool {
+        matches!(self, VesselClass::Projectile)
+    }
 }
@@ -0,0 +1,583 @@
+use std::{collections::{BTreeMap, BTreeSet, HashMap}, error::Error, fmt::Display, fs, path::Path, sync::{LazyLock, RwLock}};
+
+use log::info;
+use nalgebra_glm::{vec2, DVec2};
+use serde::{Deserialize, Serialize};
+
+use super::{cargo_hold::CargoHold, class::VesselClass, docking::{Docking, DockingPortLocation}, engine::Engine, fuel_tank::FuelTank, rcs::{Rcs, RcsControlScheme, RcsThruster}, resource::ResourceType, slots::{Slot, SlotType}, torpedo_launcher::TorpedoLauncher, torpedo_storage::TorpedoStorage};
+
+/// Where designers put vessel class definitions which override the built-in ones
+pub const VESSEL_CLASS_DIRECTORY: &str = "data/vessels";
+
+static OVERRIDES: LazyLock<RwLock<HashMap<VesselClass, VesselClassDefinition>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// False for NaN and infinities as well as values at or below zero
+fn is_positive(value: f64) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+fn is_non_negative(value: f64) -> bool {
+    value.is_finite() && value >= 0.0
+}
+
+#[derive(Debug)]
+pub enum VesselClassDefinitionError {
+    Io { path: String, reason: String },
+    Parse { path: String, reason: String },
+    Duplicate { path: String, class: VesselClass },
+}
+
+impl Display for VesselClassDefinitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VesselClassDefinitionError::Io { path, reason } => write!(f, "failed to read {path}: {reason}"),
+            VesselClassDefinitionError::Parse { path, reason } => write!(f, "failed to parse {path}: {reason}"),
+            VesselClassDefinitionError::Duplicate { path, class } => write!(f, "{path} defines {class:?}, which is already defined by another file"),
+        }
+    }
+}
+
+impl Error for VesselClassDefinitionError {}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EngineDefinition {
+    fuel_kg_per_second: f64,
+    thrust_newtons: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CargoHoldDefinition {
+    mass_capacity_kg: f64,
+    volume_capacity_litres: f64,
+}
+
+/// What a station's shipyard needs to build a vessel of the class
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BuildCostDefinition {
+    seconds: f64,
+    resources: BTreeMap<ResourceType, f64>,
+}
+
+impl BuildCostDefinition {
+    pub fn seconds(&self) -> f64 {
+        self.seconds
+    }
+
+    pub fn resources(&self) -> &BTreeMap<ResourceType, f64> {
+        &self.resources
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RcsThrusterDefinition {
+    thrust: f64,
+    fuel_kg_per_second: f64,
+    position: DVec2,
+    force_unit: DVec2,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RcsDefinition {
+    mass: f64,
+    thrusters: Vec<RcsThrusterDefinition>,
+    turn_angular_force: f64,
+    /// Throttle of each thruster when turning anticlockwise
+    turn_throttles_positive: Vec<f64>,
+    /// Throttle of each thruster when turning clockwise
+    turn_throttles_negative: Vec<f64>,
+}
+
+impl RcsDefinition {
+    fn validate(&self) -> Result<(), String> {
+        if !is_non_negative(self.mass) {
+            return Err("RCS mass must not be negative".to_string());
+        }
+        if !is_positive(self.turn_angular_force) {
+            return Err("RCS turn angular force must be positive".to_string());
+        }
+        if self.thrusters.is_empty() {
+            return Err("RCS must have at least one thruster".to_string());
+        }
+        for thruster in &self.thrusters {
+            if !is_positive(thruster.thrust) || !is_non_negative(thruster.fuel_kg_per_second) {
+                return Err("RCS thrusters must have positive thrust and non-negative fuel consumption".to_string());
+            }
+            if !thruster.position.x.is_finite() || !thruster.position.y.is_finite()
+                    || !is_positive(thruster.force_unit.magnitude()) {
+                return Err("RCS thrusters must have a finite position and non-zero force direction".to_string());
+            }
+        }
+        for throttles in [&self.turn_throttles_positive, &self.turn_throttles_negative] {
+            if throttles.len() != self.thrusters.len() {
+                return Err(format!("RCS has {} thrusters but {} turn throttles", self.thrusters.len(), throttles.len()));
+            }
+            if throttles.iter().any(|throttle| !(0.0..=1.0).contains(throttle)) {
+                return Err("RCS turn throttles must be between 0 and 1".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn build(&self) -> Rcs {
+        let thrusters = self.thrusters.iter()
+            .map(|thruster| RcsThruster::new(thruster.thrust, thruster.fuel_kg_per_second, thruster.position, thruster.force_unit.normalize()))
+            .collect();
+        let turn = RcsControlScheme::new(None, Some(self.turn_angular_force), self.turn_throttles_positive.clone(), self.turn_throttles_negative.clone());
+        Rcs::new(self.mass, thrusters, turn)
+    }
+}
+
+/// Every stat of a vessel class, as loaded from a file in
+/// `VESSEL_CLASS_DIRECTORY` or taken from the built-in defaults
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct VesselClassDefinition {
+    class: VesselClass,
+    dry_mass: f64,
+    hit_points: f64,
+    /// Mass of explosive carried, if the vessel detonates on intercept
+    warhead_mass: Option<f64>,
+    can_dock: bool,
+    dimensions: DVec2,
+    fuel_capacity_litres: Option<f64>,
+    engine: Option<EngineDefinition>,
+    rcs: Option<RcsDefinition>,
+    torpedo_capacity: Option<usize>,
+    torpedo_launcher_cooldown: Option<f64>,
+    docking_ports: Option<Vec<DockingPortLocation>>,
+    /// Definitions written before cargo existed have no hold
+    #[serde(default)]
+    cargo_hold: Option<CargoHoldDefinition>,
+    /// Modules fitted to these slots add to the stats above, except that
+    /// a fitted engine replaces the built-in one
+    #[serde(default)]
+    slots: Vec<Slot>,
+    /// Classes without a build cost cannot be built by shipyards
+    #[serde(default)]
+    build_cost: Option<BuildCostDefinition>,
+}
+
+fn standard_rcs() -> RcsDefinition {
+    let thruster = |position: DVec2, force_unit: DVec2| RcsThrusterDefinition { thrust: 1.0, fuel_kg_per_second: 0.1, position, force_unit };
+    RcsDefinition {
+        mass: 5.0,
+        thrusters: vec![
+            thruster(vec2(10.0, 78.0), vec2(0.0, -1.0)),
+            thruster(vec2(10.0, 78.0), vec2(1.0, 0.0)),
+            thruster(vec2(10.0, 78.0), vec2(-1.0, 0.0)),
+
+            thruster(vec2(10.0, -78.0), vec2(0.0, 1.0)),
+            thruster(vec2(10.0, -78.0), vec2(1.0, 0.0)),
+            thruster(vec2(10.0, -78.0), vec2(-1.0, 0.0)),
+
+            thruster(vec2(-199.0, 88.0), vec2(0.0, -1.0)),
+            thruster(vec2(-199.0, 88.0), vec2(1.0, 0.0)),
+            thruster(vec2(-199.0, 88.0), vec2(-1.0, 0.0)),
+
+            thruster(vec2(-199.0, -88.0), vec2(0.0, 1.0)),
+            thruster(vec2(-199.0, -88.0), vec2(1.0, 0.0)),
+            thruster(vec2(-199.0, -88.0), vec2(-1.0, 0.0)),
+        ],
+        turn_angular_force: 541.0,
+        turn_throttles_positive: vec![
+            0.0, 0.0, 1.0,
+            1.0, 1.0, 0.0,
+            1.0, 0.0, 1.0,
+            0.0, 1.0, 0.0,
+        ],
+        turn_throttles_negative: vec![
+            1.0, 1.0, 0.0,
+            0.0, 0.0, 1.0,
+            0.0, 1.0, 0.0,
+            1.0, 0.0, 1.0,
+        ],
+    }
+}
+
+impl VesselClassDefinition {
+    /// The definition compiled into the game, used when no file overrides it
+    pub fn builtin(class: VesselClass) -> Self {
+        match class {
+            VesselClass::Torpedo => Self {
+                class,
+                dry_mass: 2.0e3,
+                hit_points: 10.0,
+                warhead_mass: Some(500.0),
+                can_dock: false,
+                dimensions: vec2(0.0, 0.0),
+                fuel_capacity_litres: Some(10_000.0),
+                engine: Some(EngineDefinition { fuel_kg_per_second: 7.0, thrust_newtons: 15_000.0 }),
+                rcs: None,
+                torpedo_capacity: None,
+                torpedo_launcher_cooldown: None,
+                docking_ports: None,
+                cargo_hold: None,
+                slots: vec![],
+                build_cost: None,
+            },
+
+            VesselClass::Projectile => Self {
+                class,
+                dry_mass: 50.0,
+                hit_points: 1.0,
+                warhead_mass: None,
+                can_dock: false,
+                dimensions: vec2(0.0, 0.0),
+                // Every vessel with a path needs a tank, even an empty one
+                fuel_capacity_litres: Some(0.0),
+                engine: None,
+                rcs: None,
+                torpedo_capacity: None,
+                torpedo_launcher_cooldown: None,
+                docking_ports: None,
+                cargo_hold: None,
+                slots: vec![],
+                build_cost: None,
+            },
+
+            VesselClass::Station => Self {
+                class,
+                dry_mass: 380.0e3,
+                hit_points: 2000.0,
+                warhead_mass: None,
+                can_dock: false,
+                dimensions: vec2(2680.0, 2000.0),
+                fuel_capacity_litres: Some(140_000.0),
+                engine: None,
+                rcs: None,
+                torpedo_capacity: Some(5),
+                torpedo_launcher_cooldown: None,
+                docking_ports: Some(vec![DockingPortLocation::West, DockingPortLocation::East]),
+                cargo_hold: Some(CargoHoldDefinition { mass_capacity_kg: 500.0e3, volume_capacity_litres: 400.0e3 }),
+                slots: vec![],
+                build_cost: None,
+            },
+
+            VesselClass::Scout1 => Self {
+                class,
+                dry_mass: 10.0e3,
+                hit_points: 100.0,
+                warhead_mass: None,
+                can_dock: true,
+                dimensions: vec2(540.0, 200.0),
+                fuel_capacity_litres: Some(30_000.0),
+                engine: Some(EngineDefinition { fuel_kg_per_second: 30.0, thrust_newtons: 70_000.0 }),
+                rcs: Some(standard_rcs()),
+                torpedo_capacity: None,
+                torpedo_launcher_cooldown: None,
+                docking_ports: None,
+                cargo_hold: None,
+                slots: vec![Slot::new(SlotType::Engine, None), Slot::new(SlotType::FuelTank, None), Slot::new(SlotType::Sensor, None)],
+                build_cost: Some(BuildCostDefinition { seconds: 6.0 * 60.0 * 60.0, resources: BTreeMap::from([(ResourceType::Metal, 8.0e3), (ResourceType::Alloys, 2.0e3)]) }),
+            },
+
+            VesselClass::Frigate1 => Self {
+                class,
+                dry_mass: 30.0e3,
+                hit_points: 300.0,
+                warhead_mass: None,
+                can_dock: true,
+                dimensions: vec2(0.0, 0.0),
+                fuel_capacity_litres: Some(60_000.0),
+                engine: Some(EngineDefinition { fuel_kg_per_second: 30.0, thrust_newtons: 70_000.0 }),
+                rcs: None,
+                torpedo_capacity: Some(2),
+                torpedo_launcher_cooldown: Some(2.0 * 60.0 * 60.0),
+                docking_ports: None,
+                cargo_hold: Some(CargoHoldDefinition { mass_capacity_kg: 10.0e3, volume_capacity_litres: 10.0e3 }),
+                slots: vec![Slot::new(SlotType::Engine, None), Slot::new(SlotType::FuelTank, None), Slot::new(SlotType::Weapon, None), Slot::new(SlotType::Weapon, None), Slot::new(SlotType::Sensor, None), Slot::new(SlotType::Utility, None)],
+                build_cost: Some(BuildCostDefinition { seconds: 2.0 * 24.0 * 60.0 * 60.0, resources: BTreeMap::from([(ResourceType::Metal, 20.0e3), (ResourceType::Alloys, 10.0e3), (ResourceType::Oil, 2.0e3)]) }),
+            },
+
+            VesselClass::TestShip => Self {
+                class,
+                dry_mass: 50.0e3,
+                hit_points: 300.0,
+                warhead_mass: None,
+                can_dock: true,
+                dimensions: vec2(0.0, 0.0),
+                fuel_capacity_litres: Some(60_000.0),
+                engine: Some(EngineDefinition { fuel_kg_per_second: 10000.0, thrust_newtons: 10_000_000.0 }),
+                rcs: Some(standard_rcs()),
+                torpedo_capacity: Some(8),
+                torpedo_launcher_cooldown: Some(60.0 * 60.0),
+                docking_ports: None,
+                cargo_hold: Some(CargoHoldDefinition { mass_capacity_kg: 50.0e3, volume_capacity_litres: 50.0e3 }),
+                slots: vec![Slot::new(SlotType::Engine, None), Slot::new(SlotType::FuelTank, None), Slot::new(SlotType::FuelTank, None), Slot::new(SlotType::Weapon, None), Slot::new(SlotType::Sensor, None), Slot::new(SlotType::Utility, None)],
+                build_cost: Some(BuildCostDefinition { seconds: 60.0 * 60.0, resources: BTreeMap::from([(ResourceType::Metal, 1.0e3)]) }),
+            },
+        }
+    }
+
+    /// Parses and validates a definition
+    /// # Errors
+    /// Returns the reason if the definition is malformed or invalid
+    pub fn parse(serialized: &str) -> Result<Self, String> {
+        let definition: Self = serde_json::from_str(serialized).map_err(|error| error.to_string())?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !is_positive(self.dry_mass) {
+            return Err("dry mass must be positive".to_string());
+        }
+        if !is_positive(self.hit_points) {
+            return Err("hit points must be positive".to_string());
+        }
+        if self.warhead_mass.is_some_and(|warhead_mass| !is_positive(warhead_mass)) {
+            return Err("warhead mass must be positive".to_string());
+        }
+        if !is_non_negative(self.dimensions.x) || !is_non_negative(self.dimensions.y) {
+            return Err("dimensions must not be negative".to_string());
+        }
+        if self.fuel_capacity_litres.is_some_and(|capacity| !is_non_negative(capacity)) {
+            return Err("fuel capacity must not be negative".to_string());
+        }
+        if let Some(engine) = &self.engine {
+            if !is_positive(engine.fuel_kg_per_second) || !is_positive(engine.thrust_newtons) {
+                return Err("engine must have positive fuel consumption and thrust".to_string());
+            }
+            if self.fuel_capacity_litres.is_none() {
+                return Err("engine requires a fuel tank".to_string());
+            }
+        }
+        if let Some(rcs) = &self.rcs {
+            rcs.validate()?;
+            if self.fuel_capacity_litres.is_none() {
+                return Err("RCS requires a fuel tank".to_string());
+            }
+        }
+        if let Some(cooldown) = self.torpedo_launcher_cooldown {
+            if !is_non_negative(cooldown) {
+                return Err("torpedo launcher cooldown must not be negative".to_string());
+            }
+            if self.torpedo_capacity.is_none() {
+                return Err("torpedo launcher requires torpedo storage".to_string());
+            }
+        }
+        if self.slots.iter().any(|slot| !slot.can_fit(slot.module())) {
+            return Err("slots must only contain modules of their type".to_string());
+        }
+        if let Some(cargo_hold) = &self.cargo_hold {
+            if !is_non_negative(cargo_hold.mass_capacity_kg) || !is_non_negative(cargo_hold.volume_capacity_litres) {
+                return Err("cargo hold capacities must not be negative".to_string());
+            }
+        }
+        if let Some(build_cost) = &self.build_cost {
+            if !is_positive(build_cost.seconds) {
+                return Err("build time must be positive".to_string());
+            }
+            if build_cost.resources.iter().any(|(resource, amount)| !resource.is_cargo() || !is_non_negative(*amount)) {
+                return Err("build costs must be non-negative amounts of cargo resources".to_string());
+            }
+        }
+        if let Some(docking_ports) = &self.docking_ports {
+            if docking_ports.is_empty() {
+                return Err("docking must have at least one port".to_string());
+            }
+            if docking_ports.iter().collect::<BTreeSet<_>>().len() != docking_ports.len() {
+                return Err("docking ports must not be repeated".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn class(&self) -> VesselClass {
+        self.class
+    }
+
+    pub fn dry_mass(&self) -> f64 {
+        self.dry_mass
+    }
+
+    pub fn hit_points(&self) -> f64 {
+        self.hit_points
+    }
+
+    pub fn warhead_mass(&self) -> Option<f64> {
+        self.warhead_mass
+    }
+
+    pub fn can_dock(&self) -> bool {
+        self.can_dock
+    }
+
+    pub fn dimensions(&self) -> DVec2 {
+        self.dimensions
+    }
+
+    pub fn fuel_tank(&self) -> Option<FuelTank> {
+        self.fuel_capacity_litres.map(FuelTank::new)
+    }
+
+    pub fn engine(&self) -> Option<Engine> {
+        self.engine.as_ref().map(|engine| Engine::new(engine.fuel_kg_per_second, engine.thrust_newtons))
+    }
+
+    pub fn rcs(&self) -> Option<Rcs> {
+        self.rcs.as_ref().map(RcsDefinition::build)
+    }
+
+    pub fn torpedo_storage(&self) -> Option<TorpedoStorage> {
+        self.torpedo_capacity.map(TorpedoStorage::new)
+    }
+
+    pub fn torpedo_launcher(&self) -> Option<TorpedoLauncher> {
+        self.torpedo_launcher_cooldown.map(TorpedoLauncher::new)
+    }
+
+    pub fn docking(&self) -> Option<Docking> {
+        self.docking_ports.clone().map(Docking::new)
+    }
+
+    pub fn cargo_hold(&self) -> Option<CargoHold> {
+        self.cargo_hold.as_ref().map(|cargo_hold| CargoHold::new(cargo_hold.mass_capacity_kg, cargo_hold.volume_capacity_litres))
+    }
+
+    pub fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+
+    pub fn build_cost(&self) -> Option<&BuildCostDefinition> {
+        self.build_cost.as_ref()
+    }
+}
+
+impl VesselClass {
+    /// The definition loaded for this class, or the built-in one if none was loaded
+    /// # Panics
+    /// Panics if the definition lock is poisoned
+    pub fn definition(self) -> VesselClassDefinition {
+        OVERRIDES.read()
+            .unwrap()
+            .get(&self)
+            .cloned()
+            .unwrap_or_else(|| VesselClassDefinition::builtin(self))
+    }
+}
+
+fn read_definition(path: &Path) -> Result<VesselClassDefinition, VesselClassDefinitionError> {
+    let display_path = path.display().to_string();
+    let serialized = fs::read_to_string(path)
+        .map_err(|error| VesselClassDefinitionError::Io { path: display_path.clone(), reason: error.to_string() })?;
+    VesselClassDefinition::parse(&serialized)
+        .map_err(|reason| VesselClassDefinitionError::Parse { path: display_path, reason })
+}
+
+/// Loads every `.json` vessel class definition in `directory`, replacing the
+/// built-in definitions of those classes. Files which fail to load or
+/// validate are skipped, leaving the previous definition in place, and
+/// their errors are returned. A missing directory is not an error.
+/// # Panics
+/// Panics if the definition lock is poisoned
+pub fn load_vessel_class_definitions(directory: &Path) -> Vec<VesselClassDefinitionError> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        info!("No vessel class definitions found at {}", directory.display());
+        return vec![];
+    };
+
+    // Sorted so that which of two duplicate files wins does not depend on the filesystem
+    let mut paths: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect();
+    paths.sort();
+
+    let mut errors = vec![];
+    let mut definitions = HashMap::new();
+    for path in paths {
+        match read_definition(&path) {
+            Ok(definition) => {
+                if definitions.contains_key(&definition.class) {
+                    errors.push(VesselClassDefinitionError::Duplicate { path: path.display().to_string(), class: definition.class });
+                    continue;
+                }
+                info!("Loaded {:?} definition from {}", definition.class, path.display());
+                definitions.insert(definition.class, definition);
+            }
+            Err(error) => errors.push(error),
+        }
+    }
+
+    OVERRIDES.write().unwrap().extend(definitions);
+    errors
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use crate::components::vessel_component::{class::VesselClass, resource::ResourceType};
+
+    use super::{load_vessel_class_definitions, VesselClassDefinition, VesselClassDefinitionError};
+
+    #[test]
+    fn test_builtin_definitions_are_valid() {
+        for class in VesselClass::ALL {
+            let definition = VesselClassDefinition::builtin(class);
+            assert!(definition.validate().is_ok(), "{class:?}");
+            let serialized = serde_json::to_string(&definition).unwrap();
+            let parsed = VesselClassDefinition::parse(&serialized).unwrap();
+            assert_eq!(parsed.class(), class);
+            assert_eq!(parsed.dry_mass(), definition.dry_mass());
+        }
+    }
+
+    #[test]
+    fn test_invalid_definitions_are_rejected() {
+        let mut definition = VesselClassDefinition::builtin(VesselClass::Scout1);
+        definition.fuel_capacity_litres = None;
+        assert!(definition.validate().is_err());
+
+        let mut definition = VesselClassDefinition::builtin(VesselClass::Scout1);
+        definition.rcs.as_mut().unwrap().turn_throttles_positive.pop();
+        assert!(definition.validate().is_err());
+
+        let mut definition = VesselClassDefinition::builtin(VesselClass::Frigate1);
+        definition.torpedo_capacity = None;
+        assert!(definition.validate().is_err());
+
+        let mut definition = VesselClassDefinition::builtin(VesselClass::Station);
+        definition.dry_mass = -1.0;
+        assert!(definition.validate().is_err());
+
+        let mut definition = VesselClassDefinition::builtin(VesselClass::Station);
+        definition.cargo_hold.as_mut().unwrap().volume_capacity_litres = -1.0;
+        assert!(definition.validate().is_err());
+
+        let mut definition = VesselClassDefinition::builtin(VesselClass::Scout1);
+        definition.build_cost.as_mut().unwrap().resources.insert(ResourceType::Fuel, 100.0);
+        assert!(definition.validate().is_err());
+
+        let mut definition = VesselClassDefinition::builtin(VesselClass::Torpedo);
+        definition.hit_points = 0.0;
+        assert!(definition.validate().is_err());
+
+        assert!(VesselClassDefinition::parse("{\"class\": \"Scout1\"}").is_err());
+    }
+
+    #[test]
+    fn test_load_invalid_directory() {
+        // Unique to this run, so concurrent runs do not delete each other's files
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        let directory = std::env::temp_dir().join(format!("transfer-window-test-vessel-classes-{}-{nanos}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("broken.json"), "{").unwrap();
+        fs::write(directory.join("ignored.txt"), "{").unwrap();
+
+        let errors = load_vessel_class_definitions(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], VesselClassDefinitionError::Parse { .. }));
+
+        assert!(load_vessel_class_definitions(&directory).is_empty());
+    }
+}
@@ -4,10 +4,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::storage::entity_allocator::Entity;
 
+use super::{resource::{ResourceTransferRate, ResourceType}, shipyard::BuildQueue};
+
 pub const DOCKING_DISTANCE: f64 = 1.0e2;
 pub const DOCKING_SPEED: f64 = 10.0;
-pub const FUEL_TRANSFER_RATE: f64 = 0.1;
-pub const TORPEDO_TRANSFER_TIME: f64 = 1800.0;
+/// Transfers with less than this left to move are finished
+const MIN_TRANSFER_AMOUNT: f64 = 1.0e-3;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DockingPortLocation {
@@ -44,102 +46,115 @@ impl ResourceTransferDirection {
     }
 }
 
+/// Moves one resource between a station and a docked vessel. Without an
+/// amount the transfer runs until the source is empty or the destination
+/// is full
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ContinuousResourceTransfer {
+pub struct ResourceTransfer {
+    resource: ResourceType,
     direction: ResourceTransferDirection,
-    rate: f64,
+    /// Until the next unit of a discrete resource arrives
+    time_to_next: f64,
+    /// What is left of the requested amount, if there was one
+    remaining: Option<f64>,
 }
 
-impl ContinuousResourceTransfer {
-    pub fn new(direction: ResourceTransferDirection) -> Self {
-        Self { direction, rate: FUEL_TRANSFER_RATE }
-    }
-
-    pub fn direction(&self) -> ResourceTransferDirection {
-        self.direction
-    }
-
-    pub fn rate(&self) -> f64 {
-        self.rate
+impl ResourceTransfer {
+    /// Discrete resources only move in whole units, so the amount is rounded down
+    pub fn new(resource: ResourceType, direction: ResourceTransferDirection, amount: Option<f64>) -> Self {
+        let (time_to_next, remaining) = match resource.definition().transfer_rate() {
+            ResourceTransferRate::Continuous { .. } => (0.0, amount),
+            ResourceTransferRate::Discrete { seconds_per_unit } => (seconds_per_unit, amount.map(f64::floor)),
+        };
+        Self { resource, direction, time_to_next, remaining }
     }
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct DiscreteResourceTransfer {
-    direction: ResourceTransferDirection,
-    interval: f64,
-    time_to_next: f64,
-}
 
-impl DiscreteResourceTransfer {
-    pub fn new(direction: ResourceTransferDirection) -> Self {
-        Self { direction, interval: TORPEDO_TRANSFER_TIME, time_to_next: TORPEDO_TRANSFER_TIME }
+    pub fn resource(&self) -> ResourceType {
+        self.resource
     }
 
     pub fn direction(&self) -> ResourceTransferDirection {
         self.direction
     }
 
-    pub fn interval(&self) -> f64 {
-        self.interval
+    pub fn rate(&self) -> ResourceTransferRate {
+        self.resource.definition().transfer_rate()
     }
 
     pub fn time_to_next(&self) -> f64 {
         self.time_to_next
     }
 
-    pub fn step(&self, dt: f64) -> Self {
-        let direction = self.direction;
-        let interval = self.interval;
-        let time_to_next = self.time_to_next - dt;
-        Self { direction, interval, time_to_next }
+    pub fn remaining(&self) -> Option<f64> {
+        self.remaining
+    }
+
+    /// Whether all of the requested amount has been moved
+    pub fn is_complete(&self) -> bool {
+        self.remaining.is_some_and(|remaining| remaining < MIN_TRANSFER_AMOUNT)
+    }
+
+    pub(crate) fn step(&mut self, dt: f64) {
+        self.time_to_next -= dt;
     }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DockedVessel {
     entity: Entity,
-    fuel_transfer: Option<ContinuousResourceTransfer>,
-    torpedo_transfer: Option<DiscreteResourceTransfer>,
+    /// At most one for each resource. Saves from before cargo existed have
+    /// their fuel and torpedo transfers converted by the save migration
+    #[serde(default)]
+    transfers: Vec<ResourceTransfer>,
 }
 
 impl DockedVessel {
     pub fn new(entity: Entity) -> Self {
-        let fuel_transfer = None;
-        let torpedo_transfer = None;
-        Self { entity, fuel_transfer, torpedo_transfer }
+        let transfers = vec![];
+        Self { entity, transfers }
     }
 
     pub fn entity(&self) -> Entity {
         self.entity
     }
 
-    pub fn fuel_transfer(&self) -> Option<&ContinuousResourceTransfer> {
-        self.fuel_transfer.as_ref()
+    pub fn transfers(&self) -> &[ResourceTransfer] {
+        &self.transfers
     }
-    
-    pub fn torpedo_transfer(&self) -> Option<&DiscreteResourceTransfer> {
-        self.torpedo_transfer.as_ref()
-    }
-    
-    pub fn start_fuel_transfer(&mut self, direction: ResourceTransferDirection) {
-        self.fuel_transfer = Some(ContinuousResourceTransfer::new(direction));
+
+    pub fn transfer(&self, resource: ResourceType) -> Option<&ResourceTransfer> {
+        self.transfers.iter().find(|transfer| transfer.resource == resource)
     }
 
-    pub fn stop_fuel_transfer(&mut self) {
-        self.fuel_transfer = None;
+    pub(crate) fn transfer_mut(&mut self, resource: ResourceType) -> Option<&mut ResourceTransfer> {
+        self.transfers.iter_mut().find(|transfer| transfer.resource == resource)
     }
 
-    pub fn start_torpedo_transfer(&mut self, direction: ResourceTransferDirection) {
-        self.torpedo_transfer = Some(DiscreteResourceTransfer::new(direction));
+    /// Replaces any transfer of the same resource. Nothing is started if
+    /// the amount is too small to move anything
+    pub fn start_transfer(&mut self, resource: ResourceType, direction: ResourceTransferDirection, amount: Option<f64>) {
+        self.stop_transfer(resource);
+        let transfer = ResourceTransfer::new(resource, direction, amount);
+        if !transfer.is_complete() {
+            self.transfers.push(transfer);
+        }
     }
 
-    pub fn step_torpedo_transfer(&mut self, dt: f64) {
-        self.torpedo_transfer = Some(self.torpedo_transfer.as_mut().unwrap().step(dt));
+    pub fn stop_transfer(&mut self, resource: ResourceType) {
+        self.transfers.retain(|transfer| transfer.resource != resource);
     }
 
-    pub fn stop_torpedo_transfer(&mut self) {
-        self.torpedo_transfer = None;
+    /// Stops the transfer once the requested amount has been moved
+    pub(crate) fn on_transferred(&mut self, resource: ResourceType, amount: f64) {
+        let Some(transfer) = self.transfer_mut(resource) else {
+            return;
+        };
+        if let Some(remaining) = &mut transfer.remaining {
+            *remaining -= amount;
+        }
+        if transfer.is_complete() {
+            self.stop_transfer(resource);
+        }
     }
 }
 
@@ -172,6 +187,9 @@ impl DockingPort {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Docking {
     docking_ports: BTreeMap<DockingPortLocation, DockingPort>,
+    /// Stations from saves before shipyards existed have nothing queued
+    #[serde(default)]
+    build_queue: BuildQueue,
 }
 
 impl Docking {
@@ -179,7 +197,8 @@ impl Docking {
         let docking_ports = locations.into_iter()
             .map(|location| (location, DockingPort::default()))
             .collect();
-        Self { docking_ports }
+        let build_queue = BuildQueue::default();
+        Self { docking_ports, build_queue }
     }
 
     pub fn docking_ports(&self) -> &BTreeMap<DockingPortLocation, DockingPort> {
@@ -198,6 +217,14 @@ impl Docking {
         self.docking_ports.get_mut(&location).expect("Attempt to get nonexistant docking port")
     }
 
+    pub fn build_queue(&self) -> &BuildQueue {
+        &self.build_queue
+    }
+
+    pub(crate) fn build_queue_mut(&mut self) -> &mut BuildQueue {
+        &mut self.build_queue
+    }
+
     pub fn dock(&mut self, location: DockingPortLocation, entity: Entity) {
         self.docking_port_mut(location).docked_vessel = Some(DockedVessel::new(entity));
     }
@@ -1,21 +1,88 @@
-use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
-pub enum Faction {
-    Player,
-    Ally,
-    Enemy,
-}
+use serde::{de::{self, Visitor}, Deserialize, Deserializer, Serialize};
+
+/// Identifies a faction registered in the model. The first three factions
+/// are registered by default and are used by the stories
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Faction(usize);
 
 impl Faction {
-    pub fn has_intel_for(self, other: Self) -> bool {
-        match self {
-            Faction::Player | Faction::Ally => !matches!(other, Faction::Enemy),
-            Faction::Enemy => matches!(other, Faction::Enemy)
-        }
+    pub const PLAYER: Faction = Faction(0);
+    pub const ALLY: Faction = Faction(1);
+    pub const ENEMY: Faction = Faction(2);
+
+    pub(crate) fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    pub fn index(self) -> usize {
+        self.0
     }
 
     pub fn can_control(self, other: Self) -> bool {
         self == other
     }
-}
\ No newline at end of file
+}
+
+struct FactionVisitor;
+
+impl Visitor<'_> for FactionVisitor {
+    type Value = Faction;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a faction index")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Faction, E> {
+        usize::try_from(value).map(Faction).map_err(E::custom)
+    }
+
+    /// Map keys are always serialized as strings
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Faction, E> {
+        value.parse().map(Faction).map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Faction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(FactionVisitor)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Stance {
+    Allied,
+    Neutral,
+    Hostile,
+}
+
+/// How two factions regard each other. Intel sharing is separate from the
+/// stance, so for example allies can keep their plans to themselves
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Relation {
+    stance: Stance,
+    shares_intel: bool,
+}
+
+impl Relation {
+    pub fn new(stance: Stance, shares_intel: bool) -> Self {
+        Self { stance, shares_intel }
+    }
+
+    pub fn stance(&self) -> Stance {
+        self.stance
+    }
+
+    pub fn shares_intel(&self) -> bool {
+        self.shares_intel
+    }
+
+    pub fn is_hostile(&self) -> bool {
+        self.stance == Stance::Hostile
+    }
+
+    pub fn is_allied(&self) -> bool {
+        self.stance == Stance::Allied
+    }
+}
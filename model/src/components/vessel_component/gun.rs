@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Gun {
+    muzzle_speed: f64,
+    cooldown: f64,
+    ammo_capacity: usize,
+    ammo: usize,
+}
+
+impl Gun {
+    pub fn new(muzzle_speed: f64, cooldown: f64, ammo_capacity: usize) -> Gun {
+        let ammo = ammo_capacity;
+        Gun { muzzle_speed, cooldown, ammo_capacity, ammo }
+    }
+
+    /// Guns fitted together fire in turn, so their rates of fire and ammo
+    /// add up while the fastest muzzle speed is used
+    pub fn combine(&self, other: &Gun) -> Gun {
+        let muzzle_speed = f64::max(self.muzzle_speed, other.muzzle_speed);
+        let cooldown = 1.0 / (1.0 / self.cooldown + 1.0 / other.cooldown);
+        let ammo_capacity = self.ammo_capacity + other.ammo_capacity;
+        let ammo = self.ammo + other.ammo;
+        Gun { muzzle_speed, cooldown, ammo_capacity, ammo }
+    }
+
+    /// Speed of projectiles relative to the vessel firing them
+    pub fn muzzle_speed(&self) -> f64 {
+        self.muzzle_speed
+    }
+
+    /// Minimum time between shots
+    pub fn cooldown(&self) -> f64 {
+        self.cooldown
+    }
+
+    pub fn ammo_capacity(&self) -> usize {
+        self.ammo_capacity
+    }
+
+    pub fn ammo(&self) -> usize {
+        self.ammo
+    }
+
+    pub fn set_ammo(&mut self, ammo: usize) {
+        self.ammo = ammo;
+        assert!(self.ammo <= self.ammo_capacity);
+    }
+
+    pub fn decrement_ammo(&mut self) {
+        self.ammo = self.ammo.saturating_sub(1);
+    }
+}
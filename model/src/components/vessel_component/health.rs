@@ -0,0 +1,65 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+/// Parts of a vessel which can be knocked out by a hit without destroying it
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Subsystem {
+    Engine,
+    Rcs,
+    TorpedoLauncher,
+}
+
+impl Subsystem {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Subsystem::Engine => "Engine",
+            Subsystem::Rcs => "RCS",
+            Subsystem::TorpedoLauncher => "Torpedo launcher",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Health {
+    max_hit_points: f64,
+    hit_points: f64,
+    disabled_subsystems: BTreeSet<Subsystem>,
+}
+
+impl Health {
+    pub fn new(max_hit_points: f64) -> Self {
+        let hit_points = max_hit_points;
+        let disabled_subsystems = BTreeSet::new();
+        Self { max_hit_points, hit_points, disabled_subsystems }
+    }
+
+    pub fn max_hit_points(&self) -> f64 {
+        self.max_hit_points
+    }
+
+    pub fn hit_points(&self) -> f64 {
+        self.hit_points
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        self.hit_points <= 0.0
+    }
+
+    /// Hit points never go below zero
+    pub fn damage(&mut self, amount: f64) {
+        self.hit_points = f64::max(self.hit_points - amount, 0.0);
+    }
+
+    pub fn disabled_subsystems(&self) -> &BTreeSet<Subsystem> {
+        &self.disabled_subsystems
+    }
+
+    pub fn is_disabled(&self, subsystem: Subsystem) -> bool {
+        self.disabled_subsystems.contains(&subsystem)
+    }
+
+    pub fn disable(&mut self, subsystem: Subsystem) {
+        self.disabled_subsystems.insert(subsystem);
+    }
+}
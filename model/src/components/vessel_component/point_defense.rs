@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PointDefense {
+    range: f64,
+    fire_interval: f64,
+    hit_probability: f64,
+    ammo_capacity: usize,
+    ammo: usize,
+}
+
+impl PointDefense {
+    pub fn new(range: f64, fire_interval: f64, hit_probability: f64, ammo_capacity: usize) -> PointDefense {
+        let ammo = ammo_capacity;
+        PointDefense { range, fire_interval, hit_probability, ammo_capacity, ammo }
+    }
+
+    /// Turrets fitted together fire independently, so their rates of fire
+    /// and ammo add up while the longest range is used
+    pub fn combine(&self, other: &PointDefense) -> PointDefense {
+        let range = f64::max(self.range, other.range);
+        let fire_interval = 1.0 / (1.0 / self.fire_interval + 1.0 / other.fire_interval);
+        let hit_probability = f64::max(self.hit_probability, other.hit_probability);
+        let ammo_capacity = self.ammo_capacity + other.ammo_capacity;
+        let ammo = self.ammo + other.ammo;
+        PointDefense { range, fire_interval, hit_probability, ammo_capacity, ammo }
+    }
+
+    pub fn range(&self) -> f64 {
+        self.range
+    }
+
+    /// Time between shots
+    pub fn fire_interval(&self) -> f64 {
+        self.fire_interval
+    }
+
+    pub fn hit_probability(&self) -> f64 {
+        self.hit_probability
+    }
+
+    pub fn ammo_capacity(&self) -> usize {
+        self.ammo_capacity
+    }
+
+    pub fn ammo(&self) -> usize {
+        self.ammo
+    }
+
+    pub fn set_ammo(&mut self, ammo: usize) {
+        self.ammo = ammo;
+        assert!(self.ammo <= self.ammo_capacity);
+    }
+
+    pub fn consume_ammo(&mut self, shots: usize) {
+        self.ammo = self.ammo.saturating_sub(shots);
+    }
+}
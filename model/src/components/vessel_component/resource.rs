@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use super::fuel_tank::FUEL_DENSITY_KG_PER_LITRE;
+
+pub const FUEL_TRANSFER_RATE: f64 = 0.1;
+pub const TORPEDO_TRANSFER_TIME: f64 = 1800.0;
+pub const BULK_TRANSFER_RATE: f64 = 1.0;
+
+/// Where a vessel keeps a resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceStorage {
+    FuelTank,
+    TorpedoStorage,
+    CargoHold,
+}
+
+/// How quickly a resource moves between docked vessels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResourceTransferRate {
+    /// Flows continuously at this many units per second
+    Continuous { units_per_second: f64 },
+    /// Moves one whole unit every this many seconds
+    Discrete { seconds_per_unit: f64 },
+}
+
+/// Every stat of a resource type
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceDefinition {
+    name: &'static str,
+    /// Suffix used when displaying an amount of the resource
+    unit: &'static str,
+    unit_mass_kg: f64,
+    unit_volume_litres: f64,
+    storage: ResourceStorage,
+    transfer_rate: ResourceTransferRate,
+}
+
+impl ResourceDefinition {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn unit(&self) -> &'static str {
+        self.unit
+    }
+
+    pub fn unit_mass_kg(&self) -> f64 {
+        self.unit_mass_kg
+    }
+
+    pub fn unit_volume_litres(&self) -> f64 {
+        self.unit_volume_litres
+    }
+
+    pub fn storage(&self) -> ResourceStorage {
+        self.storage
+    }
+
+    pub fn transfer_rate(&self) -> ResourceTransferRate {
+        self.transfer_rate
+    }
+}
+
+/// Every kind of resource a vessel can carry. Amounts of continuous
+/// resources are in kilograms, and amounts of discrete ones are whole units
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ResourceType {
+    Fuel,
+    Torpedoes,
+    Metal,
+    Alloys,
+    Oil,
+}
+
+impl ResourceType {
+    pub const ALL: [ResourceType; 5] = [ResourceType::Fuel, ResourceType::Torpedoes, ResourceType::Metal, ResourceType::Alloys, ResourceType::Oil];
+
+    pub fn definition(self) -> ResourceDefinition {
+        let bulk = |name, unit_volume_litres| ResourceDefinition {
+            name,
+            unit: "kg",
+            unit_mass_kg: 1.0,
+            unit_volume_litres,
+            storage: ResourceStorage::CargoHold,
+            transfer_rate: ResourceTransferRate::Continuous { units_per_second: BULK_TRANSFER_RATE },
+        };
+        match self {
+            ResourceType::Fuel => ResourceDefinition {
+                name: "Fuel",
+                unit: "kg",
+                unit_mass_kg: 1.0,
+                unit_volume_litres: 1.0 / FUEL_DENSITY_KG_PER_LITRE,
+                storage: ResourceStorage::FuelTank,
+                transfer_rate: ResourceTransferRate::Continuous { units_per_second: FUEL_TRANSFER_RATE },
+            },
+            ResourceType::Torpedoes => ResourceDefinition {
+                name: "Torpedoes",
+                unit: "",
+                unit_mass_kg: 2.0e3,
+                unit_volume_litres: 10.0e3,
+                storage: ResourceStorage::TorpedoStorage,
+                transfer_rate: ResourceTransferRate::Discrete { seconds_per_unit: TORPEDO_TRANSFER_TIME },
+            },
+            ResourceType::Metal => bulk("Metal", 0.13),
+            ResourceType::Alloys => bulk("Alloys", 0.15),
+            ResourceType::Oil => bulk("Oil", 1.1),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        self.definition().name()
+    }
+
+    pub fn is_discrete(self) -> bool {
+        matches!(self.definition().transfer_rate(), ResourceTransferRate::Discrete { .. })
+    }
+
+    pub fn is_cargo(self) -> bool {
+        self.definition().storage() == ResourceStorage::CargoHold
+    }
+}
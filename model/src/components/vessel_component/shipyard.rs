@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{class::VesselClass, resource::ResourceType};
+
+/// A vessel waiting to be built, or being built, by a station
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildOrder {
+    class: VesselClass,
+    /// What was taken from the station when the order was placed, which is
+    /// given back if it is cancelled
+    paid: BTreeMap<ResourceType, f64>,
+    time_remaining: f64,
+}
+
+impl BuildOrder {
+    pub fn new(class: VesselClass, paid: BTreeMap<ResourceType, f64>, time_remaining: f64) -> Self {
+        Self { class, paid, time_remaining }
+    }
+
+    pub fn class(&self) -> VesselClass {
+        self.class
+    }
+
+    pub fn paid(&self) -> &BTreeMap<ResourceType, f64> {
+        &self.paid
+    }
+
+    pub fn time_remaining(&self) -> f64 {
+        self.time_remaining
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.time_remaining <= 0.0
+    }
+
+    /// Returns the part of `dt` which was not needed to finish the order
+    pub(crate) fn step(&mut self, dt: f64) -> f64 {
+        let used = f64::min(dt, self.time_remaining);
+        self.time_remaining -= used;
+        dt - used
+    }
+}
+
+/// Only the first order is worked on. Once it is finished, it waits there
+/// until a docking port is free for the new vessel
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BuildQueue {
+    orders: Vec<BuildOrder>,
+}
+
+impl BuildQueue {
+    pub fn orders(&self) -> &[BuildOrder] {
+        &self.orders
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    pub(crate) fn push(&mut self, order: BuildOrder) {
+        self.orders.push(order);
+    }
+
+    pub(crate) fn front_mut(&mut self) -> Option<&mut BuildOrder> {
+        self.orders.first_mut()
+    }
+
+    /// # Panics
+    /// Panics if there is no order at `index`
+    pub(crate) fn remove(&mut self, index: usize) -> BuildOrder {
+        assert!(index < self.orders.len(), "Attempt to remove nonexistant build order");
+        self.orders.remove(index)
+    }
+
+    /// Moves the order at `from` so that it ends up at `to`, keeping any
+    /// progress made on it
+    /// # Panics
+    /// Panics if either index is out of range
+    pub(crate) fn reorder(&mut self, from: usize, to: usize) {
+        assert!(from < self.orders.len() && to < self.orders.len(), "Attempt to reorder nonexistant build order");
+        let order = self.orders.remove(from);
+        self.orders.insert(to, order);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use crate::components::vessel_component::class::VesselClass;
+
+    use super::{BuildOrder, BuildQueue};
+
+    #[test]
+    fn test_reorder_keeps_progress() {
+        let mut queue = BuildQueue::default();
+        queue.push(BuildOrder::new(VesselClass::Scout1, BTreeMap::new(), 100.0));
+        queue.push(BuildOrder::new(VesselClass::Frigate1, BTreeMap::new(), 200.0));
+
+        assert!((queue.front_mut().unwrap().step(150.0) - 50.0).abs() < 1.0e-6);
+        assert!(queue.orders()[0].is_finished());
+
+        queue.reorder(0, 1);
+        assert_eq!(queue.orders()[0].class(), VesselClass::Frigate1);
+        assert_eq!(queue.orders()[1].class(), VesselClass::Scout1);
+        assert!(queue.orders()[1].is_finished());
+
+        assert_eq!(queue.remove(0).class(), VesselClass::Frigate1);
+        assert_eq!(queue.orders().len(), 1);
+    }
+}
@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+
+use super::{engine::Engine, gun::Gun, point_defense::PointDefense};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SlotType {
+    Engine,
+    FuelTank,
+    Weapon,
+    Sensor,
+    Utility,
+}
+
+impl SlotType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SlotType::Engine => "Engine",
+            SlotType::FuelTank => "Fuel tank",
+            SlotType::Weapon => "Weapon",
+            SlotType::Sensor => "Sensor",
+            SlotType::Utility => "Utility",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModuleType {
+    EngineRegular,
+    EngineEfficient,
+    EngineBooster,
+    FuelTankSmall,
+    FuelTankLarge,
+    TorpedoLauncherLight,
+    TorpedoLauncherHeavy,
+    PointDefenseTurret,
+    KineticGun,
+    SensorShortRange,
+    SensorLongRange,
+    MiningDrill,
+}
+
+impl ModuleType {
+    pub const ALL: [ModuleType; 12] = [
+        ModuleType::EngineRegular,
+        ModuleType::EngineEfficient,
+        ModuleType::EngineBooster,
+        ModuleType::FuelTankSmall,
+        ModuleType::FuelTankLarge,
+        ModuleType::TorpedoLauncherLight,
+        ModuleType::TorpedoLauncherHeavy,
+        ModuleType::PointDefenseTurret,
+        ModuleType::KineticGun,
+        ModuleType::SensorShortRange,
+        ModuleType::SensorLongRange,
+        ModuleType::MiningDrill,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModuleType::EngineRegular => "Regular engine",
+            ModuleType::EngineEfficient => "Efficient engine",
+            ModuleType::EngineBooster => "Booster engine",
+            ModuleType::FuelTankSmall => "Small fuel tank",
+            ModuleType::FuelTankLarge => "Large fuel tank",
+            ModuleType::TorpedoLauncherLight => "Light torpedo launcher",
+            ModuleType::TorpedoLauncherHeavy => "Heavy torpedo launcher",
+            ModuleType::PointDefenseTurret => "Point defense turret",
+            ModuleType::KineticGun => "Kinetic gun",
+            ModuleType::SensorShortRange => "Short range sensor",
+            ModuleType::SensorLongRange => "Long range sensor",
+            ModuleType::MiningDrill => "Mining drill",
+        }
+    }
+
+    pub fn slot_type(&self) -> SlotType {
+        match self {
+            ModuleType::EngineRegular | ModuleType::EngineEfficient | ModuleType::EngineBooster => SlotType::Engine,
+            ModuleType::FuelTankSmall | ModuleType::FuelTankLarge => SlotType::FuelTank,
+            ModuleType::TorpedoLauncherLight | ModuleType::TorpedoLauncherHeavy | ModuleType::PointDefenseTurret | ModuleType::KineticGun => SlotType::Weapon,
+            ModuleType::SensorShortRange | ModuleType::SensorLongRange => SlotType::Sensor,
+            ModuleType::MiningDrill => SlotType::Utility,
+        }
+    }
+
+    /// Added to the dry mass of the vessel the module is fitted to
+    pub fn mass(&self) -> f64 {
+        match self {
+            ModuleType::EngineRegular => 2.0e3,
+            ModuleType::EngineEfficient => 3.0e3,
+            ModuleType::EngineBooster => 4.0e3,
+            ModuleType::FuelTankSmall => 1.0e3,
+            ModuleType::FuelTankLarge => 2.5e3,
+            ModuleType::TorpedoLauncherLight => 1.5e3,
+            ModuleType::TorpedoLauncherHeavy => 3.5e3,
+            ModuleType::PointDefenseTurret => 0.8e3,
+            ModuleType::KineticGun => 1.8e3,
+            ModuleType::SensorShortRange => 0.5e3,
+            ModuleType::SensorLongRange => 1.2e3,
+            ModuleType::MiningDrill => 2.8e3,
+        }
+    }
+
+    pub fn engine(&self) -> Option<Engine> {
+        match self {
+            ModuleType::EngineRegular => Some(Engine::new(30.0, 70_000.0)),
+            ModuleType::EngineEfficient => Some(Engine::new(10.0, 35_000.0)),
+            ModuleType::EngineBooster => Some(Engine::new(60.0, 150_000.0)),
+            _ => None,
+        }
+    }
+
+    pub fn fuel_capacity_litres(&self) -> f64 {
+        match self {
+            ModuleType::FuelTankSmall => 10_000.0,
+            ModuleType::FuelTankLarge => 30_000.0,
+            _ => 0.0,
+        }
+    }
+
+    pub fn torpedo_launcher_cooldown(&self) -> Option<f64> {
+        match self {
+            ModuleType::TorpedoLauncherLight => Some(2.0 * 60.0 * 60.0),
+            ModuleType::TorpedoLauncherHeavy => Some(60.0 * 60.0),
+            _ => None,
+        }
+    }
+
+    /// Torpedoes carried in the launcher's magazine
+    pub fn torpedo_capacity(&self) -> usize {
+        match self {
+            ModuleType::TorpedoLauncherLight => 2,
+            ModuleType::TorpedoLauncherHeavy => 4,
+            _ => 0,
+        }
+    }
+
+    /// Fires once a second at torpedoes within 20km
+    pub fn point_defense(&self) -> Option<PointDefense> {
+        match self {
+            ModuleType::PointDefenseTurret => Some(PointDefense::new(20.0e3, 1.0, 0.1, 60)),
+            _ => None,
+        }
+    }
+
+    /// Fires a projectile at 3km/s every minute
+    pub fn gun(&self) -> Option<Gun> {
+        match self {
+            ModuleType::KineticGun => Some(Gun::new(3.0e3, 60.0, 20)),
+            _ => None,
+        }
+    }
+
+    /// Range at which a vessel with an ordinary signature is detected. Every
+    /// vessel can also see up to `PASSIVE_SENSOR_RANGE` without a sensor
+    pub fn sensor_range(&self) -> f64 {
+        match self {
+            ModuleType::SensorShortRange => 1.0e6,
+            ModuleType::SensorLongRange => 5.0e6,
+            _ => 0.0,
+        }
+    }
+
+    /// Kilograms extracted per second from an asteroid
+    pub fn mining_rate(&self) -> f64 {
+        match self {
+            ModuleType::MiningDrill => 2.0,
+            _ => 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Slot {
+    #[serde(rename = "type")]
+    type_: SlotType,
+    #[serde(default)]
+    module: Option<ModuleType>,
+}
+
+impl Slot {
+    pub fn new(type_: SlotType, module: Option<ModuleType>) -> Self {
+        Self { type_, module }
+    }
+
+    pub fn type_(&self) -> SlotType {
+        self.type_
+    }
+
+    pub fn module(&self) -> Option<ModuleType> {
+        self.module
+    }
+
+    pub fn can_fit(&self, module: Option<ModuleType>) -> bool {
+        module.is_none_or(|module| module.slot_type() == self.type_)
+    }
+
+    pub(crate) fn set_module(&mut self, module: Option<ModuleType>) {
+        assert!(self.can_fit(module), "Attempt to fit module into slot of the wrong type");
+        self.module = module;
+    }
+}
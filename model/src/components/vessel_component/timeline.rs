@@ -1,19 +1,31 @@
 use std::collections::VecDeque;
 
+use dock::DockEvent;
+use fire_gun::FireGunEvent;
+use impact::ImpactEvent;
 use intercept::InterceptEvent;
 use log::trace;
+use point_defense::PointDefenseEvent;
 use serde::{Deserialize, Serialize};
 use start_turn::StartTurnEvent;
+use transfer::TransferEvent;
+use undock::UndockEvent;
 
-use crate::model::Model;
+use crate::{model::Model, storage::entity_allocator::Entity};
 
 use self::{start_guidance::StartGuidanceEvent, fire_torpedo::FireTorpedoEvent, start_burn::StartBurnEvent};
 
+pub mod impact;
 pub mod intercept;
+pub mod point_defense;
 pub mod start_guidance;
 pub mod start_burn;
 pub mod start_turn;
 pub mod fire_torpedo;
+pub mod fire_gun;
+pub mod dock;
+pub mod undock;
+pub mod transfer;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum TimelineEvent {
@@ -22,6 +34,12 @@ pub enum TimelineEvent {
     StartBurn(StartBurnEvent),
     StartTurn(StartTurnEvent),
     StartGuidance(StartGuidanceEvent),
+    Impact(ImpactEvent),
+    PointDefense(PointDefenseEvent),
+    FireGun(FireGunEvent),
+    Dock(DockEvent),
+    Undock(UndockEvent),
+    Transfer(TransferEvent),
 }
 
 impl TimelineEvent {
@@ -32,6 +50,12 @@ impl TimelineEvent {
             TimelineEvent::Intercept(event) => event.execute(model),
             TimelineEvent::StartGuidance(event) => event.execute(model),
             TimelineEvent::FireTorpedo(event) => event.execute(model),
+            TimelineEvent::Impact(event) => event.execute(model),
+            TimelineEvent::PointDefense(event) => event.execute(model),
+            TimelineEvent::FireGun(event) => event.execute(model),
+            TimelineEvent::Dock(event) => event.execute(model),
+            TimelineEvent::Undock(event) => event.execute(model),
+            TimelineEvent::Transfer(event) => event.execute(model),
         }
     }
 
@@ -42,6 +66,12 @@ impl TimelineEvent {
             TimelineEvent::Intercept(event) => event.cancel(model),
             TimelineEvent::StartGuidance(event) => event.cancel(model),
             TimelineEvent::FireTorpedo(event) => event.cancel(model),
+            TimelineEvent::Impact(event) => event.cancel(model),
+            TimelineEvent::PointDefense(event) => event.cancel(model),
+            TimelineEvent::FireGun(event) => event.cancel(model),
+            TimelineEvent::Dock(event) => event.cancel(model),
+            TimelineEvent::Undock(event) => event.cancel(model),
+            TimelineEvent::Transfer(event) => event.cancel(model),
         }
     }
 
@@ -52,6 +82,12 @@ impl TimelineEvent {
             TimelineEvent::Intercept(event) => event.time(),
             TimelineEvent::StartGuidance(event) => event.time(),
             TimelineEvent::FireTorpedo(event) => event.time(),
+            TimelineEvent::Impact(event) => event.time(),
+            TimelineEvent::PointDefense(event) => event.time(),
+            TimelineEvent::FireGun(event) => event.time(),
+            TimelineEvent::Dock(event) => event.time(),
+            TimelineEvent::Undock(event) => event.time(),
+            TimelineEvent::Transfer(event) => event.time(),
         }
     }
 
@@ -62,6 +98,12 @@ impl TimelineEvent {
             TimelineEvent::Intercept(event) => event.can_remove(),
             TimelineEvent::StartGuidance(event) => event.can_remove(model),
             TimelineEvent::FireTorpedo(event) => event.can_remove(),
+            TimelineEvent::Impact(event) => event.can_remove(),
+            TimelineEvent::PointDefense(event) => event.can_remove(),
+            TimelineEvent::FireGun(event) => event.can_remove(),
+            TimelineEvent::Dock(event) => event.can_remove(),
+            TimelineEvent::Undock(event) => event.can_remove(),
+            TimelineEvent::Transfer(event) => event.can_remove(),
         }
     }
 
@@ -72,6 +114,12 @@ impl TimelineEvent {
             TimelineEvent::Intercept(event) => event.can_remove(),
             TimelineEvent::StartGuidance(event) => event.can_remove(model),
             TimelineEvent::FireTorpedo(event) => event.can_remove(),
+            TimelineEvent::Impact(event) => event.can_remove(),
+            TimelineEvent::PointDefense(event) => event.can_remove(),
+            TimelineEvent::FireGun(event) => event.can_remove(),
+            TimelineEvent::Dock(event) => event.can_remove(),
+            TimelineEvent::Undock(event) => event.can_remove(),
+            TimelineEvent::Transfer(event) => event.can_remove(),
         }
     }
 
@@ -83,6 +131,29 @@ impl TimelineEvent {
             TimelineEvent::Intercept(event) => event.is_blocking(),
             TimelineEvent::StartGuidance(event) => event.is_blocking(),
             TimelineEvent::FireTorpedo(event) => event.is_blocking(),
+            TimelineEvent::Impact(event) => event.is_blocking(),
+            TimelineEvent::PointDefense(event) => event.is_blocking(),
+            TimelineEvent::FireGun(event) => event.is_blocking(),
+            TimelineEvent::Dock(event) => event.is_blocking(),
+            TimelineEvent::Undock(event) => event.is_blocking(),
+            TimelineEvent::Transfer(event) => event.is_blocking(),
+        }
+    }
+
+    /// The ghost torpedo or projectile which the event launches
+    pub fn ghost(&self) -> Option<Entity> {
+        match self {
+            TimelineEvent::FireTorpedo(event) => Some(event.ghost()),
+            TimelineEvent::FireGun(event) => Some(event.projectile()),
+            TimelineEvent::StartBurn(_)
+                | TimelineEvent::StartTurn(_)
+                | TimelineEvent::Intercept(_)
+                | TimelineEvent::StartGuidance(_)
+                | TimelineEvent::Impact(_)
+                | TimelineEvent::PointDefense(_)
+                | TimelineEvent::Dock(_)
+                | TimelineEvent::Undock(_)
+                | TimelineEvent::Transfer(_) => None,
         }
     }
 
@@ -106,6 +177,30 @@ impl TimelineEvent {
         matches!(self, TimelineEvent::FireTorpedo(_))
     }
 
+    pub fn is_impact(&self) -> bool {
+        matches!(self, TimelineEvent::Impact(_))
+    }
+
+    pub fn is_point_defense(&self) -> bool {
+        matches!(self, TimelineEvent::PointDefense(_))
+    }
+
+    pub fn is_fire_gun(&self) -> bool {
+        matches!(self, TimelineEvent::FireGun(_))
+    }
+
+    pub fn is_dock(&self) -> bool {
+        matches!(self, TimelineEvent::Dock(_))
+    }
+
+    pub fn is_undock(&self) -> bool {
+        matches!(self, TimelineEvent::Undock(_))
+    }
+
+    pub fn is_transfer(&self) -> bool {
+        matches!(self, TimelineEvent::Transfer(_))
+    }
+
     pub fn as_start_burn(&self) -> Option<StartBurnEvent> {
         if let TimelineEvent::StartBurn(event_type) = self {
             Some(event_type.clone())
@@ -138,6 +233,14 @@ impl TimelineEvent {
         }
     }
 
+    pub fn as_impact(&self) -> Option<ImpactEvent> {
+        if let TimelineEvent::Impact(event_type) = self {
+            Some(event_type.clone())
+        } else {
+            None
+        }
+    }
+
     pub fn as_fire_torpedo(&self) -> Option<FireTorpedoEvent> {
         if let TimelineEvent::FireTorpedo(event_type) = self {
             Some(event_type.clone())
@@ -145,6 +248,46 @@ impl TimelineEvent {
             None
         }
     }
+
+    pub fn as_point_defense(&self) -> Option<PointDefenseEvent> {
+        if let TimelineEvent::PointDefense(event_type) = self {
+            Some(event_type.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn as_fire_gun(&self) -> Option<FireGunEvent> {
+        if let TimelineEvent::FireGun(event_type) = self {
+            Some(event_type.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn as_dock(&self) -> Option<DockEvent> {
+        if let TimelineEvent::Dock(event_type) = self {
+            Some(event_type.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn as_undock(&self) -> Option<UndockEvent> {
+        if let TimelineEvent::Undock(event_type) = self {
+            Some(event_type.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn as_transfer(&self) -> Option<TransferEvent> {
+        if let TimelineEvent::Transfer(event_type) = self {
+            Some(event_type.clone())
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -168,6 +311,10 @@ impl Timeline {
         self.events.insert(i, event);
     }
 
+    pub fn retain(&mut self, f: impl FnMut(&TimelineEvent) -> bool) {
+        self.events.retain(f);
+    }
+
     pub fn event_at_time(&self, time: f64) -> Option<&TimelineEvent> {
         self.events.iter().find(|event| event.time() == time)
     }
@@ -183,6 +330,12 @@ impl Timeline {
             .clone()
     }
 
+    pub fn last_fire_gun_event(&self) -> Option<FireGunEvent> {
+        self.events.iter()
+            .rev()
+            .find_map(TimelineEvent::as_fire_gun)
+    }
+
     pub fn last_blocking_event(&self) -> Option<TimelineEvent> {
         self.events.iter()
             .rev()
@@ -196,8 +349,16 @@ impl Timeline {
             .count()
     }
 
+    pub fn depleted_gun_ammo(&self) -> usize {
+        self.events.iter()
+            .filter(|event| event.is_fire_gun())
+            .count()
+    }
+
     pub fn is_time_after_last_blocking_event(&self, time: f64) -> bool {
         match self.last_blocking_event() {
+            // The vessel has no trajectory to plan on once it has docked
+            Some(event) if event.is_dock() => false,
             Some(event) => event.time() <= time,
             None => true,
         }
@@ -210,6 +371,12 @@ impl Timeline {
         self.events.pop_back().unwrap()
     }
 
+    /// Includes any event at `time`, without cancelling them
+    pub fn remove_events_from(&mut self, time: f64) -> Vec<TimelineEvent> {
+        let index = self.events.iter().position(|event| event.time() >= time).unwrap_or(self.events.len());
+        self.events.split_off(index).into()
+    }
+
     /// Does not include any event at `time`
     #[allow(clippy::missing_panics_doc)]
     pub fn pop_events_before(&mut self, time: f64) -> Vec<TimelineEvent> {
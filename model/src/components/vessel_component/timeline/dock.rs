@@ -0,0 +1,73 @@
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::{components::vessel_component::VesselComponent, model::Model, storage::entity_allocator::Entity};
+
+/// Docks the vessel to the station at a planned rendezvous. Must be the
+/// last event in the timeline, since the vessel has no trajectory once docked
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DockEvent {
+    entity: Entity,
+    station: Entity,
+    time: f64,
+}
+
+impl DockEvent {
+    pub fn new(entity: Entity, station: Entity, time: f64) -> Self {
+        Self { entity, station, time }
+    }
+
+    /// Checked again against the predicted positions, because the station
+    /// may have moved or had its ports filled since the dock was planned.
+    /// Scheduled undocks count as free ports when planning, so a port must
+    /// also actually be free now
+    pub fn execute(&self, model: &mut Model) {
+        if model.try_vessel_component(self.station).is_none()
+            || !model.can_dock_at(self.entity, self.station, self.time)
+            || model.find_free_docking_port(self.station).is_none() {
+            debug!("Scheduled dock of {:?} to {:?} is no longer possible", self.entity, self.station);
+            return;
+        }
+        model.dock_to_free_port(self.station, self.entity);
+    }
+
+    pub fn cancel(&self, _model: &mut Model) {}
+
+    pub fn is_blocking(&self) -> bool {
+        true
+    }
+
+    pub fn can_remove(&self) -> bool {
+        true
+    }
+
+    pub fn can_adjust(&self) -> bool {
+        false
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn station(&self) -> Entity {
+        self.station
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn can_create_ever(model: &Model, entity: Entity, station: Entity) -> bool {
+        model.vessel_component(entity).can_dock()
+            && model.try_vessel_component(station).is_some_and(VesselComponent::has_docking)
+    }
+
+    pub fn can_create(model: &Model, entity: Entity, station: Entity, time: f64) -> bool {
+        let timeline = model.vessel_component(entity).timeline();
+        time > model.time()
+            && timeline.is_time_after_last_blocking_event(time)
+            && timeline.last_event().is_none_or(|event| event.time() < time)
+            && model.path_component(entity).end_segment().end_time() > time
+            && model.can_dock_at(entity, station, time)
+    }
+}
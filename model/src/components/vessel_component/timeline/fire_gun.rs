@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{components::{name_component::NameComponent, path_component::PathComponent, vessel_component::{class::VesselClass, VesselComponent}}, model::{state_query::StateQuery, Model}, storage::{entity_allocator::Entity, entity_builder::EntityBuilder}};
+
+use super::{intercept::InterceptEvent, TimelineEvent};
+
+/// Fires an inert projectile in the direction the vessel is pointing. The
+/// projectile coasts on pure orbits, so whether it hits the vessel's target
+/// is predicted as soon as it is fired. Either way, the projectile is
+/// removed at the end of its flight
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FireGunEvent {
+    time: f64,
+    fire_from: Entity,
+    projectile: Entity,
+    hit_time: Option<f64>,
+}
+
+impl FireGunEvent {
+    /// # Panics
+    /// Panics if the vessel has no gun or target, or is not on an orbit at `time`
+    pub fn new(model: &mut Model, fire_from: Entity, time: f64) -> Self {
+        let mut vessel_component = VesselComponent::new(VesselClass::Projectile, model.vessel_component(fire_from).faction());
+        let target = model.vessel_component(fire_from)
+            .target()
+            .expect("Attempt to fire a gun from a vessel without a target");
+        vessel_component.set_target(Some(target));
+
+        let rotation = model.snapshot_at(time).rotation(fire_from);
+        let orbit = model.projectile_orbit(fire_from, time, rotation)
+            .expect("Attempt to fire a gun from a vessel which cannot fire");
+
+        let projectile = model.allocate(EntityBuilder::default()
+            .with_name_component(NameComponent::new("Projectile".to_string()))
+            .with_path_component(PathComponent::new_with_orbit(orbit))
+            .with_vessel_component(vessel_component));
+        model.recompute_trajectory(projectile);
+
+        let (end_time, hit) = model.predict_projectile_flight(projectile, target, time);
+        model.path_component_mut(projectile).remove_segments_after(end_time);
+        let event = TimelineEvent::Intercept(InterceptEvent::new(model, projectile, target, end_time));
+        model.vessel_component_mut(projectile).timeline_mut().add(event);
+        let hit_time = hit.then_some(end_time);
+
+        Self { time, fire_from, projectile, hit_time }
+    }
+
+    pub fn execute(&self, model: &mut Model) {
+        model.vessel_component_mut(self.projectile).unset_ghost();
+        model.vessel_component_mut(self.fire_from).decrement_gun_ammo();
+        model.reveal(self.fire_from);
+    }
+
+    pub fn cancel(&self, model: &mut Model) {
+        model.deallocate(self.projectile);
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        true
+    }
+
+    pub fn can_remove(&self) -> bool {
+        true
+    }
+
+    pub fn can_create_ever(model: &Model, entity: Entity) -> bool {
+        model.vessel_component(entity).has_gun()
+    }
+
+    /// The vessel has to be coasting, since projectiles are fired with
+    /// the velocity of the orbit it is on
+    pub fn can_create(model: &Model, entity: Entity, time: f64) -> bool {
+        let vessel_component = model.vessel_component(entity);
+        let Some(gun) = vessel_component.gun() else {
+            return false;
+        };
+        if vessel_component.timeline().last_fire_gun_event().is_some_and(|event| event.time + gun.cooldown() > time) {
+            return false;
+        }
+        vessel_component.timeline().is_time_after_last_blocking_event(time)
+            && vessel_component.target().is_some()
+            && vessel_component.final_gun_ammo() != 0
+            && model.projectile_orbit(entity, time, 0.0).is_some()
+    }
+
+    pub fn projectile(&self) -> Entity {
+        self.projectile
+    }
+
+    /// When the projectile is predicted to hit the target, if it will
+    pub fn hit_time(&self) -> Option<f64> {
+        self.hit_time
+    }
+}
@@ -1,7 +1,7 @@
 use nalgebra_glm::DVec2;
 use serde::{Deserialize, Serialize};
 
-use crate::{components::{name_component::NameComponent, path_component::{orbit::builder::OrbitBuilder, rocket_equation_function::RocketEquationFunction, PathComponent}, vessel_component::{class::VesselClass, VesselComponent}}, model::{state_query::StateQuery, Model}, storage::{entity_allocator::Entity, entity_builder::EntityBuilder}};
+use crate::{components::{name_component::NameComponent, path_component::{orbit::builder::OrbitBuilder, rocket_equation_function::RocketEquationFunction, PathComponent}, vessel_component::{class::VesselClass, health::Subsystem, VesselComponent}}, model::{state_query::StateQuery, Model}, storage::{entity_allocator::Entity, entity_builder::EntityBuilder}};
 
 const TIME_BEFORE_BURN_START: f64 = 0.1;
 const INITIAL_DV: DVec2 = DVec2::new(0.0, 1.0);
@@ -17,6 +17,9 @@ pub struct FireTorpedoEvent {
 impl FireTorpedoEvent {
     pub fn new(model: &mut Model, fire_from: Entity, time: f64) -> Self {
         let mut vessel_component = VesselComponent::new(VesselClass::Torpedo, model.vessel_component(fire_from).faction());
+        if model.vessel_component(fire_from).is_ai_controlled() {
+            vessel_component = vessel_component.with_ai_control();
+        }
         vessel_component.set_target(model.vessel_component(fire_from).target());
 
         let snapshot = &model.snapshot_at(time);
@@ -52,6 +55,7 @@ impl FireTorpedoEvent {
         model.vessel_component_mut(self.ghost).unset_ghost();
         model.vessel_component_mut(self.fire_from).decrement_torpedoes();
         model.vessel_component_mut(self.fire_from).torpedo_launcher.as_mut().unwrap().reset_time_to_reload();
+        model.reveal(self.fire_from);
     }
 
     pub fn cancel(&self, model: &mut Model) {
@@ -82,6 +86,7 @@ impl FireTorpedoEvent {
         let vessel_component = &model.vessel_component(entity);
         vessel_component.has_torpedo_storage()
             && vessel_component.has_torpedo_launcher()
+            && !vessel_component.is_subsystem_disabled(Subsystem::TorpedoLauncher)
     }
 
     pub fn can_create(model: &Model, entity: Entity, time: f64) -> bool {
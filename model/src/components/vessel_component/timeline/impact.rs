@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{model::{explosion::Explosion, state_query::StateQuery, story_event::StoryEvent, Model}, storage::entity_allocator::Entity};
+
+/// Added by trajectory prediction when a vessel's path reaches the surface of a body
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImpactEvent {
+    entity: Entity,
+    time: f64,
+}
+
+impl ImpactEvent {
+    pub fn new(entity: Entity, time: f64) -> Self {
+        Self { entity, time }
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    pub fn execute(&self, model: &mut Model) {
+        let snapshot = model.snapshot_at(self.time);
+        let parent = snapshot.parent(self.entity).unwrap();
+        let offset = snapshot.position(self.entity);
+        let mass = snapshot.mass(self.entity);
+        model.add_explosion(Explosion::new(parent, offset, mass));
+        model.add_story_event(StoryEvent::Impact(self.entity));
+        model.deallocate(self.entity);
+    }
+
+    pub fn cancel(&self, _model: &mut Model) {}
+
+    pub fn is_blocking(&self) -> bool {
+        false
+    }
+
+    pub fn can_remove(&self) -> bool {
+        false
+    }
+
+    pub fn can_adjust(&self) -> bool {
+        false
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+}
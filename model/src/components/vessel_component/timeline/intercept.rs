@@ -1,6 +1,7 @@
+use log::debug;
 use serde::{Deserialize, Serialize};
 
-use crate::{model::{explosion::Explosion, state_query::StateQuery, Model}, storage::entity_allocator::Entity};
+use crate::{model::Model, storage::entity_allocator::Entity};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct InterceptEvent {
@@ -14,15 +15,16 @@ impl InterceptEvent {
         Self { entity, target, time }
     }
 
-    #[allow(clippy::missing_panics_doc)]
+    /// Projectiles are unguided and their flight always ends here, so if
+    /// they were predicted to miss, or the target has moved since the hit
+    /// was predicted, they are removed
     pub fn execute(&self, model: &mut Model) {
-        let snapshot = model.snapshot_at(self.time);
-        let parent = snapshot.parent(self.entity).unwrap();
-        let offset = snapshot.position(self.entity);
-        let combined_mass = snapshot.mass(self.entity) + snapshot.mass(self.target);
-        model.add_explosion(Explosion::new(parent, offset, combined_mass));
-        model.deallocate(self.entity);
-        model.deallocate(self.target);
+        if model.vessel_component(self.entity).class().is_projectile() && !model.projectile_hits(self.entity, self.target, self.time) {
+            debug!("Projectile {:?} missed {:?}", self.entity, self.target);
+            model.deallocate(self.entity);
+            return;
+        }
+        model.detonate(self.entity, self.target, self.time);
     }
 
     pub fn cancel(&self, _model: &mut Model) {}
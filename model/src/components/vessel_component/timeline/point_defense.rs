@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{model::{explosion::Explosion, state_query::StateQuery, story_event::StoryEvent, Model}, storage::entity_allocator::Entity};
+
+/// Added alongside guidance when a hostile vessel's point defense will
+/// engage the torpedo. Happens at the time of the last shot, which is the
+/// one that destroys the torpedo if any do
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PointDefenseEvent {
+    entity: Entity,
+    defender: Entity,
+    time: f64,
+    shots: usize,
+    destroys_torpedo: bool,
+}
+
+impl PointDefenseEvent {
+    pub fn new(entity: Entity, defender: Entity, time: f64, shots: usize, destroys_torpedo: bool) -> Self {
+        Self { entity, defender, time, shots, destroys_torpedo }
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    pub fn execute(&self, model: &mut Model) {
+        if let Some(vessel_component) = model.try_vessel_component_mut(self.defender) {
+            vessel_component.consume_point_defense_ammo(self.shots);
+            model.reveal(self.defender);
+        }
+        if !self.destroys_torpedo {
+            return;
+        }
+        let snapshot = model.snapshot_at(self.time);
+        let parent = snapshot.parent(self.entity).unwrap();
+        let offset = snapshot.position(self.entity);
+        let mass = snapshot.mass(self.entity);
+        model.add_explosion(Explosion::new(parent, offset, mass));
+        model.add_story_event(StoryEvent::TorpedoShotDown { torpedo: self.entity, defender: self.defender });
+        model.deallocate(self.entity);
+    }
+
+    pub fn cancel(&self, _model: &mut Model) {}
+
+    pub fn is_blocking(&self) -> bool {
+        false
+    }
+
+    pub fn can_remove(&self) -> bool {
+        false
+    }
+
+    pub fn can_adjust(&self) -> bool {
+        false
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn defender(&self) -> Entity {
+        self.defender
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn shots(&self) -> usize {
+        self.shots
+    }
+
+    pub fn destroys_torpedo(&self) -> bool {
+        self.destroys_torpedo
+    }
+}
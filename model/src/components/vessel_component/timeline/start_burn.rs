@@ -1,7 +1,7 @@
 use nalgebra_glm::{vec2, DMat2, DVec2};
 use serde::{Deserialize, Serialize};
 
-use crate::{model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
+use crate::{components::vessel_component::health::Subsystem, model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
 
 const MIN_DV_TO_CREATE_BURN: f64 = 1.0;
 
@@ -22,6 +22,13 @@ impl StartBurnEvent {
         Self { entity, time }
     }
 
+    /// Creates the burn with a known delta-v (relative to the tangent),
+    /// for example one proposed by the intercept planner
+    pub fn new_with_delta_v(model: &mut Model, entity: Entity, time: f64, delta_v: DVec2) -> Self {
+        model.create_burn(entity, time, delta_v);
+        Self { entity, time }
+    }
+
     pub fn execute(&self, _model: &mut Model) {}
 
     pub fn cancel(&self, model: &mut Model) {
@@ -56,6 +63,7 @@ impl StartBurnEvent {
     pub fn can_create_ever(model: &Model, entity: Entity) -> bool {
         model.vessel_component(entity).has_engine()
             && model.vessel_component(entity).has_fuel_tank()
+            && !model.vessel_component(entity).is_subsystem_disabled(Subsystem::Engine)
     }
 
     #[allow(clippy::missing_panics_doc)]
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
+use crate::{components::vessel_component::health::Subsystem, model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
 
 const MIN_DV_TO_ENABLE_GUIDANCE: f64 = 1.0;
 
@@ -40,6 +40,7 @@ impl StartGuidanceEvent {
 
     pub fn can_create_ever(model: &Model, entity: Entity) -> bool {
         model.vessel_component(entity).class().is_torpedo()
+            && !model.vessel_component(entity).is_subsystem_disabled(Subsystem::Engine)
     }
 
     #[allow(clippy::missing_panics_doc)]
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
+use crate::{components::vessel_component::health::Subsystem, model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
 
 const MIN_FUEL_TO_CREATE_TURN: f64 = 1.0;
 
@@ -45,6 +45,7 @@ impl StartTurnEvent {
     pub fn can_create_ever(model: &Model, entity: Entity) -> bool {
         model.vessel_component(entity).has_rcs()
             && model.vessel_component(entity).has_fuel_tank()
+            && !model.vessel_component(entity).is_subsystem_disabled(Subsystem::Rcs)
     }
 
     #[allow(clippy::missing_panics_doc)]
@@ -0,0 +1,78 @@
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::{components::vessel_component::{docking::ResourceTransferDirection, resource::ResourceType}, model::Model, storage::entity_allocator::Entity};
+
+/// Starts a resource transfer between a station and a docked vessel at a
+/// planned time. Lives on the station's timeline, like undocking
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferEvent {
+    station: Entity,
+    entity: Entity,
+    time: f64,
+    resource: ResourceType,
+    direction: ResourceTransferDirection,
+    /// Without an amount the transfer runs until it can go no further
+    amount: Option<f64>,
+}
+
+impl TransferEvent {
+    pub fn new(station: Entity, entity: Entity, time: f64, resource: ResourceType, direction: ResourceTransferDirection, amount: Option<f64>) -> Self {
+        Self { station, entity, time, resource, direction, amount }
+    }
+
+    /// The vessel may have undocked, or docked to a different port, since
+    /// the transfer was planned
+    pub fn execute(&self, model: &mut Model) {
+        if model.find_docking_port(self.station, self.entity).is_none() {
+            debug!("Scheduled transfer between {:?} and {:?} is no longer possible", self.station, self.entity);
+            return;
+        }
+        model.start_transfer(self.station, self.entity, self.resource, self.direction, self.amount);
+    }
+
+    pub fn cancel(&self, _model: &mut Model) {}
+
+    pub fn is_blocking(&self) -> bool {
+        false
+    }
+
+    pub fn can_remove(&self) -> bool {
+        true
+    }
+
+    pub fn can_adjust(&self) -> bool {
+        false
+    }
+
+    pub fn station(&self) -> Entity {
+        self.station
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn resource(&self) -> ResourceType {
+        self.resource
+    }
+
+    pub fn direction(&self) -> ResourceTransferDirection {
+        self.direction
+    }
+
+    pub fn amount(&self) -> Option<f64> {
+        self.amount
+    }
+
+    /// The vessel has to be docked now, since there is no way to know which
+    /// vessels will be docked in the future
+    pub fn can_create(model: &Model, station: Entity, entity: Entity, time: f64) -> bool {
+        time > model.time()
+            && model.find_docking_port(station, entity).is_some()
+    }
+}
@@ -0,0 +1,71 @@
+use log::debug;
+use nalgebra_glm::DVec2;
+use serde::{Deserialize, Serialize};
+
+use crate::{model::Model, storage::entity_allocator::Entity};
+
+/// Just enough to drift clear of the station
+pub const DEFAULT_UNDOCK_DELTA_V: DVec2 = DVec2::new(1.0, 0.0);
+
+/// Undocks a vessel from the station at a planned time. Lives on the
+/// station's timeline, since docked vessels have no trajectory of their own
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UndockEvent {
+    station: Entity,
+    entity: Entity,
+    time: f64,
+    /// Relative to the station's velocity, with x prograde
+    delta_v: DVec2,
+}
+
+impl UndockEvent {
+    pub fn new(station: Entity, entity: Entity, time: f64, delta_v: DVec2) -> Self {
+        Self { station, entity, time, delta_v }
+    }
+
+    pub fn execute(&self, model: &mut Model) {
+        if model.find_docking_port(self.station, self.entity).is_none() {
+            debug!("Scheduled undock of {:?} from {:?} is no longer possible", self.entity, self.station);
+            return;
+        }
+        model.undock_with_delta_v(self.station, self.entity, self.time, self.delta_v);
+    }
+
+    pub fn cancel(&self, _model: &mut Model) {}
+
+    pub fn is_blocking(&self) -> bool {
+        false
+    }
+
+    pub fn can_remove(&self) -> bool {
+        true
+    }
+
+    pub fn can_adjust(&self) -> bool {
+        false
+    }
+
+    pub fn station(&self) -> Entity {
+        self.station
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn delta_v(&self) -> DVec2 {
+        self.delta_v
+    }
+
+    /// Only one undock can be scheduled for each docked vessel
+    pub fn can_create(model: &Model, station: Entity, entity: Entity, time: f64) -> bool {
+        time > model.time()
+            && model.path_component(station).end_segment().end_time() > time
+            && model.find_docking_port(station, entity).is_some()
+            && model.scheduled_undock(station, entity).is_none()
+    }
+}
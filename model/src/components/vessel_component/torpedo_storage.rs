@@ -28,4 +28,9 @@ impl TorpedoStorage {
     pub fn decrement(&mut self) {
         self.torpedoes -= 1;
     }
+
+    pub fn set_torpedoes(&mut self, torpedoes: usize) {
+        self.torpedoes = torpedoes;
+        assert!(self.torpedoes <= self.capacity());
+    }
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
 use std::{collections::HashSet, sync::Mutex};
 
+use detection::Intel;
 use encounters::Encounter;
 use explosion::Explosion;
+use factions::Factions;
+use history::TimelineHistory;
 use nalgebra_glm::{vec2, DVec2};
 use serde::{Deserialize, Serialize};
 use state_query::StateQuery;
@@ -15,11 +18,23 @@ pub const SEGMENTS_TO_PREDICT: usize = 3;
 pub mod closest_approach;
 pub mod closest_point;
 pub mod component;
+pub mod damage;
+pub mod detection;
 pub mod docking;
 pub mod encounters;
 pub mod explosion;
+pub mod factions;
+pub mod gun;
+pub mod history;
+pub mod intercept_planner;
+pub mod loadout;
+pub mod mining;
+pub mod point_defense;
+pub mod save;
 pub mod segment;
+pub mod shipyard;
 pub mod snapshot;
+pub mod state_hash;
 pub mod state_query;
 pub mod story_event;
 pub mod time;
@@ -39,6 +54,16 @@ pub struct Model {
     warp: Option<TimeWarp>,
     force_paused: bool,
     explosions_started_this_frame: Vec<Explosion>,
+    /// Saves from before point defense existed all use the same seed
+    #[serde(default)]
+    seed: u64,
+    #[serde(default)]
+    intel: Intel,
+    /// Saves from before factions were registered get the default three
+    #[serde(default)]
+    factions: Factions,
+    #[serde(default)]
+    timeline_history: TimelineHistory,
 }
 
 impl Default for Model {
@@ -55,47 +80,33 @@ impl Default for Model {
             warp: None,
             force_paused: false,
             explosions_started_this_frame: vec![],
+            seed: 0,
+            intel: Intel::default(),
+            factions: Factions::default(),
+            timeline_history: TimelineHistory::default(),
         }
     }
 }
 
 impl Model {
-
-    /// # Errors
-    /// Forwards serde deserialization error if deserialization fails
-    pub fn deserialize(serialized: &str) -> Result<Self, String> {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Deserialize");
-        match serde_json::from_str(serialized) {
-            Ok(model) => Ok(model),
-            Err(error) => Err(error.to_string()),
-        }
-    }
-
-    /// # Errors
-    /// Forwards serde serialization error if serialization fails
-    pub fn serialize(&self) -> Result<String, String> {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Serialize");
-        match serde_json::to_string(self) {
-            Ok(serialized) => Ok(serialized),
-            Err(error) => Err(error.to_string()),
-        }
-    }
-
     pub fn update(&mut self, dt: f64) -> Vec<StoryEvent> {
         #[cfg(feature = "profiling")]
         let _span = tracy_client::span!("Model update");
         self.explosions_started_this_frame.clear();
+        let previous_time = self.time();
         self.update_warp(dt);
         self.update_time(dt);
         self.update_timeline();
         self.update_objects();
         self.update_target();
         self.update_stations(dt);
+        self.update_mining(dt);
+        self.update_shipyards(dt);
         self.update_launcher_cooldown(dt);
         self.update_trajectory();
+        self.update_intel();
         self.update_guidance();
+        self.update_ai(previous_time);
         let story_events = self.story_events.lock().unwrap().clone();
         self.story_events = Mutex::new(vec![]);
         story_events
@@ -136,6 +147,7 @@ impl Model {
         self.orbitable_components.remove_if_exists(entity);
         self.path_components.remove_if_exists(entity);
         self.vessel_components.remove_if_exists(entity);
+        self.timeline_history.forget(entity);
     }
 
     pub fn exists(&self, entity: Entity) -> bool {
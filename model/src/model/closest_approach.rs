@@ -1,7 +1,7 @@
 use log::error;
 use transfer_window_common::numerical_methods::itp::itp;
 
-use crate::{components::{path_component::orbit::Orbit, vessel_component::faction::Faction}, storage::entity_allocator::Entity};
+use crate::{components::{path_component::{orbit::Orbit, segment::Segment}, vessel_component::faction::Faction}, storage::entity_allocator::Entity};
 
 use super::{snapshot::Snapshot, state_query::StateQuery, Model};
 
@@ -11,67 +11,69 @@ const DISTANCE_DERIVATIVE_DELTA: f64 = 0.1;
 /// enough to catch all approaches. We choose this by choosing the 
 /// minimum of the durations, and if applicable, periods, then dividing 
 /// by a constant
-fn compute_time_step(orbit_a: &Orbit, orbit_b: &Orbit, start_time: f64, end_time: f64) -> f64 {
+fn compute_time_step(segment_a: &Segment, segment_b: &Segment, start_time: f64, end_time: f64) -> f64 {
     let mut step_time = end_time - start_time;
-    if let Some(period) = orbit_a.period() {
+    if let Some(period) = segment_a.as_orbit().and_then(Orbit::period) {
         step_time = f64::min(step_time, period);
     }
-    if let Some(period) = orbit_b.period() {
+    if let Some(period) = segment_b.as_orbit().and_then(Orbit::period) {
         step_time = f64::min(step_time, period);
     }
     step_time / 16.0
 }
 
-/// Returns an ordered vector of pairs of orbits that have the same parent
-fn find_same_parent_orbit_pairs(snapshot: &Snapshot, entity_a: Entity, entity_b: Entity) -> Vec<(&Orbit, &Orbit)> {
-    #[cfg(feature = "profiling")]
-    let _span = tracy_client::span!("Find same parent orbits");
-    let orbits_a = snapshot.future_orbits(entity_a);
-    let orbits_b = snapshot.future_orbits(entity_b);
+/// Orbits and drag segments are both unpowered, so they are
+/// the segments we search for closest approaches
+fn future_unpowered_segments(snapshot: &Snapshot, entity: Entity) -> Vec<&Segment> {
+    snapshot.future_segments(entity)
+        .into_iter()
+        .filter(|segment| segment.is_orbit() || segment.is_drag())
+        .collect()
+}
 
-    let mut same_parent_orbit_pairs = vec![];
+/// Returns an ordered vector of pairs of segments that have the same parent
+fn pair_same_parent_segments<'a>(segments_a: &[&'a Segment], segments_b: &[&'a Segment]) -> Vec<(&'a Segment, &'a Segment)> {
+    let mut same_parent_segment_pairs = vec![];
     let mut index_a = 0;
     let mut index_b = 0;
 
-    while index_a < orbits_a.len() && index_b < orbits_b.len() {
-        let orbit_a = orbits_a[index_a];
-        let orbit_b = orbits_b[index_b];
+    while index_a < segments_a.len() && index_b < segments_b.len() {
+        let segment_a = segments_a[index_a];
+        let segment_b = segments_b[index_b];
 
-        if orbit_a.parent() == orbit_b.parent() {
-            same_parent_orbit_pairs.push((orbit_a, orbit_b));
+        if segment_a.parent() == segment_b.parent() {
+            same_parent_segment_pairs.push((segment_a, segment_b));
         }
 
-        if orbit_a.end_point().time() < orbit_b.end_point().time() {
+        if segment_a.end_time() < segment_b.end_time() {
             index_a += 1;
         } else {
             index_b += 1;
         }
     }
 
-    same_parent_orbit_pairs
+    same_parent_segment_pairs
 }
 
-/// Returns the time at which the next *perceived* closest approach will occur.
-/// This ignore burns. Why? Picture two spacecraft getting closer
-/// to each other. One of them starts burning to accelerate and
-/// ends up moving away from the other spacecraft. This is logical
-/// and makes sense, but in practice is very counterintuitive and
-/// isn't really useful information eg when trying to plan a
-/// rendezvous.
-pub fn find_next_closest_approach(model: &Model, entity_a: Entity, entity_b: Entity, start_time: f64, observer: Option<Faction>) -> Option<f64> {
+/// Returns an ordered vector of pairs of unpowered segments that have the same parent
+fn find_same_parent_segment_pairs(snapshot: &Snapshot, entity_a: Entity, entity_b: Entity) -> Vec<(&Segment, &Segment)> {
     #[cfg(feature = "profiling")]
-    let _span = tracy_client::span!("Find next closest approach");
+    let _span = tracy_client::span!("Find same parent segments");
+    let segments_a = future_unpowered_segments(snapshot, entity_a);
+    let segments_b = future_unpowered_segments(snapshot, entity_b);
+    pair_same_parent_segments(&segments_a, &segments_b)
+}
 
-    let snapshot = model.snapshot(start_time, observer);
-    let same_parent_orbit_pairs = find_same_parent_orbit_pairs(&snapshot, entity_a, entity_b);
-
-    for pair in same_parent_orbit_pairs {
-        let orbit_a = pair.0;
-        let orbit_b = pair.1;
-        let start_time = f64::max(f64::max(orbit_b.start_point().time(), orbit_a.start_point().time()), start_time);
-        let end_time = f64::min(orbit_a.end_point().time(), orbit_b.end_point().time());
-        let time_step = compute_time_step(orbit_a, orbit_b, start_time, end_time);
-        let distance = |time: f64| (orbit_a.point_at_time(time).position() - orbit_b.point_at_time(time).position()).magnitude();
+/// Returns the time of the first closest approach after `start_time` out of
+/// all of the pairs of segments
+fn find_next_closest_approach_between(same_parent_segment_pairs: Vec<(&Segment, &Segment)>, start_time: f64) -> Option<f64> {
+    for pair in same_parent_segment_pairs {
+        let segment_a = pair.0;
+        let segment_b = pair.1;
+        let start_time = f64::max(f64::max(segment_b.start_time(), segment_a.start_time()), start_time);
+        let end_time = f64::min(segment_a.end_time(), segment_b.end_time());
+        let time_step = compute_time_step(segment_a, segment_b, start_time, end_time);
+        let distance = |time: f64| (segment_a.position_at_time(time) - segment_b.position_at_time(time)).magnitude();
         let distance_prime = |time: f64| (distance(time + DISTANCE_DERIVATIVE_DELTA) - distance(time)) / DISTANCE_DERIVATIVE_DELTA;
 
         if start_time > end_time {
@@ -112,6 +114,34 @@ pub fn find_next_closest_approach(model: &Model, entity_a: Entity, entity_b: Ent
     None
 }
 
+/// Returns the time at which the next *perceived* closest approach will occur.
+/// This ignore burns. Why? Picture two spacecraft getting closer
+/// to each other. One of them starts burning to accelerate and
+/// ends up moving away from the other spacecraft. This is logical
+/// and makes sense, but in practice is very counterintuitive and
+/// isn't really useful information eg when trying to plan a
+/// rendezvous.
+pub fn find_next_closest_approach(model: &Model, entity_a: Entity, entity_b: Entity, start_time: f64, observer: Option<Faction>) -> Option<f64> {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Find next closest approach");
+
+    let snapshot = model.snapshot(start_time, observer);
+    let same_parent_segment_pairs = find_same_parent_segment_pairs(&snapshot, entity_a, entity_b);
+    find_next_closest_approach_between(same_parent_segment_pairs, start_time)
+}
+
+/// Returns the time of the next closest approach between `segment`, which
+/// does not need to belong to any entity, and the unpowered segments of `entity`
+pub fn find_next_closest_approach_to_segment(model: &Model, segment: &Segment, entity: Entity, start_time: f64, observer: Option<Faction>) -> Option<f64> {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Find next closest approach to segment");
+
+    let snapshot = model.snapshot(start_time, observer);
+    let segments = future_unpowered_segments(&snapshot, entity);
+    let same_parent_segment_pairs = pair_same_parent_segments(&[segment], &segments);
+    find_next_closest_approach_between(same_parent_segment_pairs, start_time)
+}
+
 pub fn find_next_two_closest_approaches(
     model: &Model, 
     entity_a: Entity, 
@@ -133,7 +163,7 @@ pub fn find_next_two_closest_approaches(
 mod test {
     use nalgebra_glm::vec2;
 
-    use crate::{components::{orbitable_component::{OrbitableComponent, OrbitableComponentPhysics, OrbitableType}, path_component::{orbit::{builder::OrbitBuilder, orbit_direction::OrbitDirection, Orbit}, segment::Segment, PathComponent}}, model::{closest_approach::{find_next_closest_approach, find_same_parent_orbit_pairs}, Model}, storage::{entity_allocator::Entity, entity_builder::EntityBuilder}, test_util};
+    use crate::{components::{orbitable_component::{OrbitableComponent, OrbitableComponentPhysics, OrbitableType}, path_component::{orbit::{builder::OrbitBuilder, orbit_direction::OrbitDirection, Orbit}, segment::Segment, PathComponent}}, model::{closest_approach::{find_next_closest_approach, find_same_parent_segment_pairs}, Model}, storage::{entity_allocator::Entity, entity_builder::EntityBuilder}, test_util};
 
     #[test]
     fn test_find_same_parent_orbit_pairs() {
@@ -211,7 +241,7 @@ mod test {
         ];
 
         let snapshot = model.snapshot_now();
-        let actual = find_same_parent_orbit_pairs(&snapshot, entity_d, entity_e);
+        let actual = find_same_parent_segment_pairs(&snapshot, entity_d, entity_e);
 
         assert_eq!(actual.len(), expected.len());
 
@@ -0,0 +1,285 @@
+use log::debug;
+use nalgebra_glm::DVec2;
+
+use crate::{components::{path_component::segment::Segment, vessel_component::{health::{Health, Subsystem}, timeline::TimelineEvent}, ComponentType}, storage::entity_allocator::Entity};
+
+use super::{explosion::Explosion, state_query::StateQuery, story_event::StoryEvent, Model};
+
+const BLAST_DAMAGE_PER_KG: f64 = 0.1;
+const KINETIC_DAMAGE_PER_KG_METER_PER_SECOND: f64 = 1.0e-4;
+/// Vessels within this distance of a detonation which were not hit directly
+/// take a share of the blast damage, falling off linearly with distance
+const PROXIMITY_DAMAGE_RADIUS: f64 = 5.0e3;
+const PROXIMITY_DAMAGE_PROPORTION: f64 = 0.5;
+/// A direct hit doing at least this proportion of a vessel's hit points
+/// disables the subsystem facing the warhead
+const SUBSYSTEM_DAMAGE_THRESHOLD: f64 = 0.2;
+
+pub fn warhead_damage(warhead_mass: f64, relative_speed: f64) -> f64 {
+    warhead_mass * (BLAST_DAMAGE_PER_KG + relative_speed * KINETIC_DAMAGE_PER_KG_METER_PER_SECOND)
+}
+
+/// Inert projectiles only do damage through their own mass
+pub fn kinetic_damage(mass: f64, relative_speed: f64) -> f64 {
+    mass * relative_speed * KINETIC_DAMAGE_PER_KG_METER_PER_SECOND
+}
+
+/// Only the blast reaches vessels which are not hit directly
+pub fn proximity_damage(warhead_mass: f64, distance: f64) -> f64 {
+    let falloff = f64::max(1.0 - distance / PROXIMITY_DAMAGE_RADIUS, 0.0);
+    warhead_mass * BLAST_DAMAGE_PER_KG * PROXIMITY_DAMAGE_PROPORTION * falloff
+}
+
+/// The subsystem on the side of a vessel facing `direction`; hits from
+/// the front take out the launcher, from behind the engine, and from
+/// either side the RCS
+pub fn subsystem_facing(rotation: f64, direction: DVec2) -> Subsystem {
+    let angle = f64::atan2(direction.y, direction.x) - rotation;
+    let cos = angle.cos();
+    if cos > f64::cos(std::f64::consts::FRAC_PI_4) {
+        Subsystem::TorpedoLauncher
+    } else if cos < -f64::cos(std::f64::consts::FRAC_PI_4) {
+        Subsystem::Engine
+    } else {
+        Subsystem::Rcs
+    }
+}
+
+fn requires_subsystem(event: &TimelineEvent, subsystem: Subsystem) -> bool {
+    match subsystem {
+        Subsystem::Engine => event.is_start_burn() || event.is_enable_guidance(),
+        Subsystem::Rcs => event.is_start_turn(),
+        Subsystem::TorpedoLauncher => event.is_fire_torpedo(),
+    }
+}
+
+fn segment_requires_subsystem(segment: &Segment, subsystem: Subsystem) -> bool {
+    match subsystem {
+        Subsystem::Engine => segment.is_burn() || segment.is_guidance(),
+        Subsystem::Rcs => segment.is_turn(),
+        Subsystem::TorpedoLauncher => false,
+    }
+}
+
+impl Model {
+    fn destroy(&mut self, entity: Entity) {
+        debug!("Vessel {entity:?} destroyed");
+        self.add_story_event(StoryEvent::Destroyed(entity));
+        self.deallocate(entity);
+    }
+
+    /// Also cancels any planned events which can no longer happen. Burns,
+    /// turns and guidance shape the trajectory every later event was planned
+    /// from, so everything after the first of them goes too. One which is
+    /// in progress at `time` is cut off there, along with every planned
+    /// event. Torpedoes do not shape the trajectory, so only the torpedoes
+    /// themselves are cancelled
+    fn disable_subsystem(&mut self, entity: Entity, subsystem: Subsystem, time: f64) {
+        let vessel_component = self.vessel_component(entity);
+        if !vessel_component.has_subsystem(subsystem) || vessel_component.is_subsystem_disabled(subsystem) {
+            return;
+        }
+        debug!("{} of {entity:?} disabled", subsystem.name());
+        self.vessel_component_mut(entity).disable_subsystem(subsystem);
+        self.add_story_event(StoryEvent::SubsystemDisabled { entity, subsystem });
+
+        match subsystem {
+            Subsystem::Engine | Subsystem::Rcs => {
+                let interrupted = self.try_path_component(entity)
+                    .is_some_and(|path_component| segment_requires_subsystem(path_component.future_segment_at_time(time), subsystem));
+                let first_time = if interrupted {
+                    Some(time)
+                } else {
+                    self.vessel_component(entity)
+                        .timeline()
+                        .events()
+                        .iter()
+                        .find(|event| requires_subsystem(event, subsystem))
+                        .map(TimelineEvent::time)
+                };
+                let Some(first_time) = first_time else {
+                    return;
+                };
+                while self.vessel_component(entity).timeline().last_event().is_some_and(|event| event.time() >= first_time) {
+                    self.cancel_last_event(entity);
+                }
+                // The same as cancelling the current segment
+                if interrupted {
+                    self.forget_timeline_edits(entity);
+                    self.recompute_entire_trajectory_from(entity, time);
+                }
+            }
+            Subsystem::TorpedoLauncher => {
+                let events: Vec<TimelineEvent> = self.vessel_component(entity)
+                    .timeline()
+                    .events()
+                    .iter()
+                    .filter(|event| requires_subsystem(event, subsystem))
+                    .cloned()
+                    .collect();
+                self.vessel_component_mut(entity)
+                    .timeline_mut()
+                    .retain(|event| !requires_subsystem(event, subsystem));
+                for event in events {
+                    debug!("Cancelled timeline event {event:?}");
+                    event.cancel(self);
+                }
+            }
+        }
+    }
+
+    /// Destroys the vessel if its hull reaches zero
+    pub(crate) fn damage(&mut self, entity: Entity, amount: f64) {
+        debug!("Vessel {entity:?} took {amount} damage");
+        self.add_story_event(StoryEvent::Damaged { entity, damage: amount });
+        if self.vessel_component_mut(entity).damage(amount) {
+            self.destroy(entity);
+        }
+    }
+
+    /// Destroys `entity` and damages `target` according to the relative
+    /// speed at `time` and the entity's warhead, or its mass if it has no
+    /// warhead. Vessels near the detonation take proximity damage
+    #[allow(clippy::missing_panics_doc)]
+    pub(crate) fn detonate(&mut self, entity: Entity, target: Entity, time: f64) {
+        let snapshot = self.snapshot_at(time);
+        let parent = snapshot.parent(entity).unwrap();
+        let offset = snapshot.position(entity);
+        let combined_mass = snapshot.mass(entity) + snapshot.mass(target);
+        let relative_velocity = snapshot.relative_velocity(entity, target);
+        let target_rotation = snapshot.rotation(target);
+        let warhead_mass = self.vessel_component(entity).class().definition().warhead_mass();
+        let direct_damage = match warhead_mass {
+            Some(warhead_mass) => warhead_damage(warhead_mass, relative_velocity.magnitude()),
+            None => kinetic_damage(snapshot.mass(entity), relative_velocity.magnitude()),
+        };
+        let warhead_mass = warhead_mass.unwrap_or(0.0);
+        // The warhead approaches from the opposite direction to its velocity relative to the target
+        let subsystem = subsystem_facing(target_rotation, -relative_velocity);
+        let proximity_damages: Vec<(Entity, f64)> = self.entities(vec![ComponentType::VesselComponent, ComponentType::PathComponent])
+            .into_iter()
+            .filter(|other| *other != entity && *other != target && !self.vessel_component(*other).is_ghost())
+            .filter(|other| snapshot.parent(*other) == Some(parent))
+            .map(|other| (other, proximity_damage(warhead_mass, snapshot.distance(entity, other))))
+            .filter(|(_, damage)| *damage > 0.0)
+            .collect();
+
+        self.add_explosion(Explosion::new(parent, offset, combined_mass));
+        self.deallocate(entity);
+
+        // The target may already have been destroyed by another warhead
+        if let Some(vessel_component) = self.try_vessel_component(target) {
+            let max_hit_points = vessel_component.health().map(Health::max_hit_points);
+            if max_hit_points.is_some_and(|max_hit_points| direct_damage >= max_hit_points * SUBSYSTEM_DAMAGE_THRESHOLD) {
+                self.disable_subsystem(target, subsystem, time);
+            }
+            self.damage(target, direct_damage);
+        }
+
+        for (other, damage) in proximity_damages {
+            self.damage(other, damage);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+
+    use nalgebra_glm::vec2;
+
+    use crate::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, faction::Faction, health::Subsystem, timeline::{fire_torpedo::FireTorpedoEvent, start_burn::StartBurnEvent, TimelineEvent}, VesselComponent}}, model::Model, storage::entity_builder::VesselBuilder, test_util};
+
+    use super::{kinetic_damage, proximity_damage, subsystem_facing, warhead_damage};
+
+    #[test]
+    fn test_warhead_damage() {
+        assert!(warhead_damage(500.0, 2000.0) > warhead_damage(500.0, 1000.0));
+        assert!(warhead_damage(500.0, 1000.0) > warhead_damage(250.0, 1000.0));
+        assert!(warhead_damage(500.0, 0.0) > 0.0);
+    }
+
+    #[test]
+    fn test_kinetic_damage() {
+        assert!(kinetic_damage(50.0, 3000.0) > kinetic_damage(50.0, 1000.0));
+        assert_eq!(kinetic_damage(50.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_proximity_damage() {
+        assert!(proximity_damage(500.0, 0.0) < warhead_damage(500.0, 0.0));
+        assert!(proximity_damage(500.0, 1.0e3) > proximity_damage(500.0, 2.0e3));
+        assert!(proximity_damage(500.0, 1.0e6) == 0.0);
+    }
+
+    #[test]
+    fn test_subsystem_facing() {
+        assert_eq!(subsystem_facing(0.0, vec2(1.0, 0.0)), Subsystem::TorpedoLauncher);
+        assert_eq!(subsystem_facing(0.0, vec2(-1.0, 0.1)), Subsystem::Engine);
+        assert_eq!(subsystem_facing(0.0, vec2(0.0, -1.0)), Subsystem::Rcs);
+        assert_eq!(subsystem_facing(PI, vec2(1.0, 0.0)), Subsystem::Engine);
+    }
+
+    #[test]
+    fn test_disable_subsystem_cancels_only_dependent_events() {
+        let mut model = Model::default();
+        let sun = test_util::sun(&mut model);
+        let earth = test_util::earth(&mut model, sun);
+        let frigate = VesselBuilder {
+            name: "Frigate".to_string(),
+            vessel_component: VesselComponent::new(VesselClass::Frigate1, Faction::PLAYER),
+            orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.01e9, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+        }.build(&mut model);
+
+        let event = TimelineEvent::FireTorpedo(FireTorpedoEvent::new(&mut model, frigate, 100.0));
+        let torpedo = event.ghost().unwrap();
+        model.add_event(frigate, event);
+        let event = TimelineEvent::StartBurn(StartBurnEvent::new(&mut model, frigate, 1000.0));
+        model.add_event(frigate, event);
+
+        model.disable_subsystem(frigate, Subsystem::TorpedoLauncher, 0.0);
+        let events = model.vessel_component(frigate).timeline().events();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].is_start_burn());
+        assert!(model.try_vessel_component(torpedo).is_none());
+
+        model.disable_subsystem(frigate, Subsystem::Engine, 0.0);
+        assert!(model.vessel_component(frigate).timeline().events().is_empty());
+    }
+
+    #[test]
+    fn test_disable_engine_cuts_off_burn_in_progress() {
+        let mut model = Model::default();
+        let sun = test_util::sun(&mut model);
+        let earth = test_util::earth(&mut model, sun);
+        let vessel = VesselBuilder {
+            name: "Vessel".to_string(),
+            vessel_component: VesselComponent::new(VesselClass::TestShip, Faction::PLAYER),
+            orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.01e9, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+        }.build(&mut model);
+
+        let event = TimelineEvent::StartBurn(StartBurnEvent::new(&mut model, vessel, 100.0));
+        model.add_event(vessel, event);
+        model.start_burn_event_at_time(vessel, 100.0).unwrap().adjust(&mut model, vec2(300.0, 0.0));
+        let burn_start_time = model.start_burn_event_at_time(vessel, 100.0).unwrap().burn_segment_time(&model);
+        let event = TimelineEvent::StartBurn(StartBurnEvent::new(&mut model, vessel, 5000.0));
+        model.add_event(vessel, event);
+
+        while model.time() < burn_start_time + 1.0 {
+            model.update(0.25);
+        }
+        assert!(model.path_component(vessel).current_segment().is_burn());
+
+        // Damage during a frame lands part way through the time step
+        let time = model.time() - 0.1;
+        let expected_position = model.path_component(vessel).future_segment_at_time(time).position_at_time(time);
+        model.disable_subsystem(vessel, Subsystem::Engine, time);
+
+        let path_component = model.path_component(vessel);
+        assert!(path_component.future_burns().is_empty());
+        assert!(path_component.current_segment().is_orbit());
+        assert_eq!(path_component.current_segment().start_time(), time);
+        assert_eq!(path_component.current_segment().start_position(), expected_position);
+        assert!(model.vessel_component(vessel).timeline().events().is_empty());
+    }
+}
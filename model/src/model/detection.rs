@@ -0,0 +1,306 @@
+use std::collections::{HashMap, HashSet};
+
+use nalgebra_glm::DVec2;
+use serde::{Deserialize, Serialize};
+
+use crate::{components::{path_component::{orbit::builder::OrbitBuilder, segment::Segment}, vessel_component::faction::Faction, ComponentType}, storage::entity_allocator::Entity};
+
+use super::{snapshot::Snapshot, state_hash::StateHasher, state_query::StateQuery, Model};
+
+/// Every vessel can see this far without a sensor module
+pub const PASSIVE_SENSOR_RANGE: f64 = 0.5e6;
+/// Vessels in the shadow of their parent are harder to see
+const SHADOW_SIGNATURE: f64 = 0.5;
+/// A burning engine can be seen from much further away
+const BURNING_SIGNATURE: f64 = 4.0;
+/// How long firing a weapon reveals the shooter to everyone for
+const REVEAL_DURATION: f64 = 10.0 * 60.0;
+
+/// Multiplier on the range at which a vessel can be detected
+pub fn signature(is_in_shadow: bool, is_burning: bool) -> f64 {
+    let mut signature = 1.0;
+    if is_in_shadow {
+        signature *= SHADOW_SIGNATURE;
+    }
+    if is_burning {
+        signature *= BURNING_SIGNATURE;
+    }
+    signature
+}
+
+/// Whether a point at `position` relative to a body of `radius` is in the
+/// body's shadow, where `sun_direction` points from the body towards the sun
+pub fn is_in_shadow(position: DVec2, radius: f64, sun_direction: DVec2) -> bool {
+    let along = position.dot(&sun_direction);
+    along < 0.0 && (position - along * sun_direction).magnitude() < radius
+}
+
+/// Where a faction last saw a vessel it can no longer detect
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LastKnownPosition {
+    entity: Entity,
+    parent: Entity,
+    position: DVec2,
+    velocity: DVec2,
+    time: f64,
+    /// The orbit the vessel was on when it was lost, which observed
+    /// snapshots assume it is still following. Positions recorded before
+    /// this was added are not extrapolated
+    #[serde(default)]
+    segment: Option<Segment>,
+}
+
+impl LastKnownPosition {
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn parent(&self) -> Entity {
+        self.parent
+    }
+
+    /// Relative to the parent
+    pub fn position(&self) -> DVec2 {
+        self.position
+    }
+
+    pub fn velocity(&self) -> DVec2 {
+        self.velocity
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn segment(&self) -> Option<&Segment> {
+        self.segment.as_ref()
+    }
+}
+
+/// What each faction can currently see. Recomputed every update, but saved
+/// so that nothing is revealed for a frame after loading
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Intel {
+    detected: HashMap<Faction, HashSet<Entity>>,
+    last_known_positions: HashMap<Faction, Vec<LastKnownPosition>>,
+    /// Vessels which recently fired a weapon, and when they stop being revealed
+    revealed_until: Vec<(Entity, f64)>,
+}
+
+impl Intel {
+    /// Detected sets and last known positions are sorted first, since their
+    /// order can differ between runs
+    pub(super) fn hash(&self, hasher: &mut StateHasher) {
+        let mut detected: Vec<(Faction, Vec<Entity>)> = self.detected.iter()
+            .map(|(faction, entities)| {
+                let mut entities: Vec<Entity> = entities.iter().copied().collect();
+                entities.sort_by_key(|entity| (entity.index(), entity.generation()));
+                (*faction, entities)
+            })
+            .collect();
+        detected.sort_by_key(|(faction, _)| *faction);
+        hasher.hash(&detected);
+        let last_known_positions: HashMap<Faction, Vec<&LastKnownPosition>> = self.last_known_positions.iter()
+            .map(|(faction, last_known_positions)| {
+                let mut last_known_positions: Vec<&LastKnownPosition> = last_known_positions.iter().collect();
+                last_known_positions.sort_by_key(|last_known| (last_known.entity.index(), last_known.entity.generation()));
+                (*faction, last_known_positions)
+            })
+            .collect();
+        hasher.hash(&last_known_positions);
+        hasher.hash(&self.revealed_until);
+    }
+}
+
+impl Model {
+    /// Vessels which the observer has intel for are always detected, as
+    /// is anything which is not a vessel
+    pub fn is_detected(&self, observer: Faction, entity: Entity) -> bool {
+        self.is_detected_at(observer, entity, self.time)
+    }
+
+    /// Detection at or before the current time is what was worked out on
+    /// the last update. Later than that, it is predicted from where every
+    /// vessel will be, assuming nobody fires a weapon in the meantime
+    pub fn is_detected_at(&self, observer: Faction, entity: Entity, time: f64) -> bool {
+        let Some(vessel_component) = self.try_vessel_component(entity) else {
+            return true;
+        };
+        if self.has_intel_for(observer, vessel_component.faction()) {
+            return true;
+        }
+        if time <= self.time {
+            return self.intel.detected.get(&observer).is_some_and(|detected| detected.contains(&entity));
+        }
+        let vessels: Vec<Entity> = self.entities(vec![ComponentType::VesselComponent, ComponentType::PathComponent])
+            .into_iter()
+            .filter(|vessel| !self.vessel_component(*vessel).is_ghost())
+            .filter(|vessel| self.path_component(*vessel).end_segment().end_time() >= time)
+            .collect();
+        let snapshot = self.snapshot_at(time);
+        let sensors = self.sensors(observer, &vessels);
+        self.can_detect(&snapshot, &sensors, entity, self.sun())
+    }
+
+    /// Vessels which `observer` has seen before but cannot currently detect
+    pub fn last_known_positions(&self, observer: Faction) -> &[LastKnownPosition] {
+        self.intel.last_known_positions.get(&observer).map_or(&[], Vec::as_slice)
+    }
+
+    /// The last known position of `entity` to `observer`, if it has lost track of it
+    pub fn last_known_position(&self, observer: Faction, entity: Entity) -> Option<&LastKnownPosition> {
+        self.last_known_positions(observer)
+            .iter()
+            .find(|last_known| last_known.entity == entity)
+    }
+
+    /// Makes `entity` visible to every faction for a while, for example
+    /// because it fired a weapon
+    pub(crate) fn reveal(&mut self, entity: Entity) {
+        let until = self.time + REVEAL_DURATION;
+        self.intel.revealed_until.retain(|(other, _)| *other != entity);
+        self.intel.revealed_until.push((entity, until));
+    }
+
+    fn is_revealed_at(&self, entity: Entity, time: f64) -> bool {
+        self.intel.revealed_until.iter().any(|(other, until)| *other == entity && *until > time)
+    }
+
+    /// The body everything ultimately orbits, which lights everything else
+    pub(crate) fn sun(&self) -> Option<Entity> {
+        self.entities(vec![ComponentType::OrbitableComponent])
+            .into_iter()
+            .find(|entity| self.orbitable_component(*entity).physics().is_stationary())
+    }
+
+    /// Only the vessel's parent is considered, and only if it is not the sun itself
+    fn is_vessel_in_shadow(&self, snapshot: &Snapshot, entity: Entity, sun: Option<Entity>) -> bool {
+        let (Some(sun), Some(parent)) = (sun, snapshot.parent(entity)) else {
+            return false;
+        };
+        if parent == sun {
+            return false;
+        }
+        let sun_direction = (snapshot.absolute_position(sun) - snapshot.absolute_position(parent)).normalize();
+        is_in_shadow(snapshot.position(entity), self.orbitable_component(parent).radius(), sun_direction)
+    }
+
+    /// Every vessel out of `vessels` which `observer` has intel for, along
+    /// with its sensor range. These share what they see
+    fn sensors(&self, observer: Faction, vessels: &[Entity]) -> Vec<(Entity, f64)> {
+        vessels.iter()
+            .filter(|entity| self.has_intel_for(observer, self.faction(**entity)))
+            .map(|entity| (*entity, f64::max(PASSIVE_SENSOR_RANGE, self.vessel_component(*entity).sensor_range())))
+            .collect()
+    }
+
+    /// Whether any of `sensors` can see `entity` at the time of `snapshot`,
+    /// which must not have an observer
+    fn can_detect(&self, snapshot: &Snapshot, sensors: &[(Entity, f64)], entity: Entity, sun: Option<Entity>) -> bool {
+        if self.is_revealed_at(entity, snapshot.time()) {
+            return true;
+        }
+        let segment = snapshot.segment(entity);
+        let is_burning = segment.is_burn() || segment.is_guidance();
+        let signature = signature(self.is_vessel_in_shadow(snapshot, entity, sun), is_burning);
+        sensors.iter().any(|(sensor, range)| snapshot.distance(*sensor, entity) < range * signature)
+    }
+
+    /// Which vessels `observer` can currently detect with the sensors of
+    /// every vessel it has intel for
+    pub(crate) fn compute_detected(&self, observer: Faction, vessels: &[Entity], sun: Option<Entity>) -> HashSet<Entity> {
+        let snapshot = self.snapshot_now();
+        let sensors = self.sensors(observer, vessels);
+        vessels.iter()
+            .copied()
+            .filter(|entity| !self.has_intel_for(observer, self.faction(*entity)))
+            .filter(|entity| self.can_detect(&snapshot, &sensors, *entity, sun))
+            .collect()
+    }
+
+    pub(crate) fn expire_reveals(&mut self) {
+        let time = self.time;
+        self.intel.revealed_until.retain(|(_, until)| *until > time);
+    }
+
+    /// Records what `observer` can now detect out of `vessels`. Vessels
+    /// which were detected before but no longer are get a last known position
+    pub(crate) fn set_detected(&mut self, observer: Faction, vessels: &[Entity], detected: HashSet<Entity>) {
+        let snapshot = self.snapshot_now();
+        let mut last_known_positions: Vec<LastKnownPosition> = self.last_known_positions(observer)
+            .iter()
+            .filter(|last_known| self.try_vessel_component(last_known.entity).is_some() && !detected.contains(&last_known.entity))
+            .cloned()
+            .collect();
+        let mut lost: Vec<&Entity> = self.intel.detected.get(&observer)
+            .into_iter()
+            .flatten()
+            .filter(|entity| !detected.contains(entity) && vessels.contains(entity))
+            .collect();
+        // The detected set's order differs between runs
+        lost.sort_by_key(|entity| (entity.index(), entity.generation()));
+        for entity in lost {
+            let parent = snapshot.parent(*entity).unwrap();
+            let position = snapshot.position(*entity);
+            let velocity = snapshot.velocity(*entity);
+            let orbit = OrbitBuilder {
+                parent,
+                mass: snapshot.mass(*entity),
+                parent_mass: snapshot.mass(parent),
+                rotation: snapshot.rotation(*entity),
+                position,
+                velocity,
+                time: self.time,
+            }.build();
+            last_known_positions.push(LastKnownPosition {
+                entity: *entity,
+                parent,
+                position,
+                velocity,
+                time: self.time,
+                segment: Some(Segment::Orbit(orbit)),
+            });
+        }
+        self.intel.last_known_positions.insert(observer, last_known_positions);
+        self.intel.detected.insert(observer, detected);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra_glm::vec2;
+
+    use crate::{components::vessel_component::faction::Faction, model::Model, storage::entity_allocator::EntityAllocator};
+
+    use super::{is_in_shadow, signature, LastKnownPosition};
+
+    #[test]
+    fn test_signature() {
+        assert!(signature(true, false) < signature(false, false));
+        assert!(signature(false, true) > signature(false, false));
+        assert!(signature(true, true) > signature(true, false));
+    }
+
+    #[test]
+    fn test_is_in_shadow() {
+        let sun_direction = vec2(1.0, 0.0);
+        assert!(is_in_shadow(vec2(-2.0, 0.5), 1.0, sun_direction));
+        assert!(!is_in_shadow(vec2(2.0, 0.5), 1.0, sun_direction));
+        assert!(!is_in_shadow(vec2(-2.0, 1.5), 1.0, sun_direction));
+    }
+
+    #[test]
+    fn test_intel_hash_ignores_order_of_last_known_positions() {
+        let mut allocator = EntityAllocator::default();
+        let parent = allocator.allocate();
+        let a = allocator.allocate();
+        let b = allocator.allocate();
+        let last_known = |entity| LastKnownPosition { entity, parent, position: vec2(1.0, 0.0), velocity: vec2(0.0, 1.0), time: 0.0, segment: None };
+        let hash = |last_known_positions| {
+            let mut model = Model::default();
+            model.intel.last_known_positions.insert(Faction::PLAYER, last_known_positions);
+            model.state_hash()
+        };
+        assert_eq!(hash(vec![last_known(a), last_known(b)]), hash(vec![last_known(b), last_known(a)]));
+    }
+}
@@ -1,8 +1,8 @@
-use crate::{components::{path_component::{orbit::builder::OrbitBuilder, PathComponent}, vessel_component::docking::{DockingPort, DockingPortLocation, DOCKING_DISTANCE, DOCKING_SPEED}, ComponentType}, storage::entity_allocator::Entity};
+use nalgebra_glm::{DMat2, DVec2};
 
-use super::{state_query::StateQuery, Model};
+use crate::{components::{path_component::{orbit::builder::OrbitBuilder, PathComponent}, vessel_component::{docking::{DockingPort, DockingPortLocation, ResourceTransferDirection, DOCKING_DISTANCE, DOCKING_SPEED}, resource::{ResourceTransferRate, ResourceType}, timeline::{undock::{UndockEvent, DEFAULT_UNDOCK_DELTA_V}, TimelineEvent}}, ComponentType}, storage::entity_allocator::Entity};
 
-const EXTRA_UNDOCK_VELOCITY: f64 = 1.0;
+use super::{state_query::StateQuery, Model};
 
 impl Model {
     pub fn can_ever_dock_to_target(&self, entity: Entity) -> bool {
@@ -17,6 +17,40 @@ impl Model {
             && self.find_free_docking_port(target).is_some()
     }
 
+    /// Uses the predicted positions at `time`, and whether a port will be
+    /// free for `entity` at `time`
+    pub fn can_dock_at(&self, entity: Entity, station: Entity, time: f64) -> bool {
+        let snapshot = self.snapshot_at(time);
+        snapshot.distance(entity, station) < DOCKING_DISTANCE
+            && snapshot.relative_speed(entity, station) < DOCKING_SPEED
+            && self.free_docking_ports_at(entity, station, time) > 0
+    }
+
+    /// Ports free now, plus those freed by scheduled undocks, minus those
+    /// taken by other vessels' scheduled docks, up to and including `time`
+    fn free_docking_ports_at(&self, entity: Entity, station: Entity, time: f64) -> usize {
+        let free = self.vessel_component(station)
+            .docking_ports()
+            .expect("Attempt to find free docking ports on non-station entity")
+            .values()
+            .filter(|docking_port| !docking_port.has_docked_vessel())
+            .count();
+        let undocks = self.vessel_component(station)
+            .timeline()
+            .events()
+            .iter()
+            .filter_map(TimelineEvent::as_undock)
+            .filter(|event| event.time() <= time)
+            .count();
+        let docks = self.entities(vec![ComponentType::VesselComponent])
+            .into_iter()
+            .filter(|other| *other != entity)
+            .flat_map(|other| self.vessel_component(other).timeline().events().iter().filter_map(TimelineEvent::as_dock))
+            .filter(|event| event.station() == station && event.time() <= time)
+            .count();
+        (free + undocks).saturating_sub(docks)
+    }
+
     pub fn find_free_docking_port(&self, entity: Entity) -> Option<DockingPortLocation> {
         self.vessel_component(entity)
             .docking_ports()
@@ -47,33 +81,74 @@ impl Model {
     pub fn dock(&mut self, station: Entity, entity: Entity) {
         assert!(self.can_ever_dock_to_target(entity));
         assert!(self.can_dock(entity));
+        self.dock_to_free_port(station, entity);
+    }
+
+    /// Docks without checking whether the vessel is close enough, which is
+    /// up to the caller. Anything still planned for the vessel is cancelled
+    pub(crate) fn dock_to_free_port(&mut self, station: Entity, entity: Entity) {
+        while self.vessel_component(entity).timeline().last_event().is_some() {
+            self.cancel_last_event(entity);
+        }
         self.path_components.remove_if_exists(entity);
+        self.timeline_history.forget(entity);
         let docking_port_location = self.find_free_docking_port(station).unwrap();
         self.vessel_component_mut(station).dock(docking_port_location, entity);
     }
 
     pub fn undock(&mut self, station: Entity, entity: Entity) {
+        self.undock_with_delta_v(station, entity, self.time, DEFAULT_UNDOCK_DELTA_V);
+    }
+
+    /// The vessel leaves the station at `time`, which is earlier than now
+    /// when this runs as a timeline event during a large time step.
+    /// `delta_v` is relative to the station's velocity, with x prograde
+    pub fn undock_with_delta_v(&mut self, station: Entity, entity: Entity, time: f64, delta_v: DVec2) {
         assert!(self.docked(entity));
         let docking_port_location = self.find_docking_port(station, entity).unwrap();
         self.vessel_component_mut(station).undock(docking_port_location);
-
-        let parent = self.parent(station).unwrap();
-        let extra_velocity = self.velocity(station).normalize() * EXTRA_UNDOCK_VELOCITY;
-        let velocity = self.velocity(station) + extra_velocity;
+        self.cancel_scheduled_undock(station, entity);
+        self.vessel_component_mut(station)
+            .timeline_mut()
+            .retain(|event| event.as_transfer().is_none_or(|event| event.entity() != entity));
+
+        // The station's current point may not have been moved to `time` yet
+        // when this runs as a timeline event
+        let snapshot = self.snapshot_at(time);
+        let parent = snapshot.parent(station).unwrap();
+        let station_velocity = snapshot.velocity(station);
+        let tangent = station_velocity.normalize();
+        let extra_velocity = DMat2::new(tangent.x, -tangent.y, tangent.y, tangent.x) * delta_v;
+        let velocity = station_velocity + extra_velocity;
         let orbit = OrbitBuilder {
             parent,
             mass: self.vessel_component(entity).mass(),
             parent_mass: self.mass(parent),
             rotation: f64::atan2(velocity.y, velocity.x),
-            position: self.position(station),
+            position: snapshot.position(station),
             velocity,
-            time: self.time,
+            time,
         }.build();
 
         self.path_components.set(entity, Some(PathComponent::new_with_orbit(orbit)));
         self.recompute_trajectory(entity);
     }
 
+    pub fn scheduled_undock(&self, station: Entity, entity: Entity) -> Option<UndockEvent> {
+        self.vessel_component(station)
+            .timeline()
+            .events()
+            .iter()
+            .filter_map(TimelineEvent::as_undock)
+            .find(|event| event.entity() == entity)
+    }
+
+    pub fn cancel_scheduled_undock(&mut self, station: Entity, entity: Entity) {
+        self.vessel_component_mut(station)
+            .timeline_mut()
+            .retain(|event| event.as_undock().is_none_or(|event| event.entity() != entity));
+    }
+
     pub fn docked(&self, entity: Entity) -> bool {
         self.try_vessel_component(entity).is_some() && self.try_path_component(entity).is_none()
     }
@@ -98,27 +173,52 @@ impl Model {
         self.docking_port(entity, location).docked_vessel().entity()
     }
 
-    pub fn can_transfer_fuel_to_docked(&self, entity: Entity, location: DockingPortLocation) -> bool {
-        let from = entity;
-        let to = self.docked_entity(entity, location);
-        !self.vessel_component(from).is_fuel_empty() && !self.vessel_component(to).is_fuel_full()
+    fn can_transfer(&self, from: Entity, to: Entity, resource: ResourceType) -> bool {
+        let from = self.vessel_component(from);
+        let to = self.vessel_component(to);
+        from.can_store(resource) && to.can_store(resource)
+            && !from.is_resource_empty(resource) && !to.is_resource_full(resource)
+    }
+
+    pub fn can_transfer_to_docked(&self, entity: Entity, location: DockingPortLocation, resource: ResourceType) -> bool {
+        self.can_transfer(entity, self.docked_entity(entity, location), resource)
+    }
+
+    pub fn can_transfer_from_docked(&self, entity: Entity, location: DockingPortLocation, resource: ResourceType) -> bool {
+        self.can_transfer(self.docked_entity(entity, location), entity, resource)
     }
 
-    pub fn can_transfer_fuel_from_docked(&self, entity: Entity, location: DockingPortLocation) -> bool {
-        let to = entity;
-        let from = self.docked_entity(entity, location);
-        !self.vessel_component(from).is_fuel_empty() && !self.vessel_component(to).is_fuel_full()
+    /// Starts a transfer between a station and one of its docked vessels,
+    /// replacing any transfer of the same resource already happening
+    pub fn start_transfer(&mut self, station: Entity, entity: Entity, resource: ResourceType, direction: ResourceTransferDirection, amount: Option<f64>) {
+        let location = self.find_docking_port(station, entity).expect("Attempt to start transfer to undocked vessel");
+        self.docking_port_mut(station, location).docked_vessel_mut().start_transfer(resource, direction, amount);
     }
 
-    pub fn can_transfer_torpedoes_to_docked(&self, entity: Entity, location: DockingPortLocation) -> bool {
-        let from = entity;
-        let to = self.docked_entity(entity, location);
-        !self.vessel_component(from).is_torpedoes_empty() && !self.vessel_component(to).is_torpedoes_full()
+    pub(crate) fn transfer_endpoints(&self, station: Entity, location: DockingPortLocation, direction: ResourceTransferDirection) -> (Entity, Entity) {
+        let docked = self.docked_entity(station, location);
+        match direction {
+            ResourceTransferDirection::FromDocked => (docked, station),
+            ResourceTransferDirection::ToDocked => (station, docked),
+        }
     }
 
-    pub fn can_transfer_torpedoes_from_docked(&self, entity: Entity, location: DockingPortLocation) -> bool {
-        let to = entity;
-        let from = self.docked_entity(entity, location);
-        !self.vessel_component(from).is_torpedoes_empty() && !self.vessel_component(to).is_torpedoes_full()
+    /// When the transfer of `resource` at `location` will finish if nothing
+    /// else changes the amount of it in either vessel
+    pub fn transfer_completion_time(&self, station: Entity, location: DockingPortLocation, resource: ResourceType) -> Option<f64> {
+        let transfer = self.docking_port(station, location).docked_vessel().transfer(resource)?;
+        let (from, to) = self.transfer_endpoints(station, location, transfer.direction());
+        let amount = f64::min(self.vessel_component(from).resource_amount(resource), self.vessel_component(to).resource_space(resource));
+        let amount = transfer.remaining().map_or(amount, |remaining| f64::min(amount, remaining));
+        match transfer.rate() {
+            ResourceTransferRate::Continuous { units_per_second } => Some(self.time + amount / units_per_second),
+            ResourceTransferRate::Discrete { seconds_per_unit } => {
+                let count = amount.round();
+                if count < 1.0 {
+                    return Some(self.time);
+                }
+                Some(self.time + transfer.time_to_next() + (count - 1.0) * seconds_per_unit)
+            }
+        }
     }
 }
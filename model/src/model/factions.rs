@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use crate::components::vessel_component::faction::{Faction, Relation, Stance};
+
+use super::Model;
+
+/// Every registered faction along with the relation between each pair of
+/// them. Relations are symmetric, so `relations[a][b] == relations[b][a]`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Factions {
+    names: Vec<String>,
+    relations: Vec<Vec<Relation>>,
+}
+
+impl Default for Factions {
+    /// The player and ally share intel and are both hostile to the enemy
+    fn default() -> Self {
+        let mut factions = Self { names: vec![], relations: vec![] };
+        factions.add("Player");
+        factions.add("Ally");
+        factions.add("Enemy");
+        factions.set(Faction::PLAYER, Faction::ALLY, Relation::new(Stance::Allied, true));
+        factions.set(Faction::PLAYER, Faction::ENEMY, Relation::new(Stance::Hostile, false));
+        factions.set(Faction::ALLY, Faction::ENEMY, Relation::new(Stance::Hostile, false));
+        factions
+    }
+}
+
+impl Factions {
+    /// New factions start neutral to every other faction
+    fn add(&mut self, name: &str) -> Faction {
+        let faction = Faction::new(self.names.len());
+        self.names.push(name.to_string());
+        for relations in &mut self.relations {
+            relations.push(Relation::new(Stance::Neutral, false));
+        }
+        let mut relations = vec![Relation::new(Stance::Neutral, false); self.names.len()];
+        relations[faction.index()] = Relation::new(Stance::Allied, true);
+        self.relations.push(relations);
+        faction
+    }
+
+    fn set(&mut self, faction: Faction, other: Faction, relation: Relation) {
+        assert!(faction != other, "Attempt to set the relation of a faction with itself");
+        self.relations[faction.index()][other.index()] = relation;
+        self.relations[other.index()][faction.index()] = relation;
+    }
+}
+
+impl Model {
+    pub fn add_faction(&mut self, name: &str) -> Faction {
+        self.factions.add(name)
+    }
+
+    pub fn factions(&self) -> impl Iterator<Item = Faction> {
+        (0..self.factions.names.len()).map(Faction::new)
+    }
+
+    pub fn faction_name(&self, faction: Faction) -> &str {
+        &self.factions.names[faction.index()]
+    }
+
+    /// Case insensitive
+    pub fn faction_named(&self, name: &str) -> Option<Faction> {
+        self.factions().find(|faction| self.faction_name(*faction).eq_ignore_ascii_case(name))
+    }
+
+    pub fn relation(&self, faction: Faction, other: Faction) -> Relation {
+        self.factions.relations[faction.index()][other.index()]
+    }
+
+    /// # Panics
+    /// Panics if `faction` and `other` are the same
+    pub fn set_relation(&mut self, faction: Faction, other: Faction, relation: Relation) {
+        self.factions.set(faction, other, relation);
+    }
+
+    /// Whether `observer` can see the true plans of `other`'s vessels
+    pub fn has_intel_for(&self, observer: Faction, other: Faction) -> bool {
+        self.relation(observer, other).shares_intel()
+    }
+
+    pub fn is_hostile_to(&self, faction: Faction, other: Faction) -> bool {
+        self.relation(faction, other).is_hostile()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::components::vessel_component::faction::{Faction, Relation, Stance};
+
+    use super::Factions;
+
+    #[test]
+    fn test_defaults() {
+        let factions = Factions::default();
+        let relation = |a: Faction, b: Faction| factions.relations[a.index()][b.index()];
+        assert!(relation(Faction::PLAYER, Faction::PLAYER).shares_intel());
+        assert!(relation(Faction::PLAYER, Faction::ALLY).shares_intel());
+        assert!(!relation(Faction::ENEMY, Faction::ALLY).shares_intel());
+        assert!(relation(Faction::ALLY, Faction::ENEMY).is_hostile());
+        assert!(!relation(Faction::PLAYER, Faction::ALLY).is_hostile());
+    }
+
+    #[test]
+    fn test_add_and_set() {
+        let mut factions = Factions::default();
+        let pirates = factions.add("Pirates");
+        assert_eq!(factions.relations[pirates.index()][Faction::PLAYER.index()].stance(), Stance::Neutral);
+        assert_eq!(factions.relations[Faction::ENEMY.index()][pirates.index()].stance(), Stance::Neutral);
+
+        factions.set(Faction::ENEMY, pirates, Relation::new(Stance::Hostile, false));
+        assert!(factions.relations[pirates.index()][Faction::ENEMY.index()].is_hostile());
+        assert!(factions.relations[Faction::ENEMY.index()][pirates.index()].is_hostile());
+    }
+}
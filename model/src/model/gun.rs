@@ -0,0 +1,199 @@
+use nalgebra_glm::{vec2, DVec2};
+
+use crate::{components::{path_component::{orbit::{builder::OrbitBuilder, Orbit}, segment::Segment}, vessel_component::class::VesselClass}, storage::entity_allocator::Entity};
+
+use super::{closest_approach::{find_next_closest_approach, find_next_closest_approach_to_segment}, intercept_planner::golden_section_minimum, state_query::StateQuery, Model};
+
+/// A projectile passing within this distance of its target hits it
+pub const PROJECTILE_HIT_RADIUS: f64 = 200.0;
+/// Firing solutions only consider projectiles which reach the target within
+/// this time, and projectiles which never come close to their target are
+/// removed once they have coasted for this long
+const MAX_FLIGHT_TIME: f64 = 60.0 * 60.0;
+const FIRE_TIME_SAMPLES: usize = 16;
+const FIRE_TIME_STEP: f64 = 60.0;
+/// Rotations are searched either side of the straight line lead by this angle
+const ROTATION_SEARCH_ANGLE: f64 = 0.25;
+/// The lead is usually close already, so only a few rotations are sampled
+/// before refining around the best of them
+const ROTATION_SAMPLES: usize = 8;
+
+/// A time and rotation at which firing a gun would hit the target, as
+/// found by `Model::firing_solution`
+#[derive(Debug, Clone)]
+pub struct FiringSolution {
+    time: f64,
+    rotation: f64,
+    hit_time: f64,
+    miss_distance: f64,
+}
+
+impl FiringSolution {
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn rotation(&self) -> f64 {
+        self.rotation
+    }
+
+    pub fn hit_time(&self) -> f64 {
+        self.hit_time
+    }
+
+    /// How far from the target's centre the projectile passes
+    pub fn miss_distance(&self) -> f64 {
+        self.miss_distance
+    }
+}
+
+/// The direction to fire in to meet a target at `relative_position` moving
+/// at `relative_velocity`, ignoring gravity. Solves |r + vt| = st for the
+/// earliest positive flight time t
+fn lead_direction(relative_position: DVec2, relative_velocity: DVec2, muzzle_speed: f64) -> Option<DVec2> {
+    let a = relative_velocity.magnitude_squared() - muzzle_speed.powi(2);
+    let b = 2.0 * relative_position.dot(&relative_velocity);
+    let c = relative_position.magnitude_squared();
+    let flight_time = if a.abs() < 1.0e-9 {
+        -c / b
+    } else {
+        let discriminant = b.powi(2) - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        [(-b - discriminant.sqrt()) / (2.0 * a), (-b + discriminant.sqrt()) / (2.0 * a)]
+            .into_iter()
+            .filter(|flight_time| *flight_time > 0.0)
+            .reduce(f64::min)?
+    };
+    (flight_time > 0.0).then(|| relative_position + relative_velocity * flight_time)
+}
+
+impl Model {
+    /// The orbit a projectile fired by `entity` at `time` with the vessel
+    /// pointing at `rotation` would follow, starting with the vessel's
+    /// velocity plus the gun's muzzle velocity. Returns None if the vessel
+    /// has no gun or is not on an orbit at `time`
+    pub fn projectile_orbit(&self, entity: Entity, time: f64, rotation: f64) -> Option<Orbit> {
+        let muzzle_speed = self.vessel_component(entity).gun()?.muzzle_speed();
+        let orbit = self.departure_orbit(entity, time)?;
+        let point = orbit.point_at_time(time);
+        Some(OrbitBuilder {
+            parent: orbit.parent(),
+            mass: VesselClass::Projectile.definition().dry_mass(),
+            parent_mass: self.mass(orbit.parent()),
+            rotation,
+            position: point.position(),
+            velocity: point.velocity() + muzzle_speed * vec2(rotation.cos(), rotation.sin()),
+            time,
+        }.build())
+    }
+
+    /// When the flight of `projectile` fired at `time` ends, along with
+    /// whether it hits `target`. The flight ends at the projectile's next
+    /// closest approach to the target, whether or not it is within the hit
+    /// radius, or after `MAX_FLIGHT_TIME` if it never gets any closer
+    pub(crate) fn predict_projectile_flight(&self, projectile: Entity, target: Entity, time: f64) -> (f64, bool) {
+        let expiry_time = time + MAX_FLIGHT_TIME;
+        match find_next_closest_approach(self, projectile, target, time, None) {
+            Some(closest_approach_time) if closest_approach_time < expiry_time => {
+                let hit = self.snapshot_at(closest_approach_time).distance(projectile, target) < PROJECTILE_HIT_RADIUS;
+                (closest_approach_time, hit)
+            }
+            _ => (expiry_time, false),
+        }
+    }
+
+    /// Whether `projectile` is within the hit radius of `target` at `time`.
+    /// The target may have manoeuvred or been destroyed since the hit was predicted
+    pub(crate) fn projectile_hits(&self, projectile: Entity, target: Entity, time: f64) -> bool {
+        if self.try_vessel_component(target).is_none() {
+            return false;
+        }
+        let snapshot = self.snapshot_at(time);
+        snapshot.parent(projectile) == snapshot.parent(target) && snapshot.distance(projectile, target) < PROJECTILE_HIT_RADIUS
+    }
+
+    /// The closest approach of a projectile on `orbit` to `target`, as
+    /// perceived by `entity`, as a time and distance
+    fn projectile_miss(&self, entity: Entity, target: Entity, orbit: &Orbit) -> Option<(f64, f64)> {
+        let start_time = orbit.start_point().time();
+        let segment = Segment::Orbit(orbit.clone().with_end_at(start_time + MAX_FLIGHT_TIME));
+        let observer = Some(self.faction(entity));
+        let time = find_next_closest_approach_to_segment(self, &segment, target, start_time, observer)?;
+        let target_position = self.snapshot(time, observer).position(target);
+        Some((time, (segment.position_at_time(time) - target_position).magnitude()))
+    }
+
+    /// The best rotation to fire at `target` from `entity` at `time`, found
+    /// by refining the straight line lead against the projectile's orbit
+    fn aim(&self, entity: Entity, target: Entity, time: f64) -> Option<FiringSolution> {
+        let muzzle_speed = self.vessel_component(entity).gun()?.muzzle_speed();
+        let snapshot = self.snapshot_at_observe(time, self.faction(entity));
+        if snapshot.parent(entity)? != snapshot.parent(target)? {
+            return None;
+        }
+        let relative_position = snapshot.position(target) - snapshot.position(entity);
+        let relative_velocity = snapshot.velocity(target) - snapshot.velocity(entity);
+        let lead = lead_direction(relative_position, relative_velocity, muzzle_speed)?;
+        let lead_rotation = f64::atan2(lead.y, lead.x);
+
+        let miss_distance = |rotation: f64| self.projectile_orbit(entity, time, rotation)
+            .and_then(|orbit| self.projectile_miss(entity, target, &orbit))
+            .map_or(f64::MAX, |(_, distance)| distance);
+
+        let rotation = if miss_distance(lead_rotation) < PROJECTILE_HIT_RADIUS {
+            lead_rotation
+        } else {
+            #[allow(clippy::cast_precision_loss)]
+            let step = 2.0 * ROTATION_SEARCH_ANGLE / ROTATION_SAMPLES as f64;
+            #[allow(clippy::cast_precision_loss)]
+            let (closest_sample, _) = (0..=ROTATION_SAMPLES)
+                .map(|i| lead_rotation - ROTATION_SEARCH_ANGLE + i as f64 * step)
+                .map(|rotation| (rotation, miss_distance(rotation)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))?;
+            golden_section_minimum(&miss_distance, closest_sample - step, closest_sample + step)
+        };
+        let orbit = self.projectile_orbit(entity, time, rotation)?;
+        let (hit_time, miss_distance) = self.projectile_miss(entity, target, &orbit)?;
+        Some(FiringSolution { time, rotation, hit_time, miss_distance })
+    }
+
+    /// Finds the earliest time from `earliest_time` at which `entity` can
+    /// fire at `target` and hit it, along with the rotation the vessel needs
+    /// to be pointing at. The vessel must already be on an orbit at that
+    /// time; turning to the rotation is up to the caller. Returns None if no
+    /// shot in the search window hits
+    pub fn firing_solution(&self, entity: Entity, target: Entity, earliest_time: f64) -> Option<FiringSolution> {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Firing solution");
+        #[allow(clippy::cast_precision_loss)]
+        (0..FIRE_TIME_SAMPLES)
+            .map(|i| earliest_time + i as f64 * FIRE_TIME_STEP)
+            .filter_map(|time| self.aim(entity, target, time))
+            .find(|solution| solution.miss_distance < PROJECTILE_HIT_RADIUS)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra_glm::vec2;
+
+    use super::lead_direction;
+
+    #[test]
+    fn test_lead_direction() {
+        // Stationary target is aimed at directly
+        let direction = lead_direction(vec2(1000.0, 0.0), vec2(0.0, 0.0), 100.0).unwrap();
+        assert!(direction.y.abs() < 1.0e-9 && direction.x > 0.0);
+
+        // Target crossing ahead is led
+        let direction = lead_direction(vec2(1000.0, 0.0), vec2(0.0, 50.0), 100.0).unwrap();
+        assert!(direction.y > 0.0);
+        let flight_time = direction.magnitude() / 100.0;
+        assert!((direction - (vec2(1000.0, 0.0) + vec2(0.0, 50.0) * flight_time)).magnitude() < 1.0e-6);
+
+        // Target running away faster than the projectile can never be reached
+        assert!(lead_direction(vec2(1000.0, 0.0), vec2(200.0, 0.0), 100.0).is_none());
+    }
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{components::{name_component::NameComponent, path_component::{segment::Segment, PathComponent}, vessel_component::{timeline::TimelineEvent, VesselComponent}}, storage::entity_allocator::Entity};
+
+use super::Model;
+
+/// How many edits can be undone for each vessel
+const MAX_EDITS: usize = 50;
+
+/// A ghost torpedo or projectile launched by a timeline event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Ghost {
+    entity: Entity,
+    name_component: Option<NameComponent>,
+    path_component: PathComponent,
+    vessel_component: VesselComponent,
+}
+
+/// A vessel's plans from some time onwards, which is everything a timeline
+/// edit at that time can change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Plan {
+    events: Vec<TimelineEvent>,
+    segments: Vec<Segment>,
+    /// End of the segment which was in progress at the time, which edits cut short
+    split_segment_end: Option<f64>,
+    ghosts: Vec<Ghost>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Edit {
+    /// Nothing before this time is changed by the edit
+    time: f64,
+    is_adjustment: bool,
+    /// The plan to go back to
+    plan: Plan,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VesselHistory {
+    entity: Entity,
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+}
+
+/// Timeline edits made by the player for each vessel, which can be undone
+/// and redone as long as everything they changed is still in the future
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TimelineHistory {
+    vessels: Vec<VesselHistory>,
+}
+
+impl TimelineHistory {
+    fn get(&self, entity: Entity) -> Option<&VesselHistory> {
+        self.vessels.iter().find(|history| history.entity == entity)
+    }
+
+    fn get_mut(&mut self, entity: Entity) -> &mut VesselHistory {
+        let Some(index) = self.vessels.iter().position(|history| history.entity == entity) else {
+            self.vessels.push(VesselHistory { entity, undo: vec![], redo: vec![] });
+            return self.vessels.last_mut().unwrap();
+        };
+        &mut self.vessels[index]
+    }
+
+    pub(crate) fn forget(&mut self, entity: Entity) {
+        self.vessels.retain(|history| history.entity != entity);
+    }
+}
+
+impl Model {
+    /// Must be called before the player edits the timeline of `entity` at
+    /// `time`, so that the edit can be undone. Anything which could be
+    /// redone is forgotten
+    pub fn record_timeline_edit(&mut self, entity: Entity, time: f64) {
+        self.record(entity, time, false);
+    }
+
+    /// Like `record_timeline_edit`, but adjusting the same event several
+    /// times in a row is undone all at once
+    pub fn record_timeline_adjustment(&mut self, entity: Entity, time: f64) {
+        let history = self.timeline_history.get(entity);
+        let is_continuing = history.is_some_and(|history| history.redo.is_empty()
+            && history.undo.last().is_some_and(|edit| edit.is_adjustment && edit.time == time));
+        if !is_continuing {
+            self.record(entity, time, true);
+        }
+    }
+
+    fn record(&mut self, entity: Entity, time: f64, is_adjustment: bool) {
+        let plan = self.plan(entity, time);
+        let now = self.time;
+        let history = self.timeline_history.get_mut(entity);
+        // Once the latest edit has happened, none of the ones before it can be undone either
+        if history.undo.last().is_some_and(|edit| edit.time <= now) {
+            history.undo.clear();
+        }
+        history.undo.push(Edit { time, is_adjustment, plan });
+        if history.undo.len() > MAX_EDITS {
+            history.undo.remove(0);
+        }
+        history.redo.clear();
+    }
+
+    /// Forgets every edit to `entity`, for example because its trajectory
+    /// was recomputed in a way that cannot be undone
+    pub fn forget_timeline_edits(&mut self, entity: Entity) {
+        self.timeline_history.forget(entity);
+    }
+
+    pub fn can_undo_timeline_edit(&self, entity: Entity) -> bool {
+        self.timeline_history.get(entity)
+            .and_then(|history| history.undo.last())
+            .is_some_and(|edit| self.can_restore(entity, edit))
+    }
+
+    pub fn can_redo_timeline_edit(&self, entity: Entity) -> bool {
+        self.timeline_history.get(entity)
+            .and_then(|history| history.redo.last())
+            .is_some_and(|edit| self.can_restore(entity, edit))
+    }
+
+    /// Restores the exact segments, events and ghosts `entity` had before its
+    /// last edit. Does nothing if the edit cannot be undone
+    pub fn undo_timeline_edit(&mut self, entity: Entity) {
+        if !self.can_undo_timeline_edit(entity) {
+            return;
+        }
+        let edit = self.timeline_history.get_mut(entity).undo.pop().unwrap();
+        let edit = self.swap_plan(entity, edit);
+        self.timeline_history.get_mut(entity).redo.push(edit);
+    }
+
+    /// Does nothing if there is nothing which can be redone
+    pub fn redo_timeline_edit(&mut self, entity: Entity) {
+        if !self.can_redo_timeline_edit(entity) {
+            return;
+        }
+        let edit = self.timeline_history.get_mut(entity).redo.pop().unwrap();
+        let edit = self.swap_plan(entity, edit);
+        self.timeline_history.get_mut(entity).undo.push(edit);
+    }
+
+    /// An edit can only be restored if it is still in the future, would not
+    /// split a burn, turn or guidance segment, and every ghost it launches
+    /// still exists or can be brought back as the same entity
+    fn can_restore(&self, entity: Entity, edit: &Edit) -> bool {
+        // Docked vessels have no trajectory to restore
+        let Some(path_component) = self.try_path_component(entity) else {
+            return false;
+        };
+        let splits_segment = path_component.future_segments()
+            .iter()
+            .any(|segment| !segment.is_orbit() && !segment.is_drag() && segment.start_time() < edit.time && edit.time < segment.end_time());
+        edit.time > self.time
+            && !splits_segment
+            && edit.plan.ghosts.iter().all(|ghost| self.exists(ghost.entity) || self.entity_allocator.can_restore(ghost.entity))
+    }
+
+    fn plan(&self, entity: Entity, time: f64) -> Plan {
+        let events: Vec<TimelineEvent> = self.vessel_component(entity).timeline().events()
+            .iter()
+            .filter(|event| event.time() >= time)
+            .cloned()
+            .collect();
+        let path_component = self.path_component(entity);
+        let segments = path_component.future_segments()
+            .into_iter()
+            .filter(|segment| segment.start_time() >= time)
+            .cloned()
+            .collect();
+        let split_segment_end = path_component.future_segments()
+            .iter()
+            .find(|segment| segment.start_time() < time && time < segment.end_time())
+            .map(|segment| segment.end_time());
+        let ghosts = events.iter()
+            .filter_map(TimelineEvent::ghost)
+            .map(|ghost| Ghost {
+                entity: ghost,
+                name_component: self.try_name_component(ghost).cloned(),
+                path_component: self.path_component(ghost).clone(),
+                vessel_component: self.vessel_component(ghost).clone(),
+            })
+            .collect();
+        Plan { events, segments, split_segment_end, ghosts }
+    }
+
+    /// Replaces the plan of `entity` with the one in `edit`, returning an
+    /// edit which would change it back
+    fn swap_plan(&mut self, entity: Entity, edit: Edit) -> Edit {
+        let Edit { time, is_adjustment, plan } = edit;
+        let previous = self.plan(entity, time);
+
+        for ghost in previous.ghosts.iter().map(|ghost| ghost.entity) {
+            if !plan.ghosts.iter().any(|other| other.entity == ghost) {
+                self.deallocate(ghost);
+            }
+        }
+
+        let timeline = self.vessel_component_mut(entity).timeline_mut();
+        timeline.remove_events_from(time);
+        for event in plan.events {
+            timeline.add(event);
+        }
+
+        let path_component = self.path_component_mut(entity);
+        path_component.remove_segments_after(time);
+        if let Some(end_time) = plan.split_segment_end {
+            match path_component.end_segment_mut() {
+                Segment::Orbit(orbit) => orbit.end_at(end_time),
+                Segment::Drag(drag) => drag.end_at(end_time),
+                Segment::Burn(_) | Segment::Turn(_) | Segment::Guidance(_) => unreachable!("Only orbits and drag can be split"),
+            }
+        }
+        for segment in plan.segments {
+            path_component.add_segment(segment);
+        }
+
+        for ghost in plan.ghosts {
+            if !self.exists(ghost.entity) {
+                self.entity_allocator.restore(ghost.entity);
+            }
+            self.name_components.set(ghost.entity, ghost.name_component);
+            self.path_components.set(ghost.entity, Some(ghost.path_component));
+            self.vessel_components.set(ghost.entity, Some(ghost.vessel_component));
+        }
+
+        Edit { time, is_adjustment, plan: previous }
+    }
+}
@@ -0,0 +1,292 @@
+use nalgebra_glm::{vec2, DMat2, DVec2};
+use transfer_window_common::numerical_methods::lambert::lambert;
+
+use crate::{components::{path_component::{burn::builder::BurnBuilder, orbit::{builder::OrbitBuilder, scary_math::GRAVITATIONAL_CONSTANT, Orbit}, rocket_equation_function::RocketEquationFunction, turn::builder::TurnBuilder}, vessel_component::timeline::start_burn::StartBurnEvent}, storage::entity_allocator::Entity};
+
+use super::{state_query::StateQuery, Model};
+
+const DEPARTURE_SAMPLES: usize = 24;
+const FLIGHT_TIME_SAMPLES: usize = 24;
+/// Flight times are searched between these fractions of the target's period
+const MIN_FLIGHT_TIME_FRACTION: f64 = 0.05;
+const MAX_FLIGHT_TIME_FRACTION: f64 = 1.0;
+/// How many of the cheapest Lambert solutions we try to refine before giving up
+const CANDIDATES_TO_REFINE: usize = 4;
+/// The closest approach is searched for until this fraction of the flight time
+const SEARCH_END_FLIGHT_TIME_FRACTION: f64 = 1.5;
+const APPROACH_SAMPLES: usize = 128;
+const APPROACH_REFINEMENT_ITERATIONS: usize = 32;
+const MAX_REFINEMENT_ITERATIONS: usize = 64;
+const INITIAL_REFINEMENT_STEP_FRACTION: f64 = 0.05;
+const MIN_REFINEMENT_STEP: f64 = 0.01;
+
+/// A burn proposed by the intercept planner. The delta-v is relative
+/// to the tangent, as taken by `Model::create_burn`
+#[derive(Debug, Clone)]
+pub struct InterceptPlan {
+    time: f64,
+    delta_v: DVec2,
+    fuel_kg: f64,
+    closest_approach_time: f64,
+    closest_approach_distance: f64,
+}
+
+impl InterceptPlan {
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn delta_v(&self) -> DVec2 {
+        self.delta_v
+    }
+
+    pub fn dv(&self) -> f64 {
+        self.delta_v.magnitude()
+    }
+
+    pub fn fuel_kg(&self) -> f64 {
+        self.fuel_kg
+    }
+
+    pub fn closest_approach_time(&self) -> f64 {
+        self.closest_approach_time
+    }
+
+    pub fn closest_approach_distance(&self) -> f64 {
+        self.closest_approach_distance
+    }
+}
+
+fn tangent_matrix(velocity: DVec2) -> DMat2 {
+    let tangent = velocity.normalize();
+    DMat2::new(tangent.x, -tangent.y, tangent.y, tangent.x)
+}
+
+/// Golden section search for the minimum of `f` between `min` and `max`
+pub(super) fn golden_section_minimum(f: &impl Fn(f64) -> f64, mut min: f64, mut max: f64) -> f64 {
+    let ratio = (f64::sqrt(5.0) - 1.0) / 2.0;
+    for _ in 0..APPROACH_REFINEMENT_ITERATIONS {
+        let a = max - ratio * (max - min);
+        let b = min + ratio * (max - min);
+        if f(a) < f(b) {
+            max = b;
+        } else {
+            min = a;
+        }
+    }
+    f64::midpoint(min, max)
+}
+
+impl Model {
+    /// The orbit `entity` is on at `time`, if it is on one
+    pub(super) fn departure_orbit(&self, entity: Entity, time: f64) -> Option<&Orbit> {
+        let path_component = self.path_component(entity);
+        if time < self.time() || time > path_component.end_segment().end_time() {
+            return None;
+        }
+        path_component.future_segment_at_time(time).as_orbit()
+    }
+
+    /// Position of `target` relative to `parent` at `time`, as perceived by `entity`
+    fn intercept_target_position(&self, entity: Entity, target: Entity, parent: Entity, time: f64) -> Option<DVec2> {
+        let snapshot = self.snapshot_at_observe(time, self.faction(entity));
+        if self.try_orbitable_component(target).is_none() {
+            let end_time = snapshot.future_segments(target).last()?.end_time();
+            if time > end_time {
+                return None;
+            }
+        }
+        if snapshot.parent(target)? != parent {
+            return None;
+        }
+        Some(snapshot.position(target))
+    }
+
+    pub fn available_dv_at_time(&self, entity: Entity, time: f64) -> f64 {
+        let vessel_component = self.vessel_component(entity);
+        let Some(engine) = vessel_component.engine() else {
+            return 0.0;
+        };
+        let fuel_kg = self.snapshot_at(time).fuel_kg(entity);
+        RocketEquationFunction::new(vessel_component.empty_mass(), fuel_kg, engine.fuel_kg_per_second(), engine.specific_impulse())
+            .remaining_dv()
+    }
+
+    /// The range of flight times worth considering for an intercept of
+    /// `target`, based on the period of whichever of the two is orbiting
+    pub fn intercept_flight_time_range(&self, entity: Entity, target: Entity, time: f64) -> Option<(f64, f64)> {
+        let snapshot = self.snapshot_at_observe(time, self.faction(entity));
+        let period = snapshot.try_segment(target)?
+            .as_orbit()
+            .and_then(Orbit::period)
+            .or_else(|| self.departure_orbit(entity, time).and_then(Orbit::period))?;
+        Some((period * MIN_FLIGHT_TIME_FRACTION, period * MAX_FLIGHT_TIME_FRACTION))
+    }
+
+    /// Solves Lambert's problem from the position of `entity` at `departure_time`
+    /// to the position of `target` after `flight_time`. Returns the absolute
+    /// (not tangent-relative) impulse required, assuming both share a parent
+    pub fn intercept_delta_v(&self, entity: Entity, target: Entity, departure_time: f64, flight_time: f64) -> Option<DVec2> {
+        let orbit = self.departure_orbit(entity, departure_time)?;
+        let point = orbit.point_at_time(departure_time);
+        let target_position = self.intercept_target_position(entity, target, orbit.parent(), departure_time + flight_time)?;
+        let mu = GRAVITATIONAL_CONSTANT * orbit.parent_mass();
+        let (velocity, _) = lambert(point.position(), target_position, flight_time, mu, !orbit.is_clockwise())?;
+        Some(velocity - point.velocity())
+    }
+
+    /// Works out what would happen if a burn with `delta_v` were created
+    /// at `time`, mirroring `create_burn`, and finds the closest approach
+    /// to `target` before `search_end_time`
+    fn simulate_intercept(&self, entity: Entity, target: Entity, time: f64, delta_v: DVec2, search_end_time: f64) -> Option<InterceptPlan> {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Simulate intercept");
+        let orbit = self.departure_orbit(entity, time)?;
+        let point = orbit.point_at_time(time);
+        let vessel_component = self.vessel_component(entity);
+        let fuel_kg = self.snapshot_at(time).fuel_kg(entity);
+        let absolute_delta_v = tangent_matrix(point.velocity()) * delta_v;
+
+        let turn = TurnBuilder {
+            parent: orbit.parent(),
+            parent_mass: orbit.parent_mass(),
+            dry_mass: vessel_component.empty_mass(),
+            fuel_kg,
+            time,
+            position: point.position(),
+            velocity: point.velocity(),
+            rotation: orbit.rotation(),
+            target_rotation: f64::atan2(absolute_delta_v.y, absolute_delta_v.x),
+            rcs: vessel_component.rcs()?.clone(),
+        }.build();
+
+        let turn_end_point = turn.end_point();
+        let burn = BurnBuilder {
+            parent: orbit.parent(),
+            parent_mass: orbit.parent_mass(),
+            mass: turn_end_point.mass(),
+            fuel_kg,
+            engine: vessel_component.engine()?.clone(),
+            tangent: turn_end_point.velocity().normalize(),
+            delta_v,
+            time: turn_end_point.time(),
+            position: turn_end_point.position(),
+            velocity: turn_end_point.velocity(),
+        }.build();
+
+        let burn_end_point = burn.end_point();
+        let orbit_after_burn = OrbitBuilder {
+            parent: orbit.parent(),
+            mass: burn_end_point.mass(),
+            parent_mass: orbit.parent_mass(),
+            rotation: burn.rotation(),
+            position: burn_end_point.position(),
+            velocity: burn_end_point.velocity(),
+            time: burn_end_point.time(),
+        }.build();
+
+        let start_time = burn_end_point.time();
+        if search_end_time <= start_time {
+            return None;
+        }
+        let distance = |time: f64| match self.intercept_target_position(entity, target, orbit.parent(), time) {
+            Some(target_position) => (orbit_after_burn.point_at_time(time).position() - target_position).magnitude(),
+            None => f64::MAX,
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let step = (search_end_time - start_time) / APPROACH_SAMPLES as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let closest_sample = (0..=APPROACH_SAMPLES)
+            .map(|i| start_time + i as f64 * step)
+            .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))?;
+        let min = f64::max(closest_sample - step, start_time);
+        let max = f64::min(closest_sample + step, search_end_time);
+        let closest_approach_time = golden_section_minimum(&distance, min, max);
+        let closest_approach_distance = distance(closest_approach_time);
+        if closest_approach_distance == f64::MAX {
+            return None;
+        }
+
+        Some(InterceptPlan {
+            time,
+            delta_v: burn.delta_v(),
+            fuel_kg: fuel_kg - burn.end_fuel_kg(),
+            closest_approach_time,
+            closest_approach_distance,
+        })
+    }
+
+    /// Refines the delta-v with a compass search to minimise the closest
+    /// approach, since Lambert assumes an instant burn at the departure time
+    /// whereas the real burn happens after a turn and takes time
+    fn refine_intercept(&self, entity: Entity, target: Entity, time: f64, delta_v: DVec2, search_end_time: f64) -> Option<InterceptPlan> {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Refine intercept");
+        let mut best = self.simulate_intercept(entity, target, time, delta_v, search_end_time)?;
+        let mut step = f64::max(delta_v.magnitude() * INITIAL_REFINEMENT_STEP_FRACTION, 1.0);
+        let directions = [vec2(1.0, 0.0), vec2(-1.0, 0.0), vec2(0.0, 1.0), vec2(0.0, -1.0)];
+        for _ in 0..MAX_REFINEMENT_ITERATIONS {
+            if step < MIN_REFINEMENT_STEP {
+                break;
+            }
+            let improved = directions.iter()
+                .filter_map(|direction| self.simulate_intercept(entity, target, time, best.delta_v + direction * step, search_end_time))
+                .filter(|plan| plan.closest_approach_distance < best.closest_approach_distance)
+                .min_by(|a, b| a.closest_approach_distance.total_cmp(&b.closest_approach_distance));
+            match improved {
+                Some(plan) => best = plan,
+                None => step /= 2.0,
+            }
+        }
+        Some(best)
+    }
+
+    /// Proposes a burn for `entity` departing between `earliest_departure_time`
+    /// and `latest_departure_time` which brings it as close as possible to
+    /// `target`. The cheapest affordable Lambert transfers are used as starting
+    /// points. Returns None if no transfer could be found, for example because
+    /// the two do not share a parent or the vessel cannot burn in the window
+    pub fn plan_intercept(&self, entity: Entity, target: Entity, earliest_departure_time: f64, latest_departure_time: f64) -> Option<InterceptPlan> {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Plan intercept");
+        if !StartBurnEvent::can_create_ever(self, entity) {
+            return None;
+        }
+        let (min_flight_time, max_flight_time) = self.intercept_flight_time_range(entity, target, earliest_departure_time)?;
+
+        let mut candidates = vec![];
+        for i in 0..DEPARTURE_SAMPLES {
+            #[allow(clippy::cast_precision_loss)]
+            let time = earliest_departure_time + (latest_departure_time - earliest_departure_time) * i as f64 / (DEPARTURE_SAMPLES - 1) as f64;
+            if !StartBurnEvent::can_create(self, entity, time) {
+                continue;
+            }
+            let available_dv = self.available_dv_at_time(entity, time);
+            for j in 0..FLIGHT_TIME_SAMPLES {
+                #[allow(clippy::cast_precision_loss)]
+                let flight_time = min_flight_time + (max_flight_time - min_flight_time) * j as f64 / (FLIGHT_TIME_SAMPLES - 1) as f64;
+                if let Some(delta_v) = self.intercept_delta_v(entity, target, time, flight_time) {
+                    if delta_v.magnitude() < available_dv {
+                        candidates.push((time, flight_time, delta_v));
+                    }
+                }
+            }
+        }
+
+        candidates.sort_by(|a, b| a.2.magnitude().total_cmp(&b.2.magnitude()));
+        candidates.into_iter()
+            .take(CANDIDATES_TO_REFINE)
+            .filter_map(|(time, flight_time, _)| self.plan_transfer(entity, target, time, flight_time))
+            .min_by(|a, b| a.closest_approach_distance.total_cmp(&b.closest_approach_distance))
+    }
+
+    /// Proposes a burn for `entity` departing at `departure_time` which reaches
+    /// `target` after roughly `flight_time`, starting from the Lambert solution
+    pub fn plan_transfer(&self, entity: Entity, target: Entity, departure_time: f64, flight_time: f64) -> Option<InterceptPlan> {
+        let delta_v = self.intercept_delta_v(entity, target, departure_time, flight_time)?;
+        let velocity = self.departure_orbit(entity, departure_time)?.point_at_time(departure_time).velocity();
+        let delta_v = tangent_matrix(velocity).transpose() * delta_v;
+        self.refine_intercept(entity, target, departure_time, delta_v, departure_time + flight_time * SEARCH_END_FLIGHT_TIME_FRACTION)
+    }
+}
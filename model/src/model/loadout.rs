@@ -0,0 +1,24 @@
+use crate::{components::vessel_component::slots::ModuleType, storage::entity_allocator::Entity};
+
+use super::Model;
+
+impl Model {
+    /// Vessels can only be refitted while docked to a station
+    pub fn can_refit(&self, entity: Entity) -> bool {
+        self.docked(entity) && self.find_station_docked_to(entity).is_some()
+    }
+
+    /// Whether `module` can be fitted into (or, if None, removed from) `slot`
+    pub fn can_fit_module(&self, entity: Entity, slot: usize, module: Option<ModuleType>) -> bool {
+        self.can_refit(entity)
+            && self.vessel_component(entity)
+                .slots()
+                .get(slot)
+                .is_some_and(|slot| slot.can_fit(module))
+    }
+
+    pub fn fit_module(&mut self, entity: Entity, slot: usize, module: Option<ModuleType>) {
+        assert!(self.can_fit_module(entity, slot, module));
+        self.vessel_component_mut(entity).fit_module(slot, module);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{components::{orbitable_component::deposit::{MINING_DISTANCE, MINING_SPEED}, path_component::segment::Segment}, storage::entity_allocator::Entity};
+
+use super::{state_query::StateQuery, story_event::StoryEvent, Model};
+
+impl Model {
+    /// Whether `entity` is close enough to the surface of `asteroid`, and
+    /// moving slowly enough relative to it, to mine something it has room for.
+    /// Mined cargo changes the vessel's mass, which would invalidate any
+    /// planned burns or turns, so the vessel must have none
+    pub fn can_mine(&self, entity: Entity, asteroid: Entity) -> bool {
+        let vessel_component = self.vessel_component(entity);
+        let Some(orbitable_component) = self.try_orbitable_component(asteroid) else {
+            return false;
+        };
+        let has_minable_deposit = orbitable_component.deposits()
+            .iter()
+            .any(|deposit| !deposit.is_depleted() && !vessel_component.is_resource_full(deposit.resource()));
+        vessel_component.can_ever_mine()
+            && !self.docked(entity)
+            && self.path_component(entity).future_segments().into_iter().all(Segment::is_orbit)
+            && orbitable_component.is_asteroid()
+            && has_minable_deposit
+            && self.distance(entity, asteroid) - orbitable_component.radius() < MINING_DISTANCE
+            && self.relative_speed(entity, asteroid) < MINING_SPEED
+    }
+
+    /// # Panics
+    /// Panics if `entity` cannot mine `asteroid`
+    pub fn start_mining(&mut self, entity: Entity, asteroid: Entity) {
+        assert!(self.can_mine(entity, asteroid));
+        self.vessel_component_mut(entity).set_mining(Some(asteroid));
+        self.add_story_event(StoryEvent::MiningStarted { entity, asteroid });
+    }
+
+    pub fn stop_mining(&mut self, entity: Entity) {
+        let Some(asteroid) = self.vessel_component(entity).mining() else {
+            return;
+        };
+        self.vessel_component_mut(entity).set_mining(None);
+        self.add_story_event(StoryEvent::MiningStopped { entity, asteroid });
+    }
+
+    /// When mining will stop because the hold is full or the deposits have
+    /// run out, if the vessel stays where it is. Deposits are mined in order
+    pub fn mining_completion_time(&self, entity: Entity) -> Option<f64> {
+        let vessel_component = self.vessel_component(entity);
+        let asteroid = vessel_component.mining()?;
+        let mut cargo_hold = vessel_component.cargo_hold()?.clone();
+        let mut total = 0.0;
+        for deposit in self.try_orbitable_component(asteroid)?.deposits() {
+            let amount = f64::min(deposit.remaining(), cargo_hold.space_for(deposit.resource()));
+            cargo_hold.set_amount(deposit.resource(), cargo_hold.amount(deposit.resource()) + amount);
+            total += amount;
+        }
+        Some(self.time + total / vessel_component.mining_rate())
+    }
+}
@@ -0,0 +1,140 @@
+use log::trace;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{components::{path_component::guidance::Guidance, vessel_component::{point_defense::PointDefense, timeline::{point_defense::PointDefenseEvent, TimelineEvent}}, ComponentType}, storage::entity_allocator::Entity};
+
+use super::{state_query::StateQuery, Model};
+
+/// The distance between a torpedo and a defender is checked this often, and
+/// individual shots are only worked out for the intervals in which the
+/// torpedo could come within range
+const RANGE_CHECK_INTERVAL: f64 = 10.0;
+
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Combines the model seed with everything that identifies an engagement, so
+/// predicting the same engagement again always gives the same outcome
+fn engagement_seed(seed: u64, entity: Entity, defender: Entity, time: f64) -> u64 {
+    [entity.index() as u64, entity.generation() as u64, defender.index() as u64, defender.generation() as u64, time.to_bits()]
+        .into_iter()
+        .fold(splitmix64(seed), |hash, value| splitmix64(hash ^ value))
+}
+
+impl Model {
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Point defense outcomes are derived from this, so the same seed and
+    /// the same actions always play out the same way
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Shots which `defender` is already predicted to fire at torpedoes
+    /// other than `entity`, and so cannot be fired again
+    fn committed_point_defense_shots(&self, entity: Entity, defender: Entity) -> usize {
+        self.entities(vec![ComponentType::VesselComponent])
+            .into_iter()
+            .filter(|other| *other != entity)
+            .flat_map(|other| self.vessel_component(other).timeline().events().iter())
+            .filter_map(TimelineEvent::as_point_defense)
+            .filter(|event| event.defender() == defender)
+            .map(|event| event.shots())
+            .sum()
+    }
+
+    /// The distance and relative speed between `defender` and the torpedo on
+    /// `guidance` at `time`, if they are orbiting the same body
+    fn point_defense_separation(&self, guidance: &Guidance, defender: Entity, time: f64) -> Option<(f64, f64)> {
+        let snapshot = self.snapshot_at(time);
+        if snapshot.parent(defender) != Some(guidance.parent()) {
+            return None;
+        }
+        let point = guidance.point_at_time(time);
+        let distance = (point.position() - snapshot.position(defender)).magnitude();
+        let relative_speed = (point.velocity() - snapshot.velocity(defender)).magnitude();
+        Some((distance, relative_speed))
+    }
+
+    /// Times at which `defender` would fire at a torpedo on `guidance`,
+    /// limited by the ammo left after its other engagements. Shots are only
+    /// fired while the torpedo is in range and before it reaches its target
+    fn point_defense_shot_times(&self, entity: Entity, guidance: &Guidance, defender: Entity, point_defense: &PointDefense) -> Vec<f64> {
+        let ammo = point_defense.ammo().saturating_sub(self.committed_point_defense_shots(entity, defender));
+        let start_time = guidance.start_point().time();
+        let end_time = f64::min(guidance.end_point().time(), self.path_component(defender).end_segment().end_time());
+        #[allow(clippy::cast_precision_loss)]
+        let shot_time = |shot: usize| start_time + shot as f64 * point_defense.fire_interval();
+        let mut shot_times = vec![];
+        let mut shot = 0;
+        let mut time = start_time;
+        while time < end_time && shot_times.len() < ammo {
+            let check_end_time = f64::min(time + RANGE_CHECK_INTERVAL, end_time);
+            let may_be_in_range = self.point_defense_separation(guidance, defender, time)
+                .is_some_and(|(distance, relative_speed)| distance < point_defense.range() + relative_speed * RANGE_CHECK_INTERVAL);
+            while shot_time(shot) < check_end_time && shot_times.len() < ammo {
+                let time = shot_time(shot);
+                let in_range = may_be_in_range && self.point_defense_separation(guidance, defender, time)
+                    .is_some_and(|(distance, _)| distance < point_defense.range());
+                if in_range {
+                    shot_times.push(time);
+                }
+                shot += 1;
+            }
+            time = check_end_time;
+        }
+        shot_times
+    }
+
+    /// The first engagement of the torpedo `entity` by a hostile vessel's
+    /// point defense while on `guidance`, if any. Each shot hits with the
+    /// defender's hit probability, and the engagement ends at the first hit
+    pub(crate) fn predict_point_defense(&self, entity: Entity, guidance: &Guidance) -> Option<PointDefenseEvent> {
+        let faction = self.vessel_component(entity).faction();
+        let mut engagements: Vec<(Entity, Vec<f64>)> = self.entities(vec![ComponentType::VesselComponent, ComponentType::PathComponent])
+            .into_iter()
+            .filter(|defender| *defender != entity)
+            .filter_map(|defender| {
+                let vessel_component = self.vessel_component(defender);
+                if vessel_component.is_ghost() || !self.is_hostile_to(vessel_component.faction(), faction) {
+                    return None;
+                }
+                let point_defense = vessel_component.point_defense()?;
+                let shot_times = self.point_defense_shot_times(entity, guidance, defender, point_defense);
+                (!shot_times.is_empty()).then_some((defender, shot_times))
+            })
+            .collect();
+        // Entities are unordered, so ties are broken by index to keep the outcome deterministic
+        engagements.sort_by(|a, b| a.1[0].total_cmp(&b.1[0]).then(a.0.index().cmp(&b.0.index())));
+        let (defender, shot_times) = engagements.into_iter().next()?;
+
+        let hit_probability = self.vessel_component(defender).point_defense().unwrap().hit_probability();
+        let mut rng = StdRng::seed_from_u64(engagement_seed(self.seed, entity, defender, shot_times[0]));
+        let hit = shot_times.iter().position(|_| rng.gen_bool(hit_probability));
+        let shots = hit.map_or(shot_times.len(), |hit| hit + 1);
+        let time = shot_times[shots - 1];
+        trace!("Point defense of {defender:?} engages {entity:?} with {shots} shots, hit: {}", hit.is_some());
+        Some(PointDefenseEvent::new(entity, defender, time, shots, hit.is_some()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::storage::entity_allocator::Entity;
+
+    use super::engagement_seed;
+
+    #[test]
+    fn test_engagement_seed() {
+        let entity = Entity::mock();
+        assert_eq!(engagement_seed(1, entity, entity, 10.0), engagement_seed(1, entity, entity, 10.0));
+        assert_ne!(engagement_seed(1, entity, entity, 10.0), engagement_seed(2, entity, entity, 10.0));
+        assert_ne!(engagement_seed(1, entity, entity, 10.0), engagement_seed(1, entity, entity, 11.0));
+    }
+}
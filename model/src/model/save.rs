@@ -0,0 +1,377 @@
+use std::{error::Error, fmt::Display};
+
+use log::debug;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::Model;
+
+/// Version written to new saves. Bump this and append a migration to
+/// `MIGRATIONS` whenever a change to the model breaks the serialized format.
+pub const SAVE_VERSION: u32 = 3;
+
+type Migration = fn(&mut Map<String, Value>) -> Result<(), String>;
+
+/// `MIGRATIONS[i]` upgrades the save envelope from version `i` to version `i + 1`
+const MIGRATIONS: [Migration; SAVE_VERSION as usize] = [
+    migrate_0_to_1,
+    migrate_1_to_2,
+    migrate_2_to_3,
+];
+
+#[derive(Debug)]
+pub enum SaveError {
+    Corrupt(String),
+    TooNew { version: u32 },
+    Migration { from: u32, reason: String },
+    Serialization(String),
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Corrupt(reason) => write!(f, "save is corrupt: {reason}"),
+            SaveError::TooNew { version } => write!(f, "save version {version} is newer than the supported version {SAVE_VERSION}"),
+            SaveError::Migration { from, reason } => write!(f, "failed to migrate save from version {from} to {}: {reason}", from + 1),
+            SaveError::Serialization(reason) => write!(f, "failed to serialize save: {reason}"),
+        }
+    }
+}
+
+impl Error for SaveError {}
+
+#[derive(Serialize)]
+struct Envelope<'a, S: Serialize> {
+    version: u32,
+    model: &'a Model,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session: Option<&'a S>,
+}
+
+/// Everything in a save except the model, which serde skips over without
+/// building it
+#[derive(Deserialize)]
+struct SessionEnvelope<S> {
+    version: Option<u32>,
+    session: Option<S>,
+}
+
+/// Version 0 saves are the bare model without an envelope
+#[allow(clippy::unnecessary_wraps)]
+fn migrate_0_to_1(_envelope: &mut Map<String, Value>) -> Result<(), String> {
+    Ok(())
+}
+
+fn legacy_faction_index(name: &str) -> Option<usize> {
+    match name {
+        "Player" => Some(0),
+        "Ally" => Some(1),
+        "Enemy" => Some(2),
+        _ => None,
+    }
+}
+
+fn replace_faction_names(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if let Some(index) = value.as_str().filter(|_| key == "faction").and_then(legacy_faction_index) {
+                    *value = Value::from(index);
+                } else {
+                    replace_faction_names(value);
+                }
+            }
+        }
+        Value::Array(array) => array.iter_mut().for_each(replace_faction_names),
+        _ => (),
+    }
+}
+
+/// Factions used to be a fixed enum serialized by name, but are now indices
+/// of factions registered in the model
+fn migrate_1_to_2(envelope: &mut Map<String, Value>) -> Result<(), String> {
+    let model = envelope.get_mut("model").ok_or("missing model")?;
+    replace_faction_names(model);
+    let intel = model.get_mut("intel").and_then(Value::as_object_mut);
+    for map in intel.into_iter().flat_map(|intel| intel.values_mut()).filter_map(Value::as_object_mut) {
+        *map = std::mem::take(map)
+            .into_iter()
+            .map(|(key, value)| (legacy_faction_index(&key).map_or(key, |index| index.to_string()), value))
+            .collect();
+    }
+    Ok(())
+}
+
+/// Converts a docked vessel's separate fuel and torpedo transfers into a
+/// list of resource transfers
+fn replace_legacy_docked_transfers(object: &mut Map<String, Value>) {
+    let mut transfers = vec![];
+    if let Some(Value::Object(mut transfer)) = object.remove("fuel_transfer") {
+        transfer.remove("rate");
+        transfer.insert("resource".to_string(), Value::from("Fuel"));
+        transfer.insert("time_to_next".to_string(), Value::from(0.0));
+        transfers.push(Value::Object(transfer));
+    }
+    if let Some(Value::Object(mut transfer)) = object.remove("torpedo_transfer") {
+        transfer.remove("interval");
+        transfer.insert("resource".to_string(), Value::from("Torpedoes"));
+        transfers.push(Value::Object(transfer));
+    }
+    object.insert("transfers".to_string(), Value::Array(transfers));
+}
+
+/// Converts a scheduled transfer's `{ "Fuel": { "kg": .. } }` or
+/// `{ "Torpedoes": { "count": .. } }` amount into a resource and an amount
+fn replace_legacy_transfer_amount(object: &mut Map<String, Value>) {
+    let Some(Value::Object(amount)) = object.get("amount") else {
+        return;
+    };
+    let (resource, amount) = if let Some(fuel) = amount.get("Fuel") {
+        ("Fuel", fuel.get("kg").cloned().unwrap_or(Value::Null))
+    } else if let Some(torpedoes) = amount.get("Torpedoes") {
+        ("Torpedoes", torpedoes.get("count").cloned().unwrap_or(Value::Null))
+    } else {
+        return;
+    };
+    object.insert("resource".to_string(), Value::from(resource));
+    object.insert("amount".to_string(), amount);
+}
+
+fn replace_legacy_transfers(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            if object.contains_key("fuel_transfer") || object.contains_key("torpedo_transfer") {
+                replace_legacy_docked_transfers(object);
+            }
+            if object.contains_key("station") && object.contains_key("direction") {
+                replace_legacy_transfer_amount(object);
+            }
+            object.values_mut().for_each(replace_legacy_transfers);
+        }
+        Value::Array(array) => array.iter_mut().for_each(replace_legacy_transfers),
+        _ => (),
+    }
+}
+
+/// Fuel and torpedo transfers used to be special cases, but are now
+/// transfers of any resource
+fn migrate_2_to_3(envelope: &mut Map<String, Value>) -> Result<(), String> {
+    let model = envelope.get_mut("model").ok_or("missing model")?;
+    replace_legacy_transfers(model);
+    Ok(())
+}
+
+/// Splits a save into its version and envelope. Saves without an envelope
+/// predate versioning and are wrapped in one as version 0.
+fn unwrap_envelope(value: Value) -> Result<(u32, Map<String, Value>), SaveError> {
+    let Value::Object(mut object) = value else {
+        return Err(SaveError::Corrupt("expected an object".to_string()));
+    };
+
+    let Some(version) = object.remove("version") else {
+        let mut envelope = Map::new();
+        envelope.insert("model".to_string(), Value::Object(object));
+        return Ok((0, envelope));
+    };
+
+    let version = version.as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| SaveError::Corrupt(format!("invalid version {version}")))?;
+    Ok((version, object))
+}
+
+fn migrate(envelope: &mut Map<String, Value>, version: u32) -> Result<(), SaveError> {
+    if version > SAVE_VERSION {
+        return Err(SaveError::TooNew { version });
+    }
+    for from in version..SAVE_VERSION {
+        debug!("Migrating save from version {} to {}", from, from + 1);
+        MIGRATIONS[from as usize](envelope).map_err(|reason| SaveError::Migration { from, reason })?;
+    }
+    Ok(())
+}
+
+impl Model {
+    /// # Errors
+    /// Returns an error if the save is corrupt, was written by a newer version,
+    /// or cannot be migrated to the current version
+    pub fn deserialize(serialized: &str) -> Result<Self, SaveError> {
+        let (model, _) = Self::deserialize_with_session::<Value>(serialized)?;
+        Ok(model)
+    }
+
+    /// Deserializes a save along with the session data stored alongside the
+    /// model, if there is any. The model does not interpret the session.
+    /// # Errors
+    /// Returns an error if the save is corrupt, was written by a newer version,
+    /// or cannot be migrated to the current version
+    pub fn deserialize_with_session<S: DeserializeOwned>(serialized: &str) -> Result<(Self, Option<S>), SaveError> {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Deserialize");
+        let value = serde_json::from_str(serialized).map_err(|error| SaveError::Corrupt(error.to_string()))?;
+        let (version, mut envelope) = unwrap_envelope(value)?;
+        migrate(&mut envelope, version)?;
+
+        let model = envelope.remove("model").ok_or_else(|| SaveError::Corrupt("missing model".to_string()))?;
+        let model = serde_json::from_value(model).map_err(|error| SaveError::Corrupt(error.to_string()))?;
+        let session = match envelope.remove("session") {
+            Some(session) => Some(serde_json::from_value(session).map_err(|error| SaveError::Corrupt(error.to_string()))?),
+            None => None,
+        };
+        Ok((model, session))
+    }
+
+    /// Reads only the session stored alongside the model, which is much
+    /// cheaper than deserializing the whole save. Migrations only change the
+    /// model, so the session is returned as it was written
+    /// # Errors
+    /// Returns an error if the save is corrupt or was written by a newer version
+    pub fn deserialize_session<S: DeserializeOwned>(serialized: &str) -> Result<Option<S>, SaveError> {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Deserialize session");
+        let envelope: SessionEnvelope<S> = serde_json::from_str(serialized).map_err(|error| SaveError::Corrupt(error.to_string()))?;
+        // Saves without a version predate the envelope, so have no session
+        let Some(version) = envelope.version else {
+            return Ok(None);
+        };
+        if version > SAVE_VERSION {
+            return Err(SaveError::TooNew { version });
+        }
+        Ok(envelope.session)
+    }
+
+    /// # Errors
+    /// Forwards serde serialization error if serialization fails
+    pub fn serialize(&self) -> Result<String, SaveError> {
+        self.serialize_with_session::<Value>(None)
+    }
+
+    /// # Errors
+    /// Forwards serde serialization error if serialization fails
+    pub fn serialize_with_session<S: Serialize>(&self, session: Option<&S>) -> Result<String, SaveError> {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Serialize");
+        let envelope = Envelope { version: SAVE_VERSION, model: self, session };
+        serde_json::to_string(&envelope).map_err(|error| SaveError::Serialization(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use crate::{model::Model, test_util::{earth, sun}};
+
+    use super::{SaveError, SAVE_VERSION};
+
+    #[test]
+    fn test_round_trip() {
+        let mut model = Model::default();
+        let sun = sun(&mut model);
+        earth(&mut model, sun);
+        model.update(100.0);
+
+        let serialized = model.serialize().unwrap();
+        let value: Value = serde_json::from_str(&serialized).unwrap();
+        assert!(value["version"] == json!(SAVE_VERSION));
+
+        let deserialized = Model::deserialize(&serialized).unwrap();
+        assert!(deserialized.time() == model.time());
+        assert!(deserialized.entities(vec![]) == model.entities(vec![]));
+    }
+
+    #[test]
+    fn test_unversioned_save_is_migrated() {
+        let mut model = Model::default();
+        model.update(100.0);
+        let serialized = serde_json::to_string(&model).unwrap();
+
+        let deserialized = Model::deserialize(&serialized).unwrap();
+        assert!(deserialized.time() == model.time());
+    }
+
+    #[test]
+    fn test_faction_names_are_migrated() {
+        let mut model = Model::default();
+        model.update(100.0);
+        let mut value = serde_json::to_value(&model).unwrap();
+        value["intel"]["detected"] = json!({ "Enemy": [] });
+        value["vessel_components"] = json!({ "faction": "Ally", "nested": [{ "faction": "Enemy" }], "name": "Player" });
+
+        let mut envelope = json!({ "version": 1, "model": value });
+        super::migrate_1_to_2(envelope.as_object_mut().unwrap()).unwrap();
+
+        assert!(envelope["model"]["intel"]["detected"] == json!({ "2": [] }));
+        assert!(envelope["model"]["vessel_components"] == json!({ "faction": 1, "nested": [{ "faction": 2 }], "name": "Player" }));
+    }
+
+    #[test]
+    fn test_legacy_transfers_are_migrated() {
+        let docked_vessel = json!({
+            "entity": { "index": 1, "generation": 0 },
+            "fuel_transfer": { "direction": "ToDocked", "rate": 0.1, "remaining": 500.0 },
+            "torpedo_transfer": { "direction": "FromDocked", "interval": 1800.0, "time_to_next": 900.0, "remaining": null },
+        });
+        let transfer_event = json!({
+            "Transfer": { "station": { "index": 0, "generation": 0 }, "entity": { "index": 1, "generation": 0 }, "time": 10.0, "direction": "ToDocked", "amount": { "Torpedoes": { "count": 2 } } },
+        });
+        let idle_vessel = json!({ "entity": { "index": 2, "generation": 0 }, "fuel_transfer": null, "torpedo_transfer": null });
+        let mut envelope = json!({ "version": 2, "model": { "ports": [docked_vessel, idle_vessel], "events": [transfer_event] } });
+        super::migrate_2_to_3(envelope.as_object_mut().unwrap()).unwrap();
+
+        let model = &envelope["model"];
+        assert!(model["ports"][0] == json!({
+            "entity": { "index": 1, "generation": 0 },
+            "transfers": [
+                { "resource": "Fuel", "direction": "ToDocked", "time_to_next": 0.0, "remaining": 500.0 },
+                { "resource": "Torpedoes", "direction": "FromDocked", "time_to_next": 900.0, "remaining": null },
+            ],
+        }));
+        assert!(model["ports"][1] == json!({ "entity": { "index": 2, "generation": 0 }, "transfers": [] }));
+        assert!(model["events"][0]["Transfer"]["resource"] == json!("Torpedoes"));
+        assert!(model["events"][0]["Transfer"]["amount"] == json!(2));
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let model = Model::default();
+        let session = json!({ "state": "intro" });
+
+        let serialized = model.serialize_with_session(Some(&session)).unwrap();
+        let (_, deserialized) = Model::deserialize_with_session::<Value>(&serialized).unwrap();
+        assert!(deserialized == Some(session));
+
+        let serialized = model.serialize().unwrap();
+        let (_, deserialized) = Model::deserialize_with_session::<Value>(&serialized).unwrap();
+        assert!(deserialized.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_session_only() {
+        let model = Model::default();
+        let session = json!({ "state": "intro" });
+
+        let serialized = model.serialize_with_session(Some(&session)).unwrap();
+        assert!(Model::deserialize_session::<Value>(&serialized).unwrap() == Some(session));
+
+        let serialized = serde_json::to_string(&model).unwrap();
+        assert!(Model::deserialize_session::<Value>(&serialized).unwrap().is_none());
+
+        let serialized = json!({ "version": SAVE_VERSION + 1, "model": {} }).to_string();
+        assert!(matches!(Model::deserialize_session::<Value>(&serialized), Err(SaveError::TooNew { .. })));
+    }
+
+    #[test]
+    fn test_too_new() {
+        let serialized = json!({ "version": SAVE_VERSION + 1, "model": {} }).to_string();
+        assert!(matches!(Model::deserialize(&serialized), Err(SaveError::TooNew { .. })));
+    }
+
+    #[test]
+    fn test_corrupt() {
+        assert!(matches!(Model::deserialize("{ not json"), Err(SaveError::Corrupt(_))));
+        assert!(matches!(Model::deserialize("[]"), Err(SaveError::Corrupt(_))));
+        let serialized = json!({ "version": SAVE_VERSION, "model": { "time": "soon" } }).to_string();
+        assert!(matches!(Model::deserialize(&serialized), Err(SaveError::Corrupt(_))));
+    }
+}
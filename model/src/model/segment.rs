@@ -1,6 +1,6 @@
 use nalgebra_glm::{DMat2, DVec2};
 
-use crate::{components::{path_component::{burn::builder::BurnBuilder, guidance::{builder::GuidanceBuilder, Guidance}, orbit::builder::OrbitBuilder, segment::Segment, turn::builder::TurnBuilder}, vessel_component::timeline::{intercept::InterceptEvent, TimelineEvent}}, storage::entity_allocator::Entity};
+use crate::{components::{path_component::{burn::builder::BurnBuilder, guidance::{builder::GuidanceBuilder, Guidance}, orbit::builder::OrbitBuilder, segment::Segment, turn::builder::TurnBuilder}, vessel_component::timeline::{intercept::InterceptEvent, point_defense::PointDefenseEvent, TimelineEvent}}, storage::entity_allocator::Entity};
 
 use super::{state_query::StateQuery, Model};
 
@@ -34,13 +34,22 @@ impl Model {
     }
 
     // Helper for guidance components which recomputes whether the guidance will make an intercept
-    // and adds the appropriate intercept event if so
+    // and adds the appropriate intercept event if so, preceded by any point defense engagement
     fn add_guidance(&mut self, entity: Entity, guidance: Guidance) {
         if guidance.will_intercept() {
-            let event = TimelineEvent::Intercept(InterceptEvent::new(self, entity, guidance.target(), guidance.end_point().time()));
-            self.vessel_component_mut(entity)
-                .timeline_mut()
-                .add(event);
+            let point_defense = self.predict_point_defense(entity, &guidance);
+            let destroys_torpedo = point_defense.as_ref().is_some_and(PointDefenseEvent::destroys_torpedo);
+            if let Some(point_defense) = point_defense {
+                self.vessel_component_mut(entity)
+                    .timeline_mut()
+                    .add(TimelineEvent::PointDefense(point_defense));
+            }
+            if !destroys_torpedo {
+                let event = TimelineEvent::Intercept(InterceptEvent::new(self, entity, guidance.target(), guidance.end_point().time()));
+                self.vessel_component_mut(entity)
+                    .timeline_mut()
+                    .add(event);
+            }
         }
 
         self.path_component_mut(entity).add_segment(Segment::Guidance(guidance));
@@ -51,36 +60,44 @@ impl Model {
         self.path_component_mut(entity).remove_segments_after(time);
 
         let last_segment = self.path_component(entity).end_segment();
-        let tangent = last_segment.end_velocity().normalize();
+        let parent = last_segment.parent();
+        let position = last_segment.end_position();
+        let velocity = last_segment.end_velocity();
+        let tangent = velocity.normalize();
         dbg!(tangent);
         let absolute_delta_v = DMat2::new(tangent.x, -tangent.y, tangent.y, tangent.x) * delta_v;
         let target_rotation = f64::atan2(absolute_delta_v.y, absolute_delta_v.x);
         dbg!(target_rotation);
 
-        self.create_turn(entity, time, target_rotation);
+        // Vessels without RCS, like torpedoes, cannot turn, so they start
+        // burning in the direction they need straight away
+        let (mass, start_time, position, velocity) = if self.vessel_component(entity).has_rcs() {
+            self.create_turn(entity, time, target_rotation);
+            let turn_end_point = self.snapshot_at(time).turn_starting_now(entity).end_point().clone();
+            (turn_end_point.mass(), turn_end_point.time(), turn_end_point.position(), turn_end_point.velocity())
+        } else {
+            (self.snapshot_at(time).mass(entity), time, position, velocity)
+        };
 
-        let snapshot = &self.snapshot_at(time);
-        let turn = snapshot.turn_starting_now(entity);
-        let turn_end_point = turn.end_point();
         let engine = self.vessel_component(entity)
             .engine()
             .expect("Attempt to create a burn on a vessel without an engine")
             .clone();
 
         let burn = BurnBuilder {
-            parent: turn.parent(),
-            parent_mass: self.snapshot_at(time).mass(turn.parent()),
-            mass: turn_end_point.mass(),
+            parent,
+            parent_mass: self.snapshot_at(time).mass(parent),
+            mass,
             fuel_kg: self.snapshot_at(time).fuel_kg(entity),
             engine,
-            tangent: turn_end_point.velocity().normalize(),
+            tangent: velocity.normalize(),
             delta_v,
-            time: turn_end_point.time(),
-            position: turn_end_point.position(),
-            velocity: turn_end_point.velocity(),
+            time: start_time,
+            position,
+            velocity,
         }.build();
 
-        self.path_component_mut(entity).remove_segments_after(turn.end_point().time());
+        self.path_component_mut(entity).remove_segments_after(start_time);
         self.path_component_mut(entity).add_segment(Segment::Burn(burn));
         self.complete_trajectory(entity);
     }
@@ -122,7 +139,7 @@ impl Model {
         let turn = TurnBuilder {
             parent: end_segment.parent(),
             parent_mass: self.snapshot_at(end_segment.end_time()).mass(end_segment.parent()),
-            dry_mass: self.vessel_component(entity).dry_mass(),
+            dry_mass: self.vessel_component(entity).empty_mass(),
             fuel_kg: self.snapshot_at(end_segment.end_time()).fuel_kg(entity),
             time,
             position: end_segment.end_position(),
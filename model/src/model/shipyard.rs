@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+
+use crate::{components::vessel_component::{class::VesselClass, shipyard::BuildOrder}, storage::entity_allocator::Entity};
+
+use super::Model;
+
+impl Model {
+    /// Whether `station` has a shipyard and enough resources in its hold
+    /// to pay for a vessel of `class` now
+    pub fn can_build(&self, station: Entity, class: VesselClass) -> bool {
+        let vessel_component = self.vessel_component(station);
+        let definition = class.definition();
+        let Some(build_cost) = definition.build_cost() else {
+            return false;
+        };
+        vessel_component.has_docking() && build_cost.resources()
+            .iter()
+            .all(|(resource, amount)| vessel_component.resource_amount(*resource) >= *amount)
+    }
+
+    /// Takes the cost of `class` from the station straight away and adds it
+    /// to the back of the queue
+    /// # Panics
+    /// Panics if the station cannot build `class`
+    pub fn enqueue_build(&mut self, station: Entity, class: VesselClass) {
+        assert!(self.can_build(station, class));
+        let definition = class.definition();
+        let build_cost = definition.build_cost().unwrap();
+        let vessel_component = self.vessel_component_mut(station);
+        for (resource, amount) in build_cost.resources() {
+            let current = vessel_component.resource_amount(*resource);
+            vessel_component.set_resource_amount(*resource, current - amount);
+        }
+        let order = BuildOrder::new(class, build_cost.resources().clone(), build_cost.seconds());
+        vessel_component.build_queue_mut().unwrap().push(order);
+    }
+
+    /// Gives back as much of what was paid for the order as still fits in
+    /// the station's hold
+    /// # Panics
+    /// Panics if there is no order at `index`
+    pub fn cancel_build(&mut self, station: Entity, index: usize) {
+        let vessel_component = self.vessel_component_mut(station);
+        let order = vessel_component.build_queue_mut()
+            .expect("Attempt to cancel build on vessel without shipyard")
+            .remove(index);
+        let refund: BTreeMap<_, _> = order.paid()
+            .iter()
+            .map(|(resource, amount)| (*resource, f64::min(*amount, vessel_component.resource_space(*resource))))
+            .collect();
+        for (resource, amount) in refund {
+            let current = vessel_component.resource_amount(resource);
+            vessel_component.set_resource_amount(resource, current + amount);
+        }
+    }
+
+    /// # Panics
+    /// Panics if either index is out of range
+    pub fn reorder_build(&mut self, station: Entity, from: usize, to: usize) {
+        self.vessel_component_mut(station)
+            .build_queue_mut()
+            .expect("Attempt to reorder builds on vessel without shipyard")
+            .reorder(from, to);
+    }
+
+    /// When the order at `index` will be finished if the queue is left as it
+    /// is. The vessel may have to wait longer for a free docking port
+    pub fn build_completion_time(&self, station: Entity, index: usize) -> f64 {
+        let orders = self.vessel_component(station)
+            .build_queue()
+            .expect("Attempt to get build completion time on vessel without shipyard")
+            .orders();
+        self.time + orders[..=index].iter().map(BuildOrder::time_remaining).sum::<f64>()
+    }
+}
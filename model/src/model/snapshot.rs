@@ -1,6 +1,6 @@
 use nalgebra_glm::{vec2, DVec2};
 
-use crate::{components::{orbitable_component::OrbitableComponentPhysics, path_component::{burn::Burn, guidance::Guidance, orbit::Orbit, segment::Segment, turn::Turn}, vessel_component::faction::Faction}, storage::entity_allocator::Entity};
+use crate::{components::{orbitable_component::OrbitableComponentPhysics, path_component::{burn::Burn, guidance::Guidance, orbit::Orbit, segment::Segment, turn::Turn}, vessel_component::faction::Faction, ComponentType}, storage::entity_allocator::Entity};
 
 use super::{closest_approach, encounters::{self, Encounter}, state_query::StateQuery, Model};
 
@@ -8,12 +8,22 @@ pub struct Snapshot {
     model: *const Model,
     time: f64,
     observer: Option<Faction>,
+    use_last_known: bool,
 }
 
 impl Snapshot {
     pub fn new(model: &Model, time: f64, observer: Option<Faction>) -> Self {
         let model = model as *const Model;
-        Self { model, time, observer }
+        Self { model, time, observer, use_last_known: false }
+    }
+
+    /// Vessels which the observer has lost track of are placed on the
+    /// segment they were last seen on instead of being hidden, since that
+    /// is all anything aiming at them has to go on. Vessels the observer
+    /// has never seen are still hidden
+    pub fn new_with_last_known(model: &Model, time: f64, observer: Faction) -> Self {
+        let model = model as *const Model;
+        Self { model, time, observer: Some(observer), use_last_known: true }
     }
 
     pub fn model(&self) -> &Model {
@@ -22,6 +32,65 @@ impl Snapshot {
         }
     }
 
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Whether the observer, if there is one, can detect `entity` at the
+    /// snapshot time. Undetected vessels have no future segments, target or
+    /// parent
+    pub fn is_detected(&self, entity: Entity) -> bool {
+        self.observer.is_none_or(|observer| self.model().is_detected_at(observer, entity, self.time))
+    }
+
+    /// Every entity with the given components which the observer, if there
+    /// is one, can detect at the snapshot time
+    pub fn entities(&self, with_component_types: Vec<ComponentType>) -> Vec<Entity> {
+        self.model()
+            .entities(with_component_types)
+            .into_iter()
+            .filter(|entity| self.is_detected(*entity))
+            .collect()
+    }
+
+    fn is_detected_now(&self, entity: Entity) -> bool {
+        self.observer.is_none_or(|observer| self.model().is_detected(observer, entity))
+    }
+
+    /// Vessels which are detected now but not by the snapshot time are
+    /// assumed to carry on as they are seen now, so they are not hidden
+    fn is_hidden(&self, entity: Entity) -> bool {
+        !self.is_detected_now(entity) && !self.is_detected(entity)
+    }
+
+    /// Where the observer believes a vessel it has lost track of is, if it
+    /// has ever seen it
+    pub fn last_known_segment(&self, entity: Entity) -> Option<&Segment> {
+        self.model()
+            .last_known_position(self.observer?, entity)?
+            .segment()
+    }
+
+    /// The segment the observer sees `entity` on at the snapshot time, or
+    /// None if `entity` is hidden from it
+    pub fn try_segment(&self, entity: Entity) -> Option<&Segment> {
+        if let Some(orbitable_component) = self.model().try_orbitable_component(entity) {
+            if let OrbitableComponentPhysics::Orbit(orbit) = orbitable_component.physics() {
+                return Some(orbit);
+            };
+            panic!("Attempt to get segment of stationary orbitable")
+        }
+        if self.is_hidden(entity) {
+            return if self.use_last_known { self.last_known_segment(entity) } else { None };
+        }
+        if let Some(observer) = self.observer {
+            if !self.model().has_intel_for(observer, self.model().faction(entity)) {
+                return Some(self.model().path_component(entity).perceived_segment_at_time(self.time));
+            }
+        }
+        Some(self.model().path_component(entity).future_segment_at_time(self.time))
+    }
+
     pub fn segment_starting_now(&self, entity: Entity) -> &Segment {
         if let Some(orbitable_component) = self.model().try_orbitable_component(entity) {
             if let OrbitableComponentPhysics::Orbit(orbit) = orbitable_component.physics() {
@@ -30,7 +99,7 @@ impl Snapshot {
             panic!("Attempt to get segment of stationary orbitable")
         }
         if let Some(observer) = self.observer {
-            if !observer.has_intel_for(self.model().faction(entity)) {
+            if !self.model().has_intel_for(observer, self.model().faction(entity)) {
                 return self.model()
                     .path_component(entity)
                     .perceived_segment_starting_at_time(self.time)
@@ -80,8 +149,11 @@ impl StateQuery for Snapshot {
             };
             panic!("Attempt to get segment of stationary orbitable")
         }
+        if !self.is_detected(entity) {
+            return vec![];
+        }
         if let Some(observer) = self.observer {
-            if !observer.has_intel_for(self.model().faction(entity)) {
+            if !self.model().has_intel_for(observer, self.model().faction(entity)) {
                 return self.model().path_component(entity).perceived_segments();
             }
         }
@@ -124,19 +196,12 @@ impl StateQuery for Snapshot {
     /// will not be the snapshot time.
     /// This is because we'd have to clone the segment to make the adjustment so that it has 
     /// the correct current time, which could be extremely expensive.
+    /// # Panics
+    /// Panics if the entity is hidden from the observer. Use `try_segment`
+    /// or check `parent` first for vessels which may not be detected
     fn segment(&self, entity: Entity) -> &Segment {
-        if let Some(orbitable_component) = self.model().try_orbitable_component(entity) {
-            if let OrbitableComponentPhysics::Orbit(orbit) = orbitable_component.physics() {
-                return orbit;
-            };
-            panic!("Attempt to get segment of stationary orbitable")
-        }
-        if let Some(observer) = self.observer {
-            if !observer.has_intel_for(self.model().faction(entity)) {
-                return self.model().path_component(entity).perceived_segment_at_time(self.time);
-            }
-        }
-        self.model().path_component(entity).future_segment_at_time(self.time)
+        self.try_segment(entity)
+            .expect("Attempt to get segment of vessel which is hidden from the observer")
     }
 
     /// Returns the current segment at the snapshot time, but the current time within the segment
@@ -177,12 +242,12 @@ impl StateQuery for Snapshot {
                 return None;
             }
         }
-        Some(self.segment(entity).parent())
+        self.try_segment(entity).map(Segment::parent)
     }
 
     fn target(&self, entity: Entity) -> Option<Entity> {
         if let Some(observer) = self.observer {
-            if !observer.has_intel_for(self.model().faction(entity)) {
+            if !self.model().has_intel_for(observer, self.model().faction(entity)) {
                 return None;
             }
         }
@@ -257,7 +322,7 @@ impl StateQuery for Snapshot {
 
     fn mass(&self, entity: Entity) -> f64 {
         if let Some(observer) = self.observer {
-            assert!(observer.has_intel_for(self.model().faction(entity)));
+            assert!(self.model().has_intel_for(observer, self.model().faction(entity)));
         }
         if let Some(orbitable_component) = self.model().try_orbitable_component(entity) {
             return orbitable_component.mass();
@@ -267,7 +332,7 @@ impl StateQuery for Snapshot {
 
     fn fuel_kg(&self, entity: Entity) -> f64 {
         if let Some(observer) = self.observer {
-            assert!(observer.has_intel_for(self.model().faction(entity)));
+            assert!(self.model().has_intel_for(observer, self.model().faction(entity)));
         }
         if let Some(fuel_kg) = self.model().path_component(entity).fuel_kg_at_time(self.time) {
             return fuel_kg
@@ -277,7 +342,7 @@ impl StateQuery for Snapshot {
 
     fn end_fuel(&self, entity: Entity) -> Option<f64> {
         if let Some(observer) = self.observer {
-            if !observer.has_intel_for(self.model().faction(entity)){
+            if !self.model().has_intel_for(observer, self.model().faction(entity)){
                 return None;
             }
         }
@@ -291,7 +356,7 @@ impl StateQuery for Snapshot {
 
     fn end_dv(&self, entity: Entity) -> Option<f64> {
         if let Some(observer) = self.observer {
-            if !observer.has_intel_for(self.model().faction(entity)){
+            if !self.model().has_intel_for(observer, self.model().faction(entity)){
                 return None;
             }
         }
@@ -344,4 +409,12 @@ impl Model {
     pub fn snapshot_at_observe_maybe(&self, time: f64, observer: Option<Faction>) -> Snapshot {
         Snapshot::new(self, time, observer)
     }
+
+    pub fn snapshot_now_last_known(&self, observer: Faction) -> Snapshot {
+        Snapshot::new_with_last_known(self, self.time, observer)
+    }
+
+    pub fn snapshot_at_last_known(&self, time: f64, observer: Faction) -> Snapshot {
+        Snapshot::new_with_last_known(self, time, observer)
+    }
 }
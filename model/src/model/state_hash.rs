@@ -0,0 +1,388 @@
+use std::fmt::{self, Display};
+
+use serde::{ser, Serialize};
+
+use crate::{components::vessel_component::class::VesselClass, storage::entity_allocator::Entity};
+
+use super::Model;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Marks the end of a sequence or struct so that, for example, `[[1], [2]]`
+/// and `[[1, 2]]` do not hash the same
+const END: u8 = 0xff;
+
+/// FNV-1a, which unlike the standard library's hasher gives the same
+/// result on every platform and between runs. Anything serializable can be
+/// fed in through `hash`, which walks the value with serde instead of
+/// building an intermediate string
+pub(super) struct StateHasher(u64);
+
+impl StateHasher {
+    fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write(value.as_bytes());
+    }
+
+    fn write_entity(&mut self, entity: Entity) {
+        self.write_u64(entity.index() as u64);
+        self.write_u64(entity.generation() as u64);
+    }
+
+    /// # Panics
+    /// Panics if the value fails to serialize, which would also prevent the
+    /// model from being saved
+    pub(super) fn hash<T: Serialize + ?Sized>(&mut self, value: &T) {
+        value.serialize(&mut *self).expect("Failed to hash value");
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct StateHashError(String);
+
+impl Display for StateHashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for StateHashError {}
+
+impl ser::Error for StateHashError {
+    fn custom<T: Display>(message: T) -> Self {
+        Self(message.to_string())
+    }
+}
+
+/// Maps are hashed independently of their iteration order by summing the
+/// hashes of their entries
+pub(super) struct MapHasher<'a> {
+    parent: &'a mut StateHasher,
+    entry: StateHasher,
+    sum: u64,
+}
+
+impl<'a> ser::Serializer for &'a mut StateHasher {
+    type Ok = ();
+    type Error = StateHashError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = MapHasher<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), StateHashError> {
+        self.write_u8(u8::from(v));
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), StateHashError> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), StateHashError> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), StateHashError> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), StateHashError> {
+        self.write(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), StateHashError> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), StateHashError> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), StateHashError> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), StateHashError> {
+        self.write_u64(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), StateHashError> {
+        self.serialize_f64(f64::from(v))
+    }
+
+    /// Floats are hashed bit for bit, since even the smallest difference
+    /// will eventually grow into a visible one
+    fn serialize_f64(self, v: f64) -> Result<(), StateHashError> {
+        self.write_u64(v.to_bits());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), StateHashError> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), StateHashError> {
+        self.write_str(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), StateHashError> {
+        self.write_u64(v.len() as u64);
+        self.write(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), StateHashError> {
+        self.write_u8(0);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), StateHashError> {
+        self.write_u8(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), StateHashError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), StateHashError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str) -> Result<(), StateHashError> {
+        self.serialize_u32(variant_index)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), StateHashError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, variant_index: u32, _variant: &'static str, value: &T) -> Result<(), StateHashError> {
+        self.write_u64(u64::from(variant_index));
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, StateHashError> {
+        if let Some(len) = len {
+            self.write_u64(len as u64);
+        }
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, StateHashError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, StateHashError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self, StateHashError> {
+        self.write_u64(u64::from(variant_index));
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapHasher<'a>, StateHashError> {
+        Ok(MapHasher { parent: self, entry: StateHasher::new(), sum: 0 })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, StateHashError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self, StateHashError> {
+        self.write_u64(u64::from(variant_index));
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut StateHasher {
+    type Ok = ();
+    type Error = StateHashError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), StateHashError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), StateHashError> {
+        self.write_u8(END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut StateHasher {
+    type Ok = ();
+    type Error = StateHashError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), StateHashError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), StateHashError> {
+        self.write_u8(END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut StateHasher {
+    type Ok = ();
+    type Error = StateHashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), StateHashError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), StateHashError> {
+        self.write_u8(END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut StateHasher {
+    type Ok = ();
+    type Error = StateHashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), StateHashError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), StateHashError> {
+        self.write_u8(END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut StateHasher {
+    type Ok = ();
+    type Error = StateHashError;
+
+    /// Field names are hashed so that reordering or renaming fields is
+    /// noticed rather than silently producing the same hash
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), StateHashError> {
+        self.write_str(key);
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), StateHashError> {
+        self.write_u8(END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut StateHasher {
+    type Ok = ();
+    type Error = StateHashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), StateHashError> {
+        self.write_str(key);
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), StateHashError> {
+        self.write_u8(END);
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for MapHasher<'_> {
+    type Ok = ();
+    type Error = StateHashError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), StateHashError> {
+        self.entry = StateHasher::new();
+        key.serialize(&mut self.entry)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), StateHashError> {
+        value.serialize(&mut self.entry)?;
+        self.sum = self.sum.wrapping_add(self.entry.finish());
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), StateHashError> {
+        self.parent.write_u64(self.sum);
+        self.parent.write_u8(END);
+        Ok(())
+    }
+}
+
+impl Model {
+    /// A stable hash over the entire simulation state: every component
+    /// storage, the time, warp, factions, intel and timeline history.
+    /// Entities are visited in order of index, and unordered collections are
+    /// hashed independently of their iteration order, so two models in the
+    /// same state hash the same regardless of platform, run, or whether either
+    /// was loaded from a save
+    ///
+    /// Used to check that copies of the model being stepped separately have
+    /// not diverged, and to catch nondeterminism between runs
+    pub fn state_hash(&self) -> u64 {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("State hash");
+        let mut hasher = StateHasher::new();
+        hasher.hash(&self.time);
+        hasher.hash(&self.time_step);
+        hasher.hash(&self.warp);
+        hasher.hash(&self.force_paused);
+        hasher.hash(&self.seed);
+        hasher.hash(&self.factions);
+
+        let mut entities: Vec<Entity> = self.entity_allocator.entities().iter().copied().collect();
+        entities.sort_by_key(|entity| (entity.index(), entity.generation()));
+        hasher.write_u64(entities.len() as u64);
+        for entity in entities {
+            hasher.write_entity(entity);
+            hasher.hash(&self.name_components.try_get(entity));
+            hasher.hash(&self.orbitable_components.try_get(entity));
+            hasher.hash(&self.path_components.try_get(entity));
+            hasher.hash(&self.vessel_components.try_get(entity));
+        }
+
+        self.intel.hash(&mut hasher);
+        hasher.hash(&self.timeline_history);
+        hasher.write_u64(Self::class_definitions_hash());
+        hasher.finish()
+    }
+
+    /// A stable hash over the definition in use for every vessel class.
+    /// Definitions are loaded from files rather than stored in the model, so
+    /// two copies of the model only behave the same if this matches too
+    pub fn class_definitions_hash() -> u64 {
+        let mut hasher = StateHasher::new();
+        for class in VesselClass::ALL {
+            hasher.hash(&class.definition());
+        }
+        hasher.finish()
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::storage::entity_allocator::Entity;
+use crate::{components::vessel_component::{health::Subsystem, resource::ResourceType}, storage::entity_allocator::Entity};
 
 use super::Model;
 
@@ -21,6 +21,18 @@ pub enum StoryEvent {
     StartBurnAdjust,
     FireTorpedoAdjust,
     SetTarget { entity: Entity, target: Entity },
+    Impact(Entity),
+    Damaged { entity: Entity, damage: f64 },
+    SubsystemDisabled { entity: Entity, subsystem: Subsystem },
+    Destroyed(Entity),
+    TorpedoShotDown { torpedo: Entity, defender: Entity },
+    MiningStarted { entity: Entity, asteroid: Entity },
+    /// Sent when the vessel stops for any reason, including drifting away
+    /// or running out of room
+    MiningStopped { entity: Entity, asteroid: Entity },
+    DepositDepleted { asteroid: Entity, resource: ResourceType },
+    /// `entity` starts out docked to `station`
+    VesselBuilt { station: Entity, entity: Entity },
 }
 
 impl Model {
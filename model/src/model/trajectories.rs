@@ -8,8 +8,10 @@ use self::fast_solver::{apply_encounter, solver::find_next_encounter};
 
 use super::{state_query::StateQuery, Model, SEGMENTS_TO_PREDICT};
 
+mod atmosphere;
 mod encounter;
 mod fast_solver;
+mod surface;
 #[cfg(test)]
 mod test_cases;
 
@@ -20,6 +22,8 @@ impl Model {
     /// the final segment of entity's path MUST be an Orbit.
     /// Trajectory prediction is extremely complex, good luck if
     /// you need to modify this...
+    /// Prediction stops early if the path reaches a surface, in
+    /// which case the final segment is where the impact happens.
     /// Returns true is a new segment was added
     /// # Panics
     /// Panics if the last segment of the entity is a burn
@@ -40,7 +44,30 @@ impl Model {
         loop {
             match find_next_encounter(self, self.path_component(entity).end_orbit().unwrap(), entity, end_time) {
                 Ok(encounter) => {
-                    if let Some(encounter) = encounter {
+                    let mut end_orbit = self.path_component(entity).end_orbit().unwrap().clone();
+                    let encounter_time = encounter.as_ref().map_or(end_time, encounter::Encounter::time);
+                    if let Some(drag) = self.enter_atmosphere(&mut end_orbit, encounter_time) {
+                        trace!("Found atmosphere entrance at {}", drag.start_point().time());
+                        *self.path_component_mut(entity).end_segment_mut() = Segment::Orbit(end_orbit);
+                        if drag.hits_surface() {
+                            let time = drag.end_point().time();
+                            self.path_component_mut(entity).add_segment(Segment::Drag(drag));
+                            self.add_impact(entity, time);
+                            return true;
+                        }
+                        let orbit = Self::orbit_after_drag(&drag);
+                        self.path_component_mut(entity).add_segment(Segment::Drag(drag));
+                        self.path_component_mut(entity).add_segment(Segment::Orbit(orbit));
+                        segments += 1;
+                        if segments >= segment_count {
+                            break;
+                        }
+                    } else if let Some(time) = self.find_surface_impact(&end_orbit, encounter_time) {
+                        end_orbit.end_at(time);
+                        *self.path_component_mut(entity).end_segment_mut() = Segment::Orbit(end_orbit);
+                        self.add_impact(entity, time);
+                        return true;
+                    } else if let Some(encounter) = encounter {
                         trace!("Found encounter {encounter:?}");
                         apply_encounter(self, &encounter);
                         segments += 1;
@@ -69,22 +96,32 @@ impl Model {
     }
 
     pub fn recompute_trajectory(&mut self, entity: Entity) -> bool {
+        if self.retain_predicted_impact(entity) {
+            return false;
+        }
         // Add 1 because the final orbit will have duration 0
         let segments_to_predict = SEGMENTS_TO_PREDICT + 1 - self.path_component(entity).future_orbits_after_last_non_orbit().len();
         self.predict(entity, 1.0e10, segments_to_predict)
     }
 
     pub fn recompute_entire_trajectory(&mut self, entity: Entity) {
-        let current_segment = self.path_component(entity).current_segment();
-        let parent = current_segment.parent();
+        self.recompute_entire_trajectory_from(entity, self.time);
+    }
+
+    /// Replaces the whole future path with the trajectory `entity` follows
+    /// from `time` without any more burns, turns or guidance. This cuts off
+    /// whichever of those is in progress at `time`
+    pub fn recompute_entire_trajectory_from(&mut self, entity: Entity, time: f64) {
+        let segment = self.path_component(entity).future_segment_at_time(time);
+        let parent = segment.parent();
         let orbit = OrbitBuilder {
             parent,
-            mass: self.vessel_component(entity).mass(),
+            mass: segment.mass_at_time(time),
             parent_mass: self.mass(parent),
-            rotation: current_segment.current_rotation(),
-            position: current_segment.current_position(),
-            velocity: current_segment.current_velocity(),
-            time: self.time,
+            rotation: segment.rotation_at_time(time),
+            position: segment.position_at_time(time),
+            velocity: segment.velocity_at_time(time),
+            time,
         }.build();
 
         self.path_component_mut(entity).clear_future_segments();
@@ -131,7 +168,23 @@ impl Model {
 
         while segments.len() < SEGMENTS_TO_PREDICT + 1 {
             let last_orbit = segments.last_mut().unwrap().as_orbit_mut().unwrap();
-            let Some(orbit) = self.next_orbit(entity, last_orbit) else {
+            let next_orbit = self.next_orbit(entity, last_orbit);
+            // next_orbit has already ended the orbit at the encounter, so any entrance found comes first
+            if let Some(drag) = self.enter_atmosphere(last_orbit, last_orbit.end_point().time()) {
+                if drag.hits_surface() {
+                    segments.push(Segment::Drag(drag));
+                    break;
+                }
+                let orbit = Self::orbit_after_drag(&drag);
+                segments.push(Segment::Drag(drag));
+                segments.push(Segment::Orbit(orbit));
+                continue;
+            }
+            if let Some(time) = self.find_surface_impact(last_orbit, last_orbit.end_point().time()) {
+                last_orbit.end_at(time);
+                break;
+            }
+            let Some(orbit) = next_orbit else {
                 break;
             };
             segments.push(Segment::Orbit(orbit));
@@ -0,0 +1,60 @@
+use crate::{components::path_component::{drag::{builder::DragBuilder, Drag}, orbit::{builder::OrbitBuilder, Orbit}}, model::Model};
+
+use super::surface::find_descending_crossing;
+
+impl Model {
+    /// Returns the first time before `end_time` at which `orbit` enters
+    /// its parent's atmosphere, or the current time if it is already inside.
+    /// The current point is used rather than the start point so that an
+    /// orbit which is already underway never enters in the past
+    fn find_atmosphere_entrance(&self, orbit: &Orbit, end_time: f64) -> Option<f64> {
+        let parent = self.orbitable_component(orbit.parent());
+        let atmosphere = parent.atmosphere()?;
+        let atmosphere_radius = parent.radius() * (1.0 + atmosphere.height());
+        let start_time = orbit.current_point().time();
+        let start_distance = orbit.current_point().position().magnitude();
+
+        // Anything that has reached the surface is left for collision handling
+        if start_distance <= parent.radius() {
+            return None;
+        }
+
+        if start_distance < atmosphere_radius {
+            return Some(start_time);
+        }
+
+        find_descending_crossing(orbit, atmosphere_radius, end_time)
+    }
+
+    /// If `orbit` enters an atmosphere before `end_time`, ends `orbit`
+    /// at the entrance and returns the resulting drag segment
+    pub(super) fn enter_atmosphere(&self, orbit: &mut Orbit, end_time: f64) -> Option<Drag> {
+        let time = self.find_atmosphere_entrance(orbit, end_time)?;
+        orbit.end_at(time);
+        let parent = orbit.parent();
+        let orbitable_component = self.orbitable_component(parent);
+        Some(DragBuilder {
+            parent,
+            parent_mass: orbit.parent_mass(),
+            parent_radius: orbitable_component.radius(),
+            atmosphere: orbitable_component.atmosphere().unwrap().clone(),
+            mass: orbit.mass(),
+            rotation: orbit.rotation(),
+            time,
+            position: orbit.end_point().position(),
+            velocity: orbit.end_point().velocity(),
+        }.build())
+    }
+
+    pub(super) fn orbit_after_drag(drag: &Drag) -> Orbit {
+        OrbitBuilder {
+            parent: drag.parent(),
+            mass: drag.mass(),
+            parent_mass: drag.parent_mass(),
+            rotation: drag.rotation(),
+            position: drag.end_point().position(),
+            velocity: drag.end_point().velocity(),
+            time: drag.end_point().time(),
+        }.build()
+    }
+}
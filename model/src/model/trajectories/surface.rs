@@ -0,0 +1,83 @@
+use log::{error, trace};
+use transfer_window_common::numerical_methods::itp::itp;
+
+use crate::{components::{path_component::orbit::Orbit, vessel_component::timeline::{impact::ImpactEvent, TimelineEvent}}, model::Model, storage::entity_allocator::Entity};
+
+/// Returns the first time after the current point and before `end_time`
+/// at which `orbit` descends through `radius`. Expects the current point
+/// to be outside `radius`
+pub(super) fn find_descending_crossing(orbit: &Orbit, radius: f64, end_time: f64) -> Option<f64> {
+    let start_time = orbit.current_point().time();
+
+    // The first periapsis after the current point
+    let mut periapsis_time = orbit.first_periapsis_time();
+    let mut search_start_time = start_time;
+    if let Some(period) = orbit.period() {
+        periapsis_time += period * ((start_time - periapsis_time) / period).ceil();
+        search_start_time = f64::max(search_start_time, periapsis_time - period / 2.0);
+    }
+
+    if periapsis_time <= start_time || periapsis_time > end_time {
+        return None;
+    }
+
+    if orbit.point_at_time(periapsis_time).position().magnitude() >= radius {
+        return None;
+    }
+
+    let depth = |time: f64| radius - orbit.point_at_time(time).position().magnitude();
+    match itp(&depth, search_start_time, periapsis_time) {
+        Ok(time) => Some(time),
+        Err(err) => {
+            error!("Error while computing radius crossing: {}", err);
+            None
+        }
+    }
+}
+
+impl Model {
+    /// Returns the first time before `end_time` at which `orbit` hits
+    /// its parent's surface, or the current time if it is already below it
+    pub(super) fn find_surface_impact(&self, orbit: &Orbit, end_time: f64) -> Option<f64> {
+        let radius = self.orbitable_component(orbit.parent()).radius();
+        if orbit.current_point().position().magnitude() <= radius {
+            return Some(orbit.current_point().time());
+        }
+        find_descending_crossing(orbit, radius, end_time)
+    }
+
+    /// Prediction only checks orbit and drag segments for impacts. Burns,
+    /// turns and guidance belong to timeline events which can still be
+    /// adjusted or cancelled, so cutting them short at the surface would
+    /// leave those events pointing at segments that no longer exist. Instead,
+    /// they are checked here as they are flown
+    pub(crate) fn is_below_surface(&self, entity: Entity) -> bool {
+        let segment = self.path_component(entity).current_segment();
+        if segment.is_orbit() || segment.is_drag() {
+            return false;
+        }
+        segment.current_position().magnitude() <= self.orbitable_component(segment.parent()).radius()
+    }
+
+    pub(super) fn add_impact(&mut self, entity: Entity, time: f64) {
+        trace!("Found surface impact at {time}");
+        let event = TimelineEvent::Impact(ImpactEvent::new(entity, time));
+        self.vessel_component_mut(entity).timeline_mut().add(event);
+    }
+
+    /// Returns whether the path still ends in a previously predicted impact. If
+    /// the segments leading to the impact have since been removed (for example
+    /// by planning a burn before it), the impact is removed from the timeline
+    pub(super) fn retain_predicted_impact(&mut self, entity: Entity) -> bool {
+        let Some(impact) = self.try_vessel_component(entity)
+            .and_then(|vessel_component| vessel_component.timeline().last_event())
+            .and_then(|event| event.as_impact()) else {
+            return false;
+        };
+        if self.path_component(entity).end_segment().end_time() >= impact.time() {
+            return true;
+        }
+        self.vessel_component_mut(entity).timeline_mut().pop_last_event();
+        false
+    }
+}
@@ -63,6 +63,24 @@ impl EntityAllocator {
         self.free.push(entity.index);
     }
 
+    /// Whether `entity` was deallocated and its index has not been reused since
+    pub fn can_restore(&self, entity: Entity) -> bool {
+        self.entries.get(entity.index)
+            .is_some_and(|entry| !entry.is_allocated && entry.generation == entity.generation + 1)
+    }
+
+    /// Allocates `entity` again with the same generation, so that anything
+    /// still referring to it becomes valid again
+    /// # Panics
+    /// Panics if the entity cannot be restored
+    pub fn restore(&mut self, entity: Entity) {
+        assert!(self.can_restore(entity), "Attempt to restore an entity which has been reused");
+        self.entries[entity.index].is_allocated = true;
+        self.entries[entity.index].generation = entity.generation;
+        self.free.retain(|index| *index != entity.index);
+        self.entities.insert(entity);
+    }
+
     pub fn entities(&self) -> &HashSet<Entity> {
         &self.entities
     }
@@ -84,4 +102,22 @@ mod test {
         let e3 = allocator.allocate();
         assert!(e3.index == 0 && e3.generation == 1);
     }
+
+    #[test]
+    fn test_restore() {
+        let mut allocator = EntityAllocator::default();
+        let e1 = allocator.allocate();
+        let e2 = allocator.allocate();
+        allocator.deallocate(e1);
+        allocator.deallocate(e2);
+        assert!(allocator.can_restore(e1));
+        allocator.restore(e1);
+        assert!(allocator.entities().contains(&e1));
+
+        // e2's index is reused, so it can no longer be restored
+        let e3 = allocator.allocate();
+        assert!(e3.index == 1 && e3.generation == 1);
+        assert!(!allocator.can_restore(e2));
+        assert!(!allocator.can_restore(e3));
+    }
 }
\ No newline at end of file
@@ -1,6 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use crate::components::name_component::NameComponent;
 use crate::components::orbitable_component::atmosphere::Atmosphere;
 use crate::components::orbitable_component::builder::OrbitablePhysicsBuilder;
+use crate::components::orbitable_component::deposit::ResourceDeposit;
 use crate::components::orbitable_component::{OrbitableComponent, OrbitableType};
 use crate::components::path_component::orbit::builder::InitialOrbitBuilder;
 use crate::components::path_component::PathComponent;
@@ -43,9 +46,9 @@ impl EntityBuilder {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VesselBuilder {
-    pub name: &'static str,
+    pub name: String,
     pub vessel_component: VesselComponent,
     pub orbit_builder: InitialOrbitBuilder,
 }
@@ -54,7 +57,7 @@ impl VesselBuilder {
     pub fn build(self, model: &mut Model) -> Entity {
         let orbit = self.orbit_builder.build(model, self.vessel_component.mass());
         let entity = model.allocate(EntityBuilder::default()
-            .with_name_component(NameComponent::new(self.name.to_string()))
+            .with_name_component(NameComponent::new(self.name))
             .with_vessel_component(self.vessel_component)
             .with_path_component(PathComponent::new_with_orbit(orbit)));
         model.recompute_trajectory(entity);
@@ -72,6 +75,7 @@ pub struct OrbitableBuilder {
     pub type_: OrbitableType,
     pub physics: OrbitablePhysicsBuilder,
     pub atmosphere: Option<Atmosphere>,
+    pub deposits: Vec<ResourceDeposit>,
 }
 
 impl OrbitableBuilder {
@@ -85,7 +89,7 @@ impl OrbitableBuilder {
             self.type_,
             physics,
             self.atmosphere
-        );
+        ).with_deposits(self.deposits);
         model.allocate(EntityBuilder::default()
             .with_name_component(NameComponent::new(self.name.to_string()))
             .with_orbitable_component(orbitable_component))
@@ -1,4 +1,8 @@
+pub mod update_ai;
 pub mod update_guidance;
+pub mod update_intel;
+pub mod update_mining;
+pub mod update_shipyards;
 pub mod update_stations;
 mod update_target;
 pub mod update_time;
@@ -0,0 +1,142 @@
+use log::trace;
+
+use crate::{components::{vessel_component::{faction::Faction, timeline::{fire_torpedo::FireTorpedoEvent, start_burn::StartBurnEvent, start_guidance::StartGuidanceEvent, TimelineEvent}}, ComponentType}, model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
+
+/// How often (in game time) AI controlled vessels reconsider what to do
+const AI_UPDATE_INTERVAL: f64 = 60.0;
+/// Gap between the AI deciding on an action and the event taking place, so
+/// events are never created in the past
+const AI_REACTION_DELAY: f64 = 10.0;
+const TORPEDO_FIRE_RANGE: f64 = 500.0e3;
+/// Vessels which will pass within this distance of their target do not try
+/// to plan another intercept
+const ENGAGE_DISTANCE: f64 = 250.0e3;
+/// Planning an intercept is expensive, so only this many vessels may plan
+/// one per AI update. The rest try again at the next update
+const MAX_INTERCEPT_PLANS_PER_UPDATE: usize = 2;
+
+impl Model {
+    fn is_ai_update_due(&self, previous_time: f64) -> bool {
+        (previous_time / AI_UPDATE_INTERVAL).floor() != (self.time() / AI_UPDATE_INTERVAL).floor()
+    }
+
+    /// The nearest detected hostile vessel orbiting the same body, as perceived by the AI's faction
+    fn find_ai_target(&self, entity: Entity) -> Option<Entity> {
+        let faction = self.vessel_component(entity).faction();
+        let snapshot = self.snapshot_now_observe(faction);
+        let parent = snapshot.parent(entity)?;
+        self.entities(vec![ComponentType::VesselComponent, ComponentType::PathComponent])
+            .into_iter()
+            .filter(|other| {
+                let vessel_component = self.vessel_component(*other);
+                self.is_hostile_to(faction, vessel_component.faction())
+                    && !vessel_component.is_ghost()
+                    && !vessel_component.class().is_torpedo()
+                    && !vessel_component.class().is_projectile()
+                    && snapshot.is_detected(*other)
+                    && snapshot.parent(*other) == Some(parent)
+            })
+            .map(|other| (other, snapshot.distance(entity, other)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(other, _)| other)
+    }
+
+    fn update_ai_torpedo(&mut self, entity: Entity) {
+        let time = self.time() + AI_REACTION_DELAY;
+        if self.path_component(entity).end_guidance().is_some()
+                || !StartGuidanceEvent::can_create(self, entity, time) {
+            return;
+        }
+        trace!("AI enabling guidance for {entity:?}");
+        let event = TimelineEvent::StartGuidance(StartGuidanceEvent::new(self, entity, time));
+        self.add_event(entity, event);
+    }
+
+    fn update_ai_fire_torpedo(&mut self, entity: Entity, target: Entity) {
+        let time = self.time() + AI_REACTION_DELAY;
+        let faction = self.vessel_component(entity).faction();
+        if !FireTorpedoEvent::can_create_ever(self, entity)
+                || !FireTorpedoEvent::can_create(self, entity, time)
+                || !self.snapshot_at(time).segment(entity).is_orbit()
+                || self.snapshot_now_last_known(faction).parent(target).is_none()
+                || self.snapshot_now_last_known(faction).distance(entity, target) > TORPEDO_FIRE_RANGE {
+            return;
+        }
+        trace!("AI firing torpedo from {entity:?} at {target:?}");
+        let event = TimelineEvent::FireTorpedo(FireTorpedoEvent::new(self, entity, time));
+        self.add_event(entity, event);
+    }
+
+    fn update_ai_intercept(&mut self, entity: Entity, target: Entity, intercept_plans_remaining: &mut usize) {
+        let time = self.time() + AI_REACTION_DELAY;
+        if !StartBurnEvent::can_create_ever(self, entity)
+                || self.vessel_component(entity).timeline().last_blocking_event().is_some()
+                || !StartBurnEvent::can_create(self, entity, time) {
+            return;
+        }
+
+        let faction = self.vessel_component(entity).faction();
+        let closest_approach_distance = self.snapshot_now_observe(faction)
+            .find_next_closest_approach(entity, target)
+            .map(|closest_approach_time| self.snapshot_at_last_known(closest_approach_time, faction).distance(entity, target));
+        if closest_approach_distance.is_some_and(|distance| distance < ENGAGE_DISTANCE) {
+            return;
+        }
+
+        if *intercept_plans_remaining == 0 {
+            return;
+        }
+        let Some((_, max_flight_time)) = self.intercept_flight_time_range(entity, target, time) else {
+            return;
+        };
+        *intercept_plans_remaining -= 1;
+        let Some(plan) = self.plan_intercept(entity, target, time, time + max_flight_time) else {
+            trace!("AI failed to plan intercept for {entity:?}");
+            return;
+        };
+        trace!("AI planning intercept burn for {entity:?}");
+        let event = TimelineEvent::StartBurn(StartBurnEvent::new_with_delta_v(self, entity, plan.time(), plan.delta_v()));
+        self.add_event(entity, event);
+    }
+
+    fn update_ai_vessel(&mut self, entity: Entity, intercept_plans_remaining: &mut usize) {
+        let Some(target) = self.find_ai_target(entity) else {
+            return;
+        };
+        self.vessel_component_mut(entity).set_target(Some(target));
+        self.update_ai_fire_torpedo(entity, target);
+        self.update_ai_intercept(entity, target, intercept_plans_remaining);
+    }
+
+    /// Lets AI controlled vessels of non-player factions pick targets, plan
+    /// intercepts, fire torpedoes and guide them. Runs every `AI_UPDATE_INTERVAL`
+    /// of game time and only uses what the vessel's faction can observe
+    pub(crate) fn update_ai(&mut self, previous_time: f64) {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Update AI");
+        if !self.is_ai_update_due(previous_time) {
+            return;
+        }
+        // Sorted so that which vessel acts first (and gets to plan an
+        // intercept) does not depend on hash set ordering
+        let mut entities: Vec<Entity> = self.entities(vec![ComponentType::VesselComponent, ComponentType::PathComponent])
+            .into_iter()
+            .collect();
+        entities.sort_by_key(|entity| (entity.index(), entity.generation()));
+        let mut intercept_plans_remaining = MAX_INTERCEPT_PLANS_PER_UPDATE;
+        for entity in entities {
+            // The entity may have been destroyed by an earlier vessel's actions
+            let Some(vessel_component) = self.try_vessel_component(entity) else {
+                continue;
+            };
+            if !vessel_component.is_ai_controlled() || vessel_component.is_ghost() || vessel_component.faction() == Faction::PLAYER {
+                continue;
+            }
+            if vessel_component.class().is_torpedo() {
+                self.update_ai_torpedo(entity);
+            } else {
+                self.update_ai_vessel(entity, &mut intercept_plans_remaining);
+            }
+        }
+    }
+}
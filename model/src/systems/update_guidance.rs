@@ -1,6 +1,6 @@
 use log::trace;
 
-use crate::{components::{path_component::guidance::{will_intercept, Guidance}, vessel_component::timeline::{start_guidance::StartGuidanceEvent, TimelineEvent}, ComponentType}, model::{state_query::StateQuery, Model}};
+use crate::{components::{path_component::guidance::{will_intercept, Guidance}, vessel_component::timeline::{start_guidance::StartGuidanceEvent, TimelineEvent}, ComponentType}, model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
 
 impl Model {
     /// True if would have intercepted last frame but now will not intercept
@@ -8,10 +8,21 @@ impl Model {
         let target = guidance.target();
         let time = guidance.end_point().time();
         let faction = guidance.faction();
-        let end_distance = (guidance.end_point().position() - self.snapshot_at_observe(time, faction).position(target)).magnitude();
+        let snapshot = self.snapshot_at_last_known(time, faction);
+        if snapshot.parent(target).is_none() {
+            return guidance.will_intercept();
+        }
+        let end_distance = (guidance.end_point().position() - snapshot.position(target)).magnitude();
         guidance.will_intercept() && !will_intercept(end_distance)
     }
 
+    /// Cancels the intercept at the end of guidance and any point defense engagement before it
+    fn cancel_intercept_events(&mut self, entity: Entity) {
+        while self.vessel_component(entity).timeline().last_event().is_some_and(|event| event.is_intercept() || event.is_point_defense()) {
+            self.cancel_last_event(entity);
+        }
+    }
+
     /// Handles recalculation of guidance segments which had an intercept, but do not any longer.
     pub(crate) fn update_guidance(&mut self) {
         #[cfg(feature = "profiling")]
@@ -22,9 +33,7 @@ impl Model {
             };
 
             if self.docked(guidance.target()) {
-                if self.vessel_component(entity).timeline().last_event().is_some_and(|event| event.is_intercept()) {
-                    self.cancel_last_event(entity);
-                }
+                self.cancel_intercept_events(entity);
                 continue;
             }
 
@@ -32,8 +41,8 @@ impl Model {
                 continue;
             }
 
-            assert!(self.vessel_component(entity).timeline().last_event().unwrap().is_intercept());
-            self.cancel_last_event(entity);
+            assert!(self.vessel_component(entity).timeline().last_event().is_some_and(|event| event.is_intercept() || event.is_point_defense()));
+            self.cancel_intercept_events(entity);
 
             let on_guidance_segment_to_recalculate = self.time() >= self.path_component(entity).end_guidance().unwrap().start_point().time();
             if on_guidance_segment_to_recalculate {
@@ -0,0 +1,19 @@
+use crate::{components::{vessel_component::faction::Faction, ComponentType}, model::Model, storage::entity_allocator::Entity};
+
+impl Model {
+    pub(crate) fn update_intel(&mut self) {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Update intel");
+        self.expire_reveals();
+        let sun = self.sun();
+        let vessels: Vec<Entity> = self.entities(vec![ComponentType::VesselComponent, ComponentType::PathComponent])
+            .into_iter()
+            .filter(|entity| !self.vessel_component(*entity).is_ghost())
+            .collect();
+        let observers: Vec<Faction> = self.factions().collect();
+        for observer in observers {
+            let detected = self.compute_detected(observer, &vessels, sun);
+            self.set_detected(observer, &vessels, detected);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{components::ComponentType, model::{story_event::StoryEvent, Model}, storage::entity_allocator::Entity};
+
+impl Model {
+    fn mine(&mut self, entity: Entity, asteroid: Entity, dt: f64) {
+        let mut amount = self.vessel_component(entity).mining_rate() * self.time_step().time_step() * dt;
+        // The first deposit with room in the hold is used up before the next is started
+        for index in 0..self.orbitable_component(asteroid).deposits().len() {
+            let deposit = &self.orbitable_component(asteroid).deposits()[index];
+            let resource = deposit.resource();
+            if deposit.is_depleted() {
+                continue;
+            }
+
+            let space = self.vessel_component(entity).resource_space(resource);
+            let extracted = self.orbitable_component_mut(asteroid).deposits_mut()[index].extract(f64::min(amount, space));
+            let current = self.vessel_component(entity).resource_amount(resource);
+            self.vessel_component_mut(entity).set_resource_amount(resource, current + extracted);
+            amount -= extracted;
+
+            if self.orbitable_component(asteroid).deposits()[index].is_depleted() {
+                self.add_story_event(StoryEvent::DepositDepleted { asteroid, resource });
+            }
+            if amount < 1.0e-9 {
+                break;
+            }
+        }
+    }
+
+    pub(crate) fn update_mining(&mut self, dt: f64) {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Update mining");
+        // Sorted so that vessels mining the same asteroid always take their share in the same order
+        let mut entities: Vec<Entity> = self.entities(vec![ComponentType::VesselComponent]).into_iter().collect();
+        entities.sort_by_key(|entity| (entity.index(), entity.generation()));
+        for entity in entities {
+            let Some(asteroid) = self.vessel_component(entity).mining() else {
+                continue;
+            };
+            if self.can_mine(entity, asteroid) {
+                self.mine(entity, asteroid, dt);
+            } else {
+                self.stop_mining(entity);
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{components::{name_component::NameComponent, vessel_component::{class::VesselClass, VesselComponent}, ComponentType}, model::{story_event::StoryEvent, Model}, storage::{entity_allocator::Entity, entity_builder::EntityBuilder}};
+
+impl Model {
+    fn launch_built_vessel(&mut self, station: Entity, class: VesselClass) {
+        let faction = self.vessel_component(station).faction();
+        let entity = self.allocate(EntityBuilder::default()
+            .with_name_component(NameComponent::new(class.name().to_string()))
+            .with_vessel_component(VesselComponent::new(class, faction)));
+        let location = self.find_free_docking_port(station).unwrap();
+        self.vessel_component_mut(station).dock(location, entity);
+        self.add_story_event(StoryEvent::VesselBuilt { station, entity });
+    }
+
+    /// Time left over from finishing one order goes towards the next, so
+    /// several vessels can be finished in one update at high time steps
+    fn update_build_queue(&mut self, station: Entity, dt: f64) {
+        let mut dt = self.time_step().time_step() * dt;
+        while let Some(order) = self.vessel_component_mut(station).build_queue_mut().unwrap().front_mut() {
+            dt = order.step(dt);
+            let is_finished = order.is_finished();
+            if !is_finished || self.find_free_docking_port(station).is_none() {
+                break;
+            }
+            let order = self.vessel_component_mut(station).build_queue_mut().unwrap().remove(0);
+            self.launch_built_vessel(station, order.class());
+        }
+    }
+
+    pub(crate) fn update_shipyards(&mut self, dt: f64) {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Update shipyards");
+        // Sorted so that vessels built in the same update are always allocated in the same order
+        let mut entities: Vec<Entity> = self.entities(vec![ComponentType::VesselComponent]).into_iter().collect();
+        entities.sort_by_key(|entity| (entity.index(), entity.generation()));
+        for entity in entities {
+            if self.vessel_component(entity).build_queue().is_some_and(|build_queue| !build_queue.is_empty()) {
+                self.update_build_queue(entity, dt);
+            }
+        }
+    }
+}
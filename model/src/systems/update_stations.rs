@@ -1,60 +1,56 @@
-use crate::{components::{vessel_component::docking::{DockedVessel, DockingPortLocation, ResourceTransferDirection}, ComponentType}, model::Model, storage::entity_allocator::Entity};
+use crate::{components::{vessel_component::{docking::{DockingPortLocation, ResourceTransfer}, resource::{ResourceTransferRate, ResourceType}}, ComponentType}, model::Model, storage::entity_allocator::Entity};
 
 impl Model {
-    fn update_fuel_transfer(
+    fn move_resource(&mut self, from: Entity, to: Entity, resource: ResourceType, amount: f64) {
+        let from_amount = self.vessel_component(from).resource_amount(resource);
+        let to_amount = self.vessel_component(to).resource_amount(resource);
+        self.vessel_component_mut(from).set_resource_amount(resource, from_amount - amount);
+        self.vessel_component_mut(to).set_resource_amount(resource, to_amount + amount);
+    }
+
+    fn update_continuous_transfer(
         &mut self,
-        docked_vessel: &DockedVessel,
-        docked_entity: Entity, 
-        station_entity: Entity, 
-        location: DockingPortLocation, 
+        transfer: &ResourceTransfer,
+        station_entity: Entity,
+        location: DockingPortLocation,
+        units_per_second: f64,
         dt: f64
     ) {
-        let Some(transfer) = docked_vessel.fuel_transfer() else {
-            return 
-        };
-
-        let (from, to) = match transfer.direction() {
-            ResourceTransferDirection::FromDocked => (docked_entity, station_entity),
-            ResourceTransferDirection::ToDocked => (station_entity, docked_entity),
-        };
+        let resource = transfer.resource();
+        let (from, to) = self.transfer_endpoints(station_entity, location, transfer.direction());
 
-        let from_fuel = self.vessel_component(from).fuel_kg();
-        let to_fuel = self.vessel_component(to).fuel_kg();
-        let remaining_space = self.vessel_component(to).fuel_capacity_kg() - to_fuel;
-        let amount = f64::min(f64::min(dt * self.time_step().time_step() * transfer.rate(), from_fuel), remaining_space);
+        let available = self.vessel_component(from).resource_amount(resource);
+        let remaining_space = self.vessel_component(to).resource_space(resource);
+        let amount = f64::min(f64::min(dt * self.time_step().time_step() * units_per_second, available), remaining_space);
+        let amount = transfer.remaining().map_or(amount, |remaining| f64::min(amount, remaining));
 
         if amount.abs() < 1.0e-3 {
-            self.vessel_component_mut(station_entity).docking_port_mut(location).docked_vessel_mut().stop_fuel_transfer();
+            self.docking_port_mut(station_entity, location).docked_vessel_mut().stop_transfer(resource);
         } else {
-            self.vessel_component_mut(from).set_fuel_kg(from_fuel - amount);
-            self.vessel_component_mut(to).set_fuel_kg(to_fuel + amount);
+            self.move_resource(from, to, resource, amount);
+            self.docking_port_mut(station_entity, location).docked_vessel_mut().on_transferred(resource, amount);
         }
     }
 
-    fn update_torpedo_transfer(
+    fn update_discrete_transfer(
         &mut self,
-        docked_vessel: &DockedVessel, 
-        docked_entity: Entity, 
-        station_entity: Entity, 
-        location: DockingPortLocation, 
+        transfer: &ResourceTransfer,
+        station_entity: Entity,
+        location: DockingPortLocation,
+        seconds_per_unit: f64,
         dt: f64
     ) {
-        let Some(mut transfer) = docked_vessel.torpedo_transfer().cloned() else { 
-            return 
-        };
-
-        let (from, to) = match transfer.direction() {
-            ResourceTransferDirection::FromDocked => (docked_entity, station_entity),
-            ResourceTransferDirection::ToDocked => (station_entity, docked_entity),
-        };
+        let resource = transfer.resource();
+        let (from, to) = self.transfer_endpoints(station_entity, location, transfer.direction());
 
         let simulation_dt = self.time_step().time_step() * dt;
-        self.docking_port_mut(station_entity, location).docked_vessel_mut().step_torpedo_transfer(simulation_dt);
-        transfer = transfer.step(simulation_dt);
+        let mut transfer = transfer.clone();
+        self.step_transfer(station_entity, location, resource, simulation_dt);
+        transfer.step(simulation_dt);
 
         loop {
-            if self.vessel_component(from).is_torpedoes_empty() || self.vessel_component(to).is_torpedoes_full() {
-                self.docking_port_mut(station_entity, location).docked_vessel_mut().stop_torpedo_transfer();
+            if self.vessel_component(from).is_resource_empty(resource) || self.vessel_component(to).is_resource_full(resource) {
+                self.docking_port_mut(station_entity, location).docked_vessel_mut().stop_transfer(resource);
                 break;
             }
 
@@ -62,18 +58,31 @@ impl Model {
                 break;
             }
 
-            self.vessel_component_mut(from).decrement_torpedoes();
-            self.vessel_component_mut(to).increment_torpedoes();
+            self.move_resource(from, to, resource, 1.0);
+
+            self.step_transfer(station_entity, location, resource, -seconds_per_unit);
+            transfer.step(-seconds_per_unit);
+
+            self.docking_port_mut(station_entity, location).docked_vessel_mut().on_transferred(resource, 1.0);
+            if self.docking_port(station_entity, location).docked_vessel().transfer(resource).is_none() {
+                break;
+            }
+        }
+    }
 
-            self.docking_port_mut(station_entity, location).docked_vessel_mut().step_torpedo_transfer(-transfer.interval());
-            transfer = transfer.step(-transfer.interval());
+    fn step_transfer(&mut self, station_entity: Entity, location: DockingPortLocation, resource: ResourceType, dt: f64) {
+        if let Some(transfer) = self.docking_port_mut(station_entity, location).docked_vessel_mut().transfer_mut(resource) {
+            transfer.step(dt);
         }
     }
 
-    fn update_docking_port(&mut self, station_entity: Entity, location: DockingPortLocation, docked_vessel: &DockedVessel, dt: f64) {
-        let docked_entity = docked_vessel.entity();
-        self.update_fuel_transfer(docked_vessel, docked_entity, station_entity, location, dt);
-        self.update_torpedo_transfer(docked_vessel, docked_entity, station_entity, location, dt);
+    fn update_docking_port(&mut self, station_entity: Entity, location: DockingPortLocation, transfers: &[ResourceTransfer], dt: f64) {
+        for transfer in transfers {
+            match transfer.rate() {
+                ResourceTransferRate::Continuous { units_per_second } => self.update_continuous_transfer(transfer, station_entity, location, units_per_second, dt),
+                ResourceTransferRate::Discrete { seconds_per_unit } => self.update_discrete_transfer(transfer, station_entity, location, seconds_per_unit, dt),
+            }
+        }
     }
 
     pub(crate) fn update_stations(&mut self, dt: f64) {
@@ -81,7 +90,7 @@ impl Model {
             if let Some(docking_ports) = self.vessel_component(entity).docking_ports() {
                 for (location, docking_port) in docking_ports.clone() {
                     if docking_port.has_docked_vessel() {
-                        self.update_docking_port(entity, location, docking_port.docked_vessel(), dt);
+                        self.update_docking_port(entity, location, docking_port.docked_vessel().transfers(), dt);
                     }
                 }
             }
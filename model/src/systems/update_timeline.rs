@@ -21,7 +21,12 @@ impl Model {
     pub(crate) fn update_timeline(&mut self) {
         #[cfg(feature = "profiling")]
         let _span = tracy_client::span!("Update timeline");
-        for entity in self.entities(vec![ComponentType::VesselComponent, ComponentType::PathComponent]) {
+        // Stations go first, so that ports freed by undocks can be used by
+        // docks at the same time, whatever order the entities are in
+        let (stations, others): (Vec<_>, Vec<_>) = self.entities(vec![ComponentType::VesselComponent, ComponentType::PathComponent])
+            .into_iter()
+            .partition(|entity| self.vessel_component(*entity).has_docking());
+        for entity in stations.into_iter().chain(others) {
             self.update_entity_timeline(entity);
         }
     }
@@ -1,4 +1,4 @@
-use crate::{components::ComponentType, model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
+use crate::{components::{vessel_component::timeline::impact::ImpactEvent, ComponentType}, model::{state_query::StateQuery, Model}, storage::entity_allocator::Entity};
 
 impl Model {
     fn update_path_component(&mut self, entity: Entity, time: f64) {
@@ -12,7 +12,9 @@ impl Model {
         self.path_component_mut(entity).current_segment_mut().next(time);
 
         loop {
-            if !self.path_component(entity).current_segment().is_finished() {
+            // A path which ends in an impact has no segment after the last one; the
+            // impact event will remove the vessel on the next timeline update
+            if !self.path_component(entity).current_segment().is_finished() || self.path_component(entity).future_segments().len() == 1 {
                 break;
             }
 
@@ -20,6 +22,11 @@ impl Model {
             should_recompute_perceived_segments = should_recompute_perceived_segments || !self.path_component(entity).current_segment().is_orbit();
         }
 
+        if self.is_below_surface(entity) {
+            ImpactEvent::new(entity, time).execute(self);
+            return;
+        }
+
         if self.vessel_component(entity).should_recompute_trajectory() && self.recompute_trajectory(entity) {
             should_recompute_perceived_segments = true;
         }
@@ -32,7 +39,7 @@ impl Model {
             self.path_component_mut(entity).current_perceived_segment_mut().next(time);
             loop {
                 let current_segment = self.path_component(entity).current_perceived_segment();
-                if !current_segment.is_finished() {
+                if !current_segment.is_finished() || self.path_component(entity).perceived_segments().len() == 1 {
                     break;
                 }
 
@@ -26,6 +26,7 @@ pub fn sun(model: &mut Model) -> Entity {
         rotation_period: SUN_ROTATION_PERIOD,
         rotation_angle: 0.0,
         type_: OrbitableType::Star,
+        deposits: vec![],
         atmosphere: None,
         physics: OrbitablePhysicsBuilder::Stationary(vec2(0.0, 0.0)),
     }.build(model)
@@ -39,6 +40,7 @@ pub fn earth(model: &mut Model, sun: Entity) -> Entity {
         rotation_period: EARTH_ROTATION_PERIOD,
         rotation_angle: 0.0,
         type_: OrbitableType::Planet,
+        deposits: vec![],
         atmosphere: None,
         physics: OrbitablePhysicsBuilder::Orbit(InitialOrbitBuilder::Freeform { 
             parent: sun, 
@@ -58,6 +60,7 @@ pub fn moon(model: &mut Model, sun: Entity) -> Entity {
         rotation_period: MOON_ROTATION_PERIOD,
         rotation_angle: 0.0,
         type_: OrbitableType::Planet,
+        deposits: vec![],
         atmosphere: None,
         physics: OrbitablePhysicsBuilder::Orbit(InitialOrbitBuilder::Freeform { 
             parent: sun, 
@@ -71,8 +74,8 @@ pub fn moon(model: &mut Model, sun: Entity) -> Entity {
 
 pub fn station_leo(model: &mut Model, earth: Entity) -> Entity {
     VesselBuilder {
-        name: "Station",
-        vessel_component: VesselComponent::new(VesselClass::Station, Faction::Player),
+        name: "Station".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Station, Faction::PLAYER),
         orbit_builder: InitialOrbitBuilder::Circular {
             parent: earth ,
             distance: 0.01e9,
@@ -84,8 +87,8 @@ pub fn station_leo(model: &mut Model, earth: Entity) -> Entity {
 
 pub fn scout_leo(model: &mut Model, earth: Entity) -> Entity {
     VesselBuilder {
-        name: "Scout",
-        vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::Player),
+        name: "Scout".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
         orbit_builder: InitialOrbitBuilder::Circular {
             parent: earth ,
             distance: 0.011e9,
@@ -97,8 +100,8 @@ pub fn scout_leo(model: &mut Model, earth: Entity) -> Entity {
 
 pub fn test_ship_leo(model: &mut Model, earth: Entity) -> Entity {
     VesselBuilder {
-        name: "Test Ship",
-        vessel_component: VesselComponent::new(VesselClass::TestShip, Faction::Player),
+        name: "Test Ship".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::TestShip, Faction::PLAYER),
         orbit_builder: InitialOrbitBuilder::Circular {
             parent: earth ,
             distance: 0.012e9,
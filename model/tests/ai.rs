@@ -0,0 +1,68 @@
+use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, slots::{ModuleType, SlotType}, faction::Faction, timeline::TimelineEvent, VesselComponent}}, model::Model, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}, test_util};
+
+fn enemy_ship(model: &mut Model, earth: Entity, distance: f64, is_ai_controlled: bool, has_sensor: bool) -> Entity {
+    let mut vessel_component = VesselComponent::new(VesselClass::TestShip, Faction::ENEMY);
+    if is_ai_controlled {
+        vessel_component = vessel_component.with_ai_control();
+    }
+    if has_sensor {
+        let slot = vessel_component.slots().iter().position(|slot| slot.type_() == SlotType::Sensor).unwrap();
+        vessel_component = vessel_component.with_module(slot, ModuleType::SensorLongRange);
+    }
+    VesselBuilder {
+        name: "Enemy".to_string(),
+        vessel_component,
+        orbit_builder: InitialOrbitBuilder::Circular {
+            parent: earth,
+            distance,
+            angle: 0.0,
+            direction: OrbitDirection::AntiClockwise,
+        }
+    }.build(model)
+}
+
+#[test]
+fn test_ai_plans_intercept() {
+    let mut model = Model::default();
+
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let station = test_util::station_leo(&mut model, earth);
+    let enemy = enemy_ship(&mut model, earth, 0.011e9, true, true);
+
+    model.update(61.0);
+
+    assert_eq!(model.vessel_component(enemy).target(), Some(station));
+    assert!(model.vessel_component(enemy).timeline().events().iter().any(TimelineEvent::is_start_burn));
+}
+
+#[test]
+fn test_ai_fires_torpedo() {
+    let mut model = Model::default();
+
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let station = test_util::station_leo(&mut model, earth);
+    let enemy = enemy_ship(&mut model, earth, 0.0101e9, true, false);
+
+    model.update(61.0);
+
+    assert_eq!(model.vessel_component(enemy).target(), Some(station));
+    assert!(model.vessel_component(enemy).timeline().events().iter().any(TimelineEvent::is_fire_torpedo));
+}
+
+#[test]
+fn test_ai_ignores_uncontrolled_vessels() {
+    let mut model = Model::default();
+
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    test_util::station_leo(&mut model, earth);
+    let enemy = enemy_ship(&mut model, earth, 0.0101e9, false, false);
+
+    model.update(61.0);
+
+    assert_eq!(model.vessel_component(enemy).target(), None);
+    assert!(model.vessel_component(enemy).timeline().events().is_empty());
+}
+
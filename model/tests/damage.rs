@@ -0,0 +1,95 @@
+use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection, scary_math::GRAVITATIONAL_CONSTANT}, vessel_component::{class::VesselClass, faction::Faction, timeline::intercept::InterceptEvent, VesselComponent}}, model::{story_event::StoryEvent, Model}, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}, test_util::{self, EARTH_MASS}};
+
+const DISTANCE: f64 = 0.01e9;
+
+fn vessel(model: &mut Model, parent: Entity, class: VesselClass, faction: Faction, angle: f64, direction: OrbitDirection) -> Entity {
+    VesselBuilder {
+        name: "Vessel".to_string(),
+        vessel_component: VesselComponent::new(class, faction),
+        orbit_builder: InitialOrbitBuilder::Circular { parent, distance: DISTANCE, angle, direction },
+    }.build(model)
+}
+
+/// Detonates a torpedo next to `target` and returns the story events produced
+fn detonate(model: &mut Model, torpedo: Entity, target: Entity) -> Vec<StoryEvent> {
+    let time = model.time();
+    InterceptEvent::new(model, torpedo, target, time).execute(model);
+    model.update(0.01)
+}
+
+#[test]
+fn test_head_on_hit_destroys_vessel() {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let scout = vessel(&mut model, earth, VesselClass::Scout1, Faction::PLAYER, 0.0, OrbitDirection::AntiClockwise);
+    let torpedo = vessel(&mut model, earth, VesselClass::Torpedo, Faction::ENEMY, 0.0, OrbitDirection::Clockwise);
+
+    let story_events = detonate(&mut model, torpedo, scout);
+
+    assert!(model.try_vessel_component(torpedo).is_none());
+    assert!(model.try_vessel_component(scout).is_none());
+    assert!(story_events.iter().any(|event| matches!(event, StoryEvent::Destroyed(entity) if *entity == scout)));
+}
+
+#[test]
+fn test_glancing_hit_damages_vessel() {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let ship = vessel(&mut model, earth, VesselClass::TestShip, Faction::PLAYER, 0.0, OrbitDirection::AntiClockwise);
+    let torpedo = vessel(&mut model, earth, VesselClass::Torpedo, Faction::ENEMY, 0.0, OrbitDirection::AntiClockwise);
+
+    let story_events = detonate(&mut model, torpedo, ship);
+
+    assert!(model.try_vessel_component(torpedo).is_none());
+    let health = model.vessel_component(ship).health().unwrap();
+    assert!(health.hit_points() > 0.0);
+    assert!(health.hit_points() < health.max_hit_points());
+    // A small hit is not enough to knock out a subsystem
+    assert!(health.disabled_subsystems().is_empty());
+    assert!(story_events.iter().any(|event| matches!(event, StoryEvent::Damaged { entity, .. } if *entity == ship)));
+    assert!(!story_events.iter().any(|event| matches!(event, StoryEvent::Destroyed(_))));
+}
+
+#[test]
+fn test_heavy_hit_disables_subsystem() {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let ship = vessel(&mut model, earth, VesselClass::TestShip, Faction::PLAYER, 0.0, OrbitDirection::AntiClockwise);
+    // Overtakes the ship at 1km/s
+    let speed = f64::sqrt(GRAVITATIONAL_CONSTANT * EARTH_MASS / DISTANCE) + 1.0e3;
+    let torpedo = VesselBuilder {
+        name: "Torpedo".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Torpedo, Faction::ENEMY),
+        orbit_builder: InitialOrbitBuilder::Freeform { parent: earth, distance: DISTANCE, angle: 0.0, direction: OrbitDirection::AntiClockwise, speed },
+    }.build(&mut model);
+
+    detonate(&mut model, torpedo, ship);
+
+    let vessel_component = model.vessel_component(ship);
+    assert_eq!(vessel_component.health().unwrap().disabled_subsystems().len(), 1);
+    let subsystem = *vessel_component.health().unwrap().disabled_subsystems().first().unwrap();
+    assert!(vessel_component.is_subsystem_disabled(subsystem));
+    assert!(vessel_component.health().unwrap().hit_points() > 0.0);
+}
+
+#[test]
+fn test_proximity_damage() {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let target = vessel(&mut model, earth, VesselClass::Scout1, Faction::PLAYER, 0.0, OrbitDirection::AntiClockwise);
+    // About 1km from the target
+    let nearby = vessel(&mut model, earth, VesselClass::TestShip, Faction::PLAYER, 1.0e3 / DISTANCE, OrbitDirection::AntiClockwise);
+    let far = vessel(&mut model, earth, VesselClass::TestShip, Faction::PLAYER, 1.0, OrbitDirection::AntiClockwise);
+    let torpedo = vessel(&mut model, earth, VesselClass::Torpedo, Faction::ENEMY, 0.0, OrbitDirection::AntiClockwise);
+
+    detonate(&mut model, torpedo, target);
+
+    let nearby_health = model.vessel_component(nearby).health().unwrap();
+    assert!(nearby_health.hit_points() < nearby_health.max_hit_points());
+    let far_health = model.vessel_component(far).health().unwrap();
+    test_util::assert_float_equal(far_health.hit_points(), far_health.max_hit_points(), 1.0e-6);
+}
@@ -0,0 +1,169 @@
+use nalgebra_glm::vec2;
+use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, faction::Faction, slots::{ModuleType, SlotType}, timeline::{fire_gun::FireGunEvent, TimelineEvent}, VesselComponent}, ComponentType}, model::{state_query::StateQuery, Model}, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}, test_util};
+
+const DISTANCE: f64 = 0.01e9;
+
+fn with_module(vessel_component: VesselComponent, slot_type: SlotType, module: ModuleType) -> VesselComponent {
+    let slot = vessel_component.slots().iter().position(|slot| slot.type_() == slot_type).unwrap();
+    vessel_component.with_module(slot, module)
+}
+
+/// Creates a player ship and an enemy ship orbiting earth, with the enemy
+/// `separation` metres ahead of the player in the given direction
+fn setup(player: VesselComponent, enemy: VesselComponent, separation: f64, enemy_direction: OrbitDirection) -> (Model, Entity, Entity) {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+
+    let player = VesselBuilder {
+        name: "Player".to_string(),
+        vessel_component: player,
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: DISTANCE, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+    }.build(&mut model);
+
+    let enemy = VesselBuilder {
+        name: "Enemy".to_string(),
+        vessel_component: enemy,
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: DISTANCE, angle: separation / DISTANCE, direction: enemy_direction },
+    }.build(&mut model);
+
+    model.update(0.01);
+    (model, player, enemy)
+}
+
+#[test]
+fn test_nearby_vessel_detected() {
+    let player = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let enemy = VesselComponent::new(VesselClass::Scout1, Faction::ENEMY);
+    let (model, player, enemy) = setup(player, enemy, 100.0e3, OrbitDirection::AntiClockwise);
+
+    assert!(model.is_detected(Faction::PLAYER, enemy));
+    assert!(model.is_detected(Faction::ENEMY, player));
+    assert!(!model.snapshot_now_observe(Faction::PLAYER).future_segments(enemy).is_empty());
+}
+
+#[test]
+fn test_distant_vessel_hidden() {
+    let player = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let enemy = VesselComponent::new(VesselClass::Scout1, Faction::ENEMY);
+    let (model, player, enemy) = setup(player, enemy, 8.0e6, OrbitDirection::AntiClockwise);
+
+    assert!(!model.is_detected(Faction::PLAYER, enemy));
+    assert!(!model.is_detected(Faction::ENEMY, player));
+    assert!(model.is_detected(Faction::PLAYER, player));
+    assert!(model.is_detected(Faction::ALLY, player));
+    assert!(model.snapshot_now_observe(Faction::PLAYER).future_segments(enemy).is_empty());
+    assert!(!model.snapshot_now().future_segments(enemy).is_empty());
+}
+
+#[test]
+fn test_sensor_extends_range() {
+    let player = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let player = with_module(player, SlotType::Sensor, ModuleType::SensorLongRange);
+    let enemy = VesselComponent::new(VesselClass::Scout1, Faction::ENEMY);
+    let (model, player, enemy) = setup(player, enemy, 2.0e6, OrbitDirection::AntiClockwise);
+
+    assert!(model.is_detected(Faction::PLAYER, enemy));
+    assert!(!model.is_detected(Faction::ENEMY, player));
+}
+
+#[test]
+fn test_last_known_position_recorded() {
+    let player = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let enemy = VesselComponent::new(VesselClass::Scout1, Faction::ENEMY);
+    let (mut model, _, enemy) = setup(player, enemy, 100.0e3, OrbitDirection::Clockwise);
+    assert!(model.is_detected(Faction::PLAYER, enemy));
+    assert!(model.last_known_positions(Faction::PLAYER).is_empty());
+
+    while model.is_detected(Faction::PLAYER, enemy) {
+        model.update(1.0);
+        assert!(model.time() < 1000.0, "Vessels never moved out of range");
+    }
+
+    let last_known = &model.last_known_positions(Faction::PLAYER)[0];
+    assert_eq!(last_known.entity(), enemy);
+    assert!(model.time() - last_known.time() < 1.0e-3);
+    assert!((model.snapshot_now().position(enemy) - last_known.position()).magnitude() < 1.0e3);
+}
+
+#[test]
+fn test_firing_reveals_shooter() {
+    let player = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let enemy = VesselComponent::new(VesselClass::TestShip, Faction::ENEMY);
+    let enemy = with_module(enemy, SlotType::Weapon, ModuleType::KineticGun);
+    let (mut model, player, enemy) = setup(player, enemy, 8.0e6, OrbitDirection::AntiClockwise);
+    assert!(!model.is_detected(Faction::PLAYER, enemy));
+
+    model.vessel_component_mut(enemy).set_target(Some(player));
+    let time = model.time() + 1.0;
+    let event = FireGunEvent::new(&mut model, enemy, time);
+    model.add_event(enemy, TimelineEvent::FireGun(event));
+    while model.time() < time + 1.0 {
+        model.update(1.0);
+    }
+
+    assert!(model.is_detected(Faction::PLAYER, enemy));
+}
+
+#[test]
+fn test_detection_evaluated_at_snapshot_time() {
+    let player = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let enemy = VesselComponent::new(VesselClass::Scout1, Faction::ENEMY);
+    let (model, _, enemy) = setup(player, enemy, 8.0e6, OrbitDirection::Clockwise);
+    assert!(!model.snapshot_now_observe(Faction::PLAYER).is_detected(enemy));
+
+    // The vessels are on opposite orbits, so they pass each other
+    let passes = (1..200)
+        .map(|i| model.time() + f64::from(i) * 10.0)
+        .any(|time| model.snapshot_at_observe(time, Faction::PLAYER).is_detected(enemy));
+    assert!(passes);
+}
+
+#[test]
+fn test_undetected_vessel_uses_last_known_orbit() {
+    let player = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let enemy = VesselComponent::new(VesselClass::Scout1, Faction::ENEMY);
+    let (mut model, _, enemy) = setup(player, enemy, 100.0e3, OrbitDirection::Clockwise);
+    while model.is_detected(Faction::PLAYER, enemy) {
+        model.update(1.0);
+    }
+
+    // The player does not see the burn, so still expects the enemy to be on its old orbit
+    let burn_time = model.time() + 10.0;
+    model.create_burn(enemy, burn_time, vec2(500.0, 0.0));
+    let time = model.path_component(enemy).end_segment().start_time() + 600.0;
+    let last_known = model.last_known_position(Faction::PLAYER, enemy).unwrap().segment().unwrap().position_at_time(time);
+    let observed = model.snapshot_at_last_known(time, Faction::PLAYER);
+    assert!(!observed.is_detected(enemy));
+    assert!((observed.position(enemy) - last_known).magnitude() < 1.0e-3);
+    assert!((model.snapshot_at(time).position(enemy) - last_known).magnitude() > 1.0e3);
+    assert!(observed.entities(vec![ComponentType::VesselComponent]).iter().all(|entity| *entity != enemy));
+}
+
+#[test]
+fn test_undetected_vessel_hidden_from_observed_snapshot() {
+    let player = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let enemy = VesselComponent::new(VesselClass::Scout1, Faction::ENEMY);
+    let (mut model, _, enemy) = setup(player, enemy, 100.0e3, OrbitDirection::Clockwise);
+    while model.is_detected(Faction::PLAYER, enemy) {
+        model.update(1.0);
+    }
+
+    let observed = model.snapshot_now_observe(Faction::PLAYER);
+    assert!(observed.parent(enemy).is_none());
+    assert!(observed.try_segment(enemy).is_none());
+    assert!(observed.last_known_segment(enemy).is_some());
+    assert!(model.snapshot_now_last_known(Faction::PLAYER).parent(enemy).is_some());
+}
+
+#[test]
+fn test_never_detected_vessel_hidden_from_last_known_snapshot() {
+    let player = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let enemy = VesselComponent::new(VesselClass::Scout1, Faction::ENEMY);
+    let (model, _, enemy) = setup(player, enemy, 8.0e6, OrbitDirection::AntiClockwise);
+
+    let observed = model.snapshot_now_last_known(Faction::PLAYER);
+    assert!(observed.parent(enemy).is_none());
+    assert!(observed.try_segment(enemy).is_none());
+    assert!(observed.last_known_segment(enemy).is_none());
+}
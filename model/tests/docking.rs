@@ -0,0 +1,272 @@
+use nalgebra_glm::vec2;
+use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, docking::ResourceTransferDirection, faction::Faction, resource::ResourceType, timeline::{dock::DockEvent, start_burn::StartBurnEvent, transfer::TransferEvent, undock::UndockEvent, TimelineEvent}, VesselComponent}}, model::{state_query::StateQuery, Model}, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}, test_util::{self, assert_float_equal}};
+
+/// A scout docked to a station orbiting earth
+fn setup() -> (Model, Entity, Entity) {
+    setup_with(VesselClass::Scout1)
+}
+
+fn setup_with(class: VesselClass) -> (Model, Entity, Entity) {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let station = test_util::station_leo(&mut model, earth);
+    let scout = VesselBuilder {
+        name: "Scout".to_string(),
+        vessel_component: VesselComponent::new(class, Faction::PLAYER),
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.01e9, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+    }.build(&mut model);
+    model.update(0.01);
+
+    model.vessel_component_mut(scout).set_target(Some(station));
+    model.dock(station, scout);
+    (model, station, scout)
+}
+
+#[test]
+fn test_scheduled_undock() {
+    let (mut model, station, scout) = setup();
+    let time = model.time() + 100.0;
+    let delta_v = vec2(2.0, 0.0);
+    assert!(UndockEvent::can_create(&model, station, scout, time));
+    model.add_event(station, TimelineEvent::Undock(UndockEvent::new(station, scout, time, delta_v)));
+
+    // Only one undock can be planned for each vessel
+    assert!(!UndockEvent::can_create(&model, station, scout, time + 100.0));
+
+    model.update(99.0);
+    assert!(model.docked(scout));
+
+    model.update(2.0);
+    assert!(!model.docked(scout));
+    assert!(model.scheduled_undock(station, scout).is_none());
+    assert_float_equal(model.relative_speed(scout, station), delta_v.norm(), 1.0e-3);
+}
+
+#[test]
+fn test_manual_undock_cancels_scheduled_undock() {
+    let (mut model, station, scout) = setup();
+    let time = model.time() + 100.0;
+    model.add_event(station, TimelineEvent::Undock(UndockEvent::new(station, scout, time, vec2(1.0, 0.0))));
+
+    model.undock(station, scout);
+    assert!(model.scheduled_undock(station, scout).is_none());
+    assert!(model.vessel_component(station).timeline().events().is_empty());
+}
+
+#[test]
+fn test_scheduled_dock() {
+    let (mut model, station, scout) = setup();
+    model.undock_with_delta_v(station, scout, model.time(), vec2(0.5, 0.0));
+    model.update(1.0);
+
+    // Drifting apart too slowly to leave docking range for a while, but
+    // long after that the orbits are far apart
+    let time = model.time() + 20.0;
+    assert!(DockEvent::can_create_ever(&model, scout, station));
+    assert!(DockEvent::can_create(&model, scout, station, time));
+    assert!(!DockEvent::can_create(&model, scout, station, model.time() + 5.0e3));
+
+    model.add_event(scout, TimelineEvent::Dock(DockEvent::new(scout, station, time)));
+
+    // Nothing can be planned after docking
+    assert!(!StartBurnEvent::can_create(&model, scout, time + 10.0));
+    assert!(!DockEvent::can_create(&model, scout, station, time + 10.0));
+
+    model.update(19.0);
+    assert!(!model.docked(scout));
+
+    model.update(2.0);
+    assert!(model.docked(scout));
+    assert_eq!(model.find_station_docked_to(scout), Some(station));
+}
+
+#[test]
+fn test_scheduled_dock_needs_free_port_at_time() {
+    let (mut model, station, scout) = setup();
+    let earth = model.parent(station).unwrap();
+    let scout_alongside = |model: &mut Model| VesselBuilder {
+        name: "Scout".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.01e9, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+    }.build(model);
+    let other_docked = scout_alongside(&mut model);
+    model.vessel_component_mut(other_docked).set_target(Some(station));
+    model.dock(station, other_docked);
+    let arriving = scout_alongside(&mut model);
+    let rival = scout_alongside(&mut model);
+
+    let time = model.time() + 20.0;
+    assert!(!model.can_dock_at(arriving, station, time));
+
+    // A port is freed by a scheduled undock, but only once it has happened
+    model.add_event(station, TimelineEvent::Undock(UndockEvent::new(station, scout, model.time() + 10.0, vec2(1.0, 0.0))));
+    assert!(model.can_dock_at(arriving, station, time));
+    assert!(!model.can_dock_at(arriving, station, model.time() + 5.0));
+
+    // Which is then taken by another vessel's scheduled dock
+    model.add_event(rival, TimelineEvent::Dock(DockEvent::new(rival, station, model.time() + 15.0)));
+    assert!(model.can_dock_at(arriving, station, model.time() + 12.0));
+    assert!(!model.can_dock_at(arriving, station, time));
+}
+
+#[test]
+fn test_undock_and_dock_in_same_update() {
+    let (mut model, station, scout) = setup();
+    let earth = model.parent(station).unwrap();
+    let scout_alongside = |model: &mut Model| VesselBuilder {
+        name: "Scout".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.01e9, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+    }.build(model);
+    let other_docked = scout_alongside(&mut model);
+    model.vessel_component_mut(other_docked).set_target(Some(station));
+    model.dock(station, other_docked);
+    let arriving = scout_alongside(&mut model);
+
+    model.add_event(station, TimelineEvent::Undock(UndockEvent::new(station, scout, model.time() + 10.0, vec2(1.0, 0.0))));
+    model.add_event(arriving, TimelineEvent::Dock(DockEvent::new(arriving, station, model.time() + 12.0)));
+
+    // Both happen in one update, but the port is freed before it is needed
+    model.update(20.0);
+    assert!(!model.docked(scout));
+    assert!(model.docked(arriving));
+}
+
+#[test]
+fn test_undock_during_large_time_step() {
+    let (mut model, station, scout) = setup();
+    let (mut reference, _, _) = setup();
+    let time = model.time() + 10.0;
+    for model in [&mut model, &mut reference] {
+        model.add_event(station, TimelineEvent::Undock(UndockEvent::new(station, scout, time, vec2(1.0, 0.0))));
+    }
+
+    // Leaves from where the station was at the undock time, not at the end of the step
+    model.update(100.0);
+    reference.update(10.5);
+    reference.update(89.5);
+    assert!((model.position(scout) - reference.position(scout)).norm() < 1.0);
+}
+
+#[test]
+fn test_fuel_transfer_with_amount() {
+    let (mut model, station, scout) = setup();
+    let fuel_kg = model.vessel_component(scout).fuel_kg() - 2000.0;
+    model.vessel_component_mut(scout).set_fuel_kg(fuel_kg);
+
+    model.start_transfer(station, scout, ResourceType::Fuel, ResourceTransferDirection::ToDocked, Some(500.0));
+    let location = model.find_docking_port(station, scout).unwrap();
+    let completion_time = model.transfer_completion_time(station, location, ResourceType::Fuel).unwrap();
+    assert_float_equal(completion_time - model.time(), 500.0 / 0.1, 1.0e-6);
+
+    while model.time() < completion_time + 100.0 {
+        model.update(100.0);
+    }
+    assert!(model.docking_port(station, location).docked_vessel().transfer(ResourceType::Fuel).is_none());
+    assert_float_equal(model.vessel_component(scout).fuel_kg(), fuel_kg + 500.0, 1.0e-6);
+}
+
+#[test]
+fn test_torpedo_transfer_with_amount() {
+    let (mut model, station, ship) = setup_with(VesselClass::TestShip);
+    for _ in 0..3 {
+        model.vessel_component_mut(ship).decrement_torpedoes();
+    }
+    let torpedoes = model.vessel_component(ship).torpedoes();
+
+    model.start_transfer(station, ship, ResourceType::Torpedoes, ResourceTransferDirection::ToDocked, Some(2.0));
+    let location = model.find_docking_port(station, ship).unwrap();
+    let completion_time = model.transfer_completion_time(station, location, ResourceType::Torpedoes).unwrap();
+    assert_float_equal(completion_time - model.time(), 2.0 * 1800.0, 1.0e-6);
+
+    while model.time() < completion_time + 100.0 {
+        model.update(100.0);
+    }
+    assert!(model.docking_port(station, location).docked_vessel().transfer(ResourceType::Torpedoes).is_none());
+    assert_eq!(model.vessel_component(ship).torpedoes(), torpedoes + 2);
+}
+
+#[test]
+fn test_torpedo_transfer_of_nothing() {
+    let (mut model, station, ship) = setup_with(VesselClass::TestShip);
+    model.vessel_component_mut(ship).decrement_torpedoes();
+    let torpedoes = model.vessel_component(ship).torpedoes();
+
+    // Less than one torpedo rounds down to nothing
+    for amount in [0.0, 0.5] {
+        model.start_transfer(station, ship, ResourceType::Torpedoes, ResourceTransferDirection::ToDocked, Some(amount));
+        let location = model.find_docking_port(station, ship).unwrap();
+        assert!(model.docking_port(station, location).docked_vessel().transfer(ResourceType::Torpedoes).is_none());
+    }
+
+    for _ in 0..40 {
+        model.update(100.0);
+    }
+    assert_eq!(model.vessel_component(ship).torpedoes(), torpedoes);
+}
+
+#[test]
+fn test_scheduled_transfer() {
+    let (mut model, station, scout) = setup();
+    let fuel_kg = model.vessel_component(scout).fuel_kg() - 2000.0;
+    model.vessel_component_mut(scout).set_fuel_kg(fuel_kg);
+
+    let time = model.time() + 100.0;
+    assert!(TransferEvent::can_create(&model, station, scout, time));
+    let event = TransferEvent::new(station, scout, time, ResourceType::Fuel, ResourceTransferDirection::ToDocked, None);
+    model.add_event(station, TimelineEvent::Transfer(event));
+
+    let location = model.find_docking_port(station, scout).unwrap();
+    model.update(99.0);
+    assert!(model.transfer_completion_time(station, location, ResourceType::Fuel).is_none());
+    model.update(2.0);
+    let completion_time = model.transfer_completion_time(station, location, ResourceType::Fuel).unwrap();
+    assert!(completion_time > model.time());
+}
+
+#[test]
+fn test_cargo_transfer() {
+    let (mut model, station, ship) = setup_with(VesselClass::TestShip);
+    model.vessel_component_mut(station).set_resource_amount(ResourceType::Metal, 1000.0);
+    let mass = model.vessel_component(ship).mass();
+    let dv = model.vessel_component(ship).dv();
+
+    let location = model.find_docking_port(station, ship).unwrap();
+    // Only the station has any cargo, and only metal
+    assert!(model.can_transfer_to_docked(station, location, ResourceType::Metal));
+    assert!(!model.can_transfer_from_docked(station, location, ResourceType::Metal));
+    assert!(!model.can_transfer_to_docked(station, location, ResourceType::Oil));
+
+    model.start_transfer(station, ship, ResourceType::Metal, ResourceTransferDirection::ToDocked, Some(300.0));
+    let completion_time = model.transfer_completion_time(station, location, ResourceType::Metal).unwrap();
+    assert_float_equal(completion_time - model.time(), 300.0, 1.0e-6);
+
+    while model.time() < completion_time + 100.0 {
+        model.update(100.0);
+    }
+    assert!(model.docking_port(station, location).docked_vessel().transfer(ResourceType::Metal).is_none());
+    assert_float_equal(model.vessel_component(station).resource_amount(ResourceType::Metal), 700.0, 1.0e-6);
+    assert_float_equal(model.vessel_component(ship).resource_amount(ResourceType::Metal), 300.0, 1.0e-6);
+
+    // Cargo is carried through burns like any other mass
+    assert_float_equal(model.vessel_component(ship).mass(), mass + 300.0, 1.0e-6);
+    assert!(model.vessel_component(ship).dv() < dv);
+}
+
+#[test]
+fn test_cargo_transfer_stops_when_hold_is_full() {
+    let (mut model, station, ship) = setup_with(VesselClass::TestShip);
+    let space = model.vessel_component(ship).resource_space(ResourceType::Oil);
+    model.vessel_component_mut(station).set_resource_amount(ResourceType::Oil, space + 1000.0);
+
+    model.start_transfer(station, ship, ResourceType::Oil, ResourceTransferDirection::ToDocked, None);
+    let location = model.find_docking_port(station, ship).unwrap();
+    let completion_time = model.transfer_completion_time(station, location, ResourceType::Oil).unwrap();
+    while model.time() < completion_time + 100.0 {
+        model.update(100.0);
+    }
+    assert!(model.docking_port(station, location).docked_vessel().transfer(ResourceType::Oil).is_none());
+    assert!(model.vessel_component(ship).is_resource_full(ResourceType::Oil));
+    assert_float_equal(model.vessel_component(station).resource_amount(ResourceType::Oil), 1000.0, 1.0e-3);
+}
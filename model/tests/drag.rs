@@ -0,0 +1,51 @@
+use ecolor::Color32;
+use nalgebra_glm::vec2;
+use transfer_window_model::{components::{orbitable_component::{atmosphere::Atmosphere, builder::OrbitablePhysicsBuilder, OrbitableType}, path_component::{orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection, scary_math::GRAVITATIONAL_CONSTANT}, segment::Segment}, vessel_component::{class::VesselClass, faction::Faction, VesselComponent}}, model::Model, storage::entity_builder::{OrbitableBuilder, VesselBuilder}, test_util::{EARTH_MASS, EARTH_RADIUS, EARTH_ROTATION_PERIOD}};
+
+#[test]
+fn test_low_periapsis_decays_orbit() {
+    let mut model = Model::default();
+
+    let earth = OrbitableBuilder {
+        name: "Earth",
+        mass: EARTH_MASS,
+        radius: EARTH_RADIUS,
+        rotation_period: EARTH_ROTATION_PERIOD,
+        rotation_angle: 0.0,
+        type_: OrbitableType::Planet,
+        deposits: vec![],
+        physics: OrbitablePhysicsBuilder::Stationary(vec2(0.0, 0.0)),
+        atmosphere: Atmosphere::new_some(Color32::WHITE, 0.95, 0.3, 4.0, vec![]),
+    }.build(&mut model);
+
+    // Start at apoapsis with a periapsis about halfway through the atmosphere
+    let apoapsis = 2.0e7;
+    let periapsis = EARTH_RADIUS * 1.15;
+    let mu = GRAVITATIONAL_CONSTANT * EARTH_MASS;
+    let speed = f64::sqrt(mu * 2.0 * periapsis / (apoapsis * (apoapsis + periapsis)));
+    let vessel = VesselBuilder {
+        name: "Vessel".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
+        orbit_builder: InitialOrbitBuilder::Freeform { parent: earth, distance: apoapsis, angle: 0.0, direction: OrbitDirection::AntiClockwise, speed },
+    }.build(&mut model);
+
+    model.update(0.01);
+
+    let segments = model.path_component(vessel).future_segments();
+    let drag_index = segments.iter().position(|segment| segment.is_drag()).expect("No drag segment was predicted");
+    let drag = segments[drag_index].as_drag().unwrap();
+    assert!(!drag.hits_surface());
+
+    let Segment::Orbit(before) = segments[drag_index - 1] else {
+        panic!("Drag segment should follow an orbit");
+    };
+    let Segment::Orbit(after) = segments[drag_index + 1] else {
+        panic!("Drag segment should be followed by an orbit");
+    };
+    assert_eq!(before.end_point().time(), drag.start_point().time());
+    assert_eq!(drag.end_point().time(), after.start_point().time());
+    assert!(after.semi_major_axis() < before.semi_major_axis());
+
+    let perceived_segments = model.compute_perceived_path(vessel);
+    assert!(perceived_segments.iter().any(Segment::is_drag));
+}
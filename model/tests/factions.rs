@@ -0,0 +1,95 @@
+use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, slots::{ModuleType, SlotType}, faction::{Faction, Relation, Stance}, VesselComponent}}, model::{state_query::StateQuery, Model}, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}, test_util};
+
+fn ship(model: &mut Model, earth: Entity, faction: Faction, angle: f64, is_ai_controlled: bool) -> Entity {
+    let mut vessel_component = VesselComponent::new(VesselClass::TestShip, faction);
+    if is_ai_controlled {
+        vessel_component = vessel_component.with_ai_control();
+    }
+    // Some targets are beyond passive sensor range
+    let slot = vessel_component.slots().iter().position(|slot| slot.type_() == SlotType::Sensor).unwrap();
+    vessel_component = vessel_component.with_module(slot, ModuleType::SensorLongRange);
+    VesselBuilder {
+        name: "Ship".to_string(),
+        vessel_component,
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.01e9, angle, direction: OrbitDirection::AntiClockwise },
+    }.build(model)
+}
+
+#[test]
+fn test_new_faction_is_neutral() {
+    let mut model = Model::default();
+    let pirates = model.add_faction("Pirates");
+
+    assert_eq!(model.faction_name(pirates), "Pirates");
+    assert_eq!(model.faction_named("pirates"), Some(pirates));
+    assert_eq!(model.factions().count(), 4);
+    for faction in [Faction::PLAYER, Faction::ALLY, Faction::ENEMY] {
+        assert_eq!(model.relation(pirates, faction).stance(), Stance::Neutral);
+        assert!(!model.has_intel_for(pirates, faction));
+    }
+    assert!(model.has_intel_for(pirates, pirates));
+}
+
+#[test]
+fn test_defection() {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let ally = ship(&mut model, earth, Faction::ALLY, 0.0, false);
+    model.update(0.01);
+    assert!(model.has_intel_for(Faction::PLAYER, Faction::ALLY));
+
+    model.set_relation(Faction::PLAYER, Faction::ALLY, Relation::new(Stance::Hostile, false));
+
+    assert!(model.is_hostile_to(Faction::ALLY, Faction::PLAYER));
+    assert!(!model.has_intel_for(Faction::PLAYER, Faction::ALLY));
+    assert_eq!(model.snapshot_now_observe(Faction::PLAYER).target(ally), None);
+    assert!(model.has_intel_for(Faction::ALLY, Faction::ALLY));
+}
+
+#[test]
+fn test_shared_intel_extends_detection() {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let pirates = model.add_faction("Pirates");
+    let player = ship(&mut model, earth, Faction::PLAYER, 0.0, false);
+    ship(&mut model, earth, pirates, 100.0e3 / 0.01e9, false);
+    ship(&mut model, earth, Faction::ENEMY, 1.0, false);
+    model.update(0.01);
+    assert!(!model.is_detected(Faction::ENEMY, player));
+
+    model.set_relation(Faction::ENEMY, pirates, Relation::new(Stance::Allied, true));
+    model.update(0.01);
+
+    assert!(model.is_detected(Faction::ENEMY, player));
+}
+
+#[test]
+fn test_three_way_targeting() {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let pirates = model.add_faction("Pirates");
+    let pirate = ship(&mut model, earth, pirates, 0.0, true);
+    let enemy = ship(&mut model, earth, Faction::ENEMY, 1000.0e3 / 0.01e9, false);
+
+    model.update(61.0);
+    assert_eq!(model.vessel_component(pirate).target(), None);
+
+    model.set_relation(pirates, Faction::ENEMY, Relation::new(Stance::Hostile, false));
+    model.update(60.0);
+    assert_eq!(model.vessel_component(pirate).target(), Some(enemy));
+}
+
+#[test]
+fn test_factions_saved() {
+    let mut model = Model::default();
+    let pirates = model.add_faction("Pirates");
+    model.set_relation(pirates, Faction::PLAYER, Relation::new(Stance::Hostile, false));
+
+    let model = Model::deserialize(&model.serialize().unwrap()).unwrap();
+
+    assert_eq!(model.faction_named("Pirates"), Some(pirates));
+    assert!(model.is_hostile_to(Faction::PLAYER, pirates));
+}
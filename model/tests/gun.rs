@@ -0,0 +1,120 @@
+use nalgebra_glm::vec2;
+use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, faction::Faction, slots::{ModuleType, SlotType}, timeline::{fire_gun::FireGunEvent, TimelineEvent}, VesselComponent}}, model::{state_query::StateQuery, Model}, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}, test_util::{self, assert_float_equal}};
+
+const DISTANCE: f64 = 0.01e9;
+
+/// Creates a ship with a kinetic gun targeting an enemy scout 20km ahead of it on the same orbit
+fn setup() -> (Model, Entity, Entity) {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+
+    let target = VesselBuilder {
+        name: "Target".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::ENEMY),
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: DISTANCE, angle: 20.0e3 / DISTANCE, direction: OrbitDirection::AntiClockwise },
+    }.build(&mut model);
+
+    let vessel_component = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let weapon_slot = vessel_component.slots().iter().position(|slot| slot.type_() == SlotType::Weapon).unwrap();
+    let mut vessel_component = vessel_component.with_module(weapon_slot, ModuleType::KineticGun);
+    vessel_component.set_target(Some(target));
+    let ship = VesselBuilder {
+        name: "Ship".to_string(),
+        vessel_component,
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: DISTANCE, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+    }.build(&mut model);
+
+    model.update(0.01);
+    (model, ship, target)
+}
+
+/// Turns the ship to the firing solution and fires
+fn fire_on_solution(model: &mut Model, ship: Entity, target: Entity) -> FireGunEvent {
+    let turn_time = model.time() + 1.0;
+    let solution = model.firing_solution(ship, target, turn_time + 10.0 * 60.0).expect("No firing solution found");
+    model.create_turn(ship, turn_time, solution.rotation());
+    assert!(FireGunEvent::can_create(model, ship, solution.time()));
+    let event = FireGunEvent::new(model, ship, solution.time());
+    model.add_event(ship, TimelineEvent::FireGun(event.clone()));
+    event
+}
+
+#[test]
+fn test_projectile_inherits_velocity() {
+    let (mut model, ship, _) = setup();
+    let time = model.time() + 1.0;
+    let rotation = model.snapshot_at(time).rotation(ship);
+    let ship_velocity = model.snapshot_at(time).velocity(ship);
+
+    let event = FireGunEvent::new(&mut model, ship, time);
+
+    let muzzle_speed = model.vessel_component(ship).gun().unwrap().muzzle_speed();
+    let expected_velocity = ship_velocity + muzzle_speed * vec2(rotation.cos(), rotation.sin());
+    let projectile_velocity = model.snapshot_at(time).velocity(event.projectile());
+    assert_float_equal(projectile_velocity.x, expected_velocity.x, 1.0e-6);
+    assert_float_equal(projectile_velocity.y, expected_velocity.y, 1.0e-6);
+    assert!(model.path_component(event.projectile()).future_segments().iter().all(|segment| segment.is_orbit()));
+}
+
+#[test]
+fn test_firing_solution_hits() {
+    let (mut model, ship, target) = setup();
+    let event = fire_on_solution(&mut model, ship, target);
+
+    let hit_time = event.hit_time().expect("Shot on the firing solution was predicted to miss");
+    let intercept = model.vessel_component(event.projectile()).timeline().last_event().unwrap().as_intercept().unwrap();
+    assert_eq!(intercept.time(), hit_time);
+}
+
+#[test]
+fn test_projectile_damages_target() {
+    let (mut model, ship, target) = setup();
+    let event = fire_on_solution(&mut model, ship, target);
+    let hit_time = event.hit_time().unwrap();
+    let ammo = model.vessel_component(ship).gun_ammo();
+    assert_eq!(model.vessel_component(ship).final_gun_ammo(), ammo - 1);
+
+    while model.time() <= hit_time {
+        model.update(1.0);
+    }
+
+    assert_eq!(model.vessel_component(ship).gun_ammo(), ammo - 1);
+    assert!(model.try_vessel_component(event.projectile()).is_none());
+    let health = model.vessel_component(target).health().unwrap();
+    assert!(health.hit_points() < health.max_hit_points());
+}
+
+#[test]
+fn test_cannot_fire_during_cooldown() {
+    let (mut model, ship, _) = setup();
+    let time = model.time() + 1.0;
+    let event = FireGunEvent::new(&mut model, ship, time);
+    model.add_event(ship, TimelineEvent::FireGun(event));
+
+    let cooldown = model.vessel_component(ship).gun().unwrap().cooldown();
+    assert!(!FireGunEvent::can_create(&model, ship, time + cooldown / 2.0));
+    assert!(FireGunEvent::can_create(&model, ship, time + cooldown));
+}
+
+#[test]
+fn test_missed_projectile_is_removed() {
+    let (mut model, ship, _) = setup();
+    // Firing straight away from the target never brings the projectile close
+    let time = model.time() + 1.0;
+    let rotation = model.snapshot_at(time).rotation(ship) + std::f64::consts::PI;
+    model.create_turn(ship, time, rotation);
+    let fire_time = model.path_component(ship).end_segment().start_time() + 1.0;
+    let event = FireGunEvent::new(&mut model, ship, fire_time);
+    model.add_event(ship, TimelineEvent::FireGun(event.clone()));
+    assert!(event.hit_time().is_none());
+
+    let end_time = model.vessel_component(event.projectile()).timeline().last_event().unwrap().time();
+    while model.time() <= end_time {
+        model.update(10.0);
+    }
+
+    assert!(model.try_vessel_component(event.projectile()).is_none());
+    let health = model.vessel_component(model.vessel_component(ship).target().unwrap()).health().unwrap();
+    test_util::assert_float_equal(health.hit_points(), health.max_hit_points(), 1.0e-6);
+}
@@ -0,0 +1,73 @@
+use nalgebra_glm::vec2;
+use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection, scary_math::GRAVITATIONAL_CONSTANT}, vessel_component::{class::VesselClass, faction::Faction, timeline::{start_burn::StartBurnEvent, TimelineEvent}, VesselComponent}}, model::Model, storage::entity_builder::VesselBuilder, test_util::{self, EARTH_MASS, EARTH_RADIUS}};
+
+#[test]
+fn test_impact_destroys_vessel() {
+    let mut model = Model::default();
+
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+
+    // Start at apoapsis with a periapsis well below the surface
+    let apoapsis = 2.0e7;
+    let periapsis = EARTH_RADIUS * 0.5;
+    let mu = GRAVITATIONAL_CONSTANT * EARTH_MASS;
+    let speed = f64::sqrt(mu * 2.0 * periapsis / (apoapsis * (apoapsis + periapsis)));
+    let vessel = VesselBuilder {
+        name: "Vessel".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
+        orbit_builder: InitialOrbitBuilder::Freeform { parent: earth, distance: apoapsis, angle: 0.0, direction: OrbitDirection::AntiClockwise, speed },
+    }.build(&mut model);
+
+    model.update(0.01);
+
+    let impact = model.vessel_component(vessel).timeline().last_event().and_then(|event| event.as_impact()).expect("No impact was predicted");
+    let end_segment = model.path_component(vessel).end_segment();
+    assert_eq!(end_segment.end_time(), impact.time());
+    assert!((end_segment.end_position().magnitude() - EARTH_RADIUS).abs() < 1.0);
+
+    // Recomputing should not predict past the impact or add a second one
+    model.recompute_trajectory(vessel);
+    assert_eq!(model.vessel_component(vessel).timeline().events().len(), 1);
+
+    while model.time() <= impact.time() {
+        model.update(10.0);
+    }
+    model.update(0.01);
+
+    assert!(model.try_vessel_component(vessel).is_none());
+}
+
+#[test]
+fn test_impact_during_burn_destroys_vessel() {
+    let mut model = Model::default();
+
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+
+    // A slightly elliptical orbit with its periapsis just above the surface
+    let distance = EARTH_RADIUS + 500.0;
+    let speed = 1.01 * f64::sqrt(GRAVITATIONAL_CONSTANT * EARTH_MASS / distance);
+    let vessel = VesselBuilder {
+        name: "Vessel".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::TestShip, Faction::PLAYER),
+        orbit_builder: InitialOrbitBuilder::Freeform { parent: earth, distance, angle: 0.0, direction: OrbitDirection::AntiClockwise, speed },
+    }.build(&mut model);
+
+    model.update(0.01);
+
+    // Burning towards the body drops the vessel through the surface before the burn ends
+    let event = TimelineEvent::StartBurn(StartBurnEvent::new(&mut model, vessel, 10.0));
+    model.add_event(vessel, event);
+    model.start_burn_event_at_time(vessel, 10.0).unwrap().adjust(&mut model, vec2(0.0, 3000.0));
+    let burn_start_time = model.start_burn_event_at_time(vessel, 10.0).unwrap().burn_segment_time(&model);
+    let burn_end_time = model.snapshot_at(burn_start_time).burn_starting_now(vessel).end_point().time();
+
+    // Stop short of the end of the burn, so the vessel has to be destroyed during it
+    while model.time() + 0.1 < burn_end_time && model.try_vessel_component(vessel).is_some() {
+        model.update(0.1);
+    }
+
+    assert!(model.time() > burn_start_time);
+    assert!(model.try_vessel_component(vessel).is_none());
+}
@@ -0,0 +1,24 @@
+use transfer_window_model::{components::vessel_component::timeline::{start_burn::StartBurnEvent, TimelineEvent}, model::{state_query::StateQuery, Model}, test_util};
+
+#[test]
+fn test_plan_intercept() {
+    let mut model = Model::default();
+
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let station = test_util::station_leo(&mut model, earth);
+    let vessel = test_util::test_ship_leo(&mut model, earth);
+
+    model.update(0.01);
+
+    let period = model.snapshot_now().orbit(vessel).period().unwrap();
+    let plan = model.plan_intercept(vessel, station, model.time() + 10.0, model.time() + period).unwrap();
+    assert!(plan.closest_approach_distance() < 1.0e3);
+    assert!(plan.fuel_kg() > 0.0);
+
+    let event = TimelineEvent::StartBurn(StartBurnEvent::new_with_delta_v(&mut model, vessel, plan.time(), plan.delta_v()));
+    model.add_event(vessel, event);
+
+    let distance = model.snapshot_at(plan.closest_approach_time()).distance(vessel, station);
+    assert!(distance < 1.0e3);
+}
@@ -0,0 +1,82 @@
+use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, faction::Faction, slots::{ModuleType, SlotType}, VesselComponent}}, model::Model, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}, test_util};
+
+/// Creates a station and a vessel of `class` alongside it
+fn station_and_vessel(model: &mut Model, class: VesselClass) -> (Entity, Entity) {
+    let sun = test_util::sun(model);
+    let earth = test_util::earth(model, sun);
+    let station = test_util::station_leo(model, earth);
+    let vessel = VesselBuilder {
+        name: "Vessel".to_string(),
+        vessel_component: VesselComponent::new(class, Faction::PLAYER),
+        orbit_builder: InitialOrbitBuilder::Circular {
+            parent: earth,
+            distance: 0.01e9,
+            angle: 0.0,
+            direction: OrbitDirection::AntiClockwise,
+        }
+    }.build(model);
+    (station, vessel)
+}
+
+fn find_slot(model: &Model, entity: Entity, type_: SlotType) -> usize {
+    model.vessel_component(entity)
+        .slots()
+        .iter()
+        .position(|slot| slot.type_() == type_)
+        .unwrap()
+}
+
+#[test]
+fn test_refit_while_docked() {
+    let mut model = Model::default();
+    let (station, scout) = station_and_vessel(&mut model, VesselClass::Scout1);
+
+    let fuel_tank_slot = find_slot(&model, scout, SlotType::FuelTank);
+    assert!(!model.can_refit(scout));
+    assert!(!model.can_fit_module(scout, fuel_tank_slot, Some(ModuleType::FuelTankLarge)));
+
+    model.vessel_component_mut(scout).set_target(Some(station));
+    model.dock(station, scout);
+    assert!(model.can_refit(scout));
+    assert!(!model.can_fit_module(scout, fuel_tank_slot, Some(ModuleType::EngineBooster)));
+    assert!(!model.can_fit_module(scout, 100, Some(ModuleType::FuelTankLarge)));
+
+    let dry_mass = model.vessel_component(scout).dry_mass();
+    let fuel_capacity = model.vessel_component(scout).fuel_capacity_litres();
+    let fuel = model.vessel_component(scout).fuel_litres();
+    model.fit_module(scout, fuel_tank_slot, Some(ModuleType::FuelTankLarge));
+
+    let vessel_component = model.vessel_component(scout);
+    assert_eq!(vessel_component.dry_mass(), dry_mass + ModuleType::FuelTankLarge.mass());
+    assert_eq!(vessel_component.fuel_capacity_litres(), fuel_capacity + ModuleType::FuelTankLarge.fuel_capacity_litres());
+    // Fitting a tank does not fill it
+    assert_eq!(vessel_component.fuel_litres(), fuel);
+
+    model.fit_module(scout, fuel_tank_slot, None);
+    let vessel_component = model.vessel_component(scout);
+    assert_eq!(vessel_component.dry_mass(), dry_mass);
+    assert_eq!(vessel_component.fuel_capacity_litres(), fuel_capacity);
+}
+
+#[test]
+fn test_fit_weapon_and_sensor() {
+    let mut model = Model::default();
+    let (station, frigate) = station_and_vessel(&mut model, VesselClass::Frigate1);
+    model.vessel_component_mut(frigate).set_target(Some(station));
+    model.dock(station, frigate);
+
+    let torpedoes = model.vessel_component(frigate).torpedoes();
+    let torpedo_capacity = model.vessel_component(frigate).torpedo_capacity();
+    assert_eq!(model.vessel_component(frigate).sensor_range(), 0.0);
+
+    let weapon_slot = find_slot(&model, frigate, SlotType::Weapon);
+    let sensor_slot = find_slot(&model, frigate, SlotType::Sensor);
+    model.fit_module(frigate, weapon_slot, Some(ModuleType::TorpedoLauncherHeavy));
+    model.fit_module(frigate, sensor_slot, Some(ModuleType::SensorLongRange));
+
+    let vessel_component = model.vessel_component(frigate);
+    assert_eq!(vessel_component.torpedo_capacity(), torpedo_capacity + ModuleType::TorpedoLauncherHeavy.torpedo_capacity());
+    assert_eq!(vessel_component.torpedoes(), torpedoes);
+    assert_eq!(vessel_component.torpedo_launcher_time_to_reload(), ModuleType::TorpedoLauncherHeavy.torpedo_launcher_cooldown().unwrap());
+    assert_eq!(vessel_component.sensor_range(), ModuleType::SensorLongRange.sensor_range());
+}
@@ -0,0 +1,112 @@
+use transfer_window_model::{components::{orbitable_component::{builder::OrbitablePhysicsBuilder, deposit::ResourceDeposit, OrbitableType}, path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, faction::Faction, resource::ResourceType, slots::ModuleType, timeline::{start_burn::StartBurnEvent, TimelineEvent}, VesselComponent}}, model::{story_event::StoryEvent, Model}, storage::{entity_allocator::Entity, entity_builder::{OrbitableBuilder, VesselBuilder}}, test_util::{self, assert_float_equal}};
+
+const ORBIT_DISTANCE: f64 = 1.0e7;
+const UTILITY_SLOT: usize = 5;
+
+fn drill_ship() -> VesselComponent {
+    VesselComponent::new(VesselClass::TestShip, Faction::PLAYER).with_module(UTILITY_SLOT, ModuleType::MiningDrill)
+}
+
+/// A ship and an asteroid sharing an orbit around earth, `angle` radians apart
+fn setup(vessel_component: VesselComponent, deposits: Vec<ResourceDeposit>, angle: f64) -> (Model, Entity, Entity) {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let asteroid = OrbitableBuilder {
+        name: "Asteroid",
+        mass: 1.0e10,
+        radius: 10.0,
+        rotation_period: 1000.0,
+        rotation_angle: 0.0,
+        type_: OrbitableType::Asteroid,
+        deposits,
+        atmosphere: None,
+        physics: OrbitablePhysicsBuilder::Orbit(InitialOrbitBuilder::Circular {
+            parent: earth,
+            distance: ORBIT_DISTANCE,
+            angle: 0.0,
+            direction: OrbitDirection::AntiClockwise,
+        }),
+    }.build(&mut model);
+    let ship = VesselBuilder {
+        name: "Ship".to_string(),
+        vessel_component,
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: ORBIT_DISTANCE, angle, direction: OrbitDirection::AntiClockwise },
+    }.build(&mut model);
+    model.update(0.01);
+    (model, ship, asteroid)
+}
+
+fn update_until(model: &mut Model, time: f64) -> Vec<StoryEvent> {
+    let mut story_events = vec![];
+    while model.time() < time {
+        story_events.extend(model.update(100.0));
+    }
+    story_events
+}
+
+#[test]
+fn test_cannot_mine_without_drill_or_from_far_away() {
+    let deposits = vec![ResourceDeposit::new(ResourceType::Metal, 1000.0)];
+    let (model, ship, asteroid) = setup(drill_ship(), deposits.clone(), 2.0e-5);
+    assert!(model.can_mine(ship, asteroid));
+
+    let (model, ship, asteroid) = setup(drill_ship(), deposits.clone(), 1.0e-3);
+    assert!(!model.can_mine(ship, asteroid));
+
+    let (model, ship, asteroid) = setup(VesselComponent::new(VesselClass::TestShip, Faction::PLAYER), deposits, 2.0e-5);
+    assert!(!model.can_mine(ship, asteroid));
+}
+
+#[test]
+fn test_mining_depletes_deposits_in_order() {
+    let deposits = vec![ResourceDeposit::new(ResourceType::Metal, 500.0), ResourceDeposit::new(ResourceType::Oil, 1.0e6)];
+    let (mut model, ship, asteroid) = setup(drill_ship(), deposits, 2.0e-5);
+    let mass = model.vessel_component(ship).mass();
+    model.start_mining(ship, asteroid);
+
+    let time = model.time() + 400.0;
+    let story_events = update_until(&mut model, time);
+    assert!(story_events.iter().any(|event| matches!(event, StoryEvent::DepositDepleted { resource: ResourceType::Metal, .. })));
+    assert!(model.orbitable_component(asteroid).deposits()[0].is_depleted());
+    assert_float_equal(model.vessel_component(ship).resource_amount(ResourceType::Metal), 500.0, 1.0e-6);
+
+    // Oil is mined once the metal runs out
+    let oil = model.vessel_component(ship).resource_amount(ResourceType::Oil);
+    assert!(oil > 0.0);
+    assert_float_equal(model.vessel_component(ship).mass(), mass + 500.0 + oil, 1.0e-6);
+    assert_float_equal(model.orbitable_component(asteroid).deposits()[1].remaining(), 1.0e6 - oil, 1.0e-6);
+}
+
+#[test]
+fn test_planning_a_burn_stops_mining() {
+    let (mut model, ship, asteroid) = setup(drill_ship(), vec![ResourceDeposit::new(ResourceType::Oil, 1.0e6)], 2.0e-5);
+    model.start_mining(ship, asteroid);
+    let time = model.time() + 100.0;
+    update_until(&mut model, time);
+    let oil = model.vessel_component(ship).resource_amount(ResourceType::Oil);
+    assert!(oil > 0.0);
+
+    // The burn was planned for the mass at the time it was planned
+    let burn_time = model.time() + 1000.0;
+    let event = TimelineEvent::StartBurn(StartBurnEvent::new(&mut model, ship, burn_time));
+    model.add_event(ship, event);
+    assert!(!model.can_mine(ship, asteroid));
+
+    let story_events = update_until(&mut model, time + 100.0);
+    assert!(story_events.iter().any(|event| matches!(event, StoryEvent::MiningStopped { .. })));
+    assert_float_equal(model.vessel_component(ship).resource_amount(ResourceType::Oil), oil, 1.0e-9);
+}
+
+#[test]
+fn test_mining_stops_when_hold_is_full() {
+    let (mut model, ship, asteroid) = setup(drill_ship(), vec![ResourceDeposit::new(ResourceType::Oil, 1.0e6)], 2.0e-5);
+    model.start_mining(ship, asteroid);
+    let completion_time = model.mining_completion_time(ship).unwrap();
+
+    let story_events = update_until(&mut model, completion_time + 200.0);
+    assert!(story_events.iter().any(|event| matches!(event, StoryEvent::MiningStopped { .. })));
+    assert!(model.vessel_component(ship).mining().is_none());
+    assert!(model.vessel_component(ship).is_resource_full(ResourceType::Oil));
+    assert!(model.mining_completion_time(ship).is_none());
+}
@@ -0,0 +1,115 @@
+use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection, scary_math::GRAVITATIONAL_CONSTANT}, vessel_component::{class::VesselClass, faction::Faction, slots::{ModuleType, SlotType}, timeline::{start_guidance::StartGuidanceEvent, TimelineEvent}, VesselComponent}}, model::Model, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}, test_util::{self, EARTH_MASS}};
+
+const DISTANCE: f64 = 0.01e9;
+
+/// Creates a torpedo `behind` metres behind `ship` and guides it towards the ship
+fn guided_torpedo(model: &mut Model, earth: Entity, ship: Entity, behind: f64) -> Entity {
+    let mut vessel_component = VesselComponent::new(VesselClass::Torpedo, Faction::ENEMY);
+    vessel_component.unset_ghost();
+    vessel_component.set_target(Some(ship));
+    let speed = f64::sqrt(GRAVITATIONAL_CONSTANT * EARTH_MASS / DISTANCE) + 50.0;
+    let torpedo = VesselBuilder {
+        name: "Torpedo".to_string(),
+        vessel_component,
+        orbit_builder: InitialOrbitBuilder::Freeform { parent: earth, distance: DISTANCE, angle: -behind / DISTANCE, direction: OrbitDirection::AntiClockwise, speed },
+    }.build(model);
+
+    model.update(0.01);
+    let time = model.time() + 1.0;
+    assert!(StartGuidanceEvent::can_create(model, torpedo, time));
+    let event = TimelineEvent::StartGuidance(StartGuidanceEvent::new(model, torpedo, time));
+    model.add_event(torpedo, event);
+    torpedo
+}
+
+/// Creates a ship with a point defense turret and its planet
+fn defended_ship(seed: u64) -> (Model, Entity, Entity) {
+    let mut model = Model::default();
+    model.set_seed(seed);
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+
+    let vessel_component = VesselComponent::new(VesselClass::TestShip, Faction::PLAYER);
+    let weapon_slot = vessel_component.slots().iter().position(|slot| slot.type_() == SlotType::Weapon).unwrap();
+    let ship = VesselBuilder {
+        name: "Ship".to_string(),
+        vessel_component: vessel_component.with_module(weapon_slot, ModuleType::PointDefenseTurret),
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: DISTANCE, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+    }.build(&mut model);
+    (model, earth, ship)
+}
+
+/// Creates a defended ship and a torpedo 15km behind it guiding towards it
+fn engagement(seed: u64) -> (Model, Entity, Entity) {
+    let (mut model, earth, ship) = defended_ship(seed);
+    let torpedo = guided_torpedo(&mut model, earth, ship, 15.0e3);
+    (model, ship, torpedo)
+}
+
+fn predicted_shots(model: &Model, torpedo: Entity) -> usize {
+    model.vessel_component(torpedo)
+        .timeline()
+        .events()
+        .iter()
+        .find_map(TimelineEvent::as_point_defense)
+        .map_or(0, |point_defense| point_defense.shots())
+}
+
+#[test]
+fn test_point_defense_is_scheduled() {
+    let (model, ship, torpedo) = engagement(1);
+    let point_defense = model.vessel_component(torpedo)
+        .timeline()
+        .events()
+        .iter()
+        .find_map(TimelineEvent::as_point_defense)
+        .expect("No point defense engagement was predicted");
+    assert_eq!(point_defense.defender(), ship);
+    assert!(point_defense.shots() > 0);
+    // A torpedo which is shot down never reaches its target
+    let has_intercept = model.vessel_component(torpedo).timeline().events().iter().any(TimelineEvent::is_intercept);
+    assert_eq!(has_intercept, !point_defense.destroys_torpedo());
+}
+
+#[test]
+fn test_point_defense_is_deterministic() {
+    let outcome = |seed: u64| {
+        let (model, _, torpedo) = engagement(seed);
+        let point_defense = model.vessel_component(torpedo).timeline().events().iter().find_map(TimelineEvent::as_point_defense).unwrap();
+        (point_defense.time(), point_defense.shots(), point_defense.destroys_torpedo())
+    };
+    assert_eq!(outcome(7), outcome(7));
+    assert_eq!(outcome(8), outcome(8));
+}
+
+#[test]
+fn test_point_defense_consumes_ammo() {
+    let (mut model, ship, torpedo) = engagement(1);
+    let point_defense = model.vessel_component(torpedo).timeline().events().iter().find_map(TimelineEvent::as_point_defense).unwrap();
+    let ammo = model.vessel_component(ship).point_defense_ammo();
+
+    while model.time() <= point_defense.time() {
+        model.update(1.0);
+    }
+
+    assert_eq!(model.vessel_component(ship).point_defense_ammo(), ammo - point_defense.shots());
+    if point_defense.destroys_torpedo() {
+        assert!(model.try_vessel_component(torpedo).is_none());
+    }
+}
+
+#[test]
+fn test_point_defense_shares_ammo_between_engagements() {
+    let (mut model, earth, ship) = defended_ship(1);
+    let ammo = 3;
+    let consumed = model.vessel_component(ship).point_defense_ammo() - ammo;
+    model.vessel_component_mut(ship).consume_point_defense_ammo(consumed);
+
+    let first = guided_torpedo(&mut model, earth, ship, 15.0e3);
+    let second = guided_torpedo(&mut model, earth, ship, 16.0e3);
+
+    let first_shots = predicted_shots(&model, first);
+    let second_shots = predicted_shots(&model, second);
+    assert!(first_shots > 0);
+    assert!(first_shots + second_shots <= ammo);
+}
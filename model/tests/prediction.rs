@@ -57,7 +57,7 @@ fn test_prediction() {
 
     let vessel = model.allocate(EntityBuilder::default()
         .with_name_component(NameComponent::new("Vessel".to_string()))
-        .with_vessel_component(VesselComponent::new(VesselClass::Frigate1, Faction::Player))
+        .with_vessel_component(VesselComponent::new(VesselClass::Frigate1, Faction::PLAYER))
         .with_path_component(PathComponent::new_with_orbit(orbit)));
 
     model.recompute_trajectory(vessel);
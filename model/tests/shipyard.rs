@@ -0,0 +1,97 @@
+use transfer_window_model::{components::vessel_component::{class::VesselClass, resource::ResourceType}, model::{story_event::StoryEvent, time::TimeStep, Model}, storage::entity_allocator::Entity, test_util::{self, assert_float_equal}};
+
+const TEST_SHIP_BUILD_TIME: f64 = 60.0 * 60.0;
+const TEST_SHIP_METAL: f64 = 1.0e3;
+
+/// A station orbiting earth with `metal` in its hold
+fn setup(metal: f64) -> (Model, Entity) {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let station = test_util::station_leo(&mut model, earth);
+    model.vessel_component_mut(station).set_resource_amount(ResourceType::Metal, metal);
+    model.update(0.01);
+    (model, station)
+}
+
+fn built_vessels(story_events: &[StoryEvent]) -> Vec<Entity> {
+    story_events.iter()
+        .filter_map(|event| match event {
+            StoryEvent::VesselBuilt { entity, .. } => Some(*entity),
+            _ => None,
+        })
+        .collect()
+}
+
+fn queue_length(model: &Model, station: Entity) -> usize {
+    model.vessel_component(station).build_queue().unwrap().orders().len()
+}
+
+#[test]
+fn test_build_costs_resources_and_time() {
+    let (mut model, station) = setup(1.5e3);
+    assert!(model.can_build(station, VesselClass::TestShip));
+    assert!(!model.can_build(station, VesselClass::Frigate1));
+    assert!(!model.can_build(station, VesselClass::Station));
+
+    model.enqueue_build(station, VesselClass::TestShip);
+    assert_float_equal(model.vessel_component(station).resource_amount(ResourceType::Metal), 0.5e3, 1.0e-6);
+    assert!(!model.can_build(station, VesselClass::TestShip));
+    let completion_time = model.build_completion_time(station, 0);
+    assert_float_equal(completion_time - model.time(), TEST_SHIP_BUILD_TIME, 1.0e-6);
+
+    let mut story_events = vec![];
+    while model.time() < completion_time - 100.0 {
+        story_events.extend(model.update(100.0));
+    }
+    assert!(built_vessels(&story_events).is_empty());
+    assert_eq!(queue_length(&model, station), 1);
+
+    let built = built_vessels(&model.update(200.0));
+    assert_eq!(built.len(), 1);
+    assert_eq!(queue_length(&model, station), 0);
+    assert!(model.docked(built[0]));
+    assert_eq!(model.vessel_component(built[0]).class(), VesselClass::TestShip);
+    assert!(model.find_docking_port(station, built[0]).is_some());
+}
+
+#[test]
+fn test_queue_advances_at_high_time_step() {
+    let (mut model, station) = setup(3.0 * TEST_SHIP_METAL);
+    for _ in 0..3 {
+        model.enqueue_build(station, VesselClass::TestShip);
+    }
+
+    // A single update covers all three builds, but the station only has two
+    // ports, so the third waits
+    model.set_time_step(TimeStep::Level { level: 8, paused: false });
+    let built = built_vessels(&model.update(1.0));
+    assert_eq!(built.len(), 2);
+    assert_eq!(queue_length(&model, station), 1);
+    assert!(model.vessel_component(station).build_queue().unwrap().orders()[0].is_finished());
+    assert!(model.find_free_docking_port(station).is_none());
+
+    model.set_time_step(TimeStep::Level { level: 1, paused: false });
+    model.undock(station, built[0]);
+    model.update(0.01);
+    assert_eq!(queue_length(&model, station), 0);
+    assert!(model.find_free_docking_port(station).is_none());
+}
+
+#[test]
+fn test_reorder_and_cancel_build() {
+    let (mut model, station) = setup(2.0 * TEST_SHIP_METAL);
+    model.enqueue_build(station, VesselClass::TestShip);
+    model.update(TEST_SHIP_BUILD_TIME / 2.0);
+    model.enqueue_build(station, VesselClass::TestShip);
+
+    // The half finished build keeps its progress when moved to the back
+    model.reorder_build(station, 0, 1);
+    assert_float_equal(model.build_completion_time(station, 0) - model.time(), TEST_SHIP_BUILD_TIME, 1.0e-6);
+    assert_float_equal(model.build_completion_time(station, 1) - model.time(), 1.5 * TEST_SHIP_BUILD_TIME, 1.0e-6);
+
+    model.cancel_build(station, 0);
+    assert_eq!(queue_length(&model, station), 1);
+    assert_float_equal(model.vessel_component(station).resource_amount(ResourceType::Metal), TEST_SHIP_METAL, 1.0e-6);
+    assert_float_equal(model.build_completion_time(station, 0) - model.time(), 0.5 * TEST_SHIP_BUILD_TIME, 1.0e-6);
+}
@@ -0,0 +1,77 @@
+use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, faction::Faction, VesselComponent}}, model::{time::TimeStep, Model}, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}, test_util};
+
+/// Creates a few vessels orbiting earth, some of which can see each other
+fn setup() -> (Model, Entity, Entity) {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+
+    let player = VesselBuilder {
+        name: "Player".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.01e9, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+    }.build(&mut model);
+
+    let enemy = VesselBuilder {
+        name: "Enemy".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::ENEMY),
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.01e9, angle: 0.01, direction: OrbitDirection::Clockwise },
+    }.build(&mut model);
+
+    VesselBuilder {
+        name: "Ally".to_string(),
+        vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::ALLY),
+        orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.02e9, angle: 1.0, direction: OrbitDirection::AntiClockwise },
+    }.build(&mut model);
+
+    model.update(0.01);
+    (model, player, enemy)
+}
+
+#[test]
+fn test_identical_runs_hash_the_same() {
+    let (mut a, _, _) = setup();
+    let (mut b, _, _) = setup();
+    for _ in 0..100 {
+        assert_eq!(a.state_hash(), b.state_hash());
+        a.update(10.0);
+        b.update(10.0);
+    }
+    assert_eq!(a.state_hash(), b.state_hash());
+}
+
+#[test]
+fn test_hash_survives_save_and_load() {
+    let (mut model, _, _) = setup();
+    model.update(100.0);
+    let loaded = Model::deserialize(&model.serialize().unwrap()).unwrap();
+    assert_eq!(model.state_hash(), loaded.state_hash());
+}
+
+#[test]
+fn test_hash_changes_with_state() {
+    let (mut model, player, enemy) = setup();
+    let mut previous = model.state_hash();
+    let mut assert_changed = |model: &Model| {
+        let hash = model.state_hash();
+        assert_ne!(hash, previous);
+        previous = hash;
+    };
+
+    model.update(10.0);
+    assert_changed(&model);
+
+    let fuel_kg = model.vessel_component(player).fuel_kg();
+    model.vessel_component_mut(player).set_fuel_kg(fuel_kg - 1.0);
+    assert_changed(&model);
+
+    model.vessel_component_mut(player).set_target(Some(enemy));
+    assert_changed(&model);
+
+    model.set_time_step(TimeStep::Level { level: 2, paused: false });
+    assert_changed(&model);
+
+    model.deallocate(enemy);
+    assert_changed(&model);
+}
+
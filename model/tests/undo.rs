@@ -0,0 +1,125 @@
+use nalgebra_glm::vec2;
+use transfer_window_model::{components::vessel_component::timeline::{fire_torpedo::FireTorpedoEvent, start_burn::StartBurnEvent, TimelineEvent}, model::Model, storage::entity_allocator::Entity, test_util};
+
+fn setup() -> (Model, Entity) {
+    let mut model = Model::default();
+    let sun = test_util::sun(&mut model);
+    let earth = test_util::earth(&mut model, sun);
+    let vessel = test_util::test_ship_leo(&mut model, earth);
+    model.update(0.01);
+    (model, vessel)
+}
+
+/// Everything an edit to the timeline of `entity` can change
+fn plan(model: &Model, entity: Entity) -> String {
+    let path_component = serde_json::to_string(model.path_component(entity)).unwrap();
+    let timeline = serde_json::to_string(model.vessel_component(entity).timeline()).unwrap();
+    path_component + &timeline
+}
+
+fn create_burn(model: &mut Model, entity: Entity, time: f64) {
+    model.record_timeline_edit(entity, time);
+    let event = TimelineEvent::StartBurn(StartBurnEvent::new(model, entity, time));
+    model.add_event(entity, event);
+}
+
+fn adjust_burn(model: &mut Model, entity: Entity, time: f64) {
+    model.record_timeline_adjustment(entity, time);
+    model.start_burn_event_at_time(entity, time).unwrap().adjust(model, vec2(10.0, 0.0));
+}
+
+#[test]
+fn test_undo_and_redo_burn() {
+    let (mut model, vessel) = setup();
+    let before = plan(&model, vessel);
+    assert!(!model.can_undo_timeline_edit(vessel));
+
+    create_burn(&mut model, vessel, 100.0);
+    adjust_burn(&mut model, vessel, 100.0);
+    let after = plan(&model, vessel);
+    assert_ne!(before, after);
+
+    // The burn being adjusted is undone separately from it being created
+    model.undo_timeline_edit(vessel);
+    assert!(model.start_burn_event_at_time(vessel, 100.0).is_some());
+    model.undo_timeline_edit(vessel);
+    assert_eq!(plan(&model, vessel), before);
+    assert!(!model.can_undo_timeline_edit(vessel));
+
+    model.redo_timeline_edit(vessel);
+    model.redo_timeline_edit(vessel);
+    assert_eq!(plan(&model, vessel), after);
+    assert!(!model.can_redo_timeline_edit(vessel));
+}
+
+#[test]
+fn test_adjustments_are_undone_together() {
+    let (mut model, vessel) = setup();
+    create_burn(&mut model, vessel, 100.0);
+    let created = plan(&model, vessel);
+
+    for _ in 0..10 {
+        adjust_burn(&mut model, vessel, 100.0);
+    }
+    model.undo_timeline_edit(vessel);
+    assert_eq!(plan(&model, vessel), created);
+}
+
+#[test]
+fn test_undo_cancel() {
+    let (mut model, vessel) = setup();
+    create_burn(&mut model, vessel, 100.0);
+    let created = plan(&model, vessel);
+
+    model.record_timeline_edit(vessel, 100.0);
+    model.cancel_last_event(vessel);
+    assert!(model.start_burn_event_at_time(vessel, 100.0).is_none());
+
+    model.undo_timeline_edit(vessel);
+    assert_eq!(plan(&model, vessel), created);
+}
+
+#[test]
+fn test_undo_restores_ghost_torpedo() {
+    let (mut model, vessel) = setup();
+    model.record_timeline_edit(vessel, 100.0);
+    let event = FireTorpedoEvent::new(&mut model, vessel, 100.0);
+    let ghost = event.ghost();
+    model.add_event(vessel, TimelineEvent::FireTorpedo(event));
+    let ghost_plan = plan(&model, ghost);
+
+    model.undo_timeline_edit(vessel);
+    assert!(!model.exists(ghost));
+    assert!(model.vessel_component(vessel).timeline().events().is_empty());
+
+    model.redo_timeline_edit(vessel);
+    assert!(model.exists(ghost));
+    assert_eq!(plan(&model, ghost), ghost_plan);
+    assert!(model.fire_torpedo_event_at_time(vessel, 100.0).is_some());
+}
+
+#[test]
+fn test_cannot_undo_once_edit_is_past() {
+    let (mut model, vessel) = setup();
+    create_burn(&mut model, vessel, 100.0);
+    model.update(99.0);
+    assert!(model.can_undo_timeline_edit(vessel));
+    model.update(2.0);
+    assert!(!model.can_undo_timeline_edit(vessel));
+
+    // Undoing does nothing rather than rewriting the past
+    let before = plan(&model, vessel);
+    model.undo_timeline_edit(vessel);
+    assert_eq!(plan(&model, vessel), before);
+}
+
+#[test]
+fn test_undo_survives_save_and_load() {
+    let (mut model, vessel) = setup();
+    let before = plan(&model, vessel);
+    create_burn(&mut model, vessel, 100.0);
+
+    let mut model = Model::deserialize(&model.serialize().unwrap()).unwrap();
+    model.undo_timeline_edit(vessel);
+    assert_eq!(plan(&model, vessel), before);
+}
@@ -2,32 +2,40 @@ use std::{collections::HashSet, sync::{Arc, Mutex}};
 
 use eframe::{egui::{Context, Pos2, Rect}, glow, Frame};
 use events::{ModelEvent, ViewEvent};
+use log::error;
 use nalgebra_glm::DVec2;
-use overlay::{dialogue::Dialogue, objectives::Objective};
+use network::client::LockstepClient;
+use overlay::{dialogue::Dialogue, objectives::Objective, selected::{porkchop::Porkchop, vessel::{auto_intercept::AutoIntercept, docking::TransferPlan}}};
 use rendering::Renderers;
+use replay::{playback::Playback, Replay, ReplayError, ReplayRecorder};
+use serde::{Deserialize, Serialize};
 use storyteller::story::Story;
-use transfer_window_model::{components::ComponentType, model::{state_query::StateQuery, story_event::StoryEvent, Model}, storage::entity_allocator::Entity};
+use transfer_window_model::{components::{vessel_component::{faction::Faction, timeline::undock::DEFAULT_UNDOCK_DELTA_V}, ComponentType}, model::{state_query::StateQuery, story_event::StoryEvent, Model}, storage::entity_allocator::Entity};
 use util::{should_render, should_render_at_time};
 
-use crate::{controller_events::ControllerEvent, resources::Resources};
+use crate::{controller_events::ControllerEvent, resources::Resources, saves::SaveMetadata};
 
 use self::{camera::Camera, debug::DebugWindowTab, frame_history::FrameHistory, selected::Selected};
 
 mod animation;
+mod autosave;
 pub(crate) mod camera;
 mod debug;
 mod events;
 mod expiry;
 mod frame_history;
 mod misc;
+pub mod network;
 pub(crate) mod overlay;
 pub(crate) mod rendering;
+pub mod replay;
 mod selected;
+pub mod session;
 pub mod storyteller;
 mod underlay;
 mod util;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ViewConfig {
     draw_apsis_icons: bool,
     can_select: bool,
@@ -68,9 +76,29 @@ pub struct View {
     debug_window_open: bool,
     debug_window_tab: DebugWindowTab,
     exit_modal_open: bool,
+    save_modal_open: bool,
+    save_name: String,
+    saves: Vec<SaveMetadata>,
+    autosave_timer: f64,
     pointer_over_ui: bool,
     pointer_over_icon: bool,
     objectives: Vec<Objective>,
+    auto_intercept: Option<AutoIntercept>,
+    porkchop: Option<Porkchop>,
+    /// Vessel whose loadout is being edited
+    loadout: Option<Entity>,
+    /// Amount and delay used when starting resource transfers
+    transfer_plan: TransferPlan,
+    /// Departure delta-v used when scheduling undocks
+    undock_delta_v: DVec2,
+    /// The faction the player controls and sees the game as
+    faction: Faction,
+    /// Connection to the server when playing multiplayer
+    lockstep: Option<LockstepClient>,
+    /// Records the session so that it can be replayed
+    recorder: Option<ReplayRecorder>,
+    /// Drives the model instead of the player when watching a replay
+    playback: Option<Playback>,
 }
 
 impl View {
@@ -94,10 +122,46 @@ impl View {
         let debug_window_open = false;
         let debug_window_tab = DebugWindowTab::Model;
         let exit_modal_open = false;
+        let save_modal_open = false;
+        let save_name = String::new();
+        let saves = vec![];
+        let autosave_timer = 0.0;
         let pointer_over_ui = false;
         let pointer_over_icon = false;
         let objectives = vec![];
-        Self { gl, model, story, config, context, previous_screen_rect, screen_rect, controller_events, model_events, view_events, story_events, previous_story_events, camera, resources, renderers, selected, right_click_menu, dialogue, frame_history, debug_window_open, debug_window_tab, exit_modal_open, pointer_over_ui, pointer_over_icon, objectives }
+        let auto_intercept = None;
+        let porkchop = None;
+        let loadout = None;
+        let transfer_plan = TransferPlan::default();
+        let undock_delta_v = DEFAULT_UNDOCK_DELTA_V;
+        let faction = Faction::PLAYER;
+        let lockstep = None;
+        let recorder = ReplayRecorder::new(&model)
+            .inspect_err(|error| error!("Failed to start recording replay: {error}"))
+            .ok();
+        let playback = None;
+        Self { gl, model, story, config, context, previous_screen_rect, screen_rect, controller_events, model_events, view_events, story_events, previous_story_events, camera, resources, renderers, selected, right_click_menu, dialogue, frame_history, debug_window_open, debug_window_tab, exit_modal_open, save_modal_open, save_name, saves, autosave_timer, pointer_over_ui, pointer_over_icon, objectives, auto_intercept, porkchop, loadout, transfer_plan, undock_delta_v, faction, lockstep, recorder, playback }
+    }
+
+    /// A multiplayer game, where `model` is the one received from the server
+    pub fn new_multiplayer(gl: Arc<glow::Context>, model: Model, lockstep: LockstepClient, context: Context, resources: Arc<Resources>) -> Self {
+        let mut view = Self::new(gl, model, Story::empty(), context, resources, ViewConfig::default(), None);
+        view.faction = lockstep.faction();
+        view.lockstep = Some(lockstep);
+        view.recorder = None;
+        view
+    }
+
+    /// Watches a replay, where the player can seek and change speed but not
+    /// interact with the model
+    /// # Errors
+    /// Returns an error if the replay's initial model cannot be loaded
+    pub fn new_playback(gl: Arc<glow::Context>, replay: Replay, context: Context, resources: Arc<Resources>) -> Result<Self, ReplayError> {
+        let (playback, model) = Playback::new(replay)?;
+        let mut view = Self::new(gl, model, Story::empty(), context, resources, ViewConfig::default(), None);
+        view.recorder = None;
+        view.playback = Some(playback);
+        Ok(view)
     }
 
     fn update_camera_focus_position(&mut self) {
@@ -136,11 +200,63 @@ impl View {
         self.post_draw_ui();
         self.draw_underlay();
         self.handle_events();
-        self.story_events.lock().unwrap().extend(self.model.update(dt));
+        if let Some(playback) = &mut self.playback {
+            let story_events = playback.update(&mut self.model, dt);
+            self.story_events.lock().unwrap().extend(story_events);
+        } else if self.lockstep.is_some() {
+            self.update_lockstep(dt);
+        } else {
+            let time = self.model.time();
+            let story_events = self.model.update(dt);
+            if let Some(recorder) = &mut self.recorder {
+                recorder.record_update(&self.model, time, dt);
+            }
+            self.story_events.lock().unwrap().extend(story_events);
+        }
+        if self.playback.is_none() {
+            self.update_autosave(dt);
+        }
+        if let Some(auto_intercept) = &mut self.auto_intercept {
+            auto_intercept.receive();
+        }
+        if let Some(porkchop) = &mut self.porkchop {
+            porkchop.receive();
+        }
         expiry::update(self);
         self.controller_events.lock().unwrap().clone()
     }
 
+    /// Steps the model once for each tick the server has sent the commands for
+    fn update_lockstep(&mut self, dt: f64) {
+        let Some(lockstep) = &mut self.lockstep else {
+            return;
+        };
+        lockstep.advance(dt);
+        loop {
+            let lockstep = self.lockstep.as_mut().unwrap();
+            let result = if lockstep.can_submit() {
+                lockstep.submit(self.model.state_hash()).and_then(|()| lockstep.poll())
+            } else {
+                lockstep.poll()
+            };
+            match result {
+                Ok(Some(events)) => {
+                    let tick_dt = lockstep.tick_dt();
+                    for event in events {
+                        self.handle_model_event(event);
+                    }
+                    self.story_events.lock().unwrap().extend(self.model.update(tick_dt));
+                }
+                Ok(None) => break,
+                Err(error) => {
+                    error!("Lost connection to the server: {error}");
+                    self.lockstep = None;
+                    break;
+                }
+            }
+        }
+    }
+
     pub(crate) fn add_controller_event(&self, event: ControllerEvent) {
         self.controller_events.lock().unwrap().push(event);
     }
@@ -213,6 +329,7 @@ impl View {
 
 impl Drop for View {
     fn drop(&mut self) {
+        self.save_replay();
         self.renderers.destroy(&self.gl);
     }
 }
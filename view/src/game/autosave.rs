@@ -0,0 +1,19 @@
+use log::info;
+
+use crate::saves::{self, AUTOSAVE_INTERVAL_SECONDS};
+
+use super::View;
+
+impl View {
+    pub(crate) fn update_autosave(&mut self, dt: f64) {
+        self.autosave_timer += dt;
+        if self.autosave_timer < AUTOSAVE_INTERVAL_SECONDS {
+            return;
+        }
+        self.autosave_timer = 0.0;
+        let name = saves::next_autosave_name();
+        info!("Autosaving to {}", name);
+        self.save_game(&name);
+        self.save_replay();
+    }
+}
@@ -1,6 +1,7 @@
 use eframe::{egui::Pos2, epaint::Rect};
 
 use nalgebra_glm::{scale2d, translate2d, vec2, DMat3, DVec2, Mat3, Vec2};
+use serde::{Deserialize, Serialize};
 use transfer_window_model::storage::entity_allocator::Entity;
 
 pub fn f64_to_f32_pair(v: f64) -> (f32, f32) {
@@ -9,6 +10,7 @@ pub fn f64_to_f32_pair(v: f64) -> (f32, f32) {
     (upper, lower)
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Camera {
     focus: Option<Entity>,
     focus_position: DVec2,
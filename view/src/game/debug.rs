@@ -4,11 +4,13 @@ use super::{events::ViewEvent, View};
 
 mod overview;
 mod entities;
+mod factions;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DebugWindowTab {
     Model,
     Entities,
+    Factions,
     Gui,
 }
 
@@ -26,6 +28,7 @@ pub fn draw(view: &View) {
         ui.horizontal(|ui| {
             ui.selectable_value(&mut debug_window_tab, DebugWindowTab::Model, "Model");
             ui.selectable_value(&mut debug_window_tab, DebugWindowTab::Entities, "Entities");
+            ui.selectable_value(&mut debug_window_tab, DebugWindowTab::Factions, "Factions");
             ui.selectable_value(&mut debug_window_tab, DebugWindowTab::Gui, "GUI");
         });
         view.add_view_event(ViewEvent::SetDebugWindowTab(debug_window_tab));
@@ -33,6 +36,7 @@ pub fn draw(view: &View) {
         match view.debug_window_tab {
             DebugWindowTab::Model => overview::draw(view, ui),
             DebugWindowTab::Entities => entities::draw(&view.model, ui),
+            DebugWindowTab::Factions => factions::draw(view, ui),
             DebugWindowTab::Gui => view.context.inspection_ui(ui),
         }
     });
@@ -1,5 +1,5 @@
 use eframe::egui::{ScrollArea, Ui};
-use transfer_window_model::{components::{orbitable_component::{OrbitableComponent, OrbitableComponentPhysics}, path_component::{burn::{burn_point::BurnPoint, Burn}, guidance::{guidance_point::GuidancePoint, Guidance}, orbit::{orbit_point::OrbitPoint, Orbit}, segment::Segment, turn::{turn_point::TurnPoint, Turn}, PathComponent}}, model::Model, storage::entity_allocator::Entity};
+use transfer_window_model::{components::{orbitable_component::{OrbitableComponent, OrbitableComponentPhysics}, path_component::{burn::{burn_point::BurnPoint, Burn}, drag::{drag_point::DragPoint, Drag}, guidance::{guidance_point::GuidancePoint, Guidance}, orbit::{orbit_point::OrbitPoint, Orbit}, segment::Segment, turn::{turn_point::TurnPoint, Turn}, PathComponent}}, model::Model, storage::entity_allocator::Entity};
 
 use crate::game::util::format_time;
 
@@ -24,6 +24,13 @@ fn draw_guidance_point(ui: &mut Ui, point: &GuidancePoint) {
     ui.label(format!("Time: {:.3?}", point.time()));
 }
 
+fn draw_drag_point(ui: &mut Ui, point: &DragPoint) {
+    ui.label(format!("Position: {:.3?}", point.position()));
+    ui.label(format!("Velocity: {:.3?}", point.velocity()));
+    ui.label(format!("Drag acceleration: {:.3?}", point.drag_acceleration()));
+    ui.label(format!("Time: {:.3?}", point.time()));
+}
+
 fn draw_turn_point(ui: &mut Ui, point: &TurnPoint) {
     ui.label(format!("Position: {:.3?}", point.position()));
     ui.label(format!("Velocity: {:.3?}", point.velocity()));
@@ -87,6 +94,14 @@ fn draw_turn(ui: &mut Ui, turn: &Turn) {
     ui.collapsing("End", |ui| draw_turn_point(ui, turn.end_point()));
 }
 
+fn draw_drag(ui: &mut Ui, drag: &Drag) {
+    ui.label(format!("Duration: {}", format_time(drag.duration())));
+    ui.label(format!("Hits surface: {}", drag.hits_surface()));
+    ui.collapsing("Start", |ui| draw_drag_point(ui, drag.start_point()));
+    ui.collapsing("Current", |ui| draw_drag_point(ui, drag.current_point()));
+    ui.collapsing("End", |ui| draw_drag_point(ui, drag.end_point()));
+}
+
 fn draw_path(ui: &mut Ui, path_component: &PathComponent) {
     for segment in path_component.future_segments() {
         match segment {
@@ -94,6 +109,7 @@ fn draw_path(ui: &mut Ui, path_component: &PathComponent) {
             Segment::Burn(burn) => draw_burn(ui, burn),
             Segment::Guidance(guidance) => draw_guidance(ui, guidance),
             Segment::Turn(turn) => draw_turn(ui, turn),
+            Segment::Drag(drag) => draw_drag(ui, drag),
         }
     } 
 }
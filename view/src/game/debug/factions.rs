@@ -0,0 +1,29 @@
+use eframe::egui::{Grid, Ui};
+use transfer_window_model::components::vessel_component::faction::{Relation, Stance};
+
+use crate::game::{events::ModelEvent, View};
+
+pub fn draw(view: &View, ui: &mut Ui) {
+    let factions: Vec<_> = view.model.factions().collect();
+    Grid::new("Debug faction relations").show(ui, |ui| {
+        for (i, faction) in factions.iter().enumerate() {
+            for other in &factions[i + 1..] {
+                let relation = view.model.relation(*faction, *other);
+                let mut stance = relation.stance();
+                let mut shares_intel = relation.shares_intel();
+                ui.label(view.model.faction_name(*faction));
+                ui.label(view.model.faction_name(*other));
+                ui.selectable_value(&mut stance, Stance::Allied, "Allied");
+                ui.selectable_value(&mut stance, Stance::Neutral, "Neutral");
+                ui.selectable_value(&mut stance, Stance::Hostile, "Hostile");
+                ui.checkbox(&mut shares_intel, "Shares intel");
+                ui.end_row();
+
+                if stance != relation.stance() || shares_intel != relation.shares_intel() {
+                    let relation = Relation::new(stance, shares_intel);
+                    view.add_model_event(ModelEvent::SetRelation { faction: *faction, other: *other, relation });
+                }
+            }
+        }
+    });
+}
@@ -8,6 +8,7 @@ pub fn draw(view: &View, ui: &mut Ui) {
     if let Some(warp) = view.model.warp() {
         ui.label(format!("Warp: {warp:?}"));
     }
+    ui.label(format!("State hash: {:016x}", view.model.state_hash()));
     if ui.button("Save").clicked() {
         view.add_model_event(ModelEvent::SaveGame { name: "debug".to_string() });
     }
@@ -2,9 +2,11 @@
 use log::debug;
 use log::error;
 use nalgebra_glm::DVec2;
-use transfer_window_model::{components::vessel_component::docking::{DockingPortLocation, ResourceTransferDirection}, model::{state_query::StateQuery, story_event::StoryEvent, time::TimeStep}, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}};
+use serde::{Deserialize, Serialize};
+use transfer_window_model::{components::vessel_component::{class::VesselClass, docking::{DockingPortLocation, ResourceTransferDirection}, faction::{Faction, Relation}, resource::ResourceType, slots::ModuleType}, model::{state_query::StateQuery, story_event::StoryEvent, time::TimeStep}, storage::{entity_allocator::Entity, entity_builder::VesselBuilder}};
 
 use crate::game::selected::util::BurnState;
+use crate::game::overlay::selected::{porkchop::Porkchop, vessel::{auto_intercept::AutoIntercept, docking::TransferPlan}};
 use crate::game::{overlay::{dialogue::Dialogue, objectives::Objective}, util::ApsisType};
 
 use super::ViewConfig;
@@ -13,9 +15,10 @@ use super::{debug::DebugWindowTab, selected::Selected, View};
 mod model;
 mod view;
 
-#[derive(Debug, Clone)]
+/// Commands which change the model. These are serializable so that they
+/// can be sent to other clients in multiplayer
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ModelEvent {
-    #[allow(unused)]
     SaveGame { name: String },
     TogglePaused,
     IncreaseTimeStepLevel,
@@ -28,20 +31,33 @@ pub enum ModelEvent {
     DeleteVessel { entity: Entity },
     CreateBurn { entity: Entity, time: f64 },
     AdjustBurn { entity: Entity, time: f64, amount: DVec2 },
+    CreatePlannedBurn { entity: Entity, time: f64, delta_v: DVec2 },
     CreateTurn { entity: Entity, time: f64 },
     AdjustTurn { entity: Entity, time: f64, amount: f64 },
     SetTarget { entity: Entity, target: Option<Entity> },
     CreateFireTorpedo { entity: Entity, time: f64 },
     AdjustFireTorpedo { entity: Entity, time: f64, amount: DVec2 },
+    CreateFireGun { entity: Entity, time: f64 },
     CreateGuidance { entity: Entity, time: f64, },
     CancelLastTimelineEvent { entity: Entity },
     CancelCurrentSegment { entity: Entity },
     Dock { station: Entity, entity: Entity },
     Undock { station: Entity, entity: Entity },
-    StartFuelTransfer { station: Entity, location: DockingPortLocation, direction: ResourceTransferDirection },
-    StopFuelTransfer { station: Entity, location: DockingPortLocation },
-    StartTorpedoTransfer { station: Entity, location: DockingPortLocation, direction: ResourceTransferDirection },
-    StopTorpedoTransfer { station: Entity, location: DockingPortLocation },
+    CreateDock { entity: Entity, station: Entity, time: f64 },
+    CreateUndock { station: Entity, entity: Entity, time: f64, delta_v: DVec2 },
+    CancelUndock { station: Entity, entity: Entity },
+    CreateTransfer { station: Entity, entity: Entity, time: f64, resource: ResourceType, direction: ResourceTransferDirection, amount: Option<f64> },
+    StartTransfer { station: Entity, location: DockingPortLocation, resource: ResourceType, direction: ResourceTransferDirection, #[serde(default)] amount: Option<f64> },
+    StopTransfer { station: Entity, location: DockingPortLocation, resource: ResourceType },
+    FitModule { entity: Entity, slot: usize, module: Option<ModuleType> },
+    StartMining { entity: Entity, asteroid: Entity },
+    StopMining { entity: Entity },
+    EnqueueBuild { station: Entity, class: VesselClass },
+    CancelBuild { station: Entity, index: usize },
+    ReorderBuild { station: Entity, from: usize, to: usize },
+    SetRelation { faction: Faction, other: Faction, relation: Relation },
+    UndoTimelineEdit { entity: Entity },
+    RedoTimelineEdit { entity: Entity },
 }
 
 #[derive(Debug, Clone)]
@@ -59,11 +75,25 @@ pub enum ViewEvent {
     ShowDialogue(Dialogue),
     #[allow(unused)]
     CloseDialogue,
-    StartObjective(&'static str),
-    FinishObjective(&'static str),
+    StartObjective(String),
+    FinishObjective(String),
     ToggleExitModal,
+    ToggleSaveModal,
+    SetSaveName(String),
+    DeleteSave { name: String },
     SetConfig(ViewConfig),
     SetPersistentData(&'static str, Entity),
+    StartAutoIntercept { entity: Entity, target: Entity },
+    CloseAutoIntercept,
+    OpenPorkchop { entity: Entity, target: Entity },
+    ClosePorkchop,
+    OpenLoadout(Entity),
+    CloseLoadout,
+    SetUndockDeltaV(DVec2),
+    SetTransferPlan(TransferPlan),
+    SetPlaybackPaused(bool),
+    SetPlaybackSpeed(f64),
+    SeekPlayback(usize),
 }
 
 impl View {
@@ -79,35 +109,18 @@ impl View {
         let mut model_events = model_events.lock().unwrap();
         model_events.reverse(); // process in the order they were added
         while let Some(event) = model_events.pop() {
-            debug!("Handling model event {:?}", event);
-            match event {
-                ModelEvent::SaveGame { name } => self.save_game(name.as_str()),
-                ModelEvent::TogglePaused => self.toggle_paused(),
-                ModelEvent::IncreaseTimeStepLevel => self.increase_time_step_level(),
-                ModelEvent::DecreaseTimeStepLevel => self.decrease_time_step_level(),
-                ModelEvent::StartWarp { end_time } => self.start_warp(end_time),
-                ModelEvent::SetTimeStep { time_step } => self.set_time_step(time_step),
-                ModelEvent::ForcePause => self.force_pause(),
-                ModelEvent::ForceUnpause => self.force_unpause(),
-                ModelEvent::BuildVessel { vessel_builder } => self.build_vessel(vessel_builder),
-                ModelEvent::DeleteVessel { entity } => self.delete_vessel(entity),
-                ModelEvent::CreateBurn { entity, time } => self.create_burn(entity, time),
-                ModelEvent::AdjustBurn { entity, time, amount } => self.adjust_burn(entity, time, amount),
-                ModelEvent::CreateTurn { entity, time } => self.create_turn(entity, time),
-                ModelEvent::AdjustTurn { entity, time, amount } => self.adjust_turn(entity, time, amount),
-                ModelEvent::SetTarget { entity, target } => self.set_target(entity, target),
-                ModelEvent::CreateFireTorpedo { entity, time } => self.create_fire_torpedo(entity, time),
-                ModelEvent::AdjustFireTorpedo { entity, time, amount } => self.adjust_fire_torpedo(entity, time, amount),
-                ModelEvent::CancelLastTimelineEvent { entity } => self.cancel_last_event(entity),
-                ModelEvent::CreateGuidance { entity, time } => self.enable_torpedo_guidance(entity, time),
-                ModelEvent::CancelCurrentSegment { entity } => self.cancel_current_segment(entity),
-                ModelEvent::Dock { station, entity } => self.dock(station, entity),
-                ModelEvent::Undock { station, entity } => self.undock(station, entity),
-                ModelEvent::StartFuelTransfer { station, location, direction } => self.start_fuel_transfer(station, location, direction),
-                ModelEvent::StopFuelTransfer { station, location } => self.stop_fuel_transfer(station, location),
-                ModelEvent::StartTorpedoTransfer { station, location, direction } => self.start_torpedo_transfer(station, location, direction),
-                ModelEvent::StopTorpedoTransfer { station, location } => self.stop_torpedo_transfer(station, location),
+            // In multiplayer, everything but saving has to wait to be applied with the other clients' commands
+            if let Some(lockstep) = &mut self.lockstep {
+                if !matches!(event, ModelEvent::SaveGame { .. }) {
+                    lockstep.queue(event);
+                    continue;
+                }
+            }
+            // The replay is the only thing allowed to change the model while watching one
+            if self.playback.is_some() && !matches!(event, ModelEvent::SaveGame { .. }) {
+                continue;
             }
+            self.handle_model_event(event);
         }
 
         let view_events = self.view_events.clone();
@@ -159,16 +172,46 @@ impl View {
                 ViewEvent::HideRightClickMenu => self.right_click_menu = None,
                 ViewEvent::ShowDialogue(dialogue) => self.dialogue = Some(dialogue),
                 ViewEvent::CloseDialogue => self.dialogue = None,
-                ViewEvent::StartObjective(objective) => self.objectives.push(Objective::new(objective)),
+                ViewEvent::StartObjective(objective) => self.objectives.push(Objective::new(&objective)),
                 ViewEvent::FinishObjective(objective) => {
                     self.objectives.iter_mut()
                         .find(|x| x.objective() == objective)
                         .map_or_else(|| error!("Attempt to complete nonexistent objective {}", objective), Objective::set_complete);
                 },
                 ViewEvent::ToggleExitModal => self.exit_modal_open = !self.exit_modal_open,
+                ViewEvent::ToggleSaveModal => self.toggle_save_modal(),
+                ViewEvent::SetSaveName(save_name) => self.save_name = save_name,
+                ViewEvent::DeleteSave { name } => self.delete_save(&name),
                 ViewEvent::SetConfig(config) => self.config = config,
                 ViewEvent::SetPersistentData(key, entity) => self.story.add_persistent_data(key, entity),
+                ViewEvent::StartAutoIntercept { entity, target } => self.auto_intercept = AutoIntercept::new(&self.model, entity, target),
+                ViewEvent::CloseAutoIntercept => self.auto_intercept = None,
+                ViewEvent::OpenPorkchop { entity, target } => self.porkchop = Porkchop::new(&self.model, entity, target),
+                ViewEvent::ClosePorkchop => self.porkchop = None,
+                ViewEvent::OpenLoadout(entity) => self.loadout = Some(entity),
+                ViewEvent::CloseLoadout => self.loadout = None,
+                ViewEvent::SetUndockDeltaV(delta_v) => self.undock_delta_v = delta_v,
+                ViewEvent::SetTransferPlan(transfer_plan) => self.transfer_plan = transfer_plan,
+                ViewEvent::SetPlaybackPaused(paused) => self.set_playback_paused(paused),
+                ViewEvent::SetPlaybackSpeed(speed) => self.set_playback_speed(speed),
+                ViewEvent::SeekPlayback(frame) => self.seek_playback(frame),
             }
         }
     }
+
+    pub(crate) fn handle_model_event(&mut self, event: ModelEvent) {
+        debug!("Handling model event {:?}", event);
+        match &event {
+            ModelEvent::SaveGame { name } => {
+                self.save_game(name);
+                return;
+            }
+            ModelEvent::SetTarget { entity, target: Some(target) } => self.add_story_event(StoryEvent::SetTarget { entity: *entity, target: *target }),
+            _ => (),
+        }
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_event(&event);
+        }
+        event.apply(&mut self.model);
+    }
 }
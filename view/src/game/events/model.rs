@@ -1,190 +1,500 @@
-use std::fs;
-
 use log::error;
 use nalgebra_glm::DVec2;
 use transfer_window_model::components::vessel_component::timeline::start_turn::StartTurnEvent;
-use transfer_window_model::model::story_event::StoryEvent;
+use transfer_window_model::components::ComponentType;
+use transfer_window_model::model::Model;
 use transfer_window_model::storage::entity_builder::VesselBuilder;
-use transfer_window_model::{model::time::TimeStep, components::vessel_component::{docking::{DockingPortLocation, ResourceTransferDirection}, timeline::{start_guidance::StartGuidanceEvent, fire_torpedo::FireTorpedoEvent, start_burn::StartBurnEvent, TimelineEvent}}, storage::entity_allocator::Entity};
+use transfer_window_model::{model::time::TimeStep, components::vessel_component::{class::VesselClass, docking::{DockingPort, DockingPortLocation, ResourceTransferDirection}, faction::{Faction, Relation}, resource::ResourceType, slots::ModuleType, timeline::{dock::DockEvent, transfer::TransferEvent, undock::UndockEvent, start_guidance::StartGuidanceEvent, fire_gun::FireGunEvent, fire_torpedo::FireTorpedoEvent, start_burn::StartBurnEvent, TimelineEvent}}, storage::entity_allocator::Entity};
+
+use crate::{game::View, saves};
 
-use crate::game::View;
+use super::ModelEvent;
 
 impl View {
     pub fn save_game(&mut self, name: &str) {
         #[cfg(feature = "profiling")]
         let _span = tracy_client::span!("Save game");
 
-
-        let serialized = self.model.serialize();
+        let session = self.session();
+        let serialized = self.model.serialize_with_session(Some(&session));
         let Ok(serialized) = serialized else {
             error!("Failed to handle save_game; error while serializing: {}", serialized.err().unwrap());
             return;
         };
 
-        if let Err(error) = fs::write("data/saves/".to_string() + name + ".json", serialized) {
+        if let Err(error) = saves::write(name, serialized) {
             error!("Failed to handle save_game; error while saving: {}", error);
         }
+        if self.save_modal_open {
+            self.saves = saves::list();
+        }
     }
-    pub fn toggle_paused(&mut self,) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Toggle paused");
-        self.model.toggle_paused();
-    }
+}
 
-    pub fn increase_time_step_level(&mut self,) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Increase time step level");
-        self.model.increase_time_step_level();
+impl ModelEvent {
+    /// Applies the event to `model` alone, so that it can be replayed
+    /// without a view. Saving needs a view, so is handled by the view itself
+    pub(crate) fn apply(self, model: &mut Model) {
+        match self {
+            ModelEvent::SaveGame { .. } => (),
+            ModelEvent::TogglePaused => toggle_paused(model),
+            ModelEvent::IncreaseTimeStepLevel => increase_time_step_level(model),
+            ModelEvent::DecreaseTimeStepLevel => decrease_time_step_level(model),
+            ModelEvent::StartWarp { end_time } => start_warp(model, end_time),
+            ModelEvent::SetTimeStep { time_step } => set_time_step(model, time_step),
+            ModelEvent::ForcePause => force_pause(model),
+            ModelEvent::ForceUnpause => force_unpause(model),
+            ModelEvent::BuildVessel { vessel_builder } => build_vessel(model, vessel_builder),
+            ModelEvent::DeleteVessel { entity } => delete_vessel(model, entity),
+            ModelEvent::CreateBurn { entity, time } => create_burn(model, entity, time),
+            ModelEvent::AdjustBurn { entity, time, amount } => adjust_burn(model, entity, time, amount),
+            ModelEvent::CreatePlannedBurn { entity, time, delta_v } => create_planned_burn(model, entity, time, delta_v),
+            ModelEvent::CreateTurn { entity, time } => create_turn(model, entity, time),
+            ModelEvent::AdjustTurn { entity, time, amount } => adjust_turn(model, entity, time, amount),
+            ModelEvent::SetTarget { entity, target } => set_target(model, entity, target),
+            ModelEvent::CreateFireTorpedo { entity, time } => create_fire_torpedo(model, entity, time),
+            ModelEvent::AdjustFireTorpedo { entity, time, amount } => adjust_fire_torpedo(model, entity, time, amount),
+            ModelEvent::CreateFireGun { entity, time } => create_fire_gun(model, entity, time),
+            ModelEvent::CancelLastTimelineEvent { entity } => cancel_last_event(model, entity),
+            ModelEvent::CreateGuidance { entity, time } => enable_torpedo_guidance(model, entity, time),
+            ModelEvent::CancelCurrentSegment { entity } => cancel_current_segment(model, entity),
+            ModelEvent::Dock { station, entity } => dock(model, station, entity),
+            ModelEvent::Undock { station, entity } => undock(model, station, entity),
+            ModelEvent::CreateDock { entity, station, time } => create_dock(model, entity, station, time),
+            ModelEvent::CreateUndock { station, entity, time, delta_v } => create_undock(model, station, entity, time, delta_v),
+            ModelEvent::CancelUndock { station, entity } => cancel_undock(model, station, entity),
+            ModelEvent::CreateTransfer { station, entity, time, resource, direction, amount } => create_transfer(model, station, entity, time, resource, direction, amount),
+            ModelEvent::StartTransfer { station, location, resource, direction, amount } => start_transfer(model, station, location, resource, direction, amount),
+            ModelEvent::StopTransfer { station, location, resource } => stop_transfer(model, station, location, resource),
+            ModelEvent::FitModule { entity, slot, module } => fit_module(model, entity, slot, module),
+            ModelEvent::StartMining { entity, asteroid } => start_mining(model, entity, asteroid),
+            ModelEvent::StopMining { entity } => stop_mining(model, entity),
+            ModelEvent::EnqueueBuild { station, class } => enqueue_build(model, station, class),
+            ModelEvent::CancelBuild { station, index } => cancel_build(model, station, index),
+            ModelEvent::ReorderBuild { station, from, to } => reorder_build(model, station, from, to),
+            ModelEvent::SetRelation { faction, other, relation } => set_relation(model, faction, other, relation),
+            ModelEvent::UndoTimelineEdit { entity } => undo_timeline_edit(model, entity),
+            ModelEvent::RedoTimelineEdit { entity } => redo_timeline_edit(model, entity),
+        }
     }
 
-    pub fn decrease_time_step_level(&mut self,) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Decrease time step level");
-        self.model.decrease_time_step_level();
+    /// Whether a player of `faction` may issue the event in multiplayer.
+    /// Vessels can only be commanded by their own faction, and relations
+    /// cannot be changed by players at all
+    pub(crate) fn is_permitted(&self, model: &Model, faction: Faction) -> bool {
+        let entity = match self {
+            ModelEvent::SaveGame { .. }
+                | ModelEvent::TogglePaused
+                | ModelEvent::IncreaseTimeStepLevel
+                | ModelEvent::DecreaseTimeStepLevel
+                | ModelEvent::StartWarp { .. }
+                | ModelEvent::SetTimeStep { .. }
+                | ModelEvent::ForcePause
+                | ModelEvent::ForceUnpause => return true,
+            ModelEvent::SetRelation { .. } => return false,
+            ModelEvent::BuildVessel { vessel_builder } => return vessel_builder.vessel_component.faction() == faction,
+            ModelEvent::DeleteVessel { entity }
+                | ModelEvent::CreateBurn { entity, .. }
+                | ModelEvent::AdjustBurn { entity, .. }
+                | ModelEvent::CreatePlannedBurn { entity, .. }
+                | ModelEvent::CreateTurn { entity, .. }
+                | ModelEvent::AdjustTurn { entity, .. }
+                | ModelEvent::SetTarget { entity, .. }
+                | ModelEvent::CreateFireTorpedo { entity, .. }
+                | ModelEvent::AdjustFireTorpedo { entity, .. }
+                | ModelEvent::CreateFireGun { entity, .. }
+                | ModelEvent::CreateGuidance { entity, .. }
+                | ModelEvent::CancelLastTimelineEvent { entity }
+                | ModelEvent::CancelCurrentSegment { entity }
+                | ModelEvent::Dock { entity, .. }
+                | ModelEvent::Undock { entity, .. }
+                | ModelEvent::CreateDock { entity, .. }
+                | ModelEvent::CreateUndock { entity, .. }
+                | ModelEvent::CancelUndock { entity, .. }
+                | ModelEvent::CreateTransfer { entity, .. }
+                | ModelEvent::FitModule { entity, .. }
+                | ModelEvent::StartMining { entity, .. }
+                | ModelEvent::StopMining { entity }
+                | ModelEvent::UndoTimelineEdit { entity }
+                | ModelEvent::RedoTimelineEdit { entity } => *entity,
+            ModelEvent::StartTransfer { station, .. }
+                | ModelEvent::StopTransfer { station, .. }
+                | ModelEvent::EnqueueBuild { station, .. }
+                | ModelEvent::CancelBuild { station, .. }
+                | ModelEvent::ReorderBuild { station, .. } => *station,
+        };
+        let station = match self {
+            ModelEvent::Dock { station, .. }
+                | ModelEvent::Undock { station, .. }
+                | ModelEvent::CreateDock { station, .. }
+                | ModelEvent::CreateUndock { station, .. }
+                | ModelEvent::CancelUndock { station, .. }
+                | ModelEvent::CreateTransfer { station, .. } => Some(*station),
+            _ => None,
+        };
+        // Clients can send anything, so the entity might not even exist
+        model.entities(vec![ComponentType::VesselComponent]).contains(&entity)
+            && model.vessel_component(entity).faction() == faction
+            && station.is_none_or(|station| is_friendly_station(model, faction, station))
     }
 
-    pub fn start_warp(&mut self, end_time: f64) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Start warp");
-        self.model.start_warp(end_time);
+    /// Whether the event can be applied to `model` without panicking. The
+    /// view only offers events which pass these checks, but clients can
+    /// send anything, so the host checks every command against its own
+    /// model before applying it. Assumes the event is permitted
+    pub(crate) fn can_apply(&self, model: &Model) -> bool {
+        match self {
+            ModelEvent::SaveGame { .. }
+                | ModelEvent::TogglePaused
+                | ModelEvent::IncreaseTimeStepLevel
+                | ModelEvent::DecreaseTimeStepLevel
+                | ModelEvent::SetTimeStep { .. }
+                | ModelEvent::ForcePause
+                | ModelEvent::ForceUnpause
+                | ModelEvent::StopMining { .. }
+                | ModelEvent::CancelUndock { .. }
+                | ModelEvent::SetRelation { .. }
+                | ModelEvent::UndoTimelineEdit { .. }
+                | ModelEvent::RedoTimelineEdit { .. } => true,
+            ModelEvent::StartWarp { end_time } => model.can_warp_to(*end_time),
+            ModelEvent::BuildVessel { vessel_builder } => model.try_orbitable_component(vessel_builder.orbit_builder.parent()).is_some(),
+            ModelEvent::DeleteVessel { entity } => !model.docked(*entity),
+            ModelEvent::CreateBurn { entity, time } => is_editable_time(model, *entity, *time)
+                && StartBurnEvent::can_create_ever(model, *entity)
+                && StartBurnEvent::can_create(model, *entity, *time),
+            ModelEvent::CreatePlannedBurn { entity, time, delta_v } => is_editable_time(model, *entity, *time)
+                && is_finite(*delta_v)
+                && StartBurnEvent::can_create_ever(model, *entity)
+                && StartBurnEvent::can_create(model, *entity, *time),
+            ModelEvent::AdjustBurn { entity, time, amount } => is_finite(*amount)
+                && model.start_burn_event_at_time(*entity, *time).is_some_and(|event| event.can_adjust(model)),
+            ModelEvent::CreateTurn { entity, time } => is_editable_time(model, *entity, *time)
+                && StartTurnEvent::can_create_ever(model, *entity)
+                && StartTurnEvent::can_create(model, *entity, *time),
+            ModelEvent::AdjustTurn { entity, time, amount } => amount.is_finite()
+                && model.start_turn_event_at_time(*entity, *time).is_some_and(|event| event.can_adjust(model)),
+            ModelEvent::SetTarget { entity, target } => target.is_none_or(|target| target != *entity && model.exists(target)),
+            ModelEvent::CreateFireTorpedo { entity, time } => is_editable_time(model, *entity, *time)
+                && FireTorpedoEvent::can_create_ever(model, *entity)
+                && FireTorpedoEvent::can_create(model, *entity, *time),
+            ModelEvent::AdjustFireTorpedo { entity, time, amount } => is_finite(*amount)
+                && model.fire_torpedo_event_at_time(*entity, *time).is_some_and(|event| event.can_adjust(model)),
+            ModelEvent::CreateFireGun { entity, time } => is_editable_time(model, *entity, *time)
+                && FireGunEvent::can_create_ever(model, *entity)
+                && FireGunEvent::can_create(model, *entity, *time),
+            ModelEvent::CreateGuidance { entity, time } => is_editable_time(model, *entity, *time)
+                && StartGuidanceEvent::can_create_ever(model, *entity)
+                && StartGuidanceEvent::can_create(model, *entity, *time),
+            ModelEvent::CancelLastTimelineEvent { entity } => model.vessel_component(*entity).timeline().last_event().is_some_and(|event| event.can_delete(model)),
+            ModelEvent::CancelCurrentSegment { entity } => model.try_path_component(*entity).is_some(),
+            ModelEvent::Dock { station, entity } => !model.docked(*entity)
+                && model.vessel_component(*entity).target() == Some(*station)
+                && model.can_ever_dock_to_target(*entity)
+                && model.can_dock(*entity),
+            ModelEvent::Undock { station, entity } => model.docked(*entity) && model.find_docking_port(*station, *entity).is_some(),
+            ModelEvent::CreateDock { entity, station, time } => is_editable_time(model, *entity, *time)
+                && DockEvent::can_create_ever(model, *entity, *station)
+                && DockEvent::can_create(model, *entity, *station, *time),
+            ModelEvent::CreateUndock { station, entity, time, delta_v } => is_finite(*delta_v) && UndockEvent::can_create(model, *station, *entity, *time),
+            ModelEvent::CreateTransfer { station, entity, time, amount, .. } => amount.is_none_or(f64::is_finite)
+                && TransferEvent::can_create(model, *station, *entity, *time),
+            ModelEvent::StartTransfer { station, location, amount, .. } => amount.is_none_or(f64::is_finite)
+                && has_docked_vessel_at(model, *station, *location),
+            ModelEvent::StopTransfer { station, location, .. } => has_docked_vessel_at(model, *station, *location),
+            ModelEvent::FitModule { entity, slot, module } => model.can_fit_module(*entity, *slot, *module),
+            ModelEvent::StartMining { entity, asteroid } => model.can_mine(*entity, *asteroid),
+            ModelEvent::EnqueueBuild { station, class } => model.can_build(*station, *class),
+            ModelEvent::CancelBuild { station, index } => build_queue_length(model, *station).is_some_and(|length| *index < length),
+            ModelEvent::ReorderBuild { station, from, to } => build_queue_length(model, *station).is_some_and(|length| *from < length && *to < length),
+        }
     }
+}
 
-    pub fn set_time_step(&mut self, time_step: TimeStep) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Set time step");
-        self.model.set_time_step(time_step);
-    }
+/// Stations can be used by their own faction and its allies
+fn is_friendly_station(model: &Model, faction: Faction, station: Entity) -> bool {
+    model.try_vessel_component(station).is_some_and(|vessel_component| vessel_component.has_docking()
+        && (vessel_component.faction() == faction || model.relation(faction, vessel_component.faction()).is_allied()))
+}
 
-    pub fn force_pause(&mut self) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Force pause");
-        self.model.force_pause();
-    }
+/// Events can only be added at a future time on the vessel's trajectory
+/// which does not fall inside a burn, turn or guidance segment
+fn is_editable_time(model: &Model, entity: Entity, time: f64) -> bool {
+    let Some(path_component) = model.try_path_component(entity) else {
+        return false;
+    };
+    let splits_segment = path_component.future_segments()
+        .iter()
+        .any(|segment| !segment.is_orbit() && !segment.is_drag() && segment.start_time() < time && time < segment.end_time());
+    time > model.time()
+        && time < path_component.end_segment().end_time()
+        && !splits_segment
+}
 
-    pub fn force_unpause(&mut self) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Force unpause");
-        self.model.force_unpause();
-    }
+fn has_docked_vessel_at(model: &Model, station: Entity, location: DockingPortLocation) -> bool {
+    model.vessel_component(station)
+        .docking_ports()
+        .and_then(|docking_ports| docking_ports.get(&location))
+        .is_some_and(DockingPort::has_docked_vessel)
+}
 
-    pub fn build_vessel(&mut self, vessel_builder: VesselBuilder) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Build vessel");
-        vessel_builder.build(&mut self.model);
-    }
+fn build_queue_length(model: &Model, station: Entity) -> Option<usize> {
+    Some(model.vessel_component(station).build_queue()?.orders().len())
+}
 
-    pub fn delete_vessel(&mut self, entity: Entity) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Delete vessel");
-        self.model.deallocate(entity);
-    }
+fn is_finite(vector: DVec2) -> bool {
+    vector.x.is_finite() && vector.y.is_finite()
+}
 
-    pub fn cancel_last_event(&mut self, entity: Entity) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Cancel last event");
-        self.model.cancel_last_event(entity);
-    }
+fn toggle_paused(model: &mut Model) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Toggle paused");
+    model.toggle_paused();
+}
 
-    pub fn create_burn(&mut self, entity: Entity, time: f64) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Create burn");
-        let event = TimelineEvent::StartBurn(StartBurnEvent::new(&mut self.model, entity, time));
-        self.model.add_event(entity, event);
-    }
+fn increase_time_step_level(model: &mut Model) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Increase time step level");
+    model.increase_time_step_level();
+}
 
-    pub fn adjust_burn(&mut self, entity: Entity, time: f64, amount: DVec2) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Adjust burn");
-        self.model.start_burn_event_at_time(entity, time)
-            .unwrap()
-            .adjust(&mut self.model, amount);
-    }
+fn decrease_time_step_level(model: &mut Model) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Decrease time step level");
+    model.decrease_time_step_level();
+}
 
-    pub fn create_turn(&mut self, entity: Entity, time: f64) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Create turn");
-        let event = TimelineEvent::StartTurn(StartTurnEvent::new(&mut self.model, entity, time));
-        self.model.add_event(entity, event);
-    }
+fn start_warp(model: &mut Model, end_time: f64) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Start warp");
+    model.start_warp(end_time);
+}
 
-    pub fn adjust_turn(&mut self, entity: Entity, time: f64, amount: f64) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Adjust turn");
-        self.model.start_turn_event_at_time(entity, time)
-            .unwrap()
-            .adjust(&mut self.model, amount);
-    }
+fn set_time_step(model: &mut Model, time_step: TimeStep) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Set time step");
+    model.set_time_step(time_step);
+}
 
-    pub fn set_target(&mut self, entity: Entity, target: Option<Entity>) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Set target");
-        self.model.vessel_component_mut(entity).set_target(target);
-        if let Some(target) = target {
-            self.add_story_event(StoryEvent::SetTarget { entity, target });
-        }
-    }
+fn force_pause(model: &mut Model) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Force pause");
+    model.force_pause();
+}
 
-    pub fn create_fire_torpedo(&mut self, entity: Entity, time: f64) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Fire torpedo");
-        let event = TimelineEvent::FireTorpedo(FireTorpedoEvent::new(&mut self.model, entity, time));
-        self.model.add_event(entity, event);
-    }
+fn force_unpause(model: &mut Model) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Force unpause");
+    model.force_unpause();
+}
 
-    pub fn adjust_fire_torpedo(&mut self, entity: Entity, time: f64, amount: DVec2) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Adjust fire torpedo");
-        self.model.fire_torpedo_event_at_time(entity, time)
-            .unwrap()
-            .adjust(&mut self.model, amount);
-    }
+fn build_vessel(model: &mut Model, vessel_builder: VesselBuilder) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Build vessel");
+    vessel_builder.build(model);
+}
 
-    pub fn enable_torpedo_guidance(&mut self, entity: Entity, time: f64) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Enable torpedo guidance");
-        let event = TimelineEvent::StartGuidance(StartGuidanceEvent::new(&mut self.model, entity, time));
-        self.model.add_event(entity, event);
-    }
+fn delete_vessel(model: &mut Model, entity: Entity) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Delete vessel");
+    model.deallocate(entity);
+}
 
-    pub fn cancel_current_segment(&mut self, entity: Entity) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Cancel current segment");
-        self.model.recompute_entire_trajectory(entity);
+fn cancel_last_event(model: &mut Model, entity: Entity) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Cancel last event");
+    if let Some(event) = model.vessel_component(entity).timeline().last_event() {
+        model.record_timeline_edit(entity, event.time());
     }
+    model.cancel_last_event(entity);
+}
 
-    pub fn dock(&mut self, station: Entity, entity: Entity) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Dock");
-        self.model.dock(station, entity);
-    }
+fn create_burn(model: &mut Model, entity: Entity, time: f64) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Create burn");
+    model.record_timeline_edit(entity, time);
+    let event = TimelineEvent::StartBurn(StartBurnEvent::new(model, entity, time));
+    model.add_event(entity, event);
+}
 
-    pub fn undock(&mut self, station: Entity, entity: Entity) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Undock");
-        self.model.undock(station, entity);
-    }
+fn adjust_burn(model: &mut Model, entity: Entity, time: f64, amount: DVec2) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Adjust burn");
+    model.record_timeline_adjustment(entity, time);
+    model.start_burn_event_at_time(entity, time)
+        .unwrap()
+        .adjust(model, amount);
+}
 
-    pub fn start_fuel_transfer(&mut self, station: Entity, location: DockingPortLocation, direction: ResourceTransferDirection) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Start fuel transfer");
-        self.model.docking_port_mut(station, location).docked_vessel_mut().start_fuel_transfer(direction);
-    }
+fn create_planned_burn(model: &mut Model, entity: Entity, time: f64, delta_v: DVec2) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Create planned burn");
+    model.record_timeline_edit(entity, time);
+    let event = TimelineEvent::StartBurn(StartBurnEvent::new_with_delta_v(model, entity, time, delta_v));
+    model.add_event(entity, event);
+}
 
-    pub fn stop_fuel_transfer(&mut self, station: Entity, location: DockingPortLocation) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Stop fuel transfer");
-        self.model.docking_port_mut(station, location).docked_vessel_mut().stop_fuel_transfer();
-    }
+fn create_turn(model: &mut Model, entity: Entity, time: f64) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Create turn");
+    model.record_timeline_edit(entity, time);
+    let event = TimelineEvent::StartTurn(StartTurnEvent::new(model, entity, time));
+    model.add_event(entity, event);
+}
 
-    pub fn start_torpedo_transfer(&mut self, station: Entity, location: DockingPortLocation, direction: ResourceTransferDirection) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Start torpedo transfer");
-        self.model.docking_port_mut(station, location).docked_vessel_mut().start_torpedo_transfer(direction);
-    }
+fn adjust_turn(model: &mut Model, entity: Entity, time: f64, amount: f64) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Adjust turn");
+    model.record_timeline_adjustment(entity, time);
+    model.start_turn_event_at_time(entity, time)
+        .unwrap()
+        .adjust(model, amount);
+}
 
-    pub fn stop_torpedo_transfer(&mut self, station: Entity, location: DockingPortLocation) {
-        #[cfg(feature = "profiling")]
-        let _span = tracy_client::span!("Stop torpedo transfer");
-        self.model.docking_port_mut(station, location).docked_vessel_mut().stop_torpedo_transfer();
-    }
+fn set_target(model: &mut Model, entity: Entity, target: Option<Entity>) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Set target");
+    model.vessel_component_mut(entity).set_target(target);
+}
+
+fn create_fire_torpedo(model: &mut Model, entity: Entity, time: f64) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Fire torpedo");
+    model.record_timeline_edit(entity, time);
+    let event = TimelineEvent::FireTorpedo(FireTorpedoEvent::new(model, entity, time));
+    model.add_event(entity, event);
+}
+
+fn create_fire_gun(model: &mut Model, entity: Entity, time: f64) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Fire gun");
+    model.record_timeline_edit(entity, time);
+    let event = TimelineEvent::FireGun(FireGunEvent::new(model, entity, time));
+    model.add_event(entity, event);
+}
+
+fn adjust_fire_torpedo(model: &mut Model, entity: Entity, time: f64, amount: DVec2) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Adjust fire torpedo");
+    model.record_timeline_adjustment(entity, time);
+    model.fire_torpedo_event_at_time(entity, time)
+        .unwrap()
+        .adjust(model, amount);
+}
+
+fn enable_torpedo_guidance(model: &mut Model, entity: Entity, time: f64) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Enable torpedo guidance");
+    model.record_timeline_edit(entity, time);
+    let event = TimelineEvent::StartGuidance(StartGuidanceEvent::new(model, entity, time));
+    model.add_event(entity, event);
+}
+
+fn cancel_current_segment(model: &mut Model, entity: Entity) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Cancel current segment");
+    model.forget_timeline_edits(entity);
+    model.recompute_entire_trajectory(entity);
+}
+
+fn dock(model: &mut Model, station: Entity, entity: Entity) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Dock");
+    model.dock(station, entity);
+}
+
+fn undock(model: &mut Model, station: Entity, entity: Entity) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Undock");
+    model.undock(station, entity);
+}
+
+fn create_dock(model: &mut Model, entity: Entity, station: Entity, time: f64) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Create dock");
+    model.record_timeline_edit(entity, time);
+    model.add_event(entity, TimelineEvent::Dock(DockEvent::new(entity, station, time)));
+}
+
+fn create_undock(model: &mut Model, station: Entity, entity: Entity, time: f64, delta_v: DVec2) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Create undock");
+    model.add_event(station, TimelineEvent::Undock(UndockEvent::new(station, entity, time, delta_v)));
+}
+
+fn cancel_undock(model: &mut Model, station: Entity, entity: Entity) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Cancel undock");
+    model.cancel_scheduled_undock(station, entity);
+}
+
+fn create_transfer(model: &mut Model, station: Entity, entity: Entity, time: f64, resource: ResourceType, direction: ResourceTransferDirection, amount: Option<f64>) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Create transfer");
+    model.add_event(station, TimelineEvent::Transfer(TransferEvent::new(station, entity, time, resource, direction, amount)));
+}
+
+fn start_transfer(model: &mut Model, station: Entity, location: DockingPortLocation, resource: ResourceType, direction: ResourceTransferDirection, amount: Option<f64>) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Start transfer");
+    model.docking_port_mut(station, location).docked_vessel_mut().start_transfer(resource, direction, amount);
+}
+
+fn stop_transfer(model: &mut Model, station: Entity, location: DockingPortLocation, resource: ResourceType) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Stop transfer");
+    model.docking_port_mut(station, location).docked_vessel_mut().stop_transfer(resource);
+}
+
+fn fit_module(model: &mut Model, entity: Entity, slot: usize, module: Option<ModuleType>) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Fit module");
+    model.fit_module(entity, slot, module);
+}
+
+fn start_mining(model: &mut Model, entity: Entity, asteroid: Entity) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Start mining");
+    model.start_mining(entity, asteroid);
+}
+
+fn stop_mining(model: &mut Model, entity: Entity) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Stop mining");
+    model.stop_mining(entity);
+}
+
+fn enqueue_build(model: &mut Model, station: Entity, class: VesselClass) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Enqueue build");
+    model.enqueue_build(station, class);
+}
+
+fn cancel_build(model: &mut Model, station: Entity, index: usize) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Cancel build");
+    model.cancel_build(station, index);
+}
+
+fn reorder_build(model: &mut Model, station: Entity, from: usize, to: usize) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Reorder build");
+    model.reorder_build(station, from, to);
+}
+
+fn set_relation(model: &mut Model, faction: Faction, other: Faction, relation: Relation) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Set relation");
+    model.set_relation(faction, other, relation);
+}
+
+fn undo_timeline_edit(model: &mut Model, entity: Entity) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Undo timeline edit");
+    model.undo_timeline_edit(entity);
+}
+
+fn redo_timeline_edit(model: &mut Model, entity: Entity) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Redo timeline edit");
+    model.redo_timeline_edit(entity);
 }
@@ -0,0 +1,51 @@
+use log::error;
+
+use crate::{game::{selected::Selected, View}, saves};
+
+impl View {
+    pub fn toggle_save_modal(&mut self) {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Toggle save modal");
+        self.save_modal_open = !self.save_modal_open;
+        if self.save_modal_open {
+            self.exit_modal_open = false;
+            self.saves = saves::list();
+        }
+    }
+
+    pub fn delete_save(&mut self, name: &str) {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Delete save");
+        saves::delete(name);
+        self.saves = saves::list();
+    }
+
+    pub fn set_playback_paused(&mut self, paused: bool) {
+        if let Some(playback) = &mut self.playback {
+            playback.set_paused(paused);
+        }
+    }
+
+    pub fn set_playback_speed(&mut self, speed: f64) {
+        if let Some(playback) = &mut self.playback {
+            playback.set_speed(speed);
+        }
+    }
+
+    pub fn seek_playback(&mut self, frame: usize) {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Seek playback");
+        let Some(playback) = &mut self.playback else {
+            return;
+        };
+        if let Err(error) = playback.seek(&mut self.model, frame) {
+            error!("Failed to seek replay: {error}");
+        }
+        // Anything selected might not exist at the new time
+        self.selected = Selected::None;
+        self.right_click_menu = None;
+        self.auto_intercept = None;
+        self.porkchop = None;
+        self.loadout = None;
+    }
+}
@@ -1,5 +1,5 @@
 use log::trace;
-use transfer_window_model::{components::vessel_component::faction::Faction, model::state_query::StateQuery};
+use transfer_window_model::model::state_query::StateQuery;
 
 use super::{selected::Selected, util::{should_render, should_render_at_time, ApsisType}, View};
 
@@ -23,6 +23,32 @@ pub fn update(view: &mut View) {
         }
     }
 
+    // Remove selected fire torpedo event if no longer exists
+    if let Selected::FireTorpedo { entity, time, state: _ } = view.selected.clone() {
+        if view.model.fire_torpedo_event_at_time(entity, time).is_none() {
+            trace!("Selected fire torpedo event expired at time={time}");
+            view.selected = Selected::None;
+        }
+    }
+
+    // Delete selected if its entity no longer exists or should not be rendered
+    if let Some(entity) = view.selected.entity(&view.model) {
+        if !view.model.exists(entity) {
+            view.selected = Selected::None;
+        }
+    }
+
+    // Deleted selected if its entity should not be rendered
+    if let Some(entity) = view.selected.entity(&view.model) {
+        let should_render = match view.selected.time() {
+            Some(time) => should_render_at_time(view, entity, time),
+            None => should_render(view, entity),
+        };
+        if !should_render {
+            view.selected = Selected::None;
+        }
+    }
+
     // Remove selected approach if target is no longer targeted
     if let Selected::Approach { type_: _, entity, target, time: _ } = view.selected.clone() {
         if !view.model.vessel_component(entity).has_target() || view.model.vessel_component(entity).target().unwrap() != target {
@@ -33,7 +59,7 @@ pub fn update(view: &mut View) {
 
     // Remove or update selected apsis if apsis no longer exists or is in a different place
     if let Selected::Apsis { type_, entity, time } = view.selected.clone() {
-        if let Some(orbit) = view.model.snapshot_at_observe(time, Faction::Player).segment(entity).as_orbit() {
+        if let Some(orbit) = view.model.snapshot_at_observe(time, view.faction).segment(entity).as_orbit() {
             let expected_time = match type_ {
                 ApsisType::Periapsis => orbit.next_periapsis_time(),
                 ApsisType::Apoapsis => orbit.next_apoapsis_time(),
@@ -53,7 +79,7 @@ pub fn update(view: &mut View) {
     // Remove or update selected encounter if encounter no longer exists or is in a different place
     if let Selected::Encounter { type_, entity, time, from, to } = view.selected {
         let mut any_encounter_matches = false;
-        for encounter in view.model.snapshot_now_observe(Faction::Player).future_encounters(entity) {
+        for encounter in view.model.snapshot_now_observe(view.faction).future_encounters(entity) {
             if encounter.encounter_type() == type_ && encounter.from() == from && encounter.to() == to && (time - encounter.time()).abs() < 10.0 {
                 any_encounter_matches = true;
             }
@@ -62,30 +88,4 @@ pub fn update(view: &mut View) {
             view.selected = Selected::None;
         }
     }
-
-    // Remove selected fire torpedo event if no longer exists
-    if let Selected::FireTorpedo { entity, time, state: _ } = view.selected.clone() {
-        if view.model.fire_torpedo_event_at_time(entity, time).is_none() {
-            trace!("Selected fire torpedo event expired at time={time}");
-            view.selected = Selected::None;
-        }
-    }
-
-    // Delete selected if its entity no longer exists or should not be rendered
-    if let Some(entity) = view.selected.entity(&view.model) {
-        if !view.model.exists(entity) {
-            view.selected = Selected::None;
-        }
-    }
-
-    // Deleted selected if its entity should not be rendered
-    if let Some(entity) = view.selected.entity(&view.model) {
-        let should_render = match view.selected.time() {
-            Some(time) => should_render_at_time(view, entity, time),
-            None => should_render(view, entity),
-        };
-        if !should_render {
-            view.selected = Selected::None;
-        }
-    }
 }
@@ -1,6 +1,6 @@
 use eframe::egui::Key;
 
-use crate::game::{events::{ModelEvent, ViewEvent}, selected::Selected};
+use crate::{controller_events::ControllerEvent, game::{events::{ModelEvent, ViewEvent}, selected::Selected}, saves::QUICKSAVE_NAME};
 
 use super::View;
 
@@ -25,6 +25,14 @@ pub fn update(view: &View) {
             view.add_view_event(ViewEvent::SetDebugWindowOpen(!view.debug_window_open));
         }
 
+        if input.key_pressed(Key::F5) {
+            view.add_model_event(ModelEvent::SaveGame { name: QUICKSAVE_NAME.to_string() });
+        }
+
+        if input.key_pressed(Key::F9) {
+            view.add_controller_event(ControllerEvent::LoadGame { name: QUICKSAVE_NAME.to_string() });
+        }
+
         if input.key_pressed(Key::Delete) {
             match view.selected {
                 Selected::None 
@@ -48,8 +56,26 @@ pub fn update(view: &View) {
             }
         }
 
+        if input.modifiers.command && (input.key_pressed(Key::Z) || input.key_pressed(Key::Y)) {
+            // The selected burn or torpedo may not exist once the edit is undone
+            let entity = match view.selected {
+                Selected::FireTorpedo { entity, .. } => Some(entity),
+                _ => view.selected.entity(&view.model),
+            };
+            if let Some(entity) = entity.filter(|entity| view.model.try_vessel_component(*entity).is_some()) {
+                if input.key_pressed(Key::Z) {
+                    view.add_model_event(ModelEvent::UndoTimelineEdit { entity });
+                } else {
+                    view.add_model_event(ModelEvent::RedoTimelineEdit { entity });
+                }
+                view.add_view_event(ViewEvent::SetSelected(Selected::Vessel(entity)));
+            }
+        }
+
         if input.key_pressed(Key::Escape) {
-            if matches!(view.selected, Selected::None) {
+            if view.save_modal_open {
+                view.add_view_event(ViewEvent::ToggleSaveModal);
+            } else if matches!(view.selected, Selected::None) {
                 view.add_view_event(ViewEvent::ToggleExitModal);
             } else {
                 view.add_view_event(ViewEvent::SetSelected(Selected::None));
@@ -0,0 +1,181 @@
+//! Lockstep multiplayer. Every client runs its own copy of the model, and
+//! only the commands which change it (`ModelEvent`s) are sent over the
+//! network. The server collects each client's commands for a tick and sends
+//! them all back in one order, so every copy of the model applies the same
+//! commands at the same time. Clients also send a hash of their model each
+//! tick, which the server compares to detect desyncs.
+
+use std::{error::Error, io::{self, ErrorKind, Read, Write}, net::TcpStream};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+pub mod client;
+mod protocol;
+pub mod server;
+
+fn invalid_data(error: impl Into<Box<dyn Error + Send + Sync>>) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, error)
+}
+
+/// Messages are JSON prefixed with their length as a big endian u32
+fn write_message<T: Serialize>(stream: &mut TcpStream, message: &T) -> io::Result<()> {
+    let serialized = serde_json::to_vec(message).map_err(invalid_data)?;
+    let length = u32::try_from(serialized.len()).map_err(invalid_data)?;
+    stream.write_all(&length.to_be_bytes())?;
+    stream.write_all(&serialized)
+}
+
+/// Buffers messages which have only partially arrived
+#[derive(Debug, Default)]
+struct MessageReader {
+    buffer: Vec<u8>,
+}
+
+impl MessageReader {
+    fn take<T: DeserializeOwned>(&mut self) -> io::Result<Option<T>> {
+        let Some(length) = self.buffer.get(..4) else {
+            return Ok(None);
+        };
+        let length = u32::from_be_bytes(length.try_into().unwrap()) as usize;
+        if self.buffer.len() < 4 + length {
+            return Ok(None);
+        }
+        let message = serde_json::from_slice(&self.buffer[4..4 + length]).map_err(invalid_data)?;
+        self.buffer.drain(..4 + length);
+        Ok(Some(message))
+    }
+
+    /// Returns None if the stream is non blocking and a whole message has
+    /// not arrived yet, otherwise blocks until one does
+    fn read<T: DeserializeOwned>(&mut self, stream: &mut TcpStream) -> io::Result<Option<T>> {
+        let mut chunk = [0; 4096];
+        loop {
+            if let Some(message) = self.take()? {
+                return Ok(Some(message));
+            }
+            match stream.read(&mut chunk) {
+                Ok(0) => return Err(io::Error::new(ErrorKind::UnexpectedEof, "connection closed")),
+                Ok(read) => self.buffer.extend_from_slice(&chunk[..read]),
+                Err(error) if error.kind() == ErrorKind::WouldBlock => return Ok(None),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use nalgebra_glm::DVec2;
+
+    use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, faction::{Faction, Relation, Stance}, VesselComponent}}, model::Model, storage::entity_builder::VesselBuilder, test_util};
+
+    use crate::game::events::ModelEvent;
+
+    use super::{client::LockstepClient, server::LockstepServer};
+
+    /// Submits the current tick and blocks until the server replies
+    fn step(client: &mut LockstepClient, hash: u64) -> Vec<ModelEvent> {
+        client.advance(client.tick_dt());
+        assert!(client.can_submit());
+        client.submit(hash).unwrap();
+        loop {
+            if let Some(events) = client.poll().unwrap() {
+                return events;
+            }
+        }
+    }
+
+    #[test]
+    fn test_lockstep() {
+        let mut server = LockstepServer::bind("127.0.0.1:0", &Model::default(), 0.05).unwrap();
+        let address = server.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            server.accept(&[Faction::PLAYER, Faction::ENEMY]).unwrap();
+            server.step().unwrap();
+            server.step().unwrap();
+        });
+
+        let (mut a, _) = LockstepClient::connect(address).unwrap();
+        let (mut b, _) = LockstepClient::connect(address).unwrap();
+        assert_eq!(a.faction(), Faction::PLAYER);
+        assert_eq!(b.faction(), Faction::ENEMY);
+
+        // Commands from both clients are applied in client order by both
+        b.queue(ModelEvent::TogglePaused);
+        a.queue(ModelEvent::IncreaseTimeStepLevel);
+        let b_thread = thread::spawn(move || {
+            let events = step(&mut b, 1);
+            (b, events)
+        });
+        let a_events = step(&mut a, 1);
+        let (mut b, b_events) = b_thread.join().unwrap();
+        assert!(matches!(a_events.as_slice(), [ModelEvent::IncreaseTimeStepLevel, ModelEvent::TogglePaused]));
+        assert!(matches!(b_events.as_slice(), [ModelEvent::IncreaseTimeStepLevel, ModelEvent::TogglePaused]));
+        assert_eq!(a.tick(), 1);
+
+        // Mismatched hashes are reported to every client
+        let b_thread = thread::spawn(move || {
+            step(&mut b, 3);
+            b
+        });
+        step(&mut a, 2);
+        let b = b_thread.join().unwrap();
+        server.join().unwrap();
+        assert_eq!(a.desync_tick(), Some(1));
+        assert_eq!(b.desync_tick(), Some(1));
+    }
+
+    #[test]
+    fn test_commands_for_other_factions_rejected() {
+        let mut model = Model::default();
+        let sun = test_util::sun(&mut model);
+        let earth = test_util::earth(&mut model, sun);
+        let station = test_util::station_leo(&mut model, earth);
+        let vessel_builder = VesselBuilder {
+            name: "Enemy".to_string(),
+            vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::ENEMY),
+            orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.011e9, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+        };
+
+        let mut server = LockstepServer::bind("127.0.0.1:0", &model, 0.05).unwrap();
+        let address = server.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            server.accept(&[Faction::ENEMY]).unwrap();
+            server.step().unwrap();
+        });
+
+        let (mut client, _) = LockstepClient::connect(address).unwrap();
+        client.queue(ModelEvent::CancelCurrentSegment { entity: station });
+        client.queue(ModelEvent::SetRelation { faction: Faction::PLAYER, other: Faction::ENEMY, relation: Relation::new(Stance::Allied, true) });
+        client.queue(ModelEvent::BuildVessel { vessel_builder });
+        let events = step(&mut client, 0);
+        server.join().unwrap();
+        assert!(matches!(events.as_slice(), [ModelEvent::BuildVessel { .. }]));
+    }
+
+    #[test]
+    fn test_commands_which_cannot_be_applied_rejected() {
+        let mut model = Model::default();
+        let sun = test_util::sun(&mut model);
+        let earth = test_util::earth(&mut model, sun);
+        let station = test_util::station_leo(&mut model, earth);
+
+        let mut server = LockstepServer::bind("127.0.0.1:0", &model, 0.05).unwrap();
+        let address = server.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            server.accept(&[Faction::PLAYER]).unwrap();
+            server.step().unwrap();
+        });
+
+        let (mut client, _) = LockstepClient::connect(address).unwrap();
+        client.queue(ModelEvent::FitModule { entity: station, slot: 99, module: None });
+        client.queue(ModelEvent::CancelBuild { station, index: 0 });
+        client.queue(ModelEvent::AdjustBurn { entity: station, time: 100.0, amount: DVec2::new(1.0, 0.0) });
+        client.queue(ModelEvent::StopMining { entity: station });
+        let events = step(&mut client, 0);
+        server.join().unwrap();
+        assert!(matches!(events.as_slice(), [ModelEvent::StopMining { .. }]));
+    }
+}
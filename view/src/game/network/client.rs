@@ -0,0 +1,126 @@
+use std::{io, net::{TcpStream, ToSocketAddrs}};
+
+use log::error;
+use transfer_window_model::{components::vessel_component::faction::Faction, model::Model};
+
+use crate::game::events::ModelEvent;
+
+use super::{invalid_data, protocol::{ClientMessage, ServerMessage}, write_message, MessageReader};
+
+/// How far behind real time a client can fall before it stops trying to catch up
+const MAX_CATCH_UP_TICKS: f64 = 10.0;
+
+#[derive(Debug)]
+pub struct LockstepClient {
+    stream: TcpStream,
+    reader: MessageReader,
+    client: usize,
+    faction: Faction,
+    tick_dt: f64,
+    tick: u64,
+    submitted: bool,
+    accumulated_time: f64,
+    queued: Vec<ModelEvent>,
+    desync_tick: Option<u64>,
+}
+
+impl LockstepClient {
+    /// Blocks until the server sends the model that every client starts from
+    /// # Errors
+    /// Returns an error if the connection fails, the server sends something
+    /// unexpected, or the server's vessel class definitions differ from ours
+    pub fn connect(address: impl ToSocketAddrs) -> io::Result<(Self, Model)> {
+        let mut stream = TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+        let mut reader = MessageReader::default();
+        let Some(ServerMessage::Welcome { client, faction, tick_dt, model, class_definitions_hash }) = reader.read(&mut stream)? else {
+            return Err(invalid_data("expected a welcome message"));
+        };
+        if class_definitions_hash != Model::class_definitions_hash() {
+            return Err(invalid_data("server has different vessel class definitions"));
+        }
+        let model = Model::deserialize(&model).map_err(invalid_data)?;
+        let client = Self { stream, reader, client, faction, tick_dt, tick: 0, submitted: false, accumulated_time: 0.0, queued: vec![], desync_tick: None };
+        Ok((client, model))
+    }
+
+    pub fn client(&self) -> usize {
+        self.client
+    }
+
+    /// The faction this client plays as
+    pub fn faction(&self) -> Faction {
+        self.faction
+    }
+
+    /// Amount of real time between ticks
+    pub fn tick_dt(&self) -> f64 {
+        self.tick_dt
+    }
+
+    /// The next tick to be simulated
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// The first tick at which the server found a mismatched hash, if any
+    pub fn desync_tick(&self) -> Option<u64> {
+        self.desync_tick
+    }
+
+    /// Commands are held until they are submitted with the next tick
+    pub(crate) fn queue(&mut self, event: ModelEvent) {
+        self.queued.push(event);
+    }
+
+    pub(crate) fn advance(&mut self, dt: f64) {
+        self.accumulated_time = f64::min(self.accumulated_time + dt, MAX_CATCH_UP_TICKS * self.tick_dt);
+    }
+
+    /// Whether enough time has passed to submit the current tick, which has not been submitted already
+    pub(crate) fn can_submit(&self) -> bool {
+        !self.submitted && self.accumulated_time >= self.tick_dt
+    }
+
+    /// `hash` is the hash of the model at the start of the current tick
+    pub(crate) fn submit(&mut self, hash: u64) -> io::Result<()> {
+        let events = std::mem::take(&mut self.queued);
+        write_message(&mut self.stream, &ClientMessage::Commands { tick: self.tick, events, hash })?;
+        self.submitted = true;
+        self.accumulated_time -= self.tick_dt;
+        Ok(())
+    }
+
+    /// Returns every client's commands for the current tick once the server
+    /// has sent them, after which the caller must apply them and step the
+    /// model by `tick_dt`
+    pub(crate) fn poll(&mut self) -> io::Result<Option<Vec<ModelEvent>>> {
+        // Only reads are non blocking, so that writes are never left half finished
+        self.stream.set_nonblocking(true)?;
+        let message = self.reader.read(&mut self.stream);
+        self.stream.set_nonblocking(false)?;
+        let Some(message) = message? else {
+            return Ok(None);
+        };
+        self.handle(message)
+    }
+
+    fn handle(&mut self, message: ServerMessage) -> io::Result<Option<Vec<ModelEvent>>> {
+        match message {
+            ServerMessage::Tick { tick, events } => {
+                if tick != self.tick || !self.submitted {
+                    return Err(invalid_data(format!("received tick {tick} while on tick {}", self.tick)));
+                }
+                self.tick += 1;
+                self.submitted = false;
+                Ok(Some(events))
+            }
+            ServerMessage::Desync { tick } => {
+                error!("Desync detected at tick {tick}");
+                self.desync_tick.get_or_insert(tick);
+                self.poll()
+            }
+            ServerMessage::Welcome { .. } => Err(invalid_data("unexpected welcome message")),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use transfer_window_model::components::vessel_component::faction::Faction;
+
+use crate::game::events::ModelEvent;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// Commands issued during `tick`, along with the hash of the model at
+    /// the start of the tick
+    Commands { tick: u64, events: Vec<ModelEvent>, hash: u64 },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// Sent once on connecting, with the serialized model every client starts
+    /// from and the hash of the server's vessel class definitions, which
+    /// every client must share
+    Welcome { client: usize, faction: Faction, tick_dt: f64, model: String, class_definitions_hash: u64 },
+    /// Every client's commands for `tick`, in the order they must be applied
+    Tick { tick: u64, events: Vec<ModelEvent> },
+    /// The clients' models had different hashes at the start of `tick`
+    Desync { tick: u64 },
+}
@@ -0,0 +1,112 @@
+use std::{io, net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs}};
+
+use log::{error, info, warn};
+use transfer_window_model::{components::vessel_component::faction::Faction, model::Model};
+
+use super::{invalid_data, protocol::{ClientMessage, ServerMessage}, write_message, MessageReader};
+
+/// Relays commands between clients. The server decides the order commands
+/// are applied in and is the only one which can compare every client's
+/// hash. It also keeps its own copy of the model, so that it can reject
+/// commands for vessels which the sender does not own, or which would
+/// leave the model in a state it cannot handle
+pub struct LockstepServer {
+    listener: TcpListener,
+    clients: Vec<(TcpStream, MessageReader, Faction)>,
+    model: Model,
+    serialized_model: String,
+    tick_dt: f64,
+    tick: u64,
+    desynced: bool,
+}
+
+impl LockstepServer {
+    /// # Errors
+    /// Returns an error if the address cannot be bound or the model cannot be serialized
+    pub fn bind(address: impl ToSocketAddrs, model: &Model, tick_dt: f64) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let serialized_model = model.serialize().map_err(invalid_data)?;
+        // Round trip so that the server starts from exactly the same model as the clients
+        let model = Model::deserialize(&serialized_model).map_err(invalid_data)?;
+        Ok(Self { listener, clients: vec![], model, serialized_model, tick_dt, tick: 0, desynced: false })
+    }
+
+    /// # Errors
+    /// Forwards the error from the listener
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Blocks until a client has connected to play as each of `factions`
+    /// # Errors
+    /// Returns an error if accepting or welcoming a client fails
+    pub fn accept(&mut self, factions: &[Faction]) -> io::Result<()> {
+        for faction in factions {
+            let (mut stream, address) = self.listener.accept()?;
+            stream.set_nodelay(true)?;
+            let client = self.clients.len();
+            info!("Client {client} connected from {address} as faction {}", faction.index());
+            let model = self.serialized_model.clone();
+            let class_definitions_hash = Model::class_definitions_hash();
+            write_message(&mut stream, &ServerMessage::Welcome { client, faction: *faction, tick_dt: self.tick_dt, model, class_definitions_hash })?;
+            self.clients.push((stream, MessageReader::default(), *faction));
+        }
+        Ok(())
+    }
+
+    /// Blocks until every client has sent its commands for the current tick,
+    /// then sends all of them back to every client. Commands which the
+    /// sender's faction is not permitted to issue, or which cannot be
+    /// applied to the model, are dropped
+    /// # Errors
+    /// Returns an error if any client disconnects or sends something unexpected
+    pub fn step(&mut self) -> io::Result<()> {
+        let mut events = vec![];
+        let mut hashes = vec![];
+        for (client, (stream, reader, faction)) in self.clients.iter_mut().enumerate() {
+            let Some(ClientMessage::Commands { tick, events: client_events, hash }) = reader.read(stream)? else {
+                return Err(invalid_data("expected commands"));
+            };
+            if tick != self.tick {
+                return Err(invalid_data(format!("received commands for tick {tick} while on tick {}", self.tick)));
+            }
+            // Each command is checked against the model as left by the ones
+            // before it, which is the order clients will apply them in
+            for event in client_events {
+                if event.is_permitted(&self.model, *faction) && event.can_apply(&self.model) {
+                    event.clone().apply(&mut self.model);
+                    events.push(event);
+                } else {
+                    warn!("Rejected command from client {client} at tick {tick}: {event:?}");
+                }
+            }
+            hashes.push(hash);
+        }
+
+        if !self.desynced && hashes.windows(2).any(|pair| pair[0] != pair[1]) {
+            error!("Desync detected at tick {}: {:?}", self.tick, hashes);
+            self.desynced = true;
+            self.broadcast(&ServerMessage::Desync { tick: self.tick })?;
+        }
+        self.broadcast(&ServerMessage::Tick { tick: self.tick, events })?;
+        self.model.update(self.tick_dt);
+        self.tick += 1;
+        Ok(())
+    }
+
+    /// Steps until a client disconnects
+    /// # Errors
+    /// Returns the error which ended the game
+    pub fn run(&mut self) -> io::Result<()> {
+        loop {
+            self.step()?;
+        }
+    }
+
+    fn broadcast(&mut self, message: &ServerMessage) -> io::Result<()> {
+        for (stream, _, _) in &mut self.clients {
+            write_message(stream, message)?;
+        }
+        Ok(())
+    }
+}
@@ -9,9 +9,11 @@ pub mod exit_modal;
 mod explorer;
 mod fps;
 pub mod objectives;
+mod playback;
 mod right_click_menu;
+mod save_modal;
 mod scale;
-mod selected;
+pub mod selected;
 mod time;
 pub mod widgets;
 
@@ -28,11 +30,13 @@ pub fn draw(view: &View) {
     objectives::update(view);
     scale::update(view);
     time::update(view);
+    playback::update(view);
     selected::update(view);
     right_click_menu::update(view);
 
     styles::ExitModal::apply(&view.context);
     exit_modal::update(view);
+    save_modal::update(view);
     view.context.set_style(Style::default());
 
     styles::DialogueWindow::apply(&view.context);
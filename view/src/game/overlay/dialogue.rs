@@ -1,43 +1,45 @@
 use eframe::egui::{Color32, CursorIcon, Pos2, Response, RichText, Ui, Window};
+use serde::{Deserialize, Serialize};
 use transfer_window_model::model::story_event::StoryEvent;
 
 use crate::{game::View, styles};
 
 use super::widgets::custom_image::CustomImage;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 enum DialogueComponent {
-    Normal(&'static str),
-    Bold(&'static str),
-    Image(&'static str),
+    Normal(String),
+    Bold(String),
+    Image(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dialogue {
-    character: &'static str,
+    character: String,
     components: Vec<DialogueComponent>,
     has_continue: bool,
 }
 
 impl Dialogue {
-    pub fn new(character: &'static str) -> Self {
+    pub fn new(character: &str) -> Self {
+        let character = character.to_string();
         let components = vec![];
         let has_continue = false;
         Self { character, components, has_continue }
     }
 
     pub fn normal(mut self, text: &'static str ) -> Self {
-        self.components.push(DialogueComponent::Normal(text));
+        self.components.push(DialogueComponent::Normal(text.to_string()));
         self
     }
 
     pub fn bold(mut self, text: &'static str ) -> Self {
-        self.components.push(DialogueComponent::Bold(text));
+        self.components.push(DialogueComponent::Bold(text.to_string()));
         self
     }
 
     pub fn image(mut self, text: &'static str ) -> Self {
-        self.components.push(DialogueComponent::Image(text));
+        self.components.push(DialogueComponent::Image(text.to_string()));
         self
     }
 
@@ -52,8 +54,8 @@ impl Dialogue {
             ui.spacing_mut().item_spacing.x = 0.0;
             for component in &self.components {
                 match component {
-                    DialogueComponent::Normal(text) => ui.label(RichText::new(*text).size(14.0).color(Color32::WHITE)),
-                    DialogueComponent::Bold(text) => ui.label(RichText::new(*text).size(14.0).color(Color32::GOLD)),
+                    DialogueComponent::Normal(text) => ui.label(RichText::new(text).size(14.0).color(Color32::WHITE)),
+                    DialogueComponent::Bold(text) => ui.label(RichText::new(text).size(14.0).color(Color32::GOLD)),
                     DialogueComponent::Image(texture) => ui.add(CustomImage::new(view, texture, 14)),
                 };
             }
@@ -25,7 +25,7 @@ pub fn update(view: &View) {
                 ui.horizontal(|ui| {
                     styles::DialogueContinueButton::apply(ui);
 
-                    ui.add_space(110.0); // ffs egui why isn't this centered
+                    ui.add_space(70.0); // ffs egui why isn't this centered
 
                     let response = ui.button(RichText::new("Cancel").strong().monospace().size(12.0));
                     if response.hovered() {
@@ -37,6 +37,16 @@ pub fn update(view: &View) {
 
                     ui.add_space(20.0);
 
+                    let response = ui.button(RichText::new("Save").strong().monospace().size(12.0));
+                    if response.hovered() {
+                        view.context.set_cursor_icon(CursorIcon::PointingHand);
+                    }
+                    if response.clicked() {
+                        view.add_view_event(ViewEvent::ToggleSaveModal);
+                    }
+
+                    ui.add_space(20.0);
+
                     let response = ui.button(RichText::new("Exit").strong().monospace().size(12.0));
                     if response.hovered() {
                         view.context.set_cursor_icon(CursorIcon::PointingHand);
@@ -3,23 +3,25 @@ use std::collections::HashSet;
 use eframe::{egui::{Align2, Color32, RichText, Ui, Window}, epaint};
 use transfer_window_model::{components::{vessel_component::faction::Faction, ComponentType}, model::state_query::StateQuery, storage::entity_allocator::Entity};
 
-use crate::game::{events::ViewEvent, selected::Selected, util::{orbitable_texture, vessel_texture}};
+use crate::game::{events::ViewEvent, selected::Selected, util::{orbitable_texture, standing, vessel_texture, Standing}};
 
 use super::{widgets::{custom_image::CustomImage, custom_image_button::CustomCircularImageButton, labels::draw_title}, View};
 
-pub fn vessel_hover_circle_color(faction: Faction) -> Color32 {
-    match faction {
-        Faction::Player => Color32::from_rgb(45, 90, 120),
-        Faction::Ally => Color32::from_rgb(0, 120, 90),
-        Faction::Enemy => Color32::from_rgb(120, 45, 0),
+pub fn vessel_hover_circle_color(view: &View, faction: Faction) -> Color32 {
+    match standing(view, faction) {
+        Standing::Own => Color32::from_rgb(45, 90, 120),
+        Standing::Allied => Color32::from_rgb(0, 120, 90),
+        Standing::Neutral => Color32::from_rgb(90, 90, 90),
+        Standing::Hostile => Color32::from_rgb(120, 45, 0),
     }
 }
 
-pub fn vessel_normal_circle_color(faction: Faction) -> Color32 {
-    match faction {
-        Faction::Player => Color32::from_rgb(30, 60, 80),
-        Faction::Ally => Color32::from_rgb(0, 80, 60),
-        Faction::Enemy => Color32::from_rgb(80, 30, 0),
+pub fn vessel_normal_circle_color(view: &View, faction: Faction) -> Color32 {
+    match standing(view, faction) {
+        Standing::Own => Color32::from_rgb(30, 60, 80),
+        Standing::Allied => Color32::from_rgb(0, 80, 60),
+        Standing::Neutral => Color32::from_rgb(60, 60, 60),
+        Standing::Hostile => Color32::from_rgb(80, 30, 0),
     }
 }
 
@@ -40,11 +42,12 @@ fn order_by_altitude(view: &View, mut entities: Vec<Entity>) -> Vec<Entity> {
 }
 
 fn child_vessel_entities(view: &View, parent_entity: Entity) -> Vec<Entity> {
-    let entities: Vec<Entity> = view.model.entities(vec![ComponentType::VesselComponent])
+    let snapshot = view.model.snapshot_now_observe(view.faction);
+    let entities: Vec<Entity> = snapshot.entities(vec![ComponentType::VesselComponent])
         .into_iter()
         .filter(|entity| view.model.try_vessel_component(*entity).is_some())
         .filter(|entity| !view.model.vessel_component(*entity).is_ghost())
-        .filter(|entity| view.model.parent(*entity).is_some_and(|parent| parent == parent_entity))
+        .filter(|entity| snapshot.parent(*entity).is_some_and(|parent| parent == parent_entity))
         .collect();
     order_by_altitude(view, entities)
 }
@@ -80,8 +83,8 @@ fn render_vessel(view: &View, ui: &mut Ui, entity: Entity) {
     let faction = vessel_component.faction();
     ui.add_space(-7.0);
     let button = CustomCircularImageButton::new(view, texture, 24)
-        .with_normal_color(vessel_normal_circle_color(faction))
-        .with_hover_color(vessel_hover_circle_color(faction));
+        .with_normal_color(vessel_normal_circle_color(view, faction))
+        .with_hover_color(vessel_hover_circle_color(view, faction));
     if ui.add(button).clicked() {
         view.add_view_event(ViewEvent::SetSelected(Selected::Vessel(entity)));
     }
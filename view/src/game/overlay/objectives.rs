@@ -1,20 +1,23 @@
 use eframe::{egui::{Align2, Color32, RichText, Ui, Window}, epaint};
 
+use serde::{Deserialize, Serialize};
+
 use crate::game::View;
 
 use super::widgets::custom_image::CustomImage;
 
 const FADE_OUT_TIME: f32 = 1.0;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Objective {
-    objective: &'static str,
+    objective: String,
     complete: bool,
     opacity: f32,
 }
 
 impl Objective {
-    pub fn new(objective: &'static str) -> Self {
+    pub fn new(objective: &str) -> Self {
+        let objective = objective.to_string();
         let complete = false;
         let opacity = 1.0;
         Self { objective, complete, opacity }
@@ -36,7 +39,7 @@ impl Objective {
     }
     
     pub fn objective(&self) -> &str {
-        self.objective
+        &self.objective
     }
 
     pub fn draw(&self, view: &View, ui: &mut Ui) {
@@ -51,7 +54,7 @@ impl Objective {
             ui.vertical(|ui| ui.add_space(22.0));
             ui.horizontal_wrapped(|ui| {
                 ui.set_width(200.0);
-                ui.label(RichText::new(self.objective)
+                ui.label(RichText::new(&self.objective)
                     .monospace()
                     .color(Color32::from_rgba_unmultiplied(255, 255, 255, (self.opacity * 255.0) as u8)));
             })
@@ -0,0 +1,44 @@
+use eframe::{egui::{Align2, RichText, Slider, Window}, epaint};
+
+use crate::game::{events::ViewEvent, replay::playback::PLAYBACK_SPEEDS, util::format_time, View};
+
+pub fn update(view: &View) {
+    let Some(playback) = &view.playback else {
+        return;
+    };
+
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Update playback");
+    Window::new("Replay")
+        .title_bar(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_BOTTOM, epaint::vec2(0.0, 0.0))
+        .show(&view.context.clone(), |ui| {
+            ui.horizontal(|ui| {
+                let pause_text = if playback.is_paused() { "Play" } else { "Pause" };
+                if ui.button(pause_text).clicked() {
+                    view.add_view_event(ViewEvent::SetPlaybackPaused(!playback.is_paused()));
+                }
+
+                for speed in PLAYBACK_SPEEDS {
+                    if ui.selectable_label(playback.speed() == speed, format!("{speed}x")).clicked() {
+                        view.add_view_event(ViewEvent::SetPlaybackSpeed(speed));
+                    }
+                }
+            });
+
+            // Seeking re-simulates, so only happens once the slider is let go
+            let frames = playback.replay().frames();
+            let mut frame = playback.frame();
+            let slider = Slider::new(&mut frame, 0..=playback.frame_count())
+                .custom_formatter(|frame, _| frames.get(frame as usize).map_or_else(|| format_time(playback.replay().duration()), |frame| format_time(frame.time())));
+            let response = ui.add(slider);
+            if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                view.add_view_event(ViewEvent::SeekPlayback(frame));
+            }
+
+            if let Some(desync) = playback.desync() {
+                ui.label(RichText::new(format!("Diverged from the recording at frame {desync}")).color(epaint::Color32::RED));
+            }
+        });
+}
@@ -1,6 +1,6 @@
 use eframe::egui::{Align2, Ui, Window};
 use nalgebra_glm::vec2;
-use transfer_window_model::{components::vessel_component::faction::Faction, model::state_query::StateQuery, storage::entity_allocator::Entity};
+use transfer_window_model::{model::state_query::StateQuery, storage::entity_allocator::Entity};
 
 use crate::{game::{events::{ModelEvent, ViewEvent}, View}, styles};
 
@@ -55,7 +55,7 @@ fn draw(view: &View, right_clicked: Entity) {
                 draw_focus(view, ui, right_clicked);
                 if let Some(selected) = view.selected.entity(&view.model) {
                     if let Some(vessel_component) = view.model.try_vessel_component(selected) {
-                        if Faction::Player.can_control(vessel_component.faction()) {
+                        if view.faction.can_control(vessel_component.faction()) {
                             draw_set_target(view, ui, right_clicked, selected);
                         }
                     }
@@ -0,0 +1,65 @@
+use eframe::{egui::{Align2, Button, CursorIcon, RichText, TextEdit, Window}, epaint};
+
+use crate::controller_events::ControllerEvent;
+use crate::{game::{events::{ModelEvent, ViewEvent}, View}, styles};
+
+use super::widgets::{labels::draw_title, save_list::{draw_save_list, SaveListAction}};
+
+fn is_valid_save_name(name: &str) -> bool {
+    !name.trim().is_empty() && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ' '))
+}
+
+pub fn update(view: &View) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Update save modal");
+
+    if !view.save_modal_open {
+        return;
+    }
+
+    Window::new("Save modal")
+        .title_bar(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, epaint::vec2(0.0, 0.0))
+        .show(&view.context.clone(), |ui| {
+            draw_title(ui, "Saves");
+            ui.add_space(10.0);
+
+            match draw_save_list(&view.context, ui, &view.saves, true) {
+                Some(SaveListAction::Load(name)) => view.add_controller_event(ControllerEvent::LoadGame { name }),
+                Some(SaveListAction::Overwrite(name)) => view.add_model_event(ModelEvent::SaveGame { name }),
+                Some(SaveListAction::Delete(name)) => view.add_view_event(ViewEvent::DeleteSave { name }),
+                None => (),
+            }
+
+            ui.add_space(20.0);
+            ui.horizontal(|ui| {
+                styles::DialogueContinueButton::apply(ui);
+
+                let mut save_name = view.save_name.clone();
+                if ui.add(TextEdit::singleline(&mut save_name).hint_text("Save name").desired_width(200.0)).changed() {
+                    view.add_view_event(ViewEvent::SetSaveName(save_name.clone()));
+                }
+
+                let can_save = is_valid_save_name(&save_name);
+                let response = ui.add_enabled(can_save, Button::new(RichText::new("Save").strong().monospace().size(12.0)));
+                if response.hovered() {
+                    view.context.set_cursor_icon(CursorIcon::PointingHand);
+                }
+                if response.clicked() {
+                    view.add_model_event(ModelEvent::SaveGame { name: save_name.trim().to_string() });
+                    view.add_view_event(ViewEvent::SetSaveName(String::new()));
+                }
+
+                ui.add_space(20.0);
+
+                let response = ui.button(RichText::new("Close").strong().monospace().size(12.0));
+                if response.hovered() {
+                    view.context.set_cursor_icon(CursorIcon::PointingHand);
+                }
+                if response.clicked() {
+                    view.add_view_event(ViewEvent::ToggleSaveModal);
+                }
+            });
+        });
+}
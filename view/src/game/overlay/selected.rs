@@ -13,7 +13,9 @@ mod burn_point;
 mod turn_point;
 mod guidance_point;
 mod orbit_point;
-mod vessel;
+pub mod loadout;
+pub mod porkchop;
+pub mod vessel;
 
 pub fn update(view: &View) {
     #[cfg(feature = "profiling")]
@@ -32,4 +34,6 @@ pub fn update(view: &View) {
     intercept::update(view);
     fire_torpedo::update(view);
     vessel::update(view);
+    porkchop::update(view);
+    loadout::update(view);
 }
@@ -0,0 +1,105 @@
+use eframe::{egui::{Align2, ComboBox, Grid, RichText, Ui, Window}, epaint};
+use transfer_window_model::{components::vessel_component::slots::{ModuleType, Slot}, storage::entity_allocator::Entity};
+
+use crate::game::{events::{ModelEvent, ViewEvent}, overlay::widgets::labels::{draw_key, draw_subtitle, draw_title, draw_value}, util::format_distance, View};
+
+fn module_name(module: Option<ModuleType>) -> &'static str {
+    module.map_or("Empty", |module| module.name())
+}
+
+fn draw_slot(view: &View, ui: &mut Ui, entity: Entity, index: usize, slot: &Slot) {
+    draw_key(ui, slot.type_().name());
+    let can_refit = view.model.can_refit(entity);
+    ui.add_enabled_ui(can_refit, |ui| {
+        ComboBox::from_id_source(("Loadout slot", entity, index))
+            .selected_text(module_name(slot.module()))
+            .show_ui(ui, |ui| {
+                let options = ModuleType::ALL.into_iter()
+                    .filter(|module| module.slot_type() == slot.type_())
+                    .map(Some);
+                for module in std::iter::once(None).chain(options) {
+                    let selected = module == slot.module();
+                    if ui.selectable_label(selected, module_name(module)).clicked()
+                            && !selected
+                            && view.model.can_fit_module(entity, index, module) {
+                        view.add_model_event(ModelEvent::FitModule { entity, slot: index, module });
+                    }
+                }
+            });
+    });
+    ui.end_row();
+}
+
+fn draw_stats(view: &View, ui: &mut Ui, entity: Entity) {
+    let vessel_component = view.model.vessel_component(entity);
+    Grid::new("Loadout stats grid").show(ui, |ui| {
+        draw_key(ui, "Dry mass");
+        draw_value(ui, &format!("{:.1} t", vessel_component.dry_mass() / 1000.0));
+        ui.end_row();
+
+        draw_key(ui, "Max ΔV");
+        draw_value(ui, &format!("{:.1} m/s", vessel_component.max_dv()));
+        ui.end_row();
+
+        draw_key(ui, "Fuel capacity");
+        draw_value(ui, &format!("{:.0} L", vessel_component.fuel_capacity_litres()));
+        ui.end_row();
+
+        draw_key(ui, "Torpedo capacity");
+        draw_value(ui, &vessel_component.torpedo_capacity().to_string());
+        ui.end_row();
+
+        draw_key(ui, "Point defense range");
+        draw_value(ui, &vessel_component.point_defense().map_or("-".to_string(), |point_defense| format_distance(point_defense.range())));
+        ui.end_row();
+
+        draw_key(ui, "Gun muzzle speed");
+        draw_value(ui, &vessel_component.gun().map_or("-".to_string(), |gun| format!("{:.0} m/s", gun.muzzle_speed())));
+        ui.end_row();
+
+        draw_key(ui, "Sensor range");
+        draw_value(ui, &format_distance(vessel_component.sensor_range()));
+        ui.end_row();
+
+        draw_key(ui, "Mining rate");
+        draw_value(ui, &format!("{:.1} kg/s", vessel_component.mining_rate()));
+        ui.end_row();
+    });
+}
+
+pub fn update(view: &View) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Update loadout");
+    let Some(entity) = view.loadout else {
+        return;
+    };
+    let Some(vessel_component) = view.model.try_vessel_component(entity) else {
+        view.add_view_event(ViewEvent::CloseLoadout);
+        return;
+    };
+
+    Window::new("Loadout")
+        .title_bar(false)
+        .resizable(false)
+        .anchor(Align2::RIGHT_CENTER, epaint::vec2(0.0, 0.0))
+        .show(&view.context.clone(), |ui| {
+            draw_title(ui, "Loadout");
+            draw_subtitle(ui, &view.model.name_component(entity).name());
+            if !view.model.can_refit(entity) {
+                ui.label(RichText::new("Dock at a station to refit").size(12.0).weak());
+            }
+
+            Grid::new("Loadout slots grid").show(ui, |ui| {
+                for (index, slot) in vessel_component.slots().iter().enumerate() {
+                    draw_slot(view, ui, entity, index, slot);
+                }
+            });
+
+            ui.separator();
+            draw_stats(view, ui, entity);
+
+            if ui.button("Close").clicked() {
+                view.add_view_event(ViewEvent::CloseLoadout);
+            }
+        });
+}
@@ -1,10 +1,14 @@
-use eframe::{egui::{Align2, Grid, Ui, Window}, epaint};
-use transfer_window_model::{components::{path_component::orbit::Orbit, vessel_component::faction::Faction}, model::{state_query::StateQuery, story_event::StoryEvent}, storage::entity_allocator::Entity};
+use eframe::{egui::{Align2, Grid, Slider, Ui, Window}, epaint};
+use transfer_window_model::{components::{path_component::orbit::Orbit, vessel_component::docking::DockingPort}, model::{state_query::StateQuery, story_event::StoryEvent}, storage::entity_allocator::Entity};
 
-use crate::{game::{events::{ModelEvent, ViewEvent}, overlay::widgets::{buttons::{draw_create_burn, draw_create_turn, draw_enable_guidance, draw_fire_torpedo, draw_next, draw_previous, draw_select_vessel, draw_warp_to}, labels::{draw_info_at_time_with_orbits, draw_key, draw_subtitle, draw_time_until, draw_title, draw_value}}, selected::{util::BurnState, Selected}, util::{format_distance, format_time}, View}, styles};
+use crate::{game::{events::{ModelEvent, ViewEvent}, overlay::widgets::{buttons::{draw_create_burn, draw_create_dock, draw_create_turn, draw_create_undock, draw_enable_guidance, draw_fire_gun, draw_fire_torpedo, draw_next, draw_previous, draw_select_vessel, draw_warp_to}, labels::{draw_info_at_time_with_orbits, draw_key, draw_subtitle, draw_time_until, draw_title, draw_value}}, selected::{util::BurnState, Selected}, util::{format_distance, format_time}, View}, styles};
 
 use super::vessel::visual_timeline::draw_visual_timeline;
 
+/// Undocking only pushes a vessel clear of the station, anything more is
+/// left to its engines
+const MAX_UNDOCK_DELTA_V: f64 = 5.0;
+
 fn draw_controls(view: &View, entity: Entity, ui: &mut Ui, time: f64) {
     ui.horizontal(|ui| {
         styles::SelectedMenuButton::apply(ui);
@@ -29,7 +33,7 @@ fn draw_controls(view: &View, entity: Entity, ui: &mut Ui, time: f64) {
 
         
         let faction = view.model.vessel_component(entity).faction();
-        if Faction::Player.can_control(faction) {
+        if view.faction.can_control(faction) {
             if draw_create_burn(view, ui, entity, time) {
                 let selected = Selected::Burn { entity, time, state: BurnState::Selected };
                 view.add_model_event(ModelEvent::CreateBurn { entity, time });
@@ -57,11 +61,56 @@ fn draw_controls(view: &View, entity: Entity, ui: &mut Ui, time: f64) {
                 view.add_view_event(ViewEvent::SetSelected(selected));
                 view.add_story_event(StoryEvent::FireTorpedo(entity));
             }
+
+            if draw_fire_gun(view, ui, entity, time) {
+                view.add_model_event(ModelEvent::CreateFireGun { entity, time });
+            }
+
+            if draw_create_dock(view, ui, entity, time) {
+                let station = view.model.vessel_component(entity).target().unwrap();
+                view.add_model_event(ModelEvent::CreateDock { entity, station, time });
+                view.add_view_event(ViewEvent::SetSelected(Selected::Vessel(entity)));
+            }
+
+            if let Some(docking_ports) = view.model.vessel_component(entity).docking_ports() {
+                for docking_port in docking_ports.values().filter(|docking_port| docking_port.has_docked_vessel()) {
+                    let docked = docking_port.docked_vessel().entity();
+                    if draw_create_undock(view, ui, entity, docked, time) {
+                        view.add_model_event(ModelEvent::CreateUndock { station: entity, entity: docked, time, delta_v: view.undock_delta_v });
+                    }
+                }
+            }
         }
 
     });
 }
 
+/// Departure delta-v for scheduled undocks, relative to the station
+fn draw_undock_delta_v(view: &View, ui: &mut Ui, entity: Entity) {
+    let faction = view.model.vessel_component(entity).faction();
+    let Some(docking_ports) = view.model.vessel_component(entity).docking_ports() else {
+        return;
+    };
+    if !view.faction.can_control(faction) || !docking_ports.values().any(DockingPort::has_docked_vessel) {
+        return;
+    }
+
+    draw_subtitle(ui, "Undock");
+    let mut delta_v = view.undock_delta_v;
+    Grid::new("Selected point undock delta-v").show(ui, |ui| {
+        draw_key(ui, "Prograde");
+        ui.add(Slider::new(&mut delta_v.x, -MAX_UNDOCK_DELTA_V..=MAX_UNDOCK_DELTA_V).suffix(" m/s"));
+        ui.end_row();
+
+        draw_key(ui, "Radial");
+        ui.add(Slider::new(&mut delta_v.y, -MAX_UNDOCK_DELTA_V..=MAX_UNDOCK_DELTA_V).suffix(" m/s"));
+        ui.end_row();
+    });
+    if delta_v != view.undock_delta_v {
+        view.add_view_event(ViewEvent::SetUndockDeltaV(delta_v));
+    }
+}
+
 pub fn draw_orbit_labels(view: &View, ui: &mut Ui, orbit: &Orbit) {
     if orbit.is_ellipse() {
         draw_key(ui, "Orbiting");
@@ -99,7 +148,7 @@ pub fn draw_orbit_labels(view: &View, ui: &mut Ui, orbit: &Orbit) {
 }
 
 fn draw_orbit(view: &View, ui: &mut Ui, entity: Entity, time: f64) {
-    let snapshot = view.model.snapshot_at_observe(time, Faction::Player);
+    let snapshot = view.model.snapshot_at_observe(time, view.faction);
     let orbit = snapshot.orbit(entity);
     draw_subtitle(ui, "Orbit");
     Grid::new("Selected point orbit info").show(ui, |ui| {
@@ -122,6 +171,7 @@ pub fn update(view: &View) {
         draw_title(ui, "Orbit");
         draw_time_until(view, ui, time);
         draw_controls(view, entity, ui, time);
+        draw_undock_delta_v(view, ui, entity);
         draw_info_at_time_with_orbits(view, ui, entity, time);
         draw_orbit(view, ui, entity, time);
         draw_visual_timeline(view, ui, entity, time, true);
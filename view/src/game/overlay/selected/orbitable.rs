@@ -18,6 +18,21 @@ fn draw_info(ui: &mut Ui, orbitable_component: &OrbitableComponent) {
     });
 }
 
+fn draw_deposits(ui: &mut Ui, orbitable_component: &OrbitableComponent) {
+    if orbitable_component.deposits().is_empty() {
+        return;
+    }
+
+    draw_subtitle(ui, "Deposits");
+    Grid::new("Orbitable deposits grid").show(ui, |ui| {
+        for deposit in orbitable_component.deposits() {
+            draw_key(ui, deposit.resource().name());
+            draw_value(ui, &format!("{} kg", deposit.remaining().round()));
+            ui.end_row();
+        }
+    });
+}
+
 fn draw_orbit(ui: &mut Ui, orbitable_component: &OrbitableComponent, view: &View) {
     let Some(orbit) = orbitable_component.orbit() else {
         return;
@@ -61,6 +76,7 @@ pub fn update(view: &View) {
         draw_title(ui, &name);
         draw_controls(view, ui, entity);
         draw_info(ui, orbitable_component);
+        draw_deposits(ui, orbitable_component);
         draw_orbit(ui, orbitable_component, view);
     });
 }
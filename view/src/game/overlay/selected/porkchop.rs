@@ -0,0 +1,215 @@
+use std::{sync::mpsc::{channel, Receiver}, thread};
+
+use eframe::{egui::{Align2, Color32, Pos2, Rect, RichText, Rounding, Sense, Window}, epaint};
+use log::error;
+use transfer_window_model::{components::vessel_component::timeline::start_burn::StartBurnEvent, model::Model, storage::entity_allocator::Entity};
+
+use crate::game::{events::{ModelEvent, ViewEvent}, overlay::widgets::labels::{draw_subtitle, draw_title}, selected::Selected, util::format_time, View};
+
+use super::vessel::auto_intercept::earliest_departure_time;
+
+const DEPARTURE_CELLS: usize = 48;
+const FLIGHT_TIME_CELLS: usize = 32;
+const CELL_SIZE: f32 = 6.0;
+/// Departures are plotted over this many of the longest flight times
+const DEPARTURE_WINDOW_FLIGHT_TIMES: f64 = 2.0;
+
+#[allow(clippy::cast_precision_loss)]
+fn interpolate(min: f64, max: f64, i: usize, count: usize) -> f64 {
+    min + (max - min) * i as f64 / (count - 1) as f64
+}
+
+/// Green for the cheapest transfer through to red for the most expensive
+fn dv_color(proportion: f32) -> Color32 {
+    let proportion = proportion.clamp(0.0, 1.0);
+    let red = (510.0 * proportion).min(255.0) as u8;
+    let green = (510.0 * (1.0 - proportion)).min(255.0) as u8;
+    Color32::from_rgb(red, green, 40)
+}
+
+/// Delta-v required for transfers between a vessel and its target over a grid of
+/// departure times and flight times. The grid is computed on a separate thread
+/// using a copy of the model, one departure time at a time, so the plot fills
+/// in as results arrive
+pub struct Porkchop {
+    entity: Entity,
+    target: Entity,
+    earliest_departure_time: f64,
+    latest_departure_time: f64,
+    min_flight_time: f64,
+    max_flight_time: f64,
+    /// Indexed by departure then flight time. None if there is no transfer, it
+    /// cannot be afforded, or it has not been computed yet
+    cells: Vec<Option<f64>>,
+    computed_departures: usize,
+    receiver: Receiver<(usize, Vec<Option<f64>>)>,
+}
+
+impl Porkchop {
+    pub fn new(model: &Model, entity: Entity, target: Entity) -> Option<Self> {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Start porkchop");
+        let earliest_departure_time = earliest_departure_time(model, entity);
+        let (min_flight_time, max_flight_time) = model.intercept_flight_time_range(entity, target, earliest_departure_time)?;
+        let latest_departure_time = earliest_departure_time + max_flight_time * DEPARTURE_WINDOW_FLIGHT_TIMES;
+
+        let serialized = match model.serialize() {
+            Ok(serialized) => serialized,
+            Err(err) => {
+                error!("Failed to copy model for porkchop plot: {}", err);
+                return None;
+            }
+        };
+
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            let model = match Model::deserialize(&serialized) {
+                Ok(model) => model,
+                Err(err) => {
+                    error!("Failed to copy model for porkchop plot: {}", err);
+                    return;
+                }
+            };
+            for i in 0..DEPARTURE_CELLS {
+                let time = interpolate(earliest_departure_time, latest_departure_time, i, DEPARTURE_CELLS);
+                let column = if StartBurnEvent::can_create(&model, entity, time) {
+                    let available_dv = model.available_dv_at_time(entity, time);
+                    (0..FLIGHT_TIME_CELLS)
+                        .map(|j| interpolate(min_flight_time, max_flight_time, j, FLIGHT_TIME_CELLS))
+                        .map(|flight_time| model.intercept_delta_v(entity, target, time, flight_time))
+                        .map(|delta_v| delta_v.map(|delta_v| delta_v.magnitude()).filter(|dv| *dv < available_dv))
+                        .collect()
+                } else {
+                    vec![None; FLIGHT_TIME_CELLS]
+                };
+                // The receiver is dropped when the plot is closed
+                if sender.send((i, column)).is_err() {
+                    return;
+                }
+            }
+        });
+
+        let cells = vec![None; DEPARTURE_CELLS * FLIGHT_TIME_CELLS];
+        Some(Self { entity, target, earliest_departure_time, latest_departure_time, min_flight_time, max_flight_time, cells, computed_departures: 0, receiver })
+    }
+
+    /// Collects any results the worker thread has finished since the last frame
+    pub fn receive(&mut self) {
+        while let Ok((i, column)) = self.receiver.try_recv() {
+            for (j, dv) in column.into_iter().enumerate() {
+                self.cells[i * FLIGHT_TIME_CELLS + j] = dv;
+            }
+            self.computed_departures += 1;
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.computed_departures == DEPARTURE_CELLS
+    }
+
+    fn departure_time(&self, i: usize) -> f64 {
+        interpolate(self.earliest_departure_time, self.latest_departure_time, i, DEPARTURE_CELLS)
+    }
+
+    fn flight_time(&self, j: usize) -> f64 {
+        interpolate(self.min_flight_time, self.max_flight_time, j, FLIGHT_TIME_CELLS)
+    }
+
+    fn cell(&self, i: usize, j: usize) -> Option<f64> {
+        self.cells[i * FLIGHT_TIME_CELLS + j]
+    }
+
+    fn dv_range(&self) -> Option<(f64, f64)> {
+        let computed = self.cells.iter().filter_map(|dv| *dv);
+        let min = computed.clone().reduce(f64::min)?;
+        let max = computed.reduce(f64::max)?;
+        Some((min, max))
+    }
+}
+
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn update(view: &View) {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Update porkchop");
+    let Some(porkchop) = &view.porkchop else {
+        return;
+    };
+    let Selected::Vessel(entity) = view.selected.clone() else {
+        return;
+    };
+    if entity != porkchop.entity || view.model.try_vessel_component(entity).is_none() {
+        return;
+    }
+
+    Window::new("Porkchop plot")
+        .title_bar(false)
+        .resizable(false)
+        .anchor(Align2::RIGHT_CENTER, epaint::vec2(0.0, 0.0))
+        .show(&view.context.clone(), |ui| {
+            draw_title(ui, "Transfer windows");
+            draw_subtitle(ui, &view.model.name_component(porkchop.target).name());
+            if !porkchop.is_complete() {
+                let progress = format!("Computing... {}/{}", porkchop.computed_departures, DEPARTURE_CELLS);
+                ui.label(RichText::new(progress).size(12.0).weak());
+            }
+
+            let size = epaint::vec2(DEPARTURE_CELLS as f32 * CELL_SIZE, FLIGHT_TIME_CELLS as f32 * CELL_SIZE);
+            let (response, painter) = ui.allocate_painter(size, Sense::click());
+            let dv_range = porkchop.dv_range();
+            for i in 0..DEPARTURE_CELLS {
+                for j in 0..FLIGHT_TIME_CELLS {
+                    // Flight time increases upwards
+                    let min = Pos2::new(response.rect.left() + i as f32 * CELL_SIZE, response.rect.bottom() - (j + 1) as f32 * CELL_SIZE);
+                    let rect = Rect::from_min_size(min, epaint::vec2(CELL_SIZE, CELL_SIZE));
+                    let color = match (porkchop.cell(i, j), dv_range) {
+                        (Some(dv), Some((min_dv, max_dv))) => dv_color(((dv - min_dv) / (max_dv - min_dv).max(1.0)) as f32),
+                        _ => Color32::from_gray(40),
+                    };
+                    painter.rect_filled(rect, Rounding::ZERO, color);
+                }
+            }
+
+            let hovered_cell = response.hover_pos().map(|position| {
+                let i = ((position.x - response.rect.left()) / CELL_SIZE) as usize;
+                let j = ((response.rect.bottom() - position.y) / CELL_SIZE) as usize;
+                (i.min(DEPARTURE_CELLS - 1), j.min(FLIGHT_TIME_CELLS - 1))
+            });
+
+            if let Some((i, j)) = hovered_cell {
+                let departure_time = porkchop.departure_time(i);
+                let flight_time = porkchop.flight_time(j);
+                let dv = porkchop.cell(i, j).map_or("-".to_string(), |dv| format!("{dv:.1} m/s"));
+                // The grid may have been computed against an older timeline, so check the live model
+                let can_create = StartBurnEvent::can_create_ever(&view.model, porkchop.entity)
+                    && StartBurnEvent::can_create(&view.model, porkchop.entity, departure_time);
+                let mut text = format!("Departure: T-{}\nFlight time: {}\nΔV: {}", format_time(departure_time - view.model.time()), format_time(flight_time), dv);
+                if !can_create {
+                    text.push_str("\nCannot create a burn at this time");
+                }
+                let clicked = response.clicked() && can_create && porkchop.cell(i, j).is_some();
+                response.on_hover_text_at_pointer(text);
+                if clicked {
+                    match view.model.plan_transfer(porkchop.entity, porkchop.target, departure_time, flight_time) {
+                        Some(plan) if StartBurnEvent::can_create(&view.model, porkchop.entity, plan.time()) => {
+                            view.add_model_event(ModelEvent::CreatePlannedBurn { entity: porkchop.entity, time: plan.time(), delta_v: plan.delta_v() });
+                            view.add_view_event(ViewEvent::ClosePorkchop);
+                        }
+                        Some(_) => error!("Cannot create a burn at the planned transfer time"),
+                        None => error!("Failed to plan transfer for porkchop cell"),
+                    }
+                }
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Departure →").size(12.0).weak());
+                ui.label(RichText::new("Flight time ↑").size(12.0).weak());
+                if let Some((min_dv, max_dv)) = dv_range {
+                    ui.label(RichText::new(format!("ΔV {min_dv:.0} - {max_dv:.0} m/s")).size(12.0).weak());
+                }
+            });
+
+            if ui.button("Close").clicked() {
+                view.add_view_event(ViewEvent::ClosePorkchop);
+            }
+        });
+}
@@ -1,13 +1,24 @@
+use std::f64::consts::PI;
+
+use auto_intercept::{draw_auto_intercept, earliest_departure_time};
 use docking::draw_docking;
 use eframe::{egui::{Align2, Color32, Grid, Ui, Window}, epaint};
-use transfer_window_model::{components::vessel_component::{faction::Faction, VesselComponent}, storage::entity_allocator::Entity};
+use log::info;
+use shipyard::draw_shipyard;
+use transfer_window_common::normalize_angle;
+use transfer_window_model::{components::{orbitable_component::OrbitableComponent, vessel_component::{cargo_hold::CargoHold, gun::Gun, health::Health, point_defense::PointDefense, resource::ResourceType, VesselComponent}}, model::state_query::StateQuery, storage::entity_allocator::Entity};
 use visual_timeline::draw_visual_timeline;
 
-use crate::{game::{events::{ModelEvent, ViewEvent}, overlay::widgets::{bars::{draw_filled_bar, FilledBar}, buttons::{draw_cancel_burn, draw_cancel_guidance, draw_dock, draw_focus}, labels::{draw_info, draw_key, draw_subtitle, draw_title, draw_value}, util::{should_draw_dv, should_draw_fuel, should_draw_torpedoes}}, selected::Selected, View}, styles};
+use crate::{game::{events::{ModelEvent, ViewEvent}, overlay::widgets::{bars::{draw_filled_bar, FilledBar}, buttons::{draw_auto_intercept as draw_auto_intercept_button, draw_cancel_burn, draw_cancel_dock, draw_cancel_guidance, draw_dock, draw_fire_gun_at_target, draw_focus, draw_mine, draw_porkchop, draw_stop_mining}, labels::{draw_info, draw_key, draw_subtitle, draw_title, draw_value, draw_value_with_color}, util::{should_draw_cargo, should_draw_dv, should_draw_fuel, should_draw_gun, should_draw_point_defense, should_draw_torpedoes}}, selected::Selected, util::format_time, View}, styles};
 
-mod docking;
+pub mod auto_intercept;
+pub mod docking;
+mod shipyard;
 pub mod visual_timeline;
 
+/// Time allowed for turning to a firing solution before the shot
+const AIM_TURN_DURATION: f64 = 10.0 * 60.0;
+
 pub fn draw_fuel(ui: &mut Ui, vessel_component: &VesselComponent, color: Color32) {
     let remaining_fuel = vessel_component.fuel_litres();
     let max_fuel = vessel_component.fuel_capacity_litres();
@@ -38,6 +49,67 @@ pub fn draw_torpedoes(ui: &mut Ui, vessel_component: &VesselComponent, color: Co
     draw_value(ui, &format!("{torpedoes} / {max_torpedoes}"));
 }
 
+/// Proportion of the hold's mass limit in use
+pub fn draw_cargo_hold(ui: &mut Ui, vessel_component: &VesselComponent, color: Color32) {
+    let mass = vessel_component.cargo_mass_kg();
+    let max_mass = vessel_component.cargo_hold().map_or(0.0, CargoHold::mass_capacity_kg);
+    let mass_proportion = (mass / max_mass) as f32;
+
+    draw_key(ui, "Cargo");
+    draw_filled_bar(ui, 120.0, 10.0, 2.0, 3.0, Color32::GRAY, vec![FilledBar::new(color, mass_proportion)]);
+    draw_value(ui, &format!("{:.1} / {:.1} t", mass / 1000.0, max_mass / 1000.0));
+}
+
+/// The bar shows how much of the space left in the hold this resource would fill
+pub fn draw_cargo(ui: &mut Ui, vessel_component: &VesselComponent, resource: ResourceType, color: Color32) {
+    let amount = vessel_component.resource_amount(resource);
+    let max_amount = amount + vessel_component.resource_space(resource);
+    let proportion = (amount / max_amount) as f32;
+
+    draw_key(ui, resource.name());
+    draw_filled_bar(ui, 120.0, 10.0, 2.0, 3.0, Color32::GRAY, vec![FilledBar::new(color, proportion)]);
+    draw_value(ui, &format!("{} {}", amount.round(), resource.definition().unit()));
+}
+
+pub fn draw_point_defense_ammo(ui: &mut Ui, vessel_component: &VesselComponent, color: Color32) {
+    let max_ammo = vessel_component.point_defense().map_or(0, PointDefense::ammo_capacity);
+    let ammo = vessel_component.point_defense_ammo();
+    let ammo_proportion = ammo as f32 / max_ammo as f32;
+
+    draw_key(ui, "PD ammo");
+    draw_filled_bar(ui, 120.0, 10.0, 2.0, 3.0, Color32::GRAY, vec![FilledBar::new(color, ammo_proportion)]);
+    draw_value(ui, &format!("{ammo} / {max_ammo}"));
+}
+
+pub fn draw_gun_ammo(ui: &mut Ui, vessel_component: &VesselComponent, color: Color32) {
+    let max_ammo = vessel_component.gun().map_or(0, Gun::ammo_capacity);
+    let ammo = vessel_component.gun_ammo();
+    let ammo_proportion = ammo as f32 / max_ammo as f32;
+
+    draw_key(ui, "Gun ammo");
+    draw_filled_bar(ui, 120.0, 10.0, 2.0, 3.0, Color32::GRAY, vec![FilledBar::new(color, ammo_proportion)]);
+    draw_value(ui, &format!("{ammo} / {max_ammo}"));
+}
+
+fn draw_hull(ui: &mut Ui, health: &Health, name: &str) {
+    let proportion = (health.hit_points() / health.max_hit_points()) as f32;
+    let color = if proportion > 0.5 { Color32::WHITE } else { Color32::from_rgb(255, 120, 80) };
+    draw_subtitle(ui, "Hull");
+    Grid::new("Vessel hull grid ".to_string() + name).show(ui, |ui| {
+        draw_key(ui, "Integrity");
+        draw_filled_bar(ui, 120.0, 10.0, 2.0, 3.0, Color32::GRAY, vec![FilledBar::new(color, proportion)]);
+        draw_value(ui, &format!("{} / {}", health.hit_points().round(), health.max_hit_points().round()));
+        ui.end_row();
+
+        for subsystem in health.disabled_subsystems() {
+            draw_key(ui, subsystem.name());
+            ui.horizontal(|_| ());
+            draw_value_with_color(ui, "Disabled", Color32::from_rgb(255, 120, 80));
+            ui.end_row();
+        }
+    });
+}
+
 fn draw_resources_grid(ui: &mut Ui, vessel_component: &VesselComponent, name: &str) {
     Grid::new("Vessel resource grid ".to_string() + name).show(ui, |ui| {
         if should_draw_dv(vessel_component) {
@@ -52,17 +124,64 @@ fn draw_resources_grid(ui: &mut Ui, vessel_component: &VesselComponent, name: &s
             draw_torpedoes(ui, vessel_component, Color32::WHITE);
             ui.end_row();
         }
+        if should_draw_cargo(vessel_component) {
+            draw_cargo_hold(ui, vessel_component, Color32::WHITE);
+            ui.end_row();
+            for resource in ResourceType::ALL.into_iter().filter(|resource| resource.is_cargo()) {
+                if !vessel_component.is_resource_empty(resource) {
+                    draw_cargo(ui, vessel_component, resource, Color32::WHITE);
+                    ui.end_row();
+                }
+            }
+        }
+        if should_draw_point_defense(vessel_component) {
+            draw_point_defense_ammo(ui, vessel_component, Color32::WHITE);
+            ui.end_row();
+        }
+        if should_draw_gun(vessel_component) {
+            draw_gun_ammo(ui, vessel_component, Color32::WHITE);
+            ui.end_row();
+        }
     });
 }
 
 fn draw_resources(ui: &mut Ui, vessel_component: &VesselComponent, name: &str) {
-    if should_draw_fuel(vessel_component) || should_draw_dv(vessel_component) || should_draw_torpedoes(vessel_component) {
+    if should_draw_fuel(vessel_component) || should_draw_dv(vessel_component) || should_draw_torpedoes(vessel_component) || should_draw_cargo(vessel_component) || should_draw_point_defense(vessel_component) || should_draw_gun(vessel_component) {
         draw_subtitle(ui, "Resources");
         draw_resources_grid(ui, vessel_component, name);
     }
 }
 
-fn draw_controls(vessel_component: &VesselComponent, view: &View, entity: Entity, ui: &mut Ui, has_intel: bool) {
+fn draw_mining(view: &View, ui: &mut Ui, entity: Entity, name: &str) {
+    let vessel_component = view.model.vessel_component(entity);
+    let Some(asteroid) = vessel_component.mining() else {
+        return;
+    };
+    draw_subtitle(ui, "Mining");
+    Grid::new("Vessel mining grid ".to_string() + name).show(ui, |ui| {
+        draw_key(ui, "Asteroid");
+        draw_value(ui, &view.model.name_component(asteroid).name());
+        ui.end_row();
+
+        draw_key(ui, "Rate");
+        draw_value(ui, &format!("{:.1} kg/s", vessel_component.mining_rate()));
+        ui.end_row();
+
+        if let Some(deposit) = view.model.orbitable_component(asteroid).deposits().iter().find(|deposit| !deposit.is_depleted()) {
+            draw_key(ui, deposit.resource().name());
+            draw_value(ui, &format!("{} kg left", deposit.remaining().round()));
+            ui.end_row();
+        }
+
+        if let Some(time) = view.model.mining_completion_time(entity) {
+            draw_key(ui, "Done");
+            draw_value(ui, &format!("T-{}", format_time(time - view.model.time())));
+            ui.end_row();
+        }
+    });
+}
+
+fn draw_controls(vessel_component: &VesselComponent, view: &View, entity: Entity, ui: &mut Ui, has_control: bool) {
     ui.horizontal(|ui| {
         styles::SelectedMenuButton::apply(ui);
 
@@ -71,11 +190,11 @@ fn draw_controls(vessel_component: &VesselComponent, view: &View, entity: Entity
             view.add_view_event(ViewEvent::SetCameraFocus(entity));
         }
 
-        if has_intel && view.model.path_component(entity).current_segment().is_burn() && draw_cancel_burn(view, ui) {
+        if has_control && view.model.path_component(entity).current_segment().is_burn() && draw_cancel_burn(view, ui) {
             view.add_model_event(ModelEvent::CancelCurrentSegment { entity });
         }
 
-        if has_intel && view.model.path_component(entity).current_segment().is_guidance() && draw_cancel_guidance(view, ui) {
+        if has_control && view.model.path_component(entity).current_segment().is_guidance() && draw_cancel_guidance(view, ui) {
             if view.model.vessel_component(entity).timeline().last_event().is_some_and(|event| event.is_intercept()) {
                 // also cancel intercept
                 view.add_model_event(ModelEvent::CancelLastTimelineEvent { entity });
@@ -83,7 +202,11 @@ fn draw_controls(vessel_component: &VesselComponent, view: &View, entity: Entity
             view.add_model_event(ModelEvent::CancelCurrentSegment { entity });
         }
 
-        if has_intel && vessel_component.timeline().last_blocking_event().is_none() {
+        if has_control && vessel_component.timeline().last_event().is_some_and(|event| event.is_dock()) && draw_cancel_dock(view, ui) {
+            view.add_model_event(ModelEvent::CancelLastTimelineEvent { entity });
+        }
+
+        if has_control && vessel_component.timeline().last_blocking_event().is_none() {
             if let Some(target) = vessel_component.target() {
                 if view.model.try_vessel_component(target).is_some() && view.model.can_ever_dock_to_target(entity) && draw_dock(view, ui, entity) {
                     view.add_model_event(ModelEvent::Dock { station: target, entity });
@@ -92,9 +215,55 @@ fn draw_controls(vessel_component: &VesselComponent, view: &View, entity: Entity
                 }
             }
         }
+
+        if has_control && vessel_component.can_ever_mine() {
+            if vessel_component.mining().is_some() {
+                if draw_stop_mining(view, ui) {
+                    view.add_model_event(ModelEvent::StopMining { entity });
+                }
+            } else if let Some(target) = vessel_component.target() {
+                let is_asteroid = view.model.try_orbitable_component(target).is_some_and(OrbitableComponent::is_asteroid);
+                if is_asteroid && draw_mine(view, ui, entity, target) {
+                    view.add_model_event(ModelEvent::StartMining { entity, asteroid: target });
+                }
+            }
+        }
+
+        if has_control && draw_fire_gun_at_target(view, ui, entity) {
+            if let Some(target) = vessel_component.target() {
+                fire_gun_at_target(view, entity, target);
+            }
+        }
+
+        if has_control && draw_auto_intercept_button(view, ui, entity) {
+            if let Some(target) = vessel_component.target() {
+                view.add_view_event(ViewEvent::StartAutoIntercept { entity, target });
+            }
+        }
+
+        if has_control && draw_porkchop(view, ui, entity) {
+            if let Some(target) = vessel_component.target() {
+                view.add_view_event(ViewEvent::OpenPorkchop { entity, target });
+            }
+        }
     });
 }
 
+/// Turns to the firing solution for `target` and fires on it. Nothing is
+/// planned if no shot in the search window hits
+fn fire_gun_at_target(view: &View, entity: Entity, target: Entity) {
+    let turn_time = earliest_departure_time(&view.model, entity);
+    let Some(solution) = view.model.firing_solution(entity, target, turn_time + AIM_TURN_DURATION) else {
+        info!("No firing solution found for {entity:?}");
+        return;
+    };
+    // The turn starts out pointing wherever the vessel already is, so adjust it the short way round
+    let amount = normalize_angle(solution.rotation() - view.model.snapshot_at(turn_time).rotation(entity) + PI) - PI;
+    view.add_model_event(ModelEvent::CreateTurn { entity, time: turn_time });
+    view.add_model_event(ModelEvent::AdjustTurn { entity, time: turn_time, amount });
+    view.add_model_event(ModelEvent::CreateFireGun { entity, time: solution.time() });
+}
+
 pub fn update(view: &View) {
     #[cfg(feature = "profiling")]
     let _span = tracy_client::span!("Update vessel");
@@ -104,8 +273,8 @@ pub fn update(view: &View) {
 
     let name = view.model.name_component(entity).name();
     let vessel_component = view.model.vessel_component(entity);
-    let has_intel = Faction::Player.has_intel_for(vessel_component.faction());
-    let has_control = Faction::Player.can_control(vessel_component.faction());
+    let has_intel = view.model.has_intel_for(view.faction, vessel_component.faction());
+    let has_control = view.faction.can_control(vessel_component.faction());
 
     Window::new("Selected vessel ".to_string() + &name)
             .title_bar(false)
@@ -117,12 +286,23 @@ pub fn update(view: &View) {
         draw_info(view, ui, &name, entity);
 
         if has_intel {
+            if let Some(health) = vessel_component.health() {
+                draw_hull(ui, health, &name);
+            }
             draw_resources(ui, vessel_component, &name);
+            draw_mining(view, ui, entity, &name);
             if view.model.vessel_component(entity).has_docking() {
                 draw_docking(view, ui, entity);
             }
         }
 
+        if has_control {
+            if vessel_component.has_docking() {
+                draw_shipyard(view, ui, entity);
+            }
+            draw_auto_intercept(view, ui, entity);
+        }
+
         draw_visual_timeline(view, ui, entity, view.model.time(), false);
     });
 }
@@ -0,0 +1,129 @@
+use std::{sync::mpsc::{channel, Receiver}, thread};
+
+use eframe::egui::{Button, Grid, RichText, Ui};
+use log::error;
+use transfer_window_model::{components::vessel_component::{fuel_tank::FUEL_DENSITY_KG_PER_LITRE, timeline::start_burn::StartBurnEvent}, model::{intercept_planner::InterceptPlan, Model}, storage::entity_allocator::Entity};
+
+use crate::game::{events::{ModelEvent, ViewEvent}, overlay::widgets::labels::{draw_key, draw_subtitle, draw_time_until, draw_value}, util::{format_distance, format_time}, View};
+
+/// Small delay so the burn does not start on the same frame it is planned
+const DEPARTURE_DELAY: f64 = 10.0;
+
+/// An intercept of the vessel's target, departing any time within the
+/// target's period after the last blocking event. The search is run on a
+/// separate thread using a copy of the model so it does not stall the UI
+pub struct AutoIntercept {
+    entity: Entity,
+    /// True until the worker thread has finished searching
+    searching: bool,
+    plan: Option<InterceptPlan>,
+    receiver: Receiver<Option<InterceptPlan>>,
+}
+
+/// The first time a planned burn could start, after any blocking events
+pub fn earliest_departure_time(model: &Model, entity: Entity) -> f64 {
+    let last_blocking_time = model.vessel_component(entity)
+        .timeline()
+        .last_blocking_event()
+        .map_or(model.time(), |event| event.time());
+    f64::max(last_blocking_time, model.time()) + DEPARTURE_DELAY
+}
+
+impl AutoIntercept {
+    pub fn new(model: &Model, entity: Entity, target: Entity) -> Option<Self> {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Start auto intercept");
+        let earliest_departure_time = earliest_departure_time(model, entity);
+
+        let serialized = match model.serialize() {
+            Ok(serialized) => serialized,
+            Err(err) => {
+                error!("Failed to copy model for auto intercept: {}", err);
+                return None;
+            }
+        };
+
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            let model = match Model::deserialize(&serialized) {
+                Ok(model) => model,
+                Err(err) => {
+                    error!("Failed to copy model for auto intercept: {}", err);
+                    return;
+                }
+            };
+            let plan = model.intercept_flight_time_range(entity, target, earliest_departure_time)
+                .and_then(|(_, max_flight_time)| model.plan_intercept(entity, target, earliest_departure_time, earliest_departure_time + max_flight_time));
+            // The receiver is dropped if the auto intercept was cancelled
+            let _ = sender.send(plan);
+        });
+
+        Some(Self { entity, searching: true, plan: None, receiver })
+    }
+
+    /// Collects the plan if the worker thread has finished since the last frame
+    pub fn receive(&mut self) {
+        if let Ok(plan) = self.receiver.try_recv() {
+            self.searching = false;
+            self.plan = plan;
+        }
+    }
+}
+
+pub fn draw_auto_intercept(view: &View, ui: &mut Ui, entity: Entity) {
+    let Some(auto_intercept) = &view.auto_intercept else {
+        return;
+    };
+    if auto_intercept.entity != entity {
+        return;
+    }
+
+    draw_subtitle(ui, "Auto-intercept");
+    if auto_intercept.searching {
+        ui.label(RichText::new("Searching for intercept...").size(12.0).weak());
+        if ui.button("Cancel").clicked() {
+            view.add_view_event(ViewEvent::CloseAutoIntercept);
+        }
+        return;
+    }
+    let Some(plan) = &auto_intercept.plan else {
+        ui.label(RichText::new("No intercept found").size(12.0).weak());
+        if ui.button("Dismiss").clicked() {
+            view.add_view_event(ViewEvent::CloseAutoIntercept);
+        }
+        return;
+    };
+
+    Grid::new("Auto-intercept grid").show(ui, |ui| {
+        draw_key(ui, "Departure");
+        draw_time_until(view, ui, plan.time());
+        ui.end_row();
+
+        draw_key(ui, "ΔV");
+        draw_value(ui, &format!("{:.1} m/s", plan.dv()));
+        ui.end_row();
+
+        draw_key(ui, "Fuel");
+        draw_value(ui, &format!("{} L", (plan.fuel_kg() / FUEL_DENSITY_KG_PER_LITRE).round()));
+        ui.end_row();
+
+        draw_key(ui, "Closest approach");
+        draw_value(ui, &format_distance(plan.closest_approach_distance()));
+        ui.end_row();
+
+        draw_key(ui, "Arrival");
+        draw_value(ui, &format!("T-{}", format_time(plan.closest_approach_time() - view.model.time())));
+        ui.end_row();
+    });
+
+    ui.horizontal(|ui| {
+        let can_create = StartBurnEvent::can_create(&view.model, entity, plan.time());
+        if ui.add_enabled(can_create, Button::new("Create burn")).clicked() {
+            view.add_model_event(ModelEvent::CreatePlannedBurn { entity, time: plan.time(), delta_v: plan.delta_v() });
+            view.add_view_event(ViewEvent::CloseAutoIntercept);
+        }
+        if ui.button("Cancel").clicked() {
+            view.add_view_event(ViewEvent::CloseAutoIntercept);
+        }
+    });
+}
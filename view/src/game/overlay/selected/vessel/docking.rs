@@ -1,42 +1,99 @@
-use eframe::egui::{Color32, Grid, Pos2, Rect, RichText, Rounding, Stroke, Ui};
-use transfer_window_model::{components::vessel_component::docking::{ContinuousResourceTransfer, DiscreteResourceTransfer, DockingPort, DockingPortLocation, ResourceTransferDirection}, storage::entity_allocator::Entity};
+use eframe::egui::{Color32, DragValue, Grid, Pos2, Rect, RichText, Rounding, Stroke, Ui};
+use transfer_window_model::{components::vessel_component::{docking::{DockingPort, DockingPortLocation, ResourceTransfer, ResourceTransferDirection}, resource::ResourceType, timeline::transfer::TransferEvent}, storage::entity_allocator::Entity};
 
-use crate::{game::{events::{ModelEvent, ViewEvent}, overlay::{explorer::vessel_normal_circle_color, widgets::{buttons::draw_undock, custom_image_button::CustomCircularImageButton, labels::{draw_subtitle, draw_value}, util::{advance_cursor_to, should_draw_dv, should_draw_fuel, should_draw_torpedoes}}}, selected::Selected, util::{format_time, vessel_texture}, View}, styles};
+use crate::{game::{events::{ModelEvent, ViewEvent}, overlay::{explorer::vessel_normal_circle_color, widgets::{buttons::{draw_cancel_undock, draw_edit_loadout, draw_undock}, custom_image_button::CustomCircularImageButton, labels::{draw_subtitle, draw_value}, util::{advance_cursor_to, should_draw_dv, should_draw_fuel, should_draw_torpedoes}}}, selected::Selected, util::{format_time, vessel_texture}, View}, styles};
 
-use super::{draw_dv, draw_fuel, draw_torpedoes};
+use super::{draw_cargo, draw_dv, draw_fuel, draw_torpedoes};
+
+/// How the transfer buttons start transfers. Without an amount a transfer
+/// runs until it can go no further, and with a delay it is scheduled on
+/// the station's timeline instead of starting now
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransferPlan {
+    amount: Option<f64>,
+    delay_hours: f64,
+}
+
+/// Starts or schedules a transfer according to the view's transfer plan
+fn start_transfer(view: &View, station_entity: Entity, location: DockingPortLocation, resource: ResourceType, direction: ResourceTransferDirection) {
+    let TransferPlan { amount, delay_hours } = view.transfer_plan;
+    if delay_hours <= 0.0 {
+        view.add_model_event(ModelEvent::StartTransfer { station: station_entity, location, resource, direction, amount });
+        return;
+    }
+    let entity = view.model.docking_port(station_entity, location).docked_vessel().entity();
+    let time = view.model.time() + delay_hours * 60.0 * 60.0;
+    if TransferEvent::can_create(&view.model, station_entity, entity, time) {
+        view.add_model_event(ModelEvent::CreateTransfer { station: station_entity, entity, time, resource, direction, amount });
+    }
+}
+
+fn draw_transfer_plan(view: &View, ui: &mut Ui) {
+    let mut transfer_plan = view.transfer_plan;
+    ui.horizontal(|ui| {
+        let mut has_amount = transfer_plan.amount.is_some();
+        ui.checkbox(&mut has_amount, "Amount");
+        let mut amount = transfer_plan.amount.unwrap_or(0.0);
+        ui.add_enabled(has_amount, DragValue::new(&mut amount).range(0.0..=f64::MAX).speed(10.0));
+        transfer_plan.amount = has_amount.then_some(amount);
+
+        ui.label("Start in");
+        ui.add(DragValue::new(&mut transfer_plan.delay_hours).range(0.0..=24.0 * 30.0).speed(0.1).suffix(" h"));
+    });
+    if transfer_plan.amount != view.transfer_plan.amount || transfer_plan.delay_hours != view.transfer_plan.delay_hours {
+        view.add_view_event(ViewEvent::SetTransferPlan(transfer_plan));
+    }
+}
 
 #[allow(clippy::too_many_arguments)]
-fn draw_fuel_transfer_button(view: &View, ui: &mut Ui, station_entity: Entity, location: DockingPortLocation, direction: ResourceTransferDirection, texture: &str, is_transferring: bool, is_other_transferring: bool, can_transfer: bool) {
+fn draw_transfer_button(view: &View, ui: &mut Ui, station_entity: Entity, location: DockingPortLocation, resource: ResourceType, direction: ResourceTransferDirection, texture: &str, is_transferring: bool, is_other_transferring: bool, can_transfer: bool) {
     if is_transferring {
         let button = CustomCircularImageButton::new(view, "transfer-cancel", 14);
         if ui.add(button).clicked() {
-            view.add_model_event(ModelEvent::StopFuelTransfer { station: station_entity, location });
+            view.add_model_event(ModelEvent::StopTransfer { station: station_entity, location, resource });
         }
     } else {
         let enabled = can_transfer && !is_other_transferring;
         let button = CustomCircularImageButton::new(view, texture, 14)
             .with_enabled(enabled);
         if ui.add_enabled(enabled, button).clicked() {
-            view.add_model_event(ModelEvent::StartFuelTransfer { station: station_entity, location, direction });
+            start_transfer(view, station_entity, location, resource, direction);
         }
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-fn draw_torpedo_transfer_button(view: &View, ui: &mut Ui, station_entity: Entity, location: DockingPortLocation, direction: ResourceTransferDirection, texture: &str, is_transferring: bool, is_other_transferring: bool, can_transfer: bool) {
-    if is_transferring {
-        let button = CustomCircularImageButton::new(view, "transfer-cancel", 14);
-        if ui.add(button).clicked() {
-            view.add_model_event(ModelEvent::StopTorpedoTransfer { station: station_entity, location });
-        }
-    } else {
-        let enabled = can_transfer && !is_other_transferring;
-        let button = CustomCircularImageButton::new(view, texture, 14)
-            .with_enabled(enabled);
-        if ui.add_enabled(enabled, button).clicked() {
-            view.add_model_event(ModelEvent::StartTorpedoTransfer { station: station_entity, location, direction });
+fn draw_transfer_buttons(view: &View, ui: &mut Ui, station_entity: Entity, location: DockingPortLocation, resource: ResourceType) {
+    let transfer = view.model.docking_port(station_entity, location).docked_vessel().transfer(resource);
+    let direction = transfer.map(ResourceTransfer::direction);
+    let is_transfer_from = direction.is_some_and(|direction| direction.is_from_docked());
+    let is_transfer_to = direction.is_some_and(|direction| direction.is_to_docked());
+    let can_transfer_from_docked = view.model.can_transfer_from_docked(station_entity, location, resource);
+    let can_transfer_to_docked = view.model.can_transfer_to_docked(station_entity, location, resource);
+
+    ui.horizontal(|ui| {
+        draw_transfer_button(view, ui, station_entity, location, resource, ResourceTransferDirection::FromDocked, "transfer-from", is_transfer_from, is_transfer_to, can_transfer_from_docked);
+        draw_transfer_button(view, ui, station_entity, location, resource, ResourceTransferDirection::ToDocked, "transfer-to", is_transfer_to, is_transfer_from, can_transfer_to_docked);
+    });
+}
+
+fn draw_transfer_times(view: &View, ui: &mut Ui, station_entity: Entity, location: DockingPortLocation, resource: ResourceType) {
+    if let Some(transfer) = view.model.docking_port(station_entity, location).docked_vessel().transfer(resource) {
+        if resource.is_discrete() {
+            draw_value(ui, &format!("Transfer T-{}", format_time(transfer.time_to_next())));
         }
     }
+    if let Some(time) = view.model.transfer_completion_time(station_entity, location, resource) {
+        draw_value(ui, &format!("Done T-{}", format_time(time - view.model.time())));
+    }
+}
+
+/// Cargo is only shown if either side of the port has some to move
+fn should_draw_cargo_resource(view: &View, station_entity: Entity, docked_entity: Entity, resource: ResourceType) -> bool {
+    let station = view.model.vessel_component(station_entity);
+    let docked = view.model.vessel_component(docked_entity);
+    resource.is_cargo()
+        && docked.can_store(resource)
+        && (!docked.is_resource_empty(resource) || !station.is_resource_empty(resource))
 }
 
 fn draw_resources_grid(view: &View, ui: &mut Ui, station_entity: Entity, docked_entity: Entity, location: DockingPortLocation) {
@@ -49,38 +106,23 @@ fn draw_resources_grid(view: &View, ui: &mut Ui, station_entity: Entity, docked_
             ui.end_row();
         }
 
-        if should_draw_fuel(vessel_component) {
-            let transfer = view.model.docking_port(station_entity, location).docked_vessel().fuel_transfer();
-            let direction = transfer.map(ContinuousResourceTransfer::direction);
-            let is_transfer_from = direction.is_some_and(|direction| direction.is_from_docked());
-            let is_transfer_to = direction.is_some_and(|direction| direction.is_to_docked());
-            let can_transfer_from_docked = view.model.can_transfer_fuel_from_docked(station_entity, location);
-            let can_transfer_to_docked = view.model.can_transfer_fuel_to_docked(station_entity, location);
-
-            ui.horizontal(|ui| {
-                draw_fuel_transfer_button(view, ui, station_entity, location, ResourceTransferDirection::FromDocked, "transfer-from", is_transfer_from, is_transfer_to, can_transfer_from_docked);
-                draw_fuel_transfer_button(view, ui, station_entity, location, ResourceTransferDirection::ToDocked, "transfer-to", is_transfer_to, is_transfer_from, can_transfer_to_docked);
-            });
-            draw_fuel(ui, vessel_component, Color32::WHITE);
-            ui.end_row();
-        }
-
-        if should_draw_torpedoes(vessel_component) {
-            let transfer = view.model.docking_port(station_entity, location).docked_vessel().torpedo_transfer();
-            let direction = transfer.map(DiscreteResourceTransfer::direction);
-            let is_transfer_from = direction.is_some_and(|direction| direction.is_from_docked());
-            let is_transfer_to = direction.is_some_and(|direction| direction.is_to_docked());
-            let can_transfer_from_docked = view.model.can_transfer_torpedoes_from_docked(station_entity, location);
-            let can_transfer_to_docked = view.model.can_transfer_torpedoes_to_docked(station_entity, location);
-
-            ui.horizontal(|ui| {
-                draw_torpedo_transfer_button(view, ui, station_entity, location, ResourceTransferDirection::FromDocked, "transfer-from", is_transfer_from, is_transfer_to, can_transfer_from_docked);
-                draw_torpedo_transfer_button(view, ui, station_entity, location, ResourceTransferDirection::ToDocked, "transfer-to", is_transfer_to, is_transfer_from, can_transfer_to_docked);
-            });
-            draw_torpedoes(ui, vessel_component, Color32::WHITE);
-            if let Some(transfer) = transfer {
-                draw_value(ui, &format!("Transfer T-{}", format_time(transfer.time_to_next())));
+        for resource in ResourceType::ALL {
+            match resource {
+                ResourceType::Fuel if should_draw_fuel(vessel_component) => {
+                    draw_transfer_buttons(view, ui, station_entity, location, resource);
+                    draw_fuel(ui, vessel_component, Color32::WHITE);
+                }
+                ResourceType::Torpedoes if should_draw_torpedoes(vessel_component) => {
+                    draw_transfer_buttons(view, ui, station_entity, location, resource);
+                    draw_torpedoes(ui, vessel_component, Color32::WHITE);
+                }
+                _ if should_draw_cargo_resource(view, station_entity, docked_entity, resource) => {
+                    draw_transfer_buttons(view, ui, station_entity, location, resource);
+                    draw_cargo(ui, vessel_component, resource, Color32::WHITE);
+                }
+                _ => continue,
             }
+            draw_transfer_times(view, ui, station_entity, location, resource);
             ui.end_row();
         }
     });
@@ -95,6 +137,18 @@ fn draw_controls(view: &View, station: Entity, entity: Entity, ui: &mut Ui) {
             view.add_view_event(ViewEvent::SetCameraFocus(entity));
             view.add_view_event(ViewEvent::SetSelected(Selected::Vessel(entity)));
         }
+
+        if view.model.scheduled_undock(station, entity).is_some() && draw_cancel_undock(view, ui) {
+            view.add_model_event(ModelEvent::CancelUndock { station, entity });
+        }
+
+        if draw_edit_loadout(view, ui, entity) {
+            view.add_view_event(ViewEvent::OpenLoadout(entity));
+        }
+
+        if let Some(undock) = view.model.scheduled_undock(station, entity) {
+            draw_value(ui, &format!("Undock T-{}", format_time(undock.time() - view.model.time())));
+        }
     });
 }
 
@@ -104,7 +158,7 @@ fn draw_header(ui: &mut Ui, docking_port: &DockingPort, view: &View, location: D
             let docked_entity = docking_port.docked_vessel().entity();
             let faction = view.model.vessel_component(docked_entity).faction();
             let texture = vessel_texture(view.model.vessel_component(docked_entity));
-            let color = vessel_normal_circle_color(faction);
+            let color = vessel_normal_circle_color(view, faction);
             (texture, color)
         } else {
             let texture = "dock";
@@ -132,6 +186,7 @@ fn draw_header(ui: &mut Ui, docking_port: &DockingPort, view: &View, location: D
 
 pub fn draw_docking(view: &View, ui: &mut Ui, station_entity: Entity) {
     draw_subtitle(ui, "Docking ports");
+    draw_transfer_plan(view, ui);
     for (location, docking_port) in view.model.vessel_component(station_entity).docking_ports().unwrap() {
         draw_header(ui, docking_port, view, *location);
 
@@ -155,7 +210,7 @@ pub fn draw_docking(view: &View, ui: &mut Ui, station_entity: Entity) {
             }).response.rect;
 
             let faction = view.model.vessel_component(docked_entity).faction();
-            let color = vessel_normal_circle_color(faction);
+            let color = vessel_normal_circle_color(view, faction);
             let top_left = Pos2::new(17.0, rect.top() + 5.0);
             let bottom_right = Pos2::new(22.0, rect.bottom() - 10.0);
             let line_rect = Rect::from_min_max(top_left, bottom_right);
@@ -0,0 +1,89 @@
+use eframe::egui::{Grid, Ui};
+use transfer_window_model::{components::vessel_component::{class::VesselClass, shipyard::BuildOrder}, storage::entity_allocator::Entity};
+
+use crate::game::{events::ModelEvent, overlay::widgets::{custom_image_button::CustomCircularImageButton, labels::{draw_key, draw_subtitle, draw_value}}, util::{format_time, vessel_class_texture}, View};
+
+fn format_cost(class: VesselClass) -> String {
+    let definition = class.definition();
+    let Some(build_cost) = definition.build_cost() else {
+        return String::new();
+    };
+    build_cost.resources()
+        .iter()
+        .map(|(resource, amount)| format!("{} {} {}", amount.round(), resource.definition().unit(), resource.name()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn draw_buildable_classes(view: &View, ui: &mut Ui, station: Entity, name: &str) {
+    Grid::new("Shipyard classes grid ".to_string() + name).show(ui, |ui| {
+        for class in VesselClass::ALL {
+            let definition = class.definition();
+            let Some(build_cost) = definition.build_cost() else {
+                continue;
+            };
+
+            let enabled = view.model.can_build(station, class);
+            let button = CustomCircularImageButton::new(view, vessel_class_texture(class), 14)
+                .with_enabled(enabled);
+            if ui.add_enabled(enabled, button).on_hover_text(format!("Build {}", class.name())).clicked() {
+                view.add_model_event(ModelEvent::EnqueueBuild { station, class });
+            }
+            draw_key(ui, class.name());
+            draw_value(ui, &format_cost(class));
+            draw_value(ui, &format_time(build_cost.seconds()));
+            ui.end_row();
+        }
+    });
+}
+
+fn draw_order_buttons(view: &View, ui: &mut Ui, station: Entity, index: usize, count: usize) {
+    ui.horizontal(|ui| {
+        let enabled = index > 0;
+        let button = CustomCircularImageButton::new(view, "previous-orbit", 14)
+            .with_enabled(enabled);
+        if ui.add_enabled(enabled, button).on_hover_text("Build sooner").clicked() {
+            view.add_model_event(ModelEvent::ReorderBuild { station, from: index, to: index - 1 });
+        }
+
+        let enabled = index + 1 < count;
+        let button = CustomCircularImageButton::new(view, "next-orbit", 14)
+            .with_enabled(enabled);
+        if ui.add_enabled(enabled, button).on_hover_text("Build later").clicked() {
+            view.add_model_event(ModelEvent::ReorderBuild { station, from: index, to: index + 1 });
+        }
+
+        let button = CustomCircularImageButton::new(view, "cancel", 14);
+        if ui.add(button).on_hover_text("Cancel build").clicked() {
+            view.add_model_event(ModelEvent::CancelBuild { station, index });
+        }
+    });
+}
+
+fn draw_build_queue(view: &View, ui: &mut Ui, station: Entity, orders: &[BuildOrder], name: &str) {
+    Grid::new("Shipyard queue grid ".to_string() + name).show(ui, |ui| {
+        for (index, order) in orders.iter().enumerate() {
+            draw_order_buttons(view, ui, station, index, orders.len());
+            draw_key(ui, order.class().name());
+            if order.is_finished() {
+                draw_value(ui, "Waiting for a free port");
+            } else {
+                let time = view.model.build_completion_time(station, index);
+                draw_value(ui, &format!("Done T-{}", format_time(time - view.model.time())));
+            }
+            ui.end_row();
+        }
+    });
+}
+
+pub fn draw_shipyard(view: &View, ui: &mut Ui, station: Entity) {
+    let name = view.model.name_component(station).name();
+    draw_subtitle(ui, "Shipyard");
+    draw_buildable_classes(view, ui, station, &name);
+
+    let orders = view.model.vessel_component(station).build_queue().unwrap().orders();
+    if !orders.is_empty() {
+        draw_subtitle(ui, "Build queue");
+        draw_build_queue(view, ui, station, orders, &name);
+    }
+}
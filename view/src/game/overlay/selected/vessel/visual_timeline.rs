@@ -1,7 +1,7 @@
 #![allow(clippy::match_same_arms)]
 
 use eframe::egui::{Color32, Frame, RichText, Ui};
-use transfer_window_model::{components::{path_component::segment::Segment, vessel_component::{faction::Faction, timeline::TimelineEvent}}, model::{encounters::EncounterType, state_query::StateQuery}, storage::entity_allocator::Entity};
+use transfer_window_model::{components::{path_component::segment::Segment, vessel_component::timeline::TimelineEvent}, model::{encounters::EncounterType, state_query::StateQuery}, storage::entity_allocator::Entity};
 
 use crate::game::{events::ViewEvent, overlay::widgets::{custom_image::CustomImage, labels::{draw_subtitle, draw_value}, util::advance_cursor_to}, selected::{util::BurnState, Selected}, util::{format_distance, format_time, ApproachType, ApsisType}, View};
 
@@ -38,6 +38,12 @@ impl VisualTimelineEvent {
                 TimelineEvent::StartBurn(_) => "burn",
                 TimelineEvent::StartTurn(_) => "turn",
                 TimelineEvent::StartGuidance(_) => "enable-guidance",
+                TimelineEvent::Impact(_) => "alert",
+                TimelineEvent::PointDefense(point_defense) => if point_defense.destroys_torpedo() { "cancel" } else { "alert" },
+                TimelineEvent::FireGun(_) => "intercept",
+                TimelineEvent::Dock(_) => "dock",
+                TimelineEvent::Undock(_) => "undock",
+                TimelineEvent::Transfer(transfer) => if transfer.direction().is_to_docked() { "transfer-to" } else { "transfer-from" },
             }
             VisualTimelineEvent::Apsis { type_, .. } => match type_ {
                 ApsisType::Periapsis => "periapsis",
@@ -66,6 +72,20 @@ impl VisualTimelineEvent {
                 TimelineEvent::StartBurn(_) => "Burn Start".to_string(),
                 TimelineEvent::StartTurn(_) => "Turn Start".to_string(),
                 TimelineEvent::StartGuidance(_) => "Guidance Start".to_string(),
+                TimelineEvent::Impact(_) => "Impact".to_string(),
+                TimelineEvent::PointDefense(point_defense) => if point_defense.destroys_torpedo() {
+                    "Shot Down".to_string()
+                } else {
+                    format!("Point Defense - {} shots", point_defense.shots())
+                },
+                TimelineEvent::FireGun(fire_gun) => if fire_gun.hit_time().is_some() {
+                    "Gun Fired - Hit".to_string()
+                } else {
+                    "Gun Fired - Miss".to_string()
+                },
+                TimelineEvent::Dock(dock) => format!("Dock - {}", view.model.name_component(dock.station()).name()),
+                TimelineEvent::Undock(undock) => format!("Undock - {}", view.model.name_component(undock.entity()).name()),
+                TimelineEvent::Transfer(transfer) => format!("Transfer {} - {}", transfer.resource().name(), view.model.name_component(transfer.entity()).name()),
             }
             VisualTimelineEvent::Apsis { type_, altitude: distance, .. } => match type_ {
                 ApsisType::Periapsis => format!("Periapsis - {}", format_distance(*distance)),
@@ -85,13 +105,19 @@ impl VisualTimelineEvent {
 
     pub fn selected(&self, entity: Entity) -> Option<Selected> {
         match self {
-            VisualTimelineEvent::TimelineEvent(event) => Some(match event {
-                TimelineEvent::Intercept(intercept) => Selected::Intercept { entity, time: intercept.time() },
-                TimelineEvent::FireTorpedo(fire_torpedo) => Selected::FireTorpedo { entity, time: fire_torpedo.time(), state: BurnState::Selected },
-                TimelineEvent::StartBurn(burn) => Selected::Burn { entity, time: burn.time(), state: BurnState::Selected },
-                TimelineEvent::StartTurn(turn) => Selected::Turn { entity, time: turn.time() },
-                TimelineEvent::StartGuidance(enable_guidance) => Selected::EnableGuidance { entity, time: enable_guidance.time() },
-            }),
+            VisualTimelineEvent::TimelineEvent(event) => match event {
+                TimelineEvent::Intercept(intercept) => Some(Selected::Intercept { entity, time: intercept.time() }),
+                TimelineEvent::FireTorpedo(fire_torpedo) => Some(Selected::FireTorpedo { entity, time: fire_torpedo.time(), state: BurnState::Selected }),
+                TimelineEvent::StartBurn(burn) => Some(Selected::Burn { entity, time: burn.time(), state: BurnState::Selected }),
+                TimelineEvent::StartTurn(turn) => Some(Selected::Turn { entity, time: turn.time() }),
+                TimelineEvent::StartGuidance(enable_guidance) => Some(Selected::EnableGuidance { entity, time: enable_guidance.time() }),
+                TimelineEvent::Impact(_)
+                    | TimelineEvent::PointDefense(_)
+                    | TimelineEvent::FireGun(_)
+                    | TimelineEvent::Dock(_)
+                    | TimelineEvent::Undock(_)
+                    | TimelineEvent::Transfer(_) => None,
+            },
             VisualTimelineEvent::Apsis { type_, time, altitude: _ } => Some(Selected::Apsis { type_: *type_, entity, time: *time }),
             VisualTimelineEvent::Approach { type_, target, time, distance: _ } => Some(Selected::Approach { type_: *type_, entity, target: *target, time: *time }),
             VisualTimelineEvent::Encounter { type_, time, from, to } => Some(Selected::Encounter { type_: *type_, entity, time: *time, from: *from, to: *to }),
@@ -166,14 +192,14 @@ fn generate_timeline_events(view: &View, entity: Entity, events: &mut Vec<Visual
 }
 
 fn generate_apoapsis_periapsis(view: &View, entity: Entity, events: &mut Vec<VisualTimelineEvent>) {
-    for orbit in view.model.snapshot_now_observe(Faction::Player).future_orbits(entity) {
+    for orbit in view.model.snapshot_now_observe(view.faction).future_orbits(entity) {
         if let Some(time) = orbit.next_periapsis_time() {
-            let altitude = view.model.snapshot_at_observe(time, Faction::Player).surface_altitude(entity);
+            let altitude = view.model.snapshot_at_observe(time, view.faction).surface_altitude(entity);
             events.push(VisualTimelineEvent::Apsis { type_: ApsisType::Periapsis, time, altitude });
         }
 
         if let Some(time) = orbit.next_apoapsis_time() {
-            let altitude = view.model.snapshot_at_observe(time, Faction::Player).surface_altitude(entity);
+            let altitude = view.model.snapshot_at_observe(time, view.faction).surface_altitude(entity);
             events.push(VisualTimelineEvent::Apsis { type_: ApsisType::Apoapsis, time, altitude });
         }
     }
@@ -184,21 +210,21 @@ fn generate_closest_approaches(view: &View, entity: Entity, events: &mut Vec<Vis
         return;
     };
 
-    let (approach_1_time, approach_2_time) = view.model.snapshot_now_observe(Faction::Player).find_next_two_closest_approaches(entity, target);
+    let (approach_1_time, approach_2_time) = view.model.snapshot_now_observe(view.faction).find_next_two_closest_approaches(entity, target);
 
     if let Some(time) = approach_1_time {
-        let distance = view.model.snapshot_at_observe(time, Faction::Player).distance(entity, target);
+        let distance = view.model.snapshot_at_observe(time, view.faction).distance(entity, target);
         events.push(VisualTimelineEvent::Approach { type_: ApproachType::First, target, time, distance });
     }
 
     if let Some(time) = approach_2_time {
-        let distance = view.model.snapshot_at_observe(time, Faction::Player).distance(entity, target);
+        let distance = view.model.snapshot_at_observe(time, view.faction).distance(entity, target);
         events.push(VisualTimelineEvent::Approach { type_: ApproachType::Second, target, time, distance });
     }
 }
 
 fn generate_encounters(view: &View, entity: Entity, events: &mut Vec<VisualTimelineEvent>) {
-    for encounter in view.model.snapshot_now_observe(Faction::Player).future_encounters(entity) {
+    for encounter in view.model.snapshot_now_observe(view.faction).future_encounters(entity) {
         events.push(VisualTimelineEvent::Encounter { type_: encounter.encounter_type(), time: encounter.time(), from: encounter.from(), to: encounter.to() });
     }
 }
@@ -226,7 +252,7 @@ pub fn draw_visual_timeline(view: &View, ui: &mut Ui, entity: Entity, center_tim
     
     let mut events = vec![];
     let faction = view.model.vessel_component(entity).faction();
-    let has_intel = Faction::Player.has_intel_for(faction);
+    let has_intel = view.model.has_intel_for(view.faction, faction);
 
     if has_intel {
         generate_timeline_events(view, entity, &mut events);
@@ -247,7 +273,7 @@ pub fn draw_visual_timeline(view: &View, ui: &mut Ui, entity: Entity, center_tim
     events.sort_by(|a, b| a.time().total_cmp(&b.time()));
 
     if let Some(last_event) = view.model.vessel_component(entity).timeline().last_event() {
-        if has_intel && last_event.is_intercept() {
+        if has_intel && (last_event.is_intercept() || last_event.is_impact() || last_event.is_dock()) {
             events.retain(|event| event.time() <= last_event.time());
         }
     }
@@ -3,4 +3,5 @@ pub mod buttons;
 pub mod custom_image_button;
 pub mod custom_image;
 pub mod labels;
+pub mod save_list;
 pub mod util;
\ No newline at end of file
@@ -1,5 +1,5 @@
 use eframe::egui::Ui;
-use transfer_window_model::{components::{orbitable_component::OrbitableType, vessel_component::{faction::Faction, timeline::{fire_torpedo::FireTorpedoEvent, start_burn::StartBurnEvent, start_guidance::StartGuidanceEvent, start_turn::StartTurnEvent}}}, model::state_query::StateQuery, storage::entity_allocator::Entity};
+use transfer_window_model::{components::{orbitable_component::OrbitableType, vessel_component::timeline::{dock::DockEvent, undock::UndockEvent, fire_gun::FireGunEvent, fire_torpedo::FireTorpedoEvent, start_burn::StartBurnEvent, start_guidance::StartGuidanceEvent, start_turn::StartTurnEvent}}, model::state_query::StateQuery, storage::entity_allocator::Entity};
 
 use crate::game::{util::{orbitable_texture, vessel_texture}, View};
 
@@ -31,6 +31,7 @@ pub fn draw_select_orbitable(view: &View, ui: &mut Ui, entity: Entity) -> bool {
         OrbitableType::Star => "Select star",
         OrbitableType::Planet => "Select planet",
         OrbitableType::Moon => "Select moon",
+        OrbitableType::Asteroid => "Select asteroid",
     };
     let button = CustomCircularImageButton::new(view, icon, 36);
     ui.add(button).on_hover_text(tooltip).clicked()
@@ -38,7 +39,7 @@ pub fn draw_select_orbitable(view: &View, ui: &mut Ui, entity: Entity) -> bool {
 
 // Returns new time if could be drawn & clicked
 pub fn draw_previous(view: &View, ui: &mut Ui, time: f64, entity: Entity) -> Option<f64> {
-    let snapshot = view.model.snapshot_at_observe(time, Faction::Player);
+    let snapshot = view.model.snapshot_at_observe(time, view.faction);
     let orbit = snapshot.orbit(entity);
     let time = time - orbit.period()?;
     let enabled = time > orbit.current_point().time();
@@ -53,7 +54,7 @@ pub fn draw_previous(view: &View, ui: &mut Ui, time: f64, entity: Entity) -> Opt
 
 // Returns new time if could be drawn & clicked
 pub fn draw_next(view: &View, ui: &mut Ui, time: f64, entity: Entity) -> Option<f64> {
-    let snapshot = view.model.snapshot_at_observe(time, Faction::Player);
+    let snapshot = view.model.snapshot_at_observe(time, view.faction);
     let orbit = snapshot.orbit(entity);
     let time = time + orbit.period()?;
     let enabled = time < orbit.end_point().time();
@@ -117,6 +118,50 @@ pub fn draw_fire_torpedo(view: &View, ui: &mut Ui, entity: Entity, time: f64) ->
     ui.add_enabled(enabled, button).on_hover_text("Fire torpedo").clicked()
 }
 
+pub fn draw_fire_gun(view: &View, ui: &mut Ui, entity: Entity, time: f64) -> bool {
+    if !FireGunEvent::can_create_ever(&view.model, entity) {
+        return false;
+    }
+    let enabled = FireGunEvent::can_create(&view.model, entity, time);
+    let button = CustomCircularImageButton::new(view, "intercept", 36)
+        .with_enabled(enabled);
+    ui.add_enabled(enabled, button).on_hover_text("Fire gun").clicked()
+}
+
+/// Returns true if could create and was clicked
+pub fn draw_fire_gun_at_target(view: &View, ui: &mut Ui, entity: Entity) -> bool {
+    if !FireGunEvent::can_create_ever(&view.model, entity) || !StartTurnEvent::can_create_ever(&view.model, entity) {
+        return false;
+    }
+    let vessel_component = view.model.vessel_component(entity);
+    let enabled = vessel_component.has_target() && vessel_component.final_gun_ammo() != 0;
+    let button = CustomCircularImageButton::new(view, "intercept", 36)
+        .with_enabled(enabled);
+    ui.add_enabled(enabled, button).on_hover_text("Fire gun at target").clicked()
+}
+
+/// Returns true if could create and was clicked
+pub fn draw_auto_intercept(view: &View, ui: &mut Ui, entity: Entity) -> bool {
+    if !StartBurnEvent::can_create_ever(&view.model, entity) {
+        return false;
+    }
+    let enabled = view.model.vessel_component(entity).has_target();
+    let button = CustomCircularImageButton::new(view, "intercept", 36)
+        .with_enabled(enabled);
+    ui.add_enabled(enabled, button).on_hover_text("Auto-intercept").clicked()
+}
+
+/// Returns true if could create and was clicked
+pub fn draw_porkchop(view: &View, ui: &mut Ui, entity: Entity) -> bool {
+    if !StartBurnEvent::can_create_ever(&view.model, entity) {
+        return false;
+    }
+    let enabled = view.model.vessel_component(entity).has_target();
+    let button = CustomCircularImageButton::new(view, "duration", 36)
+        .with_enabled(enabled);
+    ui.add_enabled(enabled, button).on_hover_text("Transfer windows").clicked()
+}
+
 pub fn draw_cancel_burn(view: &View, ui: &mut Ui) -> bool {
     let button = CustomCircularImageButton::new(view, "cancel", 36);
     ui.add(button).on_hover_text("Cancel burn").clicked()
@@ -143,3 +188,56 @@ pub fn draw_undock(view: &View, ui: &mut Ui) -> bool {
     let button = CustomCircularImageButton::new(view, "undock", 36);
     ui.add(button).on_hover_text("Undock").clicked()
 }
+
+/// Returns true if the vessel could ever dock to its target and was clicked
+pub fn draw_create_dock(view: &View, ui: &mut Ui, entity: Entity, time: f64) -> bool {
+    let Some(station) = view.model.vessel_component(entity).target() else {
+        return false;
+    };
+    if !DockEvent::can_create_ever(&view.model, entity, station) {
+        return false;
+    }
+    let enabled = DockEvent::can_create(&view.model, entity, station, time);
+    let button = CustomCircularImageButton::new(view, "dock", 36)
+        .with_enabled(enabled);
+    ui.add_enabled(enabled, button).on_hover_text("Dock here").clicked()
+}
+
+pub fn draw_create_undock(view: &View, ui: &mut Ui, station: Entity, entity: Entity, time: f64) -> bool {
+    let enabled = UndockEvent::can_create(&view.model, station, entity, time);
+    let name = view.model.name_component(entity).name();
+    let button = CustomCircularImageButton::new(view, "undock", 36)
+        .with_enabled(enabled);
+    ui.add_enabled(enabled, button).on_hover_text(format!("Undock {name} here")).clicked()
+}
+
+pub fn draw_cancel_dock(view: &View, ui: &mut Ui) -> bool {
+    let button = CustomCircularImageButton::new(view, "cancel", 36);
+    ui.add(button).on_hover_text("Cancel scheduled dock").clicked()
+}
+
+pub fn draw_mine(view: &View, ui: &mut Ui, entity: Entity, asteroid: Entity) -> bool {
+    let enabled = view.model.can_mine(entity, asteroid);
+    let button = CustomCircularImageButton::new(view, "output", 36)
+        .with_enabled(enabled);
+    ui.add_enabled(enabled, button).on_hover_text("Mine").clicked()
+}
+
+pub fn draw_stop_mining(view: &View, ui: &mut Ui) -> bool {
+    let button = CustomCircularImageButton::new(view, "cancel", 36);
+    ui.add(button).on_hover_text("Stop mining").clicked()
+}
+
+pub fn draw_cancel_undock(view: &View, ui: &mut Ui) -> bool {
+    let button = CustomCircularImageButton::new(view, "cancel", 36);
+    ui.add(button).on_hover_text("Cancel scheduled undock").clicked()
+}
+
+/// Returns true if the vessel has slots and was clicked
+pub fn draw_edit_loadout(view: &View, ui: &mut Ui, entity: Entity) -> bool {
+    if view.model.vessel_component(entity).slots().is_empty() {
+        return false;
+    }
+    let button = CustomCircularImageButton::new(view, "edit", 36);
+    ui.add(button).on_hover_text("Edit loadout").clicked()
+}
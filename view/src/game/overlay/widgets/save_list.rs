@@ -0,0 +1,58 @@
+use eframe::egui::{Context, CursorIcon, Grid, RichText, ScrollArea, Ui};
+
+use crate::{game::util::format_time, saves::{format_timestamp, SaveMetadata}};
+
+use super::labels::{draw_key, draw_value};
+
+pub enum SaveListAction {
+    Load(String),
+    Overwrite(String),
+    Delete(String),
+}
+
+fn draw_button(context: &Context, ui: &mut Ui, text: &str) -> bool {
+    let response = ui.button(RichText::new(text).strong().monospace().size(12.0));
+    if response.hovered() {
+        context.set_cursor_icon(CursorIcon::PointingHand);
+    }
+    response.clicked()
+}
+
+/// Draws a row for each save. Overwrite buttons are only drawn if `can_overwrite` is set.
+pub fn draw_save_list(context: &Context, ui: &mut Ui, saves: &[SaveMetadata], can_overwrite: bool) -> Option<SaveListAction> {
+    if saves.is_empty() {
+        draw_value(ui, "No saves");
+        return None;
+    }
+
+    let mut action = None;
+    ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+        Grid::new("Save list").num_columns(5).spacing([20.0, 8.0]).show(ui, |ui| {
+            draw_key(ui, "Name");
+            draw_key(ui, "Level");
+            draw_key(ui, "Time");
+            draw_key(ui, "Saved");
+            ui.end_row();
+
+            for save in saves {
+                draw_value(ui, save.name());
+                draw_value(ui, save.level().unwrap_or("-"));
+                draw_value(ui, &format_time(save.time()));
+                draw_value(ui, &format_timestamp(save.saved_at()));
+                ui.horizontal(|ui| {
+                    if draw_button(context, ui, "Load") {
+                        action = Some(SaveListAction::Load(save.name().to_string()));
+                    }
+                    if can_overwrite && draw_button(context, ui, "Overwrite") {
+                        action = Some(SaveListAction::Overwrite(save.name().to_string()));
+                    }
+                    if draw_button(context, ui, "Delete") {
+                        action = Some(SaveListAction::Delete(save.name().to_string()));
+                    }
+                });
+                ui.end_row();
+            }
+        });
+    });
+    action
+}
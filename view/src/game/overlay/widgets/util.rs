@@ -9,8 +9,9 @@ pub fn advance_cursor_to(ui: &mut Ui, x: f32) {
     ui.advance_cursor_after_rect(rect);
 }
 
+/// Projectiles have an empty tank, which is not worth showing
 pub fn should_draw_fuel(vessel_component: &VesselComponent) -> bool {
-    vessel_component.has_fuel_tank()
+    vessel_component.has_fuel_tank() && vessel_component.fuel_capacity_kg() > 0.0
 }
 
 pub fn should_draw_dv(vessel_component: &VesselComponent) -> bool {
@@ -19,4 +20,16 @@ pub fn should_draw_dv(vessel_component: &VesselComponent) -> bool {
 
 pub fn should_draw_torpedoes(vessel_component: &VesselComponent) -> bool {
     vessel_component.has_torpedo_storage()
+}
+
+pub fn should_draw_cargo(vessel_component: &VesselComponent) -> bool {
+    vessel_component.has_cargo_hold()
+}
+
+pub fn should_draw_point_defense(vessel_component: &VesselComponent) -> bool {
+    vessel_component.has_point_defense()
+}
+
+pub fn should_draw_gun(vessel_component: &VesselComponent) -> bool {
+    vessel_component.has_gun()
 }
\ No newline at end of file
@@ -0,0 +1,252 @@
+//! Replays record the model a session started from along with every model
+//! event and the dt of every frame, which is enough to reproduce the session
+//! exactly since the model is deterministic. The state hash is recorded
+//! every so often so that playback can tell when it has diverged
+
+use std::fmt::Display;
+
+use log::error;
+use serde::{Deserialize, Serialize};
+use transfer_window_model::model::{save::SaveError, Model};
+
+use crate::replays::{self, LATEST_REPLAY_NAME};
+
+use super::{events::ModelEvent, View};
+
+pub mod playback;
+
+pub const REPLAY_VERSION: u32 = 1;
+/// How many frames apart the state hash is recorded
+const HASH_INTERVAL: usize = 60;
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Corrupt(String),
+    TooNew { version: u32 },
+    Model(SaveError),
+    ClassDefinitions,
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Corrupt(reason) => write!(f, "replay is corrupt: {reason}"),
+            ReplayError::TooNew { version } => write!(f, "replay version {version} is newer than the supported version {REPLAY_VERSION}"),
+            ReplayError::Model(error) => write!(f, "replay model could not be loaded: {error}"),
+            ReplayError::ClassDefinitions => write!(f, "replay was recorded with different vessel class definitions"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Everything that happened in one frame: the events applied at `time`,
+/// followed by a model update with `dt`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    time: f64,
+    dt: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    events: Vec<ModelEvent>,
+    /// State hash after the update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<u64>,
+}
+
+impl ReplayFrame {
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn dt(&self) -> f64 {
+        self.dt
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Replay {
+    version: u32,
+    /// The initial model, in the save format so that old replays are migrated along with saves
+    model: String,
+    /// Replays recorded before this was added are played without checking it
+    #[serde(default)]
+    class_definitions_hash: Option<u64>,
+    frames: Vec<ReplayFrame>,
+}
+
+/// Only used to check the version before trying to read the rest of the replay
+#[derive(Deserialize)]
+struct ReplayHeader {
+    version: u32,
+}
+
+impl Replay {
+    /// # Errors
+    /// Returns an error if the replay is from a newer version of the game or cannot be read
+    pub fn deserialize(serialized: &str) -> Result<Self, ReplayError> {
+        let header: ReplayHeader = serde_json::from_str(serialized).map_err(|error| ReplayError::Corrupt(error.to_string()))?;
+        if header.version > REPLAY_VERSION {
+            return Err(ReplayError::TooNew { version: header.version });
+        }
+        serde_json::from_str(serialized).map_err(|error| ReplayError::Corrupt(error.to_string()))
+    }
+
+    /// # Errors
+    /// Returns an error if the replay fails to serialize
+    pub fn serialize(&self) -> Result<String, ReplayError> {
+        serde_json::to_string(self).map_err(|error| ReplayError::Corrupt(error.to_string()))
+    }
+
+    /// # Errors
+    /// Returns an error if the initial model cannot be loaded, or the replay
+    /// was recorded with different vessel class definitions
+    pub fn initial_model(&self) -> Result<Model, ReplayError> {
+        if self.class_definitions_hash.is_some_and(|hash| hash != Model::class_definitions_hash()) {
+            return Err(ReplayError::ClassDefinitions);
+        }
+        Model::deserialize(&self.model).map_err(ReplayError::Model)
+    }
+
+    pub fn frames(&self) -> &[ReplayFrame] {
+        &self.frames
+    }
+
+    /// Simulation time at the end of the replay
+    pub fn duration(&self) -> f64 {
+        self.frames.last().map_or(0.0, |frame| frame.time)
+    }
+}
+
+/// Records a session as it is played
+pub(crate) struct ReplayRecorder {
+    replay: Replay,
+    events: Vec<ModelEvent>,
+}
+
+impl ReplayRecorder {
+    pub fn new(model: &Model) -> Result<Self, ReplayError> {
+        let model = model.serialize().map_err(ReplayError::Model)?;
+        let class_definitions_hash = Some(Model::class_definitions_hash());
+        let replay = Replay { version: REPLAY_VERSION, model, class_definitions_hash, frames: vec![] };
+        Ok(Self { replay, events: vec![] })
+    }
+
+    pub fn record_event(&mut self, event: &ModelEvent) {
+        self.events.push(event.clone());
+    }
+
+    /// Must be called after every model update, where `time` is the model
+    /// time before the update
+    pub fn record_update(&mut self, model: &Model, time: f64, dt: f64) {
+        let events = std::mem::take(&mut self.events);
+        let hash = self.replay.frames.len().is_multiple_of(HASH_INTERVAL).then(|| model.state_hash());
+        self.replay.frames.push(ReplayFrame { time, dt, events, hash });
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+}
+
+impl View {
+    /// Writes the session recorded so far to the latest replay
+    pub(crate) fn save_replay(&self) {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Save replay");
+        let Some(recorder) = &self.recorder else {
+            return;
+        };
+        let serialized = match recorder.replay().serialize() {
+            Ok(serialized) => serialized,
+            Err(error) => {
+                error!("Failed to serialize replay: {error}");
+                return;
+            }
+        };
+        if let Err(error) = replays::write(LATEST_REPLAY_NAME, serialized) {
+            error!("Failed to write replay: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use transfer_window_model::{components::{path_component::orbit::{builder::InitialOrbitBuilder, orbit_direction::OrbitDirection}, vessel_component::{class::VesselClass, faction::Faction, VesselComponent}}, model::Model, storage::entity_builder::VesselBuilder, test_util};
+
+    use crate::game::events::ModelEvent;
+
+    use super::{playback::Playback, Replay, ReplayError, ReplayRecorder};
+
+    /// Plays a short session with a few commands, recording it as it goes
+    fn record() -> (Model, Replay) {
+        let mut model = Model::default();
+        let sun = test_util::sun(&mut model);
+        let earth = test_util::earth(&mut model, sun);
+        let mut recorder = ReplayRecorder::new(&model).unwrap();
+
+        let vessel_builder = VesselBuilder {
+            name: "Ship".to_string(),
+            vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
+            orbit_builder: InitialOrbitBuilder::Circular { parent: earth, distance: 0.01e9, angle: 0.0, direction: OrbitDirection::AntiClockwise },
+        };
+        let commands = [(3, ModelEvent::BuildVessel { vessel_builder }), (50, ModelEvent::IncreaseTimeStepLevel), (120, ModelEvent::TogglePaused)];
+        for frame in 0..200 {
+            for (_, event) in commands.iter().filter(|(at, _)| *at == frame) {
+                recorder.record_event(event);
+                event.clone().apply(&mut model);
+            }
+            let time = model.time();
+            // Frames are never exactly the same length
+            let dt = 1.0 / 60.0 + f64::from(frame % 7) * 1.0e-4;
+            model.update(dt);
+            recorder.record_update(&model, time, dt);
+        }
+
+        let serialized = recorder.replay().serialize().unwrap();
+        (model, Replay::deserialize(&serialized).unwrap())
+    }
+
+    #[test]
+    fn test_playback_reproduces_session() {
+        let (recorded, replay) = record();
+        let (mut playback, mut model) = Playback::new(replay).unwrap();
+        while !playback.is_finished() {
+            playback.step(&mut model);
+        }
+        assert!(playback.desync().is_none());
+        assert_eq!(model.state_hash(), recorded.state_hash());
+    }
+
+    #[test]
+    fn test_seek() {
+        let (recorded, replay) = record();
+        let (mut playback, mut model) = Playback::new(replay).unwrap();
+        playback.seek(&mut model, 150).unwrap();
+        let hash = model.state_hash();
+
+        playback.seek(&mut model, 10).unwrap();
+        assert_eq!(playback.frame(), 10);
+        playback.seek(&mut model, 150).unwrap();
+        assert_eq!(model.state_hash(), hash);
+
+        playback.seek(&mut model, playback.frame_count()).unwrap();
+        assert!(playback.is_finished());
+        assert_eq!(model.state_hash(), recorded.state_hash());
+    }
+
+    #[test]
+    fn test_desync_detected() {
+        let (_, mut replay) = record();
+        replay.frames[70].dt *= 2.0;
+        let (mut playback, mut model) = Playback::new(replay).unwrap();
+        playback.seek(&mut model, playback.frame_count()).unwrap();
+        assert_eq!(playback.desync(), Some(120));
+    }
+
+    #[test]
+    fn test_different_class_definitions_rejected() {
+        let (_, mut replay) = record();
+        replay.class_definitions_hash = replay.class_definitions_hash.map(|hash| hash ^ 1);
+        assert!(matches!(Playback::new(replay), Err(ReplayError::ClassDefinitions)));
+    }
+}
@@ -0,0 +1,147 @@
+use log::error;
+use transfer_window_model::model::{story_event::StoryEvent, Model};
+
+use super::{Replay, ReplayError};
+
+/// How many frames apart checkpoints are taken while playing, which is
+/// about a minute at 60 FPS
+const CHECKPOINT_INTERVAL: usize = 3600;
+/// Stops high speeds from stalling the game when the recorded frames are short
+const MAX_FRAMES_PER_UPDATE: usize = 1000;
+pub const PLAYBACK_SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+
+/// Re-applies the frames of a replay to a model. The model is not owned so
+/// that it can be drawn by the view like any other
+pub struct Playback {
+    replay: Replay,
+    /// Index of the next frame to play
+    frame: usize,
+    speed: f64,
+    paused: bool,
+    /// Real time which has passed but not been played yet
+    accumulated: f64,
+    /// Serialized models at the start of the frame, in order of frame
+    checkpoints: Vec<(usize, String)>,
+    /// The first frame after which the state hash did not match the recording
+    desync: Option<usize>,
+}
+
+impl Playback {
+    /// Returns the playback along with the model it starts from
+    /// # Errors
+    /// Returns an error if the initial model cannot be loaded
+    pub fn new(replay: Replay) -> Result<(Self, Model), ReplayError> {
+        let model = replay.initial_model()?;
+        let checkpoints = vec![(0, replay.model.clone())];
+        let playback = Self { replay, frame: 0, speed: 1.0, paused: false, accumulated: 0.0, checkpoints, desync: None };
+        Ok((playback, model))
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.replay.frames.len()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.frame_count()
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = speed;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn desync(&self) -> Option<usize> {
+        self.desync
+    }
+
+    /// Plays the next frame, checking the state hash if one was recorded
+    pub fn step(&mut self, model: &mut Model) -> Vec<StoryEvent> {
+        let Some(frame) = self.replay.frames.get(self.frame) else {
+            return vec![];
+        };
+
+        if self.frame.is_multiple_of(CHECKPOINT_INTERVAL) && self.checkpoints.last().is_some_and(|(checkpoint, _)| *checkpoint < self.frame) {
+            match model.serialize() {
+                Ok(serialized) => self.checkpoints.push((self.frame, serialized)),
+                Err(error) => error!("Failed to create replay checkpoint: {error}"),
+            }
+        }
+
+        for event in frame.events.clone() {
+            event.apply(model);
+        }
+        let story_events = model.update(frame.dt);
+
+        if let Some(hash) = frame.hash {
+            if self.desync.is_none() && hash != model.state_hash() {
+                error!("Replay diverged from the recording at frame {}", self.frame);
+                self.desync = Some(self.frame);
+            }
+        }
+
+        self.frame += 1;
+        story_events
+    }
+
+    /// Plays as many frames as fit in `dt` seconds of real time at the current speed
+    pub fn update(&mut self, model: &mut Model, dt: f64) -> Vec<StoryEvent> {
+        if self.paused {
+            return vec![];
+        }
+        self.accumulated += dt * self.speed;
+        let mut story_events = vec![];
+        for _ in 0..MAX_FRAMES_PER_UPDATE {
+            let Some(frame) = self.replay.frames.get(self.frame) else {
+                self.accumulated = 0.0;
+                break;
+            };
+            if frame.dt > self.accumulated {
+                return story_events;
+            }
+            self.accumulated -= frame.dt;
+            story_events.extend(self.step(model));
+        }
+        // Falling behind, so drop the backlog rather than trying to catch up
+        self.accumulated = 0.0;
+        story_events
+    }
+
+    /// Moves to the start of `frame` by re-simulating from the closest
+    /// checkpoint, or from the current frame if that is closer
+    /// # Errors
+    /// Returns an error if the checkpoint cannot be loaded
+    pub fn seek(&mut self, model: &mut Model, frame: usize) -> Result<(), ReplayError> {
+        let frame = frame.min(self.frame_count());
+        // There is always a checkpoint at the first frame
+        let index = self.checkpoints.iter().rposition(|(checkpoint, _)| *checkpoint <= frame).unwrap();
+        let checkpoint = self.checkpoints[index].0;
+        if frame < self.frame || checkpoint > self.frame {
+            *model = Model::deserialize(&self.checkpoints[index].1).map_err(ReplayError::Model)?;
+            self.frame = checkpoint;
+        }
+        while self.frame < frame {
+            self.step(model);
+        }
+        self.accumulated = 0.0;
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use eframe::egui::PointerState;
-use transfer_window_model::{components::vessel_component::faction::Faction, model::state_query::StateQuery, storage::entity_allocator::Entity};
+use transfer_window_model::{model::state_query::StateQuery, storage::entity_allocator::Entity};
 
 use crate::game::{selected::Selected, util::add_textured_square, View};
 
@@ -11,7 +11,7 @@ const SELECTED_ALPHA: f32 = 1.0;
 fn draw_selected_circle(view: &View, entity: Entity, time: f64, alpha: f32) {
     let select_radius = SELECT_RADIUS / view.camera.zoom();
     let mut vertices = vec![];
-    let snapshot = view.model.snapshot_at_observe(time, Faction::Player);
+    let snapshot = view.model.snapshot_at_observe(time, view.faction);
     let segment = snapshot.segment(entity);
     let point = view.model.absolute_position(segment.parent()) + segment.position_at_time(time);
     add_textured_square(&mut vertices, point, select_radius, alpha);
@@ -39,19 +39,19 @@ pub fn draw_hover(view: &View, pointer: &PointerState) {
     
     let select_distance = SELECT_DISTANCE / view.camera.zoom();
     let latest_world = view.window_space_to_world_space(latest_window);
-    if let Some((entity, time)) = view.model.closest_burn_point(latest_world, select_distance, Some(Faction::Player)) {
+    if let Some((entity, time)) = view.model.closest_burn_point(latest_world, select_distance, Some(view.faction)) {
         draw_selected_circle(view, entity, time, HOVERED_ALPHA);
         return;
     }
-    if let Some((entity, time)) = view.model.closest_turn_point(latest_world, select_distance, Some(Faction::Player)) {
+    if let Some((entity, time)) = view.model.closest_turn_point(latest_world, select_distance, Some(view.faction)) {
         draw_selected_circle(view, entity, time, HOVERED_ALPHA);
         return;
     }
-    if let Some((entity, time)) = view.model.closest_guidance_point(latest_world, select_distance, Some(Faction::Player)) {
+    if let Some((entity, time)) = view.model.closest_guidance_point(latest_world, select_distance, Some(view.faction)) {
         draw_selected_circle(view, entity, time, HOVERED_ALPHA);
         return;
     }
-    if let Some((entity, time)) = view.model.closest_orbit_point(latest_world, select_distance, Some(Faction::Player)) {
+    if let Some((entity, time)) = view.model.closest_orbit_point(latest_world, select_distance, Some(view.faction)) {
         draw_selected_circle(view, entity, time, HOVERED_ALPHA);
     }
 }
@@ -0,0 +1,83 @@
+use std::{collections::HashMap, sync::Arc};
+
+use eframe::{egui::Context, glow};
+use serde::{Deserialize, Serialize};
+use transfer_window_model::{model::Model, storage::entity_allocator::Entity};
+
+use crate::resources::Resources;
+
+use super::{camera::Camera, overlay::{dialogue::Dialogue, objectives::Objective}, storyteller::{stories::story_builder, story::{transition::Transition, Story}}, View, ViewConfig};
+
+/// Everything outside the model needed to resume a game where it was saved
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Session {
+    level: Option<String>,
+    /// Copy of the model's time, so that saves can be listed without
+    /// loading the model. Missing from saves written before it was added
+    #[serde(default)]
+    time: Option<f64>,
+    story_state: String,
+    /// What the story state was waiting on, so that the state does not have
+    /// to be entered again. Missing from saves written before it was added
+    #[serde(default)]
+    story_transition: Option<Transition>,
+    persistent_data: HashMap<String, Entity>,
+    config: ViewConfig,
+    camera: Camera,
+    objectives: Vec<Objective>,
+    #[serde(default)]
+    dialogue: Option<Dialogue>,
+}
+
+/// The parts of a session shown when listing saves
+#[derive(Debug, Deserialize)]
+pub struct SessionSummary {
+    level: Option<String>,
+    #[serde(default)]
+    time: Option<f64>,
+}
+
+impl SessionSummary {
+    pub fn level(&self) -> Option<&str> {
+        self.level.as_deref()
+    }
+
+    pub fn time(&self) -> Option<f64> {
+        self.time
+    }
+}
+
+impl View {
+    pub(crate) fn session(&self) -> Session {
+        Session {
+            level: self.story.level().map(str::to_string),
+            time: Some(self.model.time()),
+            story_state: self.story.state().to_string(),
+            story_transition: self.story.transition(),
+            persistent_data: self.story.persistent_data().clone(),
+            config: self.config.clone(),
+            camera: self.camera.clone(),
+            objectives: self.objectives.clone(),
+            dialogue: self.dialogue.clone(),
+        }
+    }
+
+    pub fn from_session(gl: Arc<glow::Context>, model: Model, session: Session, context: Context, resources: Arc<Resources>) -> Self {
+        let story = match session.level.as_deref().and_then(story_builder) {
+            Some(story_builder) => {
+                // The story's states capture entities from the model they were built with,
+                // which line up with the saved model as both were built by the same level
+                let (_, mut story, _, _) = story_builder.build();
+                story.restore(&session.story_state, session.story_transition, session.persistent_data);
+                story
+            }
+            None => Story::empty(),
+        };
+
+        let mut view = Self::new(gl, model, story, context, resources, session.config, None);
+        view.camera = session.camera;
+        view.objectives = session.objectives;
+        view.dialogue = session.dialogue;
+        view
+    }
+}
@@ -34,3 +34,13 @@ impl Clone for Box<dyn StoryBuilder> {
         self.clone_box()
     }
 }
+
+pub fn story_builder(level: &str) -> Option<Box<dyn StoryBuilder>> {
+    match level {
+        "1-01" => Some(Box::new(story_1_01::Story1_01)),
+        "1-02" => Some(Box::new(story_1_02::Story1_02)),
+        "1-03" => Some(Box::new(story_1_03::Story1_03)),
+        "1-04" => Some(Box::new(story_1_04::Story1_04)),
+        _ => None,
+    }
+}
@@ -42,6 +42,7 @@ impl StoryBuilder for Story1_01 {
             rotation_period: 24.0 * 60.0 * 60.0,
             rotation_angle: 100.0,
             type_: OrbitableType::Planet,
+            deposits: vec![],
             physics: OrbitablePhysicsBuilder::Stationary(vec2(0.0, 0.0)),
             atmosphere: Atmosphere::new_some(
                 Color32::from_hex("#a0b5ff").unwrap(),
@@ -59,7 +60,7 @@ impl StoryBuilder for Story1_01 {
             draw_timeline: false,
         };
 
-        let mut story = Story::new("intro-1");
+        let mut story = Story::new("1-01", "intro-1");
 
         story.add("intro-1", |view| {
             view.add_view_event(ViewEvent::ShowDialogue(
@@ -103,8 +104,8 @@ impl StoryBuilder for Story1_01 {
         story.add("created-ship", move |view| {
             view.add_model_event(ModelEvent::BuildVessel { 
                 vessel_builder: VesselBuilder {
-                    name: "Ship 1",
-                    vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::Player),
+                    name: "Ship 1".to_string(),
+                    vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
                     orbit_builder: InitialOrbitBuilder::Circular { 
                         parent: centralia,
                         distance: 1.0e7,
@@ -197,8 +198,8 @@ impl StoryBuilder for Story1_01 {
             view.add_view_event(ViewEvent::SetCameraFocus(centralia));
             view.add_model_event(ModelEvent::BuildVessel { 
                 vessel_builder: VesselBuilder {
-                    name: "Ship 2",
-                    vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::Player),
+                    name: "Ship 2".to_string(),
+                    vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
                     orbit_builder: InitialOrbitBuilder::Freeform { 
                         parent: centralia,
                         distance: 1.0e7,
@@ -255,8 +256,8 @@ impl StoryBuilder for Story1_01 {
             }));
             view.add_model_event(ModelEvent::BuildVessel { 
                 vessel_builder: VesselBuilder {
-                    name: "Ship",
-                    vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::Player),
+                    name: "Ship".to_string(),
+                    vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
                     orbit_builder: InitialOrbitBuilder::Freeform {
                         parent: centralia,
                         distance: 1.0e7,
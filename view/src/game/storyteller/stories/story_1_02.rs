@@ -41,6 +41,7 @@ impl StoryBuilder for Story1_02 {
             rotation_period: 24.0 * 60.0 * 60.0,
             rotation_angle: 100.0,
             type_: OrbitableType::Planet,
+            deposits: vec![],
             physics: OrbitablePhysicsBuilder::Stationary(vec2(0.0, 0.0)),
             atmosphere: Atmosphere::new_some(
                 Color32::from_hex("#a0b5ff").unwrap(),
@@ -59,8 +60,8 @@ impl StoryBuilder for Story1_02 {
         };
 
         let ship = VesselBuilder {
-            name: "Ship",
-            vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::Player),
+            name: "Ship".to_string(),
+            vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
             orbit_builder: InitialOrbitBuilder::Freeform { 
                 parent: centralia,
                 distance: 9.371e6,
@@ -70,7 +71,7 @@ impl StoryBuilder for Story1_02 {
             },
         }.build(&mut model);
 
-        let mut story = Story::new("intro");
+        let mut story = Story::new("1-02", "intro");
 
         story.add("intro", |view| {
             view.add_view_event(ViewEvent::ShowDialogue(
@@ -124,8 +125,8 @@ impl StoryBuilder for Story1_02 {
             view.add_model_event(ModelEvent::DeleteVessel { entity: ship });
             view.add_model_event(ModelEvent::BuildVessel { 
                 vessel_builder: VesselBuilder {
-                    name: "Ship",
-                    vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::Player),
+                    name: "Ship".to_string(),
+                    vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
                     orbit_builder: InitialOrbitBuilder::Circular { 
                         parent: centralia,
                         distance: 9.371e6,
@@ -230,7 +231,7 @@ impl StoryBuilder for Story1_02 {
 
         story.add("warp-first-burn", |view| {
             let ship = view.model.entity_by_name("Ship").unwrap();
-            let time = view.model.snapshot_now_observe(Faction::Player).future_burns(ship).first().unwrap().start_point().time() - 10.0;
+            let time = view.model.snapshot_now_observe(Faction::PLAYER).future_burns(ship).first().unwrap().start_point().time() - 10.0;
             view.add_model_event(ModelEvent::ForceUnpause);
             view.add_view_event(ViewEvent::ShowDialogue(
                 Dialogue::new("jake")
@@ -243,7 +244,7 @@ impl StoryBuilder for Story1_02 {
 
         story.add("select-ship", |view| {
             let ship = view.model.entity_by_name("Ship").unwrap();
-            let time = view.model.snapshot_now_observe(Faction::Player)
+            let time = view.model.snapshot_now_observe(Faction::PLAYER)
                 .future_burns(ship)
                 .first()
                 .map_or_else(|| view.model.time(), |burn| burn.end_point().time());
@@ -256,7 +257,7 @@ impl StoryBuilder for Story1_02 {
 
         story.add("warp-to-circle", |view| {
             let ship = view.model.entity_by_name("Ship").unwrap();
-            let time = view.model.snapshot_now_observe(Faction::Player).future_segments(ship).last().unwrap().start_time();
+            let time = view.model.snapshot_now_observe(Faction::PLAYER).future_segments(ship).last().unwrap().start_time();
             view.add_view_event(ViewEvent::ShowDialogue(
                 Dialogue::new("jake")
                     .normal("Now warp until the ship is on its final orbit.")
@@ -40,6 +40,7 @@ impl StoryBuilder for Story1_03 {
             rotation_period: 24.0 * 60.0 * 60.0,
             rotation_angle: 100.0,
             type_: OrbitableType::Planet,
+            deposits: vec![],
             physics: OrbitablePhysicsBuilder::Stationary(vec2(0.0, 0.0)),
             atmosphere: Atmosphere::new_some(
                 Color32::from_hex("#a0b5ff").unwrap(),
@@ -51,8 +52,8 @@ impl StoryBuilder for Story1_03 {
         }.build(&mut model);
 
         let player_ship = VesselBuilder {
-            name: "Ship",
-            vessel_component: VesselComponent::new(VesselClass::Frigate1, Faction::Player),
+            name: "Ship".to_string(),
+            vessel_component: VesselComponent::new(VesselClass::Frigate1, Faction::PLAYER),
             orbit_builder: InitialOrbitBuilder::Circular {
                 parent: centralia,
                 distance: 9.371e6,
@@ -62,8 +63,8 @@ impl StoryBuilder for Story1_03 {
         }.build(&mut model);
 
         let enemy_ship = VesselBuilder {
-            name: "Ship",
-            vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::Enemy),
+            name: "Ship".to_string(),
+            vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::ENEMY),
             orbit_builder: InitialOrbitBuilder::Circular {
                 parent: centralia,
                 distance: 10.371e6,
@@ -72,7 +73,7 @@ impl StoryBuilder for Story1_03 {
             },
         }.build(&mut model);
 
-        let mut story = Story::new("intro");
+        let mut story = Story::new("1-03", "intro");
 
         story.add("intro", |view| {
             view.add_view_event(ViewEvent::ShowDialogue(
@@ -1,12 +1,16 @@
+use std::f64::consts::PI;
+
 use eframe::egui::Color32;
 use nalgebra_glm::vec2;
 use transfer_window_model::components::orbitable_component::atmosphere::Atmosphere;
 use transfer_window_model::components::orbitable_component::builder::OrbitablePhysicsBuilder;
+use transfer_window_model::components::orbitable_component::deposit::ResourceDeposit;
 use transfer_window_model::components::orbitable_component::OrbitableType;
 use transfer_window_model::components::path_component::orbit::builder::InitialOrbitBuilder;
 use transfer_window_model::components::path_component::orbit::orbit_direction::OrbitDirection;
 use transfer_window_model::components::vessel_component::class::VesselClass;
 use transfer_window_model::components::vessel_component::faction::Faction;
+use transfer_window_model::components::vessel_component::resource::ResourceType;
 use transfer_window_model::components::vessel_component::VesselComponent;
 use transfer_window_model::model::Model;
 use transfer_window_model::storage::entity_allocator::Entity;
@@ -40,6 +44,7 @@ impl StoryBuilder for Story1_04 {
             rotation_period: 24.0 * 60.0 * 60.0,
             rotation_angle: 100.0,
             type_: OrbitableType::Planet,
+            deposits: vec![],
             physics: OrbitablePhysicsBuilder::Stationary(vec2(0.0, 0.0)),
             atmosphere: Atmosphere::new_some(
                 Color32::from_hex("#a0b5ff").unwrap(),
@@ -57,6 +62,7 @@ impl StoryBuilder for Story1_04 {
             rotation_period: 29.5 * 24.0 * 60.0 * 60.0,
             rotation_angle: 100.0,
             type_: OrbitableType::Moon,
+            deposits: vec![],
             physics: OrbitablePhysicsBuilder::Orbit(InitialOrbitBuilder::Freeform { 
                 parent: centralia, 
                 distance: 0.3633e9,
@@ -67,9 +73,26 @@ impl StoryBuilder for Story1_04 {
             atmosphere: None,
         }.build(&mut model);
 
+        let _vesta = OrbitableBuilder {
+            name: "Vesta",
+            mass: 2.6e10,
+            radius: 2.0e3,
+            rotation_period: 5.3 * 60.0 * 60.0,
+            rotation_angle: 0.0,
+            type_: OrbitableType::Asteroid,
+            deposits: vec![ResourceDeposit::new(ResourceType::Metal, 40.0e3), ResourceDeposit::new(ResourceType::Oil, 15.0e3)],
+            physics: OrbitablePhysicsBuilder::Orbit(InitialOrbitBuilder::Circular {
+                parent: centralia,
+                distance: 0.05e9,
+                angle: PI,
+                direction: OrbitDirection::AntiClockwise,
+            }),
+            atmosphere: None,
+        }.build(&mut model);
+
         let _player_ship = VesselBuilder {
-            name: "Ship",
-            vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::Player),
+            name: "Ship".to_string(),
+            vessel_component: VesselComponent::new(VesselClass::Scout1, Faction::PLAYER),
             orbit_builder: InitialOrbitBuilder::Circular {
                 parent: centralia,
                 distance: 9.371e6,
@@ -78,7 +101,7 @@ impl StoryBuilder for Story1_04 {
             },
         }.build(&mut model);
 
-        let mut story = Story::new("intro");
+        let mut story = Story::new("1-04", "intro");
 
         story.add("intro", |view| {
             view.add_view_event(ViewEvent::ShowDialogue(
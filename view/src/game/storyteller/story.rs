@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 
 use condition::Condition;
+use log::error;
 use state::{State, StateCreator};
 use transfer_window_model::storage::entity_allocator::Entity;
 use transition::Transition;
@@ -14,6 +15,7 @@ pub(super) mod state;
 pub mod transition;
 
 pub struct Story {
+    level: Option<&'static str>,
     state_creators: HashMap<&'static str, StateCreator>,
     state: Mutex<State>,
     state_string: Mutex<&'static str>,
@@ -21,18 +23,59 @@ pub struct Story {
 }
 
 impl Story {
-    pub fn new(root: &'static str) -> Self {
+    pub fn new(level: &'static str, root: &'static str) -> Self {
+        let mut story = Self::without_level(root);
+        story.level = Some(level);
+        story
+    }
+
+    pub fn empty() -> Self {
+        let mut story = Self::without_level("root");
+        story.add("root", |_| State::default());
+        story
+    }
+
+    fn without_level(root: &'static str) -> Self {
+        let level = None;
         let state_creators = HashMap::new();
         let state = Mutex::new(State::default().transition(Transition::new(root, Condition::none())));
         let state_string = Mutex::new("uninitialized");
         let persistent_data = HashMap::new();
-        Self { state_creators, state, state_string, persistent_data }
+        Self { level, state_creators, state, state_string, persistent_data }
     }
 
-    pub fn empty() -> Self {
-        let mut story = Self::new("root");
-        story.add("root", |_| State::default());
-        story
+    /// Restores a story saved in `state` while it was waiting on
+    /// `transition`. The state was already entered before the save, so
+    /// whatever it did on entry is not done again
+    pub(crate) fn restore(&mut self, state: &str, transition: Option<Transition>, persistent_data: HashMap<String, Entity>) {
+        let Some((state, _)) = self.state_creators.get_key_value(state) else {
+            error!("Attempt to restore nonexistent state {state}");
+            return;
+        };
+        let mut restored = State::default();
+        if let Some(transition) = transition {
+            restored = restored.transition(transition);
+        }
+        self.state = Mutex::new(restored);
+        self.state_string = Mutex::new(state);
+        self.persistent_data = persistent_data;
+    }
+
+    pub fn level(&self) -> Option<&'static str> {
+        self.level
+    }
+
+    pub fn state(&self) -> &'static str {
+        *self.state_string.lock().unwrap()
+    }
+
+    /// What the current state is waiting on before moving to the next one
+    pub(crate) fn transition(&self) -> Option<Transition> {
+        self.state.lock().unwrap().get_transition().clone()
+    }
+
+    pub(crate) fn persistent_data(&self) -> &HashMap<String, Entity> {
+        &self.persistent_data
     }
 
     pub(super) fn add(&mut self, name: &'static str, factory: impl Fn(&View) -> State + 'static) {
@@ -49,18 +92,20 @@ impl Story {
     }
 
     pub fn update(&self, view: &View) {
-        let Some((state_string, objective)) = self.state.lock().unwrap().try_transition(view) else { 
+        let Some((state_string, objective)) = self.state.lock().unwrap()
+            .try_transition(view)
+            .map(|transition| (transition.to().to_string(), transition.objective().map(str::to_string))) else { 
             return 
         };
 
+        let (state_string, state_creator) = self.state_creators.get_key_value(state_string.as_str())
+            .unwrap_or_else(|| panic!("State does not exist {state_string}"));
         *self.state_string.lock().unwrap() = state_string;
-        *self.state.lock().unwrap() = self.state_creators.get(state_string)
-            .unwrap_or_else(|| panic!("State does not exist {state_string}"))
-            .create(view);
+        *self.state.lock().unwrap() = state_creator.create(view);
         
         if let Some(transition) = self.state.lock().unwrap().get_transition() {
             if let Some(objective) = transition.objective() {
-                view.add_view_event(ViewEvent::StartObjective(objective));
+                view.add_view_event(ViewEvent::StartObjective(objective.to_string()));
             }
         }
 
@@ -73,4 +118,33 @@ impl Story {
     pub fn states(&self) -> &HashMap<&'static str, StateCreator> {
         &self.state_creators
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{condition::Condition, state::State, transition::Transition, Story};
+
+    fn test_story() -> Story {
+        let mut story = Story::new("test", "build-ship");
+        story.add("build-ship", |_| panic!("Attempt to enter build-ship again"));
+        story.add("fly-ship", |_| State::default());
+        story
+    }
+
+    #[test]
+    fn test_save_and_load_in_middle_of_state() {
+        let mut story = test_story();
+        let transition = Transition::new("fly-ship", Condition::time(100.0).objective("Wait"));
+        story.restore("build-ship", Some(transition), HashMap::new());
+        let serialized = serde_json::to_string(&story.transition()).unwrap();
+
+        let mut loaded = test_story();
+        loaded.restore(story.state(), serde_json::from_str(&serialized).unwrap(), story.persistent_data().clone());
+        assert_eq!(loaded.state(), "build-ship");
+        // Still waiting to leave the state, rather than about to enter it again
+        let transition = loaded.transition().unwrap();
+        assert_eq!(transition.to(), "fly-ship");
+        assert_eq!(transition.objective(), Some("Wait"));
+    }
+}
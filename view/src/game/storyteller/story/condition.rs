@@ -1,11 +1,13 @@
 use click_continue_condition::ClickContinueCondition;
 use create_burn_condition::CreateBurnCondition;
+use destroyed_condition::DestroyedCondition;
 use enable_guidance_condition::EnableGuidanceCondition;
 use fire_torpedo_adjust_condition::FireTorpedoAdjustCondition;
 use fire_torpedo_condition::FireTorpedoCondition;
 use first_closest_approach_condition::FirstClosestApproachCondition;
 use focus_condition::FocusCondition;
 use get_intercept::GetInterceptCondition;
+use impact_condition::ImpactCondition;
 use last_orbit_apoapsis_condition::LastOrbitApoapsis;
 use last_orbit_circular_condition::LastOrbitCircular;
 use none_condition::NoneCondition;
@@ -17,17 +19,20 @@ use set_target::SetTargetCondition;
 use start_any_warp_condition::StartAnyWarpCondition;
 use start_burn_adjust_condition::StartBurnAdjustCondition;
 use time_condition::TimeCondition;
+use serde::{Deserialize, Serialize};
 use transfer_window_model::{model::story_event::StoryEvent, storage::entity_allocator::Entity};
 
 use crate::game::View;
 
 mod click_continue_condition;
 mod create_burn_condition;
+mod destroyed_condition;
 mod enable_guidance_condition;
 mod fire_torpedo_condition;
 mod first_closest_approach_condition;
 mod focus_condition;
 mod get_intercept;
+mod impact_condition;
 mod last_orbit_apoapsis_condition;
 mod last_orbit_circular_condition;
 mod none_condition;
@@ -41,90 +46,154 @@ mod start_burn_adjust_condition;
 mod fire_torpedo_adjust_condition;
 mod time_condition;
 
+/// Every kind of condition, so that the condition a story is waiting on
+/// can be saved with the session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum Check {
+    ClickContinue(ClickContinueCondition),
+    CreateBurn(CreateBurnCondition),
+    Destroyed(DestroyedCondition),
+    EnableGuidance(EnableGuidanceCondition),
+    FireTorpedoAdjust(FireTorpedoAdjustCondition),
+    FireTorpedo(FireTorpedoCondition),
+    FirstClosestApproach(FirstClosestApproachCondition),
+    Focus(FocusCondition),
+    GetIntercept(GetInterceptCondition),
+    Impact(ImpactCondition),
+    LastOrbitApoapsis(LastOrbitApoapsis),
+    LastOrbitCircular(LastOrbitCircular),
+    None(NoneCondition),
+    Pause(PauseCondition),
+    SelectAnyOrbitPoint(SelectAnyOrbitPointCondition),
+    SelectAnyApoapsis(SelectAnyApoapsisCondition),
+    SelectVessel(SelectVesselCondition),
+    SetTarget(SetTargetCondition),
+    StartAnyWarp(StartAnyWarpCondition),
+    StartBurnAdjust(StartBurnAdjustCondition),
+    Time(TimeCondition),
+}
+
+impl Check {
+    fn met(&self, view: &View) -> bool {
+        match self {
+            Check::ClickContinue(check) => check.met(view),
+            Check::CreateBurn(check) => check.met(view),
+            Check::Destroyed(check) => check.met(view),
+            Check::EnableGuidance(check) => check.met(view),
+            Check::FireTorpedoAdjust(check) => check.met(view),
+            Check::FireTorpedo(check) => check.met(view),
+            Check::FirstClosestApproach(check) => check.met(view),
+            Check::Focus(check) => check.met(view),
+            Check::GetIntercept(check) => check.met(view),
+            Check::Impact(check) => check.met(view),
+            Check::LastOrbitApoapsis(check) => check.met(view),
+            Check::LastOrbitCircular(check) => check.met(view),
+            Check::None(check) => check.met(view),
+            Check::Pause(check) => check.met(view),
+            Check::SelectAnyOrbitPoint(check) => check.met(view),
+            Check::SelectAnyApoapsis(check) => check.met(view),
+            Check::SelectVessel(check) => check.met(view),
+            Check::SetTarget(check) => check.met(view),
+            Check::StartAnyWarp(check) => check.met(view),
+            Check::StartBurnAdjust(check) => check.met(view),
+            Check::Time(check) => check.met(view),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Condition {
-    check: Box<dyn ConditionCheck>,
-    objective: Option<&'static str>,
+    check: Check,
+    objective: Option<String>,
 }
 
 impl Condition {
     pub fn click_continue() -> Self {
-        Self { check: ClickContinueCondition::new(), objective: None }
+        Self { check: Check::ClickContinue(ClickContinueCondition::new()), objective: None }
     }
 
     pub fn create_burn(entity: Entity) -> Self {
-        Self { check: CreateBurnCondition::new(entity), objective: None }
+        Self { check: Check::CreateBurn(CreateBurnCondition::new(entity)), objective: None }
+    }
+
+    pub fn destroyed(entity: Entity) -> Self {
+        Self { check: Check::Destroyed(DestroyedCondition::new(entity)), objective: None }
     }
 
     pub fn enable_guidance(entity: Entity) -> Self {
-        Self { check: EnableGuidanceCondition::new(entity), objective: None }
+        Self { check: Check::EnableGuidance(EnableGuidanceCondition::new(entity)), objective: None }
     }
 
     pub fn fire_torpedo_adjust() -> Self {
-        Self { check: FireTorpedoAdjustCondition::new(), objective: None }
+        Self { check: Check::FireTorpedoAdjust(FireTorpedoAdjustCondition::new()), objective: None }
     }
 
     pub fn fire_torpedo(entity: Entity) -> Self {
-        Self { check: FireTorpedoCondition::new(entity), objective: None }
+        Self { check: Check::FireTorpedo(FireTorpedoCondition::new(entity)), objective: None }
     }
 
     pub fn first_closest_approach(entity: Entity, max_distance: f64) -> Self {
-        Self { check: FirstClosestApproachCondition::new(entity, max_distance), objective: None }
+        Self { check: Check::FirstClosestApproach(FirstClosestApproachCondition::new(entity, max_distance)), objective: None }
     }
 
     pub fn focus(entity: Entity) -> Self {
-        Self { check: FocusCondition::new(entity), objective: None }
+        Self { check: Check::Focus(FocusCondition::new(entity)), objective: None }
     }
 
     pub fn get_intercept(entity: Entity) -> Self {
-        Self { check: GetInterceptCondition::new(entity), objective: None }
+        Self { check: Check::GetIntercept(GetInterceptCondition::new(entity)), objective: None }
+    }
+
+    pub fn impact(entity: Entity) -> Self {
+        Self { check: Check::Impact(ImpactCondition::new(entity)), objective: None }
     }
 
     pub fn last_orbit_apoapsis(entity: Entity, min: f64, max: f64) -> Self {
-        Self { check: LastOrbitApoapsis::new(entity, min, max), objective: None }
+        Self { check: Check::LastOrbitApoapsis(LastOrbitApoapsis::new(entity, min, max)), objective: None }
     }
 
     pub fn last_orbit_circular(entity: Entity, min: f64, max: f64) -> Self {
-        Self { check: LastOrbitCircular::new(entity, min, max), objective: None }
+        Self { check: Check::LastOrbitCircular(LastOrbitCircular::new(entity, min, max)), objective: None }
     }
 
     pub fn none() -> Self {
-        Self { check: NoneCondition::new(), objective: None }
+        Self { check: Check::None(NoneCondition::new()), objective: None }
     }
 
     pub fn pause() -> Self {
-        Self { check: PauseCondition::new(), objective: None }
+        Self { check: Check::Pause(PauseCondition::new()), objective: None }
     }
 
     pub fn select_any_orbit_point(entity: Entity) -> Self {
-        Self { check: SelectAnyOrbitPointCondition::new(entity), objective: None }
+        Self { check: Check::SelectAnyOrbitPoint(SelectAnyOrbitPointCondition::new(entity)), objective: None }
     }
 
     pub fn select_any_apoapsis(entity: Entity) -> Self {
-        Self { check: SelectAnyApoapsisCondition::new(entity), objective: None }
+        Self { check: Check::SelectAnyApoapsis(SelectAnyApoapsisCondition::new(entity)), objective: None }
     }
 
     pub fn select_vessel(entity: Entity) -> Self {
-        Self { check: SelectVesselCondition::new(entity), objective: None }
+        Self { check: Check::SelectVessel(SelectVesselCondition::new(entity)), objective: None }
     }
 
     pub fn set_target(entity: Entity, target: Entity) -> Self {
-        Self { check: SetTargetCondition::new(entity, target), objective: None }
+        Self { check: Check::SetTarget(SetTargetCondition::new(entity, target)), objective: None }
     }
 
     pub fn start_any_warp() -> Self {
-        Self { check: StartAnyWarpCondition::new(), objective: None }
+        Self { check: Check::StartAnyWarp(StartAnyWarpCondition::new()), objective: None }
     }
 
     pub fn start_burn_adjust() -> Self {
-        Self { check: StartBurnAdjustCondition::new(), objective: None }
+        Self { check: Check::StartBurnAdjust(StartBurnAdjustCondition::new()), objective: None }
     }
 
     pub fn time(time: f64) -> Self {
-        Self { check: TimeCondition::new(time), objective: None }
+        Self { check: Check::Time(TimeCondition::new(time)), objective: None }
     }
 
-    pub fn objective(mut self, objective: &'static str) -> Self {
-        self.objective = Some(objective);
+    pub fn objective(mut self, objective: &str) -> Self {
+        self.objective = Some(objective.to_string());
         self
     }
 
@@ -132,8 +201,8 @@ impl Condition {
         self.check.met(view)
     }
 
-    pub(super) fn get_objective(&self) -> Option<&'static str> {
-        self.objective
+    pub(super) fn get_objective(&self) -> Option<&str> {
+        self.objective.as_deref()
     }
 }
 
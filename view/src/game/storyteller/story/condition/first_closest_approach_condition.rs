@@ -1,4 +1,4 @@
-use transfer_window_model::components::vessel_component::faction::Faction;
+use serde::{Deserialize, Serialize};
 use transfer_window_model::model::state_query::StateQuery;
 use transfer_window_model::storage::entity_allocator::Entity;
 
@@ -6,14 +6,15 @@ use crate::game::View;
 
 use super::ConditionCheck;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FirstClosestApproachCondition {
     entity: Entity,
     max_distance: f64,
 }
 
 impl FirstClosestApproachCondition {
-    pub fn new(entity: Entity, max_distance: f64) -> Box<dyn ConditionCheck> {
-        Box::new(Self { entity, max_distance })
+    pub fn new(entity: Entity, max_distance: f64) -> Self {
+        Self { entity, max_distance }
     }
 }
 
@@ -22,10 +23,10 @@ impl ConditionCheck for FirstClosestApproachCondition {
         let Some(target) = view.model.target(self.entity) else {
             return false;
         };
-        let Some(time) = view.model.snapshot_at_observe(view.model.time(), Faction::Player).find_next_closest_approach(self.entity, target) else {
+        let Some(time) = view.model.snapshot_at_observe(view.model.time(), view.faction).find_next_closest_approach(self.entity, target) else {
             return false;
         };
-        let distance = view.model.snapshot_at_observe(time, Faction::Player).distance(self.entity, target);
+        let distance = view.model.snapshot_at_observe(time, view.faction).distance(self.entity, target);
         distance < self.max_distance
     }
 }
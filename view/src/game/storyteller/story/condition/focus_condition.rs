@@ -1,16 +1,18 @@
+use serde::{Deserialize, Serialize};
 use transfer_window_model::{storage::entity_allocator::Entity, model::story_event::StoryEvent};
 
 use crate::game::View;
 
 use super::{story_events_contains, ConditionCheck};
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FocusCondition {
     entity: Entity,
 }
 
 impl FocusCondition {
-    pub fn new(entity: Entity) -> Box<dyn ConditionCheck> {
-        Box::new(Self { entity })
+    pub fn new(entity: Entity) -> Self {
+        Self { entity }
     }
 }
 
@@ -1,16 +1,18 @@
+use serde::{Deserialize, Serialize};
 use transfer_window_model::storage::entity_allocator::Entity;
 
 use crate::game::View;
 
 use super::ConditionCheck;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetInterceptCondition {
     entity: Entity,
 }
 
 impl GetInterceptCondition {
-    pub fn new(entity: Entity) -> Box<dyn ConditionCheck> {
-        Box::new(Self { entity })
+    pub fn new(entity: Entity) -> Self {
+        Self { entity }
     }
 }
 
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use transfer_window_model::storage::entity_allocator::Entity;
+use transfer_window_model::model::story_event::StoryEvent;
+
+use crate::game::View;
+
+use super::{story_events_contains, ConditionCheck};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactCondition {
+    entity: Entity,
+}
+
+impl ImpactCondition {
+    pub fn new(entity: Entity) -> Self {
+        Self { entity }
+    }
+}
+
+impl ConditionCheck for ImpactCondition {
+    fn met(&self, view: &View) -> bool {
+        let condition = |event: &StoryEvent| {
+            if let StoryEvent::Impact(entity) = event {
+                *entity == self.entity
+            } else {
+                false
+            }
+        };
+        story_events_contains(view, condition)
+    }
+}
+
@@ -1,11 +1,13 @@
 use std::f64::consts::PI;
 
+use serde::{Deserialize, Serialize};
 use transfer_window_model::storage::entity_allocator::Entity;
 
 use crate::game::View;
 
 use super::ConditionCheck;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LastOrbitCircular {
     entity: Entity,
     min: f64,
@@ -13,8 +15,8 @@ pub struct LastOrbitCircular {
 }
 
 impl LastOrbitCircular {
-    pub fn new(entity: Entity, min: f64, max: f64) -> Box<dyn ConditionCheck> {
-        Box::new(Self { entity, min, max })
+    pub fn new(entity: Entity, min: f64, max: f64) -> Self {
+        Self { entity, min, max }
     }
 }
 
@@ -1,12 +1,15 @@
+use serde::{Deserialize, Serialize};
+
 use crate::game::View;
 
 use super::ConditionCheck;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoneCondition;
 
 impl NoneCondition {
-    pub fn new() -> Box<dyn ConditionCheck> {
-        Box::new(Self {})
+    pub fn new() -> Self {
+        Self
     }
 }
 
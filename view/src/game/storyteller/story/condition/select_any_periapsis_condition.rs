@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use transfer_window_model::storage::entity_allocator::Entity;
 use transfer_window_model::model::story_event::StoryEvent;
 
@@ -5,13 +6,14 @@ use crate::game::View;
 
 use super::{story_events_contains, ConditionCheck};
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SelectAnyApoapsisCondition {
     entity: Entity,
 }
 
 impl SelectAnyApoapsisCondition {
-    pub fn new(entity: Entity) -> Box<dyn ConditionCheck> {
-        Box::new(Self { entity })
+    pub fn new(entity: Entity) -> Self {
+        Self { entity }
     }
 }
 
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use transfer_window_model::storage::entity_allocator::Entity;
 use transfer_window_model::model::story_event::StoryEvent;
 
@@ -5,14 +6,15 @@ use crate::game::View;
 
 use super::{story_events_contains, ConditionCheck};
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SetTargetCondition {
     entity: Entity,
     target: Entity,
 }
 
 impl SetTargetCondition {
-    pub fn new(entity: Entity, target: Entity) -> Box<dyn ConditionCheck> {
-        Box::new(Self { entity, target })
+    pub fn new(entity: Entity, target: Entity) -> Self {
+        Self { entity, target }
     }
 }
 
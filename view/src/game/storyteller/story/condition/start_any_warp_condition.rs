@@ -1,14 +1,16 @@
+use serde::{Deserialize, Serialize};
 use transfer_window_model::model::story_event::StoryEvent;
 
 use crate::game::View;
 
 use super::{story_events_contains, ConditionCheck};
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StartAnyWarpCondition;
 
 impl StartAnyWarpCondition {
-    pub fn new() -> Box<dyn ConditionCheck> {
-        Box::new(Self {})
+    pub fn new() -> Self {
+        Self
     }
 }
 
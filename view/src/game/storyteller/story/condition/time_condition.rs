@@ -1,16 +1,18 @@
+use serde::{Deserialize, Serialize};
 use transfer_window_model::model::story_event::StoryEvent;
 
 use crate::game::View;
 
 use super::{story_events_contains, ConditionCheck};
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeCondition{
     time: f64,
 }
 
 impl TimeCondition {
-    pub fn new(time: f64) -> Box<dyn ConditionCheck> {
-        Box::new(Self { time })
+    pub fn new(time: f64) -> Self {
+        Self { time }
     }
 }
 
@@ -33,12 +33,8 @@ impl State {
         self
     }
 
-    pub fn try_transition(&self, view: &View) -> Option<(&'static str, Option<&'static str>)> {
-        if self.transition.as_ref().is_some_and(|transition| transition.can_transition(view)) {
-            Some((self.transition.as_ref().unwrap().to(), self.transition.as_ref().unwrap().objective()))
-        } else {
-            None
-        }
+    pub fn try_transition(&self, view: &View) -> Option<&Transition> {
+        self.transition.as_ref().filter(|transition| transition.can_transition(view))
     }
 
     pub fn get_transition(&self) -> &Option<Transition> {
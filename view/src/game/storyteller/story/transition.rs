@@ -1,26 +1,29 @@
+use serde::{Deserialize, Serialize};
+
 use crate::game::View;
 
 use super::condition::Condition;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transition {
-    to: &'static str,
+    to: String,
     condition: Condition,
 }
 
 impl Transition {
-    pub fn new(to: &'static str, condition: Condition) -> Self {
-        Self { to, condition }
+    pub fn new(to: &str, condition: Condition) -> Self {
+        Self { to: to.to_string(), condition }
     }
 
     pub fn can_transition(&self, view: &View) -> bool {
         self.condition.met(view)
     }
     
-    pub fn to(&self) -> &'static str {
-        self.to
+    pub fn to(&self) -> &str {
+        &self.to
     }
 
-    pub fn objective(&self) -> Option<&'static str> {
+    pub fn objective(&self) -> Option<&str> {
         self.condition.get_objective()
     }
 }
\ No newline at end of file
@@ -2,7 +2,10 @@ use std::{cmp::Ordering, fmt::Debug};
 
 use eframe::egui::{PointerState, Pos2, Vec2};
 use encounter::Encounter;
+use impact::Impact;
 use intercept::Intercept;
+use last_known::LastKnown;
+use point_defense::PointDefense;
 use nalgebra_glm::DVec2;
 use turn::Turn;
 
@@ -18,8 +21,11 @@ mod closest_approach;
 mod encounter;
 mod fire_torpedo;
 mod guidance;
+mod impact;
 mod intercept;
+mod last_known;
 mod orbitable;
+mod point_defense;
 mod vessel;
 mod turn;
 
@@ -92,8 +98,11 @@ fn compute_initial_icons(view: &View, pointer: &PointerState) -> Vec<Box<dyn Ico
     icons.append(&mut Encounter::generate(view));
     icons.append(&mut FireTorpedo::generate(view));
     icons.append(&mut Guidance::generate(view));
+    icons.append(&mut Impact::generate(view));
     icons.append(&mut Intercept::generate(view));
+    icons.append(&mut LastKnown::generate(view));
     icons.append(&mut Orbitable::generate(view));
+    icons.append(&mut PointDefense::generate(view));
     icons.append(&mut Vessel::generate(view));
     icons
 }
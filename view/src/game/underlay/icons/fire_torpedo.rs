@@ -1,7 +1,7 @@
 use eframe::egui::PointerState;
 use log::trace;
 use nalgebra_glm::DVec2;
-use transfer_window_model::{components::{vessel_component::faction::Faction, ComponentType}, model::state_query::StateQuery, storage::entity_allocator::Entity};
+use transfer_window_model::{components::ComponentType, model::state_query::StateQuery, storage::entity_allocator::Entity};
 
 use crate::game::{events::ViewEvent, selected::{util::BurnState, Selected}, util::should_render_at_time, View};
 
@@ -18,7 +18,7 @@ impl FireTorpedo {
         let mut icons = vec![];
         for entity in view.entities_should_render(vec![ComponentType::VesselComponent, ComponentType::PathComponent]) {
             let faction = view.model.vessel_component(entity).faction();
-            if !Faction::Player.has_intel_for(faction) {
+            if !view.model.has_intel_for(view.faction, faction) {
                 continue;
             }
             for event in view.model.vessel_component(entity).timeline().events() {
@@ -66,7 +66,7 @@ impl Icon for FireTorpedo {
     fn position(&self, view: &View) -> DVec2 {
         #[cfg(feature = "profiling")]
         let _span = tracy_client::span!("Fire torpedo position");
-        let snapshot = view.model.snapshot_at_observe(self.time, Faction::Player);
+        let snapshot = view.model.snapshot_at_observe(self.time, view.faction);
         let orbit = snapshot.orbit(self.entity);
         view.model.absolute_position(orbit.parent()) + orbit.point_at_time(self.time).position()
     }
@@ -1,7 +1,7 @@
 use eframe::egui::PointerState;
 use log::trace;
 use nalgebra_glm::DVec2;
-use transfer_window_model::{components::{vessel_component::{faction::Faction, timeline::TimelineEvent}, ComponentType}, model::state_query::StateQuery, storage::entity_allocator::Entity};
+use transfer_window_model::{components::{vessel_component::timeline::TimelineEvent, ComponentType}, model::state_query::StateQuery, storage::entity_allocator::Entity};
 
 use crate::game::{events::ViewEvent, selected::Selected, util::should_render_at_time, View};
 
@@ -18,7 +18,7 @@ impl Intercept {
         let mut icons = vec![];
         for entity in view.entities_should_render(vec![ComponentType::VesselComponent, ComponentType::PathComponent]) {
             let faction = view.model.vessel_component(entity).faction();
-            if !Faction::Player.has_intel_for(faction) {
+            if !view.model.has_intel_for(view.faction, faction) {
                 continue;
             }
             if let Some(TimelineEvent::Intercept(intercept)) = view.model.vessel_component(entity).timeline().last_event() {
@@ -67,8 +67,8 @@ impl Icon for Intercept {
     fn position(&self, view: &View) -> DVec2 {
         #[cfg(feature = "profiling")]
         let _span = tracy_client::span!("Intercept position");
-        let parent = view.model.snapshot_at_observe(self.time, Faction::Player).parent(self.entity).unwrap();
-        view.model.absolute_position(parent) + view.model.snapshot_at_observe(self.time, Faction::Player).position(self.entity)
+        let parent = view.model.snapshot_at_observe(self.time, view.faction).parent(self.entity).unwrap();
+        view.model.absolute_position(parent) + view.model.snapshot_at_observe(self.time, view.faction).position(self.entity)
     }
 
     fn facing(&self, _view: &View) -> Option<DVec2> {
@@ -0,0 +1,76 @@
+use eframe::egui::PointerState;
+use nalgebra_glm::{vec2, DVec2};
+use transfer_window_model::{model::state_query::StateQuery, storage::entity_allocator::Entity};
+
+use crate::game::{util::{should_render_parent, vessel_texture}, View};
+
+use super::Icon;
+
+/// Where the player last saw a vessel they can no longer detect
+#[derive(Debug)]
+pub struct LastKnown {
+    entity: Entity,
+    position: DVec2,
+    rotation: f64,
+}
+
+impl LastKnown {
+    pub fn generate(view: &View) -> Vec<Box<dyn Icon>> {
+        let mut icons = vec![];
+        for last_known in view.model.last_known_positions(view.faction) {
+            if !should_render_parent(view, last_known.parent()) {
+                continue;
+            }
+            let velocity = last_known.velocity();
+            let icon = Self {
+                entity: last_known.entity(),
+                position: view.model.absolute_position(last_known.parent()) + last_known.position(),
+                rotation: f64::atan2(velocity.y, velocity.x),
+            };
+            icons.push(Box::new(icon) as Box<dyn Icon>);
+        }
+        icons
+    }
+}
+
+impl Icon for LastKnown {
+    fn texture(&self, view: &View) -> String {
+        vessel_texture(view.model.vessel_component(self.entity)).to_string()
+    }
+
+    fn alpha(&self, _view: &View, _is_selected: bool, is_hovered: bool, is_overlapped: bool) -> f32 {
+        if is_overlapped {
+            return 0.1;
+        }
+        if is_hovered {
+            return 0.4
+        }
+        0.25
+    }
+
+    fn radius(&self, _view: &View) -> f64 {
+        15.0
+    }
+
+    fn priorities(&self, _view: &View) -> [u64; 4] {
+        [0, 0, 0, 0]
+    }
+
+    fn position(&self, _view: &View) -> DVec2 {
+        self.position
+    }
+
+    fn facing(&self, _view: &View) -> Option<DVec2> {
+        Some(vec2(f64::cos(self.rotation), f64::sin(self.rotation)))
+    }
+
+    fn is_selected(&self, _view: &View) -> bool {
+        false
+    }
+
+    fn on_mouse_over(&self, _view: &View, _pointer: &PointerState) {}
+
+    fn selectable(&self) -> bool {
+        false
+    }
+}
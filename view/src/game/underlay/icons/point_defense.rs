@@ -0,0 +1,85 @@
+use eframe::egui::PointerState;
+use nalgebra_glm::DVec2;
+use transfer_window_model::{components::{vessel_component::timeline::TimelineEvent, ComponentType}, model::state_query::StateQuery, storage::entity_allocator::Entity};
+
+use crate::game::{util::should_render_at_time, View};
+
+use super::Icon;
+
+/// Marks where a torpedo is predicted to be shot down by point defense
+#[derive(Debug)]
+pub struct PointDefense {
+    entity: Entity,
+    time: f64,
+}
+
+impl PointDefense {
+    pub fn generate(view: &View) -> Vec<Box<dyn Icon>> {
+        let mut icons = vec![];
+        for entity in view.entities_should_render(vec![ComponentType::VesselComponent, ComponentType::PathComponent]) {
+            let faction = view.model.vessel_component(entity).faction();
+            if !view.model.has_intel_for(view.faction, faction) {
+                continue;
+            }
+            if let Some(TimelineEvent::PointDefense(point_defense)) = view.model.vessel_component(entity).timeline().last_event() {
+                if point_defense.destroys_torpedo() && should_render_at_time(view, entity, point_defense.time()) {
+                    let icon = Self { entity, time: point_defense.time() };
+                    icons.push(Box::new(icon) as Box<dyn Icon>);
+                }
+            }
+        }
+
+        icons
+    }
+}
+
+impl Icon for PointDefense {
+    fn texture(&self, _view: &View) -> String {
+        "cancel".to_string()
+    }
+
+    fn alpha(&self, _view: &View, _is_selected: bool, is_hovered: bool, is_overlapped: bool) -> f32 {
+        if is_overlapped {
+            return 0.4;
+        }
+        if is_hovered {
+            return 1.0
+        }
+        0.8
+    }
+
+    fn radius(&self, _view: &View) -> f64 {
+        8.0
+    }
+
+    fn priorities(&self, _view: &View) -> [u64; 4] {
+        [
+            0,
+            0,
+            4,
+            0,
+        ]
+    }
+
+    fn position(&self, view: &View) -> DVec2 {
+        #[cfg(feature = "profiling")]
+        let _span = tracy_client::span!("Point defense position");
+        let snapshot = view.model.snapshot_at_observe(self.time, view.faction);
+        let parent = snapshot.parent(self.entity).unwrap();
+        view.model.absolute_position(parent) + snapshot.position(self.entity)
+    }
+
+    fn facing(&self, _view: &View) -> Option<DVec2> {
+        None
+    }
+
+    fn is_selected(&self, _view: &View) -> bool {
+        false
+    }
+
+    fn on_mouse_over(&self, _view: &View, _pointer: &PointerState) {}
+
+    fn selectable(&self) -> bool {
+        false
+    }
+}
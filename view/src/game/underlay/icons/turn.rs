@@ -1,7 +1,7 @@
 use eframe::egui::PointerState;
 use log::trace;
 use nalgebra_glm::DVec2;
-use transfer_window_model::{components::{vessel_component::faction::Faction, ComponentType}, model::state_query::StateQuery, storage::entity_allocator::Entity};
+use transfer_window_model::{components::ComponentType, model::state_query::StateQuery, storage::entity_allocator::Entity};
 
 use crate::game::{events::ViewEvent, selected::Selected, util::should_render_at_time, View};
 
@@ -18,7 +18,7 @@ impl Turn {
         let mut icons = vec![];
         for entity in view.entities_should_render(vec![ComponentType::VesselComponent, ComponentType::PathComponent]) {
             let faction = view.model.vessel_component(entity).faction();
-            if !Faction::Player.has_intel_for(faction) {
+            if !view.model.has_intel_for(view.faction, faction) {
                 continue;
             }
             for event in view.model.vessel_component(entity).timeline().events() {
@@ -1,6 +1,6 @@
 use eframe::egui::PointerState;
 use nalgebra_glm::{vec2, DVec2};
-use transfer_window_model::{components::{vessel_component::faction::Faction, ComponentType}, model::state_query::StateQuery, storage::entity_allocator::Entity};
+use transfer_window_model::{components::ComponentType, model::state_query::StateQuery, storage::entity_allocator::Entity};
 
 use crate::game::{events::ViewEvent, selected::Selected, util::vessel_texture, View};
 
@@ -30,7 +30,7 @@ impl Icon for Vessel {
         let mut base_name = vessel_texture(view.model.vessel_component(self.entity)).to_string();
         if let Some(target) = view.selected.target(&view.model) {
             let selected_faction = view.model.vessel_component(view.selected.entity(&view.model).unwrap()).faction();
-            if target == self.entity && Faction::Player.has_intel_for(selected_faction) {
+            if target == self.entity && view.model.has_intel_for(view.faction, selected_faction) {
                 base_name += "-target";
             }
         }
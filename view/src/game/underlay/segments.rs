@@ -1,12 +1,14 @@
 use eframe::egui::Rgba;
 use nalgebra_glm::DVec2;
-use transfer_window_model::{components::{path_component::segment::Segment, vessel_component::faction::Faction, ComponentType}, model::state_query::StateQuery, storage::entity_allocator::Entity};
+use transfer_window_model::{components::{path_component::segment::Segment, ComponentType}, model::state_query::StateQuery, storage::entity_allocator::Entity};
 
 use crate::game::{util::{add_line, should_render_parent}, View};
 
 mod burn;
+mod drag;
 mod guidance;
 mod orbit;
+mod tessellation;
 mod turn;
 
 /// Draws a line between two points so that all the lines on a segment are connected together
@@ -72,6 +74,14 @@ fn draw_segment(view: &View, segment: &Segment, camera_centre: DVec2, zoom: f64,
                 segment_points_data.push((points, color));
             }
         }
+
+        Segment::Drag(drag) => {
+            let points = drag::compute_points(drag, absolute_parent_position, camera_centre, zoom);
+            let color = orbit::compute_color_vessel(view, entity);
+            if should_render_parent(view, drag.parent()) {
+                segment_points_data.push((points, color));
+            }
+        }
     };
 }
 
@@ -81,7 +91,7 @@ fn draw_path_segments(view: &View, entity: Entity, camera_centre: DVec2) {
     let zoom = view.camera.zoom();
 
     let mut segment_points_data = vec![];
-    for segment in &view.model.snapshot_now_observe(Faction::Player).future_segments(entity) {
+    for segment in &view.model.snapshot_now_observe(view.faction).future_segments(entity) {
         draw_segment(view, segment, camera_centre, zoom, entity, &mut segment_points_data);
     }
 
@@ -107,7 +117,7 @@ pub fn draw(view: &View) {
     #[cfg(feature = "profiling")]
     let _span = tracy_client::span!("Draw segments");
     let camera_centre = view.camera.translation();
-    for entity in view.model.entities(vec![ComponentType::PathComponent]) {
+    for entity in view.model.snapshot_now_observe(view.faction).entities(vec![ComponentType::PathComponent]) {
         draw_path_segments(view, entity, camera_centre);
     }
     for entity in view.entities_should_render(vec![ComponentType::OrbitableComponent]) {
@@ -0,0 +1,10 @@
+use nalgebra_glm::DVec2;
+use transfer_window_model::components::path_component::drag::Drag;
+
+use crate::game::underlay::segments::tessellation::compute_points_over_time;
+
+pub fn compute_points(drag: &Drag, absolute_parent_position: DVec2, camera_centre: DVec2, zoom: f64) -> Vec<DVec2> {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Compute drag points");
+    compute_points_over_time(|time| drag.point_at_time(time).position(), drag.current_point().time(), drag.remaining_time(), absolute_parent_position, camera_centre, zoom)
+}
\ No newline at end of file
@@ -1,9 +1,9 @@
 use std::f64::consts::PI;
 use eframe::{egui::Rgba, emath::normalized_angle};
 use nalgebra_glm::DVec2;
-use transfer_window_model::{components::{path_component::orbit::Orbit, vessel_component::faction::Faction}, storage::entity_allocator::Entity};
+use transfer_window_model::{components::path_component::orbit::Orbit, storage::entity_allocator::Entity};
 
-use crate::game::View;
+use crate::game::{util::{standing, Standing}, View};
 
 const INITIAL_POINT_COUNT: usize = 50;
 const TESSELLATION_THRESHOLD: f64 = 1.0e-4;
@@ -11,10 +11,11 @@ const EXTRA_MIN_DISTANCE: f64 = 1.0e-3;
 
 pub fn compute_color_vessel(view: &View, entity: Entity) -> Rgba {
     let faction = view.model.vessel_component(entity).faction();
-    let rgb = match faction {
-        Faction::Player => Rgba::from_rgb(0.0, 1.0, 1.0),
-        Faction::Ally => Rgba::from_rgb(0.6, 1.0, 0.6),
-        Faction::Enemy => Rgba::from_rgb(1.0, 0.5, 0.0),
+    let rgb = match standing(view, faction) {
+        Standing::Own => Rgba::from_rgb(0.0, 1.0, 1.0),
+        Standing::Allied => Rgba::from_rgb(0.6, 1.0, 0.6),
+        Standing::Neutral => Rgba::from_rgb(0.8, 0.8, 0.8),
+        Standing::Hostile => Rgba::from_rgb(1.0, 0.5, 0.0),
     };
 
     let alpha = if view.is_selected(entity) {
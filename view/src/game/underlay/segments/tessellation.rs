@@ -0,0 +1,67 @@
+use nalgebra_glm::DVec2;
+
+const INITIAL_POINT_COUNT: usize = 50;
+const TESSELLATION_THRESHOLD: f64 = 1.0e-3;
+const EXTRA_MIN_DISTANCE: f64 = 1.0e-3;
+
+/// Uses triangle heuristic as described in <https://www.kerbalspaceprogram.com/news/dev-diaries-orbit-tessellation>
+fn tessellate(position_at_time: &impl Fn(f64) -> DVec2, mut points: Vec<(f64, DVec2)>, absolute_parent_position: DVec2, camera_centre: DVec2, zoom: f64) -> Vec<(f64, DVec2)> {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Tessellate");
+
+    let to_screen_space = |point: DVec2| (point - camera_centre) * zoom;
+
+    let mut i = 0;
+    while i < points.len() - 2 {
+        let point1 = points[i];
+        let point2 = points[i+1];
+        let point3 = points[i+2];
+
+        let point1_screen_space = to_screen_space(point1.1);
+        let point2_screen_space = to_screen_space(point2.1);
+        let point3_screen_space = to_screen_space(point3.1);
+        
+        // Heron's method, see https://www.mathopenref.com/heronsformula.html
+        let a = (point1_screen_space - point2_screen_space).magnitude();
+        let b = (point1_screen_space - point3_screen_space).magnitude();
+        let c = (point2_screen_space - point3_screen_space).magnitude();
+        let p = (a + b + c) / 2.0;
+        let area = f64::sqrt(p * (p - a) * (p - b) * (p - c));
+
+        // If the min distance is very small, area / min_distance can get very large, causing tessellation loops
+        // We add EXTRA_MIN_DISTANCE to make sure this doesn't happen
+        let min_distance = EXTRA_MIN_DISTANCE + f64::min(point1_screen_space.magnitude_squared(), f64::min(point2_screen_space.magnitude_squared(), point3_screen_space.magnitude_squared()));
+
+        if area / min_distance > TESSELLATION_THRESHOLD {
+            let new_time_1 = f64::midpoint(point1.0, point2.0);
+            let new_time_2 = f64::midpoint(point2.0, point3.0);
+
+            points.insert(i + 1, (new_time_1, absolute_parent_position + position_at_time(new_time_1)));
+            points.insert(i + 3, (new_time_2, absolute_parent_position + position_at_time(new_time_2)));
+        } else {
+            i += 1;
+        }
+    }
+
+    points
+}
+
+fn find_initial_points(position_at_time: &impl Fn(f64) -> DVec2, start_time: f64, time_to_step_through: f64, absolute_parent_position: DVec2) -> Vec<(f64, DVec2)> {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("Find initial points");
+    let mut initial_points = vec![];
+    for i in 0..=INITIAL_POINT_COUNT {
+        let time = start_time + (i as f64 / INITIAL_POINT_COUNT as f64) * time_to_step_through;
+        initial_points.push((time, absolute_parent_position + position_at_time(time)));
+    }
+
+    initial_points
+}
+
+/// Tessellates a segment which is parameterised by time rather than by angle, such as a burn, turn, guidance or drag segment
+/// `position_at_time` should return the position relative to the parent
+pub fn compute_points_over_time(position_at_time: impl Fn(f64) -> DVec2, start_time: f64, time_to_step_through: f64, absolute_parent_position: DVec2, camera_centre: DVec2, zoom: f64) -> Vec<DVec2> {
+    let points = find_initial_points(&position_at_time, start_time, time_to_step_through, absolute_parent_position);
+    let points = tessellate(&position_at_time, points, absolute_parent_position, camera_centre, zoom);
+    points.into_iter().map(|(_, position)| position).collect()
+}
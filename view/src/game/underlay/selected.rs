@@ -1,5 +1,4 @@
 use log::trace;
-use transfer_window_model::components::vessel_component::faction::Faction;
 
 use crate::game::{events::ViewEvent, selected::{burn, fire_torpedo, segment_point::{self, SELECT_DISTANCE}, Selected}, View};
 
@@ -31,19 +30,19 @@ pub fn update(view: &View) {
         if let Some(latest_window) = pointer.latest_pos() { 
             let latest_world = view.window_space_to_world_space(latest_window);
 
-            if let Some((entity, time)) = view.model.closest_burn_point(latest_world, select_distance, Some(Faction::Player)) {
+            if let Some((entity, time)) = view.model.closest_burn_point(latest_world, select_distance, Some(view.faction)) {
                 trace!("Selected burn point at time={}", time);
                 let selected = Selected::BurnPoint { entity, time };
                 view.add_view_event(ViewEvent::SetSelected(selected));
-            } else if let Some((entity, time)) = view.model.closest_turn_point(latest_world, select_distance, Some(Faction::Player)) {
+            } else if let Some((entity, time)) = view.model.closest_turn_point(latest_world, select_distance, Some(view.faction)) {
                 trace!("Selected turn point at time={}", time);
                 let selected = Selected::TurnPoint { entity, time };
                 view.add_view_event(ViewEvent::SetSelected(selected));
-            } else if let Some((entity, time)) = view.model.closest_guidance_point(latest_world, select_distance, Some(Faction::Player)) {
+            } else if let Some((entity, time)) = view.model.closest_guidance_point(latest_world, select_distance, Some(view.faction)) {
                 trace!("Selected guidance point at time={}", time);
                 let selected = Selected::GuidancePoint { entity, time };
                 view.add_view_event(ViewEvent::SetSelected(selected));
-            } else if let Some((entity, time)) = view.model.closest_orbit_point(latest_world, select_distance, Some(Faction::Player)) {
+            } else if let Some((entity, time)) = view.model.closest_orbit_point(latest_world, select_distance, Some(view.faction)) {
                 trace!("Selected orbit point at time={}", time);
                 let selected = Selected::OrbitPoint { entity, time };
                 view.add_view_event(ViewEvent::SetSelected(selected));
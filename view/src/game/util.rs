@@ -1,7 +1,7 @@
 use eframe::epaint::Rgba;
 use nalgebra_glm::{vec2, DVec2, Vec2};
 use thousands::Separable;
-use transfer_window_model::{components::{orbitable_component::OrbitableType, vessel_component::{class::VesselClass, faction::Faction, VesselComponent}}, model::state_query::StateQuery, storage::entity_allocator::Entity};
+use transfer_window_model::{components::{orbitable_component::OrbitableType, vessel_component::{class::VesselClass, faction::{Faction, Stance}, VesselComponent}}, model::state_query::StateQuery, storage::entity_allocator::Entity};
 
 use super::{selected::util::BurnAdjustDirection, View};
 
@@ -179,24 +179,49 @@ pub fn should_render_parent(view: &View, parent: Entity) -> bool {
 }
 
 pub fn should_render(view: &View, entity: Entity) -> bool {
-    let Some(parent) = view.model.parent(entity) else {
-        return true;
-    };
-    should_render_parent(view, parent)
+    should_render_at_time(view, entity, view.model.time())
 }
 
 pub fn should_render_at_time(view: &View, entity: Entity, time: f64) -> bool {
-    let Some(parent) = view.model.snapshot_at_observe(time, Faction::Player).parent(entity) else {
+    let snapshot = view.model.snapshot_at_observe(time, view.faction);
+    if !snapshot.is_detected(entity) {
+        return false;
+    }
+    let Some(parent) = snapshot.parent(entity) else {
         return true;
     };
     should_render_parent(view, parent)
 }
 
+/// How the player regards a faction, which decides the colour it is drawn in
+#[derive(Debug, Clone, Copy)]
+pub enum Standing {
+    Own,
+    Allied,
+    Neutral,
+    Hostile,
+}
+
+pub fn standing(view: &View, faction: Faction) -> Standing {
+    if view.faction.can_control(faction) {
+        return Standing::Own;
+    }
+    match view.model.relation(view.faction, faction).stance() {
+        Stance::Allied => Standing::Allied,
+        Stance::Neutral => Standing::Neutral,
+        Stance::Hostile => Standing::Hostile,
+    }
+}
+
 pub fn vessel_texture(vessel_component: &VesselComponent) -> &'static str {
-    match vessel_component.class() {
+    vessel_class_texture(vessel_component.class())
+}
+
+pub fn vessel_class_texture(class: VesselClass) -> &'static str {
+    match class {
         VesselClass::Scout1 => "vessel-icon-scout-1",
         VesselClass::Frigate1 | VesselClass::TestShip => "vessel-icon-frigate-1",
-        VesselClass::Torpedo => "vessel-icon-torpedo",
+        VesselClass::Torpedo | VesselClass::Projectile => "vessel-icon-torpedo",
         VesselClass::Station => "vessel-icon-hub",
     }
 }
@@ -206,6 +231,7 @@ pub fn orbitable_texture(type_: OrbitableType) -> &'static str {
         OrbitableType::Star => "star",
         OrbitableType::Planet => "planet",
         OrbitableType::Moon => "moon",
+        OrbitableType::Asteroid => "asteroid",
     }
 }
 
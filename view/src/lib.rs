@@ -1,7 +1,9 @@
 pub mod controller_events;
 pub mod game;
 pub mod menu;
+pub mod replays;
 pub mod resources;
+pub mod saves;
 mod styles;
 
 pub enum Scene {
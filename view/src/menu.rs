@@ -1,10 +1,11 @@
 use std::{collections::HashSet, sync::{Arc, Mutex}};
 
-use eframe::{egui::{CentralPanel, Context, CursorIcon, Key, Rect, Sense, Ui, Vec2, Window}, glow};
+use eframe::{egui::{Align2, CentralPanel, Context, CursorIcon, Key, Rect, RichText, Sense, Ui, Vec2, Window}, epaint, glow};
 use log::trace;
 
 use crate::game::storyteller::stories::{story_1_03::Story1_03, story_1_04::Story1_04};
-use crate::{controller_events::ControllerEvent, game::{overlay::widgets::custom_image::CustomImage, rendering::screen_texture_renderer::ScreenTextureRenderer, storyteller::stories::{story_1_01::Story1_01, story_1_02::Story1_02, StoryBuilder}}, resources::Resources};
+use crate::saves::{self, SaveMetadata};
+use crate::{controller_events::ControllerEvent, game::{overlay::widgets::{custom_image::CustomImage, save_list::{draw_save_list, SaveListAction}}, rendering::screen_texture_renderer::ScreenTextureRenderer, storyteller::stories::{story_1_01::Story1_01, story_1_02::Story1_02, StoryBuilder}}, resources::Resources};
 
 impl CustomImage {
     pub fn new_menu(view: &View, texture_name: &str, width: i32, height: i32) -> Self {
@@ -24,6 +25,7 @@ pub struct View {
     resources: Arc<Resources>,
     screen_texture_renderer: Arc<Mutex<ScreenTextureRenderer>>,
     debug_window_open: bool,
+    saves: Option<Vec<SaveMetadata>>,
 }
 
 impl View {
@@ -34,7 +36,8 @@ impl View {
         let screen_rect = context.screen_rect();
         let screen_texture_renderer = Arc::new(Mutex::new(ScreenTextureRenderer::new(&gl, screen_rect)));
         let debug_window_open = false;
-        Self { gl, previous_screen_rect, screen_rect, resources, screen_texture_renderer, debug_window_open }
+        let saves = None;
+        Self { gl, previous_screen_rect, screen_rect, resources, screen_texture_renderer, debug_window_open, saves }
     }
 
     fn draw_level(&self, context: &Context, ui: &mut Ui, events: &mut Vec<ControllerEvent>, completed_levels: &HashSet<String>, level: &str, story_builder: Box<dyn StoryBuilder>) {
@@ -67,6 +70,37 @@ impl View {
         ui.add_space(10.0);
     }
 
+    fn draw_saves(&mut self, context: &Context, events: &mut Vec<ControllerEvent>) {
+        let Some(saves) = &self.saves else {
+            return;
+        };
+
+        let mut action = None;
+        let mut close = false;
+        Window::new("Saves")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(Align2::CENTER_CENTER, epaint::vec2(0.0, 0.0))
+                .show(context, |ui| {
+            action = draw_save_list(context, ui, saves, false);
+            ui.add_space(10.0);
+            close = ui.button("Close").clicked();
+        });
+
+        match action {
+            Some(SaveListAction::Load(name)) => events.push(ControllerEvent::LoadGame { name }),
+            Some(SaveListAction::Delete(name)) => {
+                saves::delete(&name);
+                self.saves = Some(saves::list());
+            }
+            Some(SaveListAction::Overwrite(_)) | None => (),
+        }
+
+        if close {
+            self.saves = None;
+        }
+    }
+
     pub fn update(&mut self, context: &Context, completed_levels: &HashSet<String>) -> Vec<ControllerEvent> {
         #[cfg(feature = "profiling")]
         let _span = tracy_client::span!("View update");
@@ -111,6 +145,14 @@ impl View {
                         self.draw_level(context, ui, &mut events, completed_levels, "1-03", Box::new(Story1_03));
                         self.draw_level(context, ui, &mut events, completed_levels, "1-04", Box::new(Story1_04));
                     });
+                    ui.add_space(15.0);
+                    let response = ui.button(RichText::new("Load game").strong().monospace().size(16.0));
+                    if response.hovered() {
+                        context.set_cursor_icon(CursorIcon::PointingHand);
+                    }
+                    if response.clicked() {
+                        self.saves = Some(saves::list());
+                    }
                     // ui.add_space(15.0);
                     // ui.horizontal(|ui| {
 
@@ -118,6 +160,8 @@ impl View {
                 })
             })
         });
+
+        self.draw_saves(context, &mut events);
         events
     }
 }
@@ -0,0 +1,16 @@
+use std::{fs, io, path::PathBuf};
+
+pub const REPLAY_DIRECTORY: &str = "data/replays";
+/// The current session is always recorded to this replay
+pub const LATEST_REPLAY_NAME: &str = "latest";
+
+pub fn path(name: &str) -> PathBuf {
+    PathBuf::from(REPLAY_DIRECTORY).join(name.to_string() + ".json")
+}
+
+/// # Errors
+/// Forwards IO errors from creating the replay directory or writing the file
+pub fn write(name: &str, serialized: String) -> io::Result<()> {
+    fs::create_dir_all(REPLAY_DIRECTORY)?;
+    fs::write(path(name), serialized)
+}
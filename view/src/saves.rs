@@ -0,0 +1,129 @@
+use std::{cmp::Reverse, fs, io, path::PathBuf, time::{SystemTime, UNIX_EPOCH}};
+
+use log::error;
+use transfer_window_model::model::Model;
+
+use crate::game::session::SessionSummary;
+
+pub const SAVE_DIRECTORY: &str = "data/saves";
+pub const QUICKSAVE_NAME: &str = "quicksave";
+pub const AUTOSAVE_PREFIX: &str = "autosave-";
+pub const AUTOSAVE_SLOTS: usize = 3;
+pub const AUTOSAVE_INTERVAL_SECONDS: f64 = 5.0 * 60.0;
+
+#[derive(Debug, Clone)]
+pub struct SaveMetadata {
+    name: String,
+    level: Option<String>,
+    time: f64,
+    saved_at: SystemTime,
+}
+
+impl SaveMetadata {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn level(&self) -> Option<&str> {
+        self.level.as_deref()
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn saved_at(&self) -> SystemTime {
+        self.saved_at
+    }
+}
+
+pub fn path(name: &str) -> PathBuf {
+    PathBuf::from(SAVE_DIRECTORY).join(name.to_string() + ".json")
+}
+
+/// # Errors
+/// Forwards IO errors from creating the save directory or writing the file
+pub fn write(name: &str, serialized: String) -> io::Result<()> {
+    fs::create_dir_all(SAVE_DIRECTORY)?;
+    fs::write(path(name), serialized)
+}
+
+pub fn delete(name: &str) {
+    if let Err(error) = fs::remove_file(path(name)) {
+        error!("Failed to delete save {}: {}", name, error);
+    }
+}
+
+fn load_metadata(path: &PathBuf) -> Option<SaveMetadata> {
+    let name = path.file_stem()?.to_str()?.to_string();
+    let saved_at = fs::metadata(path).and_then(|metadata| metadata.modified()).ok()?;
+    let serialized = fs::read_to_string(path).ok()?;
+    let result = Model::deserialize_session::<SessionSummary>(&serialized).and_then(|session| {
+        let level = session.as_ref().and_then(|session| session.level().map(str::to_string));
+        // Saves from before the session stored the time need the whole model
+        let time = match session.and_then(|session| session.time()) {
+            Some(time) => time,
+            None => Model::deserialize(&serialized)?.time(),
+        };
+        Ok(SaveMetadata { name: name.clone(), level, time, saved_at })
+    });
+    match result {
+        Ok(metadata) => Some(metadata),
+        Err(error) => {
+            error!("Failed to read save {}: {}", name, error);
+            None
+        }
+    }
+}
+
+/// Lists all readable saves, most recent first
+pub fn list() -> Vec<SaveMetadata> {
+    #[cfg(feature = "profiling")]
+    let _span = tracy_client::span!("List saves");
+    let Ok(entries) = fs::read_dir(SAVE_DIRECTORY) else {
+        return vec![];
+    };
+    let mut saves: Vec<SaveMetadata> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .filter_map(|path| load_metadata(&path))
+        .collect();
+    saves.sort_by_key(|save| Reverse(save.saved_at));
+    saves
+}
+
+/// Picks the first unused autosave slot, or the oldest one if all are used
+pub fn next_autosave_name() -> String {
+    let mut oldest: Option<(String, SystemTime)> = None;
+    for slot in 1..=AUTOSAVE_SLOTS {
+        let name = format!("{AUTOSAVE_PREFIX}{slot}");
+        let Ok(saved_at) = fs::metadata(path(&name)).and_then(|metadata| metadata.modified()) else {
+            return name;
+        };
+        if oldest.as_ref().is_none_or(|(_, oldest_saved_at)| saved_at < *oldest_saved_at) {
+            oldest = Some((name, saved_at));
+        }
+    }
+    oldest.map_or_else(|| format!("{AUTOSAVE_PREFIX}1"), |(name, _)| name)
+}
+
+/// Formats as YYYY-MM-DD HH:MM in UTC
+pub fn format_timestamp(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    let days = (seconds / 86400) as i64;
+    let minutes_of_day = (seconds % 86400) / 60;
+
+    // Converts days since the epoch to a civil date (https://howardhinnant.github.io/date_algorithms.html)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02} {:02}:{:02}", minutes_of_day / 60, minutes_of_day % 60)
+}